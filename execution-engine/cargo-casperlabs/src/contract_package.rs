@@ -18,13 +18,18 @@ const MAIN_RS_CONTENTS: &str = r#"#![cfg_attr(
 )]
 #![no_main]
 
+mod entry_points;
+
 use casperlabs_contract::{
     contract_api::{runtime, storage},
 };
-use casperlabs_types::{Key, URef};
+use casperlabs_types::{runtime_args, Key, RuntimeArgs, URef};
+
+use entry_points::{ARG_MESSAGE, ENTRY_POINT_STORE_MESSAGE};
 
 const KEY: &str = "special_value";
-const ARG_MESSAGE: &str = "message";
+const CONTRACT_KEY: &str = "contract";
+const CONTRACT_PACKAGE_KEY: &str = "contract_package";
 
 fn store(value: String) {
     // Store `value` under a new unforgeable reference.
@@ -37,12 +42,79 @@ fn store(value: String) {
     runtime::put_key(KEY, value_key);
 }
 
+// An entry point of the stored contract, as listed by `entry_points::entry_points()`.
+#[no_mangle]
+pub extern "C" fn store_message() {
+    let value: String = runtime::get_named_arg(ARG_MESSAGE);
+    store(value);
+}
+
 // All session code must have a `call` entrypoint.
 #[no_mangle]
 pub extern "C" fn call() {
-    // Get the optional first argument supplied to the argument.
+    // Install the contract with the entry points which "build.rs" also exports as its ABI.
+    let (contract_hash, _contract_version) = storage::new_contract(
+        entry_points::entry_points(),
+        None,
+        Some(CONTRACT_PACKAGE_KEY.to_string()),
+        None,
+    );
+    runtime::put_key(CONTRACT_KEY, Key::Hash(contract_hash));
+
+    // Pass on the message supplied to the session to the contract's entry point.
     let value: String = runtime::get_named_arg(ARG_MESSAGE);
-    store(value);
+    runtime::call_contract::<()>(
+        contract_hash,
+        ENTRY_POINT_STORE_MESSAGE,
+        runtime_args! { ARG_MESSAGE => value },
+    );
+}
+"#;
+
+const ENTRY_POINTS_RS_CONTENTS: &str = r#"// The entry points of the contract.  This module is compiled into the contract, which installs
+// them, and is also included by "build.rs", which exports them as the contract's ABI.
+
+use casperlabs_types::{
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter},
+    CLType,
+};
+
+pub const ENTRY_POINT_STORE_MESSAGE: &str = "store_message";
+pub const ARG_MESSAGE: &str = "message";
+
+pub fn entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_STORE_MESSAGE,
+        vec![Parameter::new(ARG_MESSAGE, CLType::String)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+"#;
+
+const BUILD_RS_CONTENTS: &str = r#"use std::{env, fs, path::PathBuf};
+
+include!("src/entry_points.rs");
+
+const ENTRY_POINTS_RS: &str = "src/entry_points.rs";
+const ABI_FILENAME: &str = "abi.json";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", ENTRY_POINTS_RS);
+
+    // Export the contract's entry points as a JSON ABI document.
+    let abi = serde_json::to_string_pretty(&entry_points()).expect("should serialize ABI");
+
+    // "OUT_DIR" is "<target dir>/<target triple>/<profile>/build/<package>-<hash>/out", so the
+    // ABI is written alongside the compiled Wasm file in "<target dir>/<target triple>/<profile>".
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("should have out dir"));
+    let profile_dir = out_dir.ancestors().nth(3).expect("should have profile dir");
+    let abi_path = profile_dir.join(ABI_FILENAME);
+    fs::write(&abi_path, abi)
+        .unwrap_or_else(|error| panic!("should write {}: {}", abi_path.display(), error));
 }
 "#;
 
@@ -54,11 +126,20 @@ lazy_static! {
     static ref CARGO_TOML: PathBuf = ARGS.root_path().join(PACKAGE_NAME).join("Cargo.toml");
     static ref RUST_TOOLCHAIN: PathBuf = ARGS.root_path().join(PACKAGE_NAME).join("rust-toolchain");
     static ref MAIN_RS: PathBuf = ARGS.root_path().join(PACKAGE_NAME).join("src/main.rs");
+    static ref ENTRY_POINTS_RS: PathBuf = ARGS
+        .root_path()
+        .join(PACKAGE_NAME)
+        .join("src/entry_points.rs");
+    static ref BUILD_RS: PathBuf = ARGS.root_path().join(PACKAGE_NAME).join("build.rs");
     static ref CONFIG: PathBuf = ARGS.root_path().join(PACKAGE_NAME).join(".cargo/config");
     static ref CARGO_TOML_ADDITIONAL_CONTENTS: String = format!(
         r#"{}
 {}
 
+[build-dependencies]
+{}
+serde_json = "1"
+
 [[bin]]
 name = "{}"
 path = "src/main.rs"
@@ -72,7 +153,10 @@ default = ["casperlabs-contract/std", "casperlabs-types/std", "casperlabs-contra
 [profile.release]
 lto = true
 "#,
-        *CL_CONTRACT, *CL_TYPES, PACKAGE_NAME
+        *CL_CONTRACT,
+        *CL_TYPES,
+        CL_TYPES.with_features(&["std", "serde"]),
+        PACKAGE_NAME
     );
 }
 
@@ -92,6 +176,14 @@ pub fn update_main_rs() {
     common::write_file(&*MAIN_RS, MAIN_RS_CONTENTS);
 }
 
+pub fn add_entry_points_rs() {
+    common::write_file(&*ENTRY_POINTS_RS, ENTRY_POINTS_RS_CONTENTS);
+}
+
+pub fn add_build_rs() {
+    common::write_file(&*BUILD_RS, BUILD_RS_CONTENTS);
+}
+
 pub fn add_config() {
     let folder = CONFIG.parent().expect("should have parent");
     common::create_dir_all(folder);
@@ -13,6 +13,7 @@ pub struct Dependency {
     version: String,
     /// Path relative to "CasperLabs/execution-engine"
     relative_path: String,
+    features: Vec<String>,
 }
 
 impl Dependency {
@@ -21,6 +22,17 @@ impl Dependency {
             name: name.to_string(),
             version: version.to_string(),
             relative_path: relative_path.to_string(),
+            features: Vec::new(),
+        }
+    }
+
+    /// Returns a copy of this dependency with the given crate features enabled.
+    pub fn with_features(&self, features: &[&str]) -> Self {
+        Dependency {
+            name: self.name.clone(),
+            version: self.version.clone(),
+            relative_path: self.relative_path.clone(),
+            features: features.iter().map(|feature| feature.to_string()).collect(),
         }
     }
 
@@ -32,17 +44,35 @@ impl Dependency {
 
 impl Display for Dependency {
     fn fmt(&self, formatter: &mut Formatter) -> Result {
+        let features = if self.features.is_empty() {
+            String::new()
+        } else {
+            let quoted: Vec<String> = self
+                .features
+                .iter()
+                .map(|feature| format!(r#""{}""#, feature))
+                .collect();
+            format!(", features = [{}]", quoted.join(", "))
+        };
+
         if let Some(workspace_path) = ARGS.workspace_path() {
             write!(
                 formatter,
-                r#"{} = {{ version = "{}", path = "{}/{}" }}"#,
+                r#"{} = {{ version = "{}", path = "{}/{}"{} }}"#,
                 self.name,
                 self.version,
                 workspace_path.display(),
-                self.relative_path
+                self.relative_path,
+                features
             )
-        } else {
+        } else if self.features.is_empty() {
             write!(formatter, r#"{} = "{}""#, self.name, self.version)
+        } else {
+            write!(
+                formatter,
+                r#"{} = {{ version = "{}"{} }}"#,
+                self.name, self.version, features
+            )
         }
     }
 }
@@ -144,6 +144,8 @@ fn main() {
     contract_package::update_cargo_toml();
     contract_package::add_rust_toolchain();
     contract_package::update_main_rs();
+    contract_package::add_entry_points_rs();
+    contract_package::add_build_rs();
     contract_package::add_config();
 
     tests_package::run_cargo_new();
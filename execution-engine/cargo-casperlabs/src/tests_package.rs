@@ -23,8 +23,9 @@ mod tests {
     use casperlabs_types::{account::AccountHash, U512, RuntimeArgs, runtime_args};
 
     const MY_ACCOUNT: AccountHash = AccountHash::new([7u8; 32]);
-    // define KEY constant to match that in the contract
+    // define KEY and CONTRACT_KEY constants to match those in the contract
     const KEY: &str = "special_value";
+    const CONTRACT_KEY: &str = "contract";
     const VALUE: &str = "hello world";
     const ARG_MESSAGE: &str = "message";
 
@@ -46,7 +47,9 @@ mod tests {
             .with_authorization_keys(&[MY_ACCOUNT])
             .build();
 
-        let result_of_query: Result<Value, Error> = context.run(session).query(MY_ACCOUNT, &[KEY]);
+        let result_of_query: Result<Value, Error> = context
+            .run(session)
+            .query(MY_ACCOUNT, &[CONTRACT_KEY, KEY]);
 
         let returned_value = result_of_query.expect("should be a value");
 
@@ -65,6 +68,7 @@ const BUILD_RS_CONTENTS: &str = r#"use std::{env, fs, path::PathBuf, process::Co
 const CONTRACT_ROOT: &str = "../contract";
 const CONTRACT_CARGO_TOML: &str = "../contract/Cargo.toml";
 const CONTRACT_MAIN_RS: &str = "../contract/src/main.rs";
+const CONTRACT_ENTRY_POINTS_RS: &str = "../contract/src/entry_points.rs";
 const BUILD_ARGS: [&str; 2] = ["build", "--release"];
 const WASM_FILENAME: &str = "contract.wasm";
 const ORIGINAL_WASM_DIR: &str = "../contract/target/wasm32-unknown-unknown/release";
@@ -74,6 +78,7 @@ fn main() {
     // Watch contract source files for changes.
     println!("cargo:rerun-if-changed={}", CONTRACT_CARGO_TOML);
     println!("cargo:rerun-if-changed={}", CONTRACT_MAIN_RS);
+    println!("cargo:rerun-if-changed={}", CONTRACT_ENTRY_POINTS_RS);
 
     // Build the contract.
     let output = Command::new("cargo")
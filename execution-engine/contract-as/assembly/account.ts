@@ -108,6 +108,10 @@ export enum ActionType {
      * Required when adding/removing associated keys, changing threshold levels.
      */
     KeyManagement = 1,
+    /**
+     * Required when transferring motes out of the account's main purse.
+     */
+    Transfer = 2,
 }
 
 /**
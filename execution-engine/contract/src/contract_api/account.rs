@@ -1,14 +1,17 @@
 //! Functions for managing accounts.
 
-use alloc::vec::Vec;
+use alloc::{collections::BTreeMap, vec::Vec};
 use core::convert::TryFrom;
 
 use casperlabs_types::{
     account::{
-        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        AccountHash, ActionType, AddKeyFailure, ContractAllowList, RecoveryConfig, RecoveryFailure,
+        RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure, Weight,
+        ACCOUNT_HASH_SERIALIZED_LENGTH,
     },
-    bytesrepr, URef, UREF_SERIALIZED_LENGTH,
+    api_error, bytesrepr,
+    contracts::Group,
+    ApiError, URef, U512, UREF_SERIALIZED_LENGTH,
 };
 
 use super::to_ptr;
@@ -43,6 +46,116 @@ pub fn set_action_threshold(
     }
 }
 
+/// Sets the most motes a single deploy may transfer out of the account's main purse, or removes
+/// the limit if `spending_limit` is `None`.
+pub fn set_spending_limit(spending_limit: Option<U512>) -> Result<(), SetThresholdFailure> {
+    let (spending_limit_ptr, spending_limit_size, _bytes) = to_ptr(spending_limit);
+    let result = unsafe { ext_ffi::set_spending_limit(spending_limit_ptr, spending_limit_size) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(SetThresholdFailure::try_from(result).unwrap_or_revert())
+    }
+}
+
+/// Restricts deploys which don't meet the key management threshold to calling the entry points
+/// in `contract_allow_list`, or removes the restriction if `contract_allow_list` is `None`.
+pub fn set_contract_allow_list(
+    contract_allow_list: Option<ContractAllowList>,
+) -> Result<(), SetThresholdFailure> {
+    let (allow_list_ptr, allow_list_size, _bytes) = to_ptr(contract_allow_list);
+    let result = unsafe { ext_ffi::set_contract_allow_list(allow_list_ptr, allow_list_size) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(SetThresholdFailure::try_from(result).unwrap_or_revert())
+    }
+}
+
+/// Creates an account owned by the package of the calling contract, and returns its
+/// [`AccountHash`].
+///
+/// Deploys for the account are authorized by the accounts holding a URef of the package's `group`
+/// in their named keys, as the group is at the time of the deploy. Each of them has a weight of 1,
+/// however many of the group's URefs it holds. May only be called from a stored contract.
+pub fn create_contract_account(
+    group: Group,
+    deployment_threshold: Weight,
+    key_management_threshold: Weight,
+) -> Result<AccountHash, ApiError> {
+    let (group_ptr, group_size, _bytes) = to_ptr(group);
+    let account_hash_non_null_ptr = contract_api::alloc_bytes(ACCOUNT_HASH_SERIALIZED_LENGTH);
+    let bytes = unsafe {
+        let ret = ext_ffi::create_contract_account(
+            group_ptr,
+            group_size,
+            deployment_threshold.value().into(),
+            key_management_threshold.value().into(),
+            account_hash_non_null_ptr.as_ptr(),
+        );
+        api_error::result_from(ret)?;
+        Vec::from_raw_parts(
+            account_hash_non_null_ptr.as_ptr(),
+            ACCOUNT_HASH_SERIALIZED_LENGTH,
+            ACCOUNT_HASH_SERIALIZED_LENGTH,
+        )
+    };
+    Ok(bytesrepr::deserialize(bytes).unwrap_or_revert())
+}
+
+/// Sets the guardians allowed to recover the account's associated keys, or disables recovery if
+/// `recovery_config` is `None`.
+///
+/// Any pending recovery is cancelled.
+pub fn set_recovery_config(recovery_config: Option<RecoveryConfig>) -> Result<(), RecoveryFailure> {
+    let (config_ptr, config_size, _bytes) = to_ptr(recovery_config);
+    let result = unsafe { ext_ffi::set_recovery_config(config_ptr, config_size) };
+    recovery_result(result)
+}
+
+/// Cancels the account's pending recovery.
+pub fn cancel_recovery() -> Result<(), RecoveryFailure> {
+    let result = unsafe { ext_ffi::cancel_recovery() };
+    recovery_result(result)
+}
+
+/// Proposes replacing the associated keys of the given account with `new_keys`, acting as one of
+/// its guardians.
+///
+/// If the pending recovery proposes the same keys, it is approved instead.
+pub fn propose_recovery(
+    account_hash: AccountHash,
+    new_keys: BTreeMap<AccountHash, Weight>,
+) -> Result<(), RecoveryFailure> {
+    let (account_hash_ptr, account_hash_size, _account_hash_bytes) = to_ptr(account_hash);
+    let (new_keys_ptr, new_keys_size, _new_keys_bytes) = to_ptr(new_keys);
+    let result = unsafe {
+        ext_ffi::propose_recovery(
+            account_hash_ptr,
+            account_hash_size,
+            new_keys_ptr,
+            new_keys_size,
+        )
+    };
+    recovery_result(result)
+}
+
+/// Replaces the associated keys of the given account with the ones of its pending recovery, acting
+/// as one of its guardians.
+pub fn finalize_recovery(account_hash: AccountHash) -> Result<(), RecoveryFailure> {
+    let (account_hash_ptr, account_hash_size, _bytes) = to_ptr(account_hash);
+    let result = unsafe { ext_ffi::finalize_recovery(account_hash_ptr, account_hash_size) };
+    recovery_result(result)
+}
+
+fn recovery_result(result: i32) -> Result<(), RecoveryFailure> {
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(RecoveryFailure::try_from(result).unwrap_or_revert())
+    }
+}
+
 /// Adds the given [`AccountHash`] with associated [`Weight`] to the account's associated keys.
 pub fn add_associated_key(account_hash: AccountHash, weight: Weight) -> Result<(), AddKeyFailure> {
     let (account_hash_ptr, account_hash_size, _bytes) = to_ptr(account_hash);
@@ -6,7 +6,7 @@ use core::{convert::From, mem::MaybeUninit};
 use casperlabs_types::{
     api_error,
     bytesrepr::{self, FromBytes, ToBytes},
-    contracts::{ContractVersion, EntryPoints, NamedKeys},
+    contracts::{ContractMetadata, ContractVersion, EntryPoints, NamedKeys},
     AccessRights, ApiError, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, URef,
     UREF_SERIALIZED_LENGTH,
 };
@@ -160,6 +160,46 @@ pub fn new_contract(
     add_contract_version(contract_package_hash, entry_points, named_keys)
 }
 
+/// Create a new locked contract stored under a Key::Hash at version 1
+/// if `named_keys` are provided, will apply them
+/// if `hash_name` is provided, puts contract package hash in current context's named keys under
+/// `hash_name`
+/// A locked contract package has no access key; no versions can ever be added or disabled.
+pub fn new_locked_contract(
+    entry_points: EntryPoints,
+    named_keys: Option<NamedKeys>,
+    hash_name: Option<String>,
+) -> (ContractHash, ContractVersion) {
+    let (entry_points_ptr, entry_points_size, _bytes1) = contract_api::to_ptr(entry_points);
+    let named_keys = match named_keys {
+        Some(named_keys) => named_keys,
+        None => NamedKeys::new(),
+    };
+    let (named_keys_ptr, named_keys_size, _bytes2) = contract_api::to_ptr(named_keys);
+
+    let mut contract_package_hash = ContractPackageHash::default();
+    let mut contract_hash = ContractHash::default();
+    let mut contract_version: ContractVersion = 0;
+    let ret = unsafe {
+        ext_ffi::new_locked_contract(
+            entry_points_ptr,
+            entry_points_size,
+            named_keys_ptr,
+            named_keys_size,
+            contract_package_hash.as_mut_ptr(),
+            contract_hash.as_mut_ptr(),
+            &mut contract_version as *mut ContractVersion,
+        )
+    };
+    api_error::result_from(ret).unwrap_or_revert();
+
+    if let Some(hash_name) = hash_name {
+        runtime::put_key(&hash_name, contract_package_hash.into());
+    };
+
+    (contract_hash, contract_version)
+}
+
 /// Create a new (versioned) contract stored under a Key::Hash. Initially there
 /// are no versions; a version must be added via `add_contract_version` before
 /// the contract can be executed.
@@ -324,6 +364,82 @@ pub fn add_contract_version(
     (contract_hash, contract_version)
 }
 
+/// Add a new version of a contract to the contract stored at the given `Key`, and migrate to it by
+/// calling its `migration` entry point with the hash of the previous version under the
+/// [`casperlabs_types::contracts::ARG_PREVIOUS_CONTRACT_HASH`] argument. The migration runs in the
+/// new version's context; if it fails, so does the upgrade. The runtime calls the migration entry
+/// point regardless of its access, but refuses a public one, since it stays an entry point of the
+/// new version and could otherwise be called again afterwards.
+pub fn add_contract_version_with_migration(
+    contract_package_hash: ContractPackageHash,
+    entry_points: EntryPoints,
+    named_keys: NamedKeys,
+    migration: &str,
+) -> (ContractHash, ContractVersion) {
+    let (contract_package_hash_ptr, contract_package_hash_size, _bytes1) =
+        contract_api::to_ptr(contract_package_hash);
+    let (entry_points_ptr, entry_points_size, _bytes4) = contract_api::to_ptr(entry_points);
+    let (named_keys_ptr, named_keys_size, _bytes5) = contract_api::to_ptr(named_keys);
+    let (migration_ptr, migration_size, _bytes6) = contract_api::to_ptr(migration);
+
+    let mut output_ptr = vec![0u8; Key::max_serialized_length()];
+    let mut total_bytes: usize = 0;
+
+    let mut contract_version: ContractVersion = 0;
+
+    let ret = unsafe {
+        ext_ffi::add_contract_version_with_migration(
+            contract_package_hash_ptr,
+            contract_package_hash_size,
+            &mut contract_version as *mut ContractVersion,
+            entry_points_ptr,
+            entry_points_size,
+            named_keys_ptr,
+            named_keys_size,
+            migration_ptr,
+            migration_size,
+            output_ptr.as_mut_ptr(),
+            output_ptr.len(),
+            &mut total_bytes as *mut usize,
+        )
+    };
+    match api_error::result_from(ret) {
+        Ok(_) => {}
+        Err(e) => revert(e),
+    }
+    output_ptr.truncate(total_bytes);
+    let contract_hash = bytesrepr::deserialize(output_ptr).unwrap_or_revert();
+    (contract_hash, contract_version)
+}
+
+/// Sets the metadata of the contract package stored at the given `Key`, or of one of its versions
+/// if `contract_hash` is given. If `metadata` is `None`, the metadata is cleared. Note that the
+/// package's access key must be present in the current context, and that the metadata of a
+/// locked package can't be changed.
+pub fn set_contract_metadata(
+    contract_package_hash: ContractPackageHash,
+    contract_hash: Option<ContractHash>,
+    metadata: Option<ContractMetadata>,
+) -> Result<(), ApiError> {
+    let (contract_package_hash_ptr, contract_package_hash_size, _bytes1) =
+        contract_api::to_ptr(contract_package_hash);
+    let (contract_hash_ptr, contract_hash_size, _bytes2) = contract_api::to_ptr(contract_hash);
+    let (metadata_ptr, metadata_size, _bytes3) = contract_api::to_ptr(metadata);
+
+    let result = unsafe {
+        ext_ffi::set_contract_metadata(
+            contract_package_hash_ptr,
+            contract_package_hash_size,
+            contract_hash_ptr,
+            contract_hash_size,
+            metadata_ptr,
+            metadata_size,
+        )
+    };
+
+    api_error::result_from(result)
+}
+
 /// Disable a version of a contract from the contract stored at the given
 /// `Key`. That version of the contract will no longer be callable by
 /// `call_versioned_contract`. Note that this contract must have been created by
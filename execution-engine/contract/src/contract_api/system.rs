@@ -146,9 +146,5 @@ pub fn transfer_from_purse_to_purse(
             amount_size,
         )
     };
-    if result == 0 {
-        Ok(())
-    } else {
-        Err(ApiError::Transfer)
-    }
+    api_error::result_from(result)
 }
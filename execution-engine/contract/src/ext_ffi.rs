@@ -216,9 +216,9 @@ extern "C" {
         weight: i32,
     ) -> i32;
     /// This function changes the threshold to perform the specified action. The
-    /// action index is interpreted as follows: 0 means deployment and 1 means key
-    /// management. Thresholds are represented internally as a `u8`, this function
-    /// will cause a `Trap` if the new threshold is not between 0 and 255
+    /// action index is interpreted as follows: 0 means deployment, 1 means key
+    /// management and 2 means transfer. Thresholds are represented internally as a `u8`, this
+    /// function will cause a `Trap` if the new threshold is not between 0 and 255
     /// inclusively. The return value is a status code where 0 means success, 1
     /// means the key management threshold cannot be set lower than the deploy
     /// threshold, 2 means the deployment threshold cannot be set higher than the
@@ -233,6 +233,103 @@ extern "C" {
     /// * `action` - index representing the action threshold to set
     /// * `threshold` - new value of the threshold for performing this action
     pub fn set_action_threshold(permission_level: u32, threshold: u32) -> i32;
+    /// This function sets the most motes a single deploy may transfer out of the
+    /// account's main purse. The limit is a serialized `Option<U512>`, where
+    /// `None` removes the limit. This function causes a `Trap` if the limit
+    /// cannot be de-serialized. The return value is a status code where 0 means
+    /// success and 3 means permission denied (this could be because the function
+    /// was called outside of session code or because the key management threshold
+    /// was not met by the keys authorizing the deploy).
+    ///
+    /// # Arguments
+    ///
+    /// * `spending_limit_ptr` - pointer in wasm memory to bytes representing the limit
+    /// * `spending_limit_size` - size of the limit (in bytes)
+    pub fn set_spending_limit(spending_limit_ptr: *const u8, spending_limit_size: usize) -> i32;
+    /// This function sets the contract entry points which deploys not meeting the
+    /// key management threshold are restricted to. The allow-list is a serialized
+    /// `Option<ContractAllowList>`, where `None` removes the restriction. This
+    /// function causes a `Trap` if the allow-list cannot be de-serialized. The
+    /// return value is a status code where 0 means success and 3 means permission
+    /// denied (this could be because the function was called outside of session
+    /// code or because the key management threshold was not met by the keys
+    /// authorizing the deploy).
+    ///
+    /// # Arguments
+    ///
+    /// * `allow_list_ptr` - pointer in wasm memory to bytes representing the allow-list
+    /// * `allow_list_size` - size of the allow-list (in bytes)
+    pub fn set_contract_allow_list(allow_list_ptr: *const u8, allow_list_size: usize) -> i32;
+    /// This function creates an account owned by the package of the calling
+    /// contract. Deploys for the account are authorized by the accounts holding
+    /// a URef of the given group of the package in their named keys, as the
+    /// group is at the time of the deploy, each with a weight of 1. If the call is
+    /// successful then the [`casperlabs_types::account::AccountHash`] of the new
+    /// account (in serialized form) is written to the indicated place in wasm
+    /// memory, which must have 32 bytes allocated. This function causes a `Trap`
+    /// if it's called outside of a stored contract.
+    /// The return value is a status code where 0 means success.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_ptr` - pointer in wasm memory to bytes representing the group
+    /// * `group_size` - size of the group (in bytes)
+    /// * `deployment_threshold` - deployment threshold of the new account
+    /// * `key_management_threshold` - key management threshold of the new account
+    /// * `account_hash_ptr` - pointer to position in wasm memory where to write the account hash
+    pub fn create_contract_account(
+        group_ptr: *const u8,
+        group_size: usize,
+        deployment_threshold: u32,
+        key_management_threshold: u32,
+        account_hash_ptr: *mut u8,
+    ) -> i32;
+    /// This function sets the guardians allowed to recover the account's
+    /// associated keys. The configuration is a serialized
+    /// `Option<RecoveryConfig>`, where `None` disables recovery; either way, any
+    /// pending recovery is cancelled. This function causes a `Trap` if the
+    /// configuration cannot be de-serialized. The return value is a status code
+    /// where 0 means success and any other value is a `RecoveryFailure`.
+    ///
+    /// # Arguments
+    ///
+    /// * `config_ptr` - pointer in wasm memory to bytes representing the configuration
+    /// * `config_size` - size of the configuration (in bytes)
+    pub fn set_recovery_config(config_ptr: *const u8, config_size: usize) -> i32;
+    /// This function cancels the account's pending recovery. The return value is
+    /// a status code where 0 means success and any other value is a
+    /// `RecoveryFailure`.
+    pub fn cancel_recovery() -> i32;
+    /// This function proposes replacing the associated keys of another account,
+    /// with the current account acting as one of its guardians. If the pending
+    /// recovery proposes the same keys, it is approved instead. The new keys are
+    /// a serialized `BTreeMap<AccountHash, Weight>`. This function causes a
+    /// `Trap` if the arguments cannot be de-serialized. The return value is a
+    /// status code where 0 means success and any other value is a
+    /// `RecoveryFailure`.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_hash_ptr` - pointer to bytes representing the recovered account
+    /// * `account_hash_size` - size of the account hash (in bytes)
+    /// * `new_keys_ptr` - pointer to bytes representing the new associated keys
+    /// * `new_keys_size` - size of the new associated keys (in bytes)
+    pub fn propose_recovery(
+        account_hash_ptr: *const u8,
+        account_hash_size: usize,
+        new_keys_ptr: *const u8,
+        new_keys_size: usize,
+    ) -> i32;
+    /// This function replaces the associated keys of another account with the
+    /// ones of its pending recovery, with the current account acting as one of
+    /// its guardians. The return value is a status code where 0 means success and
+    /// any other value is a `RecoveryFailure`.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_hash_ptr` - pointer to bytes representing the recovered account
+    /// * `account_hash_size` - size of the account hash (in bytes)
+    pub fn finalize_recovery(account_hash_ptr: *const u8, account_hash_size: usize) -> i32;
     /// This function returns the public key of the account for this deploy. The
     /// result is always 36-bytes in length (4 bytes prefix on a 32-byte public
     /// key); it is up to the caller to ensure the right amount of memory is
@@ -341,9 +438,11 @@ extern "C" {
     /// serialized 512-bit unsigned integer. This function causes a `Trap` if the
     /// source or target cannot be de-serialized as a [`casperlabs_types::uref::URef`] or the amount
     /// cannot be de-serialized into a `U512`. The return value indicated what
-    /// occurred, where 0 means a successful transfer, 1 means the transfer
-    /// failed (this could be because the source purse had insufficient tokens or
-    /// because there was not valid access to the source purse or target purse).
+    /// occurred, where 0 means a successful transfer, and any other value is a
+    /// [`casperlabs_types::ApiError`] describing why the transfer failed (this could be
+    /// because the source purse had insufficient tokens, because there was not valid
+    /// access to the source purse or target purse, or because the account's transfer
+    /// threshold or spending limit was not met).
     ///
     /// # Arguments
     ///
@@ -471,6 +570,88 @@ extern "C" {
         output_size: usize,
         bytes_written_ptr: *mut usize,
     ) -> i32;
+    /// Adds new contract version to a contract package, then calls its migration entry point in
+    /// the new contract's context with the hash of the previous version under the
+    /// [`casperlabs_types::contracts::ARG_PREVIOUS_CONTRACT_HASH`] argument. The migration entry
+    /// point must be among `entry_points`, of contract type and restricted to groups. If the
+    /// migration fails, the execution fails along with the new version.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_package_hash_ptr` - pointer to serialized contract package hash.
+    /// * `contract_package_hash_size` - size of contract package hash in serialized form.
+    /// * `version_ptr` - output parameter where new version assigned by host is set
+    /// * `entry_points_ptr` - pointer to serialized [`casperlabs_types::EntryPoints`]
+    /// * `entry_points_size` - size of serialized [`casperlabs_types::EntryPoints`]
+    /// * `named_keys_ptr` - pointer to serialized [`casperlabs_types::contracts::NamedKeys`]
+    /// * `named_keys_size` - size of serialized [`casperlabs_types::contracts::NamedKeys`]
+    /// * `migration_ptr` - pointer to serialized name of the migration entry point
+    /// * `migration_size` - size of serialized name of the migration entry point
+    /// * `output_ptr` - pointer to a memory where host assigned contract hash is set to
+    /// * `output_size` - size of memory area that host can write to
+    /// * `bytes_written_ptr` - pointer to a value where host will set a number of bytes written to
+    ///   the `output_size` pointer
+    pub fn add_contract_version_with_migration(
+        contract_package_hash_ptr: *const u8,
+        contract_package_hash_size: usize,
+        version_ptr: *const u32,
+        entry_points_ptr: *const u8,
+        entry_points_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        migration_ptr: *const u8,
+        migration_size: usize,
+        output_ptr: *mut u8,
+        output_size: usize,
+        bytes_written_ptr: *mut usize,
+    ) -> i32;
+    /// Sets or clears the metadata of a contract package, or of one of its contract versions.
+    /// Requires the package's access key. Returns non-zero standard error for a failure,
+    /// otherwise a zero indicates success.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_package_hash_ptr` - pointer to serialized contract package hash.
+    /// * `contract_package_hash_size` - size of contract package hash in serialized form.
+    /// * `contract_hash_ptr` - pointer to serialized `Option` of the contract hash of the version,
+    ///   or `None` for the package itself
+    /// * `contract_hash_size` - size of serialized `Option` of the contract hash
+    /// * `metadata_ptr` - pointer to serialized `Option` of
+    ///   [`casperlabs_types::contracts::ContractMetadata`], where `None` clears the metadata
+    /// * `metadata_size` - size of serialized `Option` of the metadata
+    pub fn set_contract_metadata(
+        contract_package_hash_ptr: *const u8,
+        contract_package_hash_size: usize,
+        contract_hash_ptr: *const u8,
+        contract_hash_size: usize,
+        metadata_ptr: *const u8,
+        metadata_size: usize,
+    ) -> i32;
+    /// Creates a new locked contract package holding a single contract version. Neither versions
+    /// nor the package's access key are ever available, so the contract can't change once it has
+    /// been stored. Returns non-zero standard error for a failure, otherwise a zero indicates
+    /// success.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry_points_ptr` - pointer to serialized [`casperlabs_types::EntryPoints`]
+    /// * `entry_points_size` - size of serialized [`casperlabs_types::EntryPoints`]
+    /// * `named_keys_ptr` - pointer to serialized [`casperlabs_types::contracts::NamedKeys`]
+    /// * `named_keys_size` - size of serialized [`casperlabs_types::contracts::NamedKeys`]
+    /// * `contract_package_hash_ptr` - pointer to a 32-byte memory area where host assigned
+    ///   contract package hash is set to
+    /// * `contract_hash_ptr` - pointer to a 32-byte memory area where host assigned contract hash
+    ///   is set to
+    /// * `version_ptr` - output parameter where version assigned by host is set
+    pub fn new_locked_contract(
+        entry_points_ptr: *const u8,
+        entry_points_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        contract_package_hash_ptr: *mut u8,
+        contract_hash_ptr: *mut u8,
+        version_ptr: *mut u32,
+    ) -> i32;
     /// Disables contract in a contract package. Returns non-zero standard error for a failure,
     /// otherwise a zero indicates success.
     ///
@@ -3,27 +3,22 @@
 
 extern crate alloc;
 
-use alloc::{collections::BTreeMap, string::String, vec};
+use alloc::{collections::BTreeMap, string::String};
 
-use alloc::{boxed::Box, string::ToString};
 use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use pos::{
-    ARG_ACCOUNT_KEY, ARG_AMOUNT, ARG_PURSE, METHOD_BOND, METHOD_FINALIZE_PAYMENT,
-    METHOD_GET_PAYMENT_PURSE, METHOD_GET_REFUND_PURSE, METHOD_SET_REFUND_PURSE, METHOD_UNBOND,
+    ARG_AMOUNT, AUCTION_CONFIG_KEY, FEE_BURN_RATE_KEY, INFLATION_RATE_KEY, STAKING_LIMITS_KEY,
 };
-use proof_of_stake::Stakes;
+use proof_of_stake::{AuctionConfig, Stakes, StakingLimits};
 use types::{
     account::AccountHash,
-    contracts::{
-        EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys, Parameter,
-        CONTRACT_INITIAL_VERSION,
-    },
+    contracts::{NamedKeys, CONTRACT_INITIAL_VERSION},
     runtime_args,
     system_contract_errors::mint,
-    CLType, CLValue, ContractPackageHash, Key, RuntimeArgs, URef, U512,
+    CLValue, ContractPackageHash, Key, RuntimeArgs, URef, U512,
 };
 
 const PLACEHOLDER_KEY: Key = Key::Hash([0u8; 32]);
@@ -33,6 +28,10 @@ const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
 
 const ARG_MINT_PACKAGE_HASH: &str = "mint_contract_package_hash";
 const ARG_GENESIS_VALIDATORS: &str = "genesis_validators";
+const ARG_AUCTION_CONFIG: &str = "auction_config";
+const ARG_STAKING_LIMITS: &str = "staking_limits";
+const ARG_INFLATION_RATE: &str = "inflation_rate";
+const ARG_FEE_BURN_RATE: &str = "fee_burn_rate";
 const ENTRY_POINT_MINT: &str = "mint";
 
 const HASH_KEY_NAME: &str = "pos_hash";
@@ -68,11 +67,25 @@ pub extern "C" fn finalize_payment() {
     pos::finalize_payment();
 }
 
+#[no_mangle]
+pub extern "C" fn run_auction() {
+    pos::run_auction();
+}
+
+#[no_mangle]
+pub extern "C" fn mint_seigniorage() {
+    pos::mint_seigniorage();
+}
+
 #[no_mangle]
 pub extern "C" fn install() {
     let mint_package_hash: ContractPackageHash = runtime::get_named_arg(ARG_MINT_PACKAGE_HASH);
     let genesis_validators: BTreeMap<AccountHash, U512> =
         runtime::get_named_arg(ARG_GENESIS_VALIDATORS);
+    let maybe_auction_config: Option<AuctionConfig> = runtime::get_named_arg(ARG_AUCTION_CONFIG);
+    let staking_limits: StakingLimits = runtime::get_named_arg(ARG_STAKING_LIMITS);
+    let inflation_rate: u64 = runtime::get_named_arg(ARG_INFLATION_RATE);
+    let fee_burn_rate: u8 = runtime::get_named_arg(ARG_FEE_BURN_RATE);
 
     let stakes = Stakes::new(genesis_validators);
 
@@ -97,71 +110,34 @@ pub extern "C" fn install() {
         named_keys.insert(String::from(*name), Key::URef(*uref));
     });
 
-    let entry_points = {
-        let mut entry_points = EntryPoints::new();
-
-        let bond = EntryPoint::new(
-            METHOD_BOND.to_string(),
-            vec![
-                Parameter::new(ARG_AMOUNT, CLType::U512),
-                Parameter::new(ARG_PURSE, CLType::URef),
-            ],
-            CLType::Unit,
-            EntryPointAccess::Public,
-            EntryPointType::Contract,
-        );
-        entry_points.add_entry_point(bond);
-
-        let unbond = EntryPoint::new(
-            METHOD_UNBOND.to_string(),
-            vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
-            CLType::Unit,
-            EntryPointAccess::Public,
-            EntryPointType::Contract,
-        );
-        entry_points.add_entry_point(unbond);
-
-        let get_payment_purse = EntryPoint::new(
-            METHOD_GET_PAYMENT_PURSE.to_string(),
-            vec![],
-            CLType::URef,
-            EntryPointAccess::Public,
-            EntryPointType::Contract,
-        );
-        entry_points.add_entry_point(get_payment_purse);
-
-        let set_refund_purse = EntryPoint::new(
-            METHOD_SET_REFUND_PURSE.to_string(),
-            vec![Parameter::new(ARG_PURSE, CLType::URef)],
-            CLType::Unit,
-            EntryPointAccess::Public,
-            EntryPointType::Contract,
-        );
-        entry_points.add_entry_point(set_refund_purse);
-
-        let get_refund_purse = EntryPoint::new(
-            METHOD_GET_REFUND_PURSE.to_string(),
-            vec![],
-            CLType::Option(Box::new(CLType::URef)),
-            EntryPointAccess::Public,
-            EntryPointType::Contract,
-        );
-        entry_points.add_entry_point(get_refund_purse);
-
-        let finalize_payment = EntryPoint::new(
-            METHOD_FINALIZE_PAYMENT.to_string(),
-            vec![
-                Parameter::new(ARG_AMOUNT, CLType::U512),
-                Parameter::new(ARG_ACCOUNT_KEY, CLType::FixedList(Box::new(CLType::U8), 32)),
-            ],
-            CLType::Unit,
-            EntryPointAccess::Public,
-            EntryPointType::Contract,
+    let staking_limits_uref = storage::new_uref(staking_limits);
+    named_keys.insert(
+        String::from(STAKING_LIMITS_KEY),
+        Key::URef(staking_limits_uref),
+    );
+
+    let inflation_rate_uref = storage::new_uref(inflation_rate);
+    named_keys.insert(
+        String::from(INFLATION_RATE_KEY),
+        Key::URef(inflation_rate_uref),
+    );
+
+    let fee_burn_rate_uref = storage::new_uref(fee_burn_rate);
+    named_keys.insert(
+        String::from(FEE_BURN_RATE_KEY),
+        Key::URef(fee_burn_rate_uref),
+    );
+
+    // The presence of the auction config enables the auction mode.
+    if let Some(auction_config) = maybe_auction_config {
+        let auction_config_uref = storage::new_uref(auction_config);
+        named_keys.insert(
+            String::from(AUCTION_CONFIG_KEY),
+            Key::URef(auction_config_uref),
         );
-        entry_points.add_entry_point(finalize_payment);
+    }
 
-        entry_points
-    };
+    let entry_points = pos::get_entry_points();
 
     let (contract_package_hash, access_uref) = storage::create_contract_package_at_hash();
     runtime::put_key(HASH_KEY_NAME, contract_package_hash.into());
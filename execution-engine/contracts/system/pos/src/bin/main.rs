@@ -30,3 +30,13 @@ pub extern "C" fn get_refund_purse() {
 pub extern "C" fn finalize_payment() {
     pos::finalize_payment();
 }
+
+#[no_mangle]
+pub extern "C" fn run_auction() {
+    pos::run_auction();
+}
+
+#[no_mangle]
+pub extern "C" fn mint_seigniorage() {
+    pos::mint_seigniorage();
+}
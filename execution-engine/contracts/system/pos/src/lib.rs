@@ -3,8 +3,10 @@
 extern crate alloc;
 
 use alloc::{
+    boxed::Box,
     collections::{BTreeMap, BTreeSet},
-    string::String,
+    string::{String, ToString},
+    vec,
 };
 
 use contract::{
@@ -12,11 +14,15 @@ use contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use proof_of_stake::{
-    MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, Stakes, StakesProvider,
+    AuctionConfig, AuctionProvider, Bids, FeeProvider, MintProvider, ProofOfStake, Queue,
+    QueueProvider, RuntimeProvider, SeigniorageProvider, Stakes, StakesProvider, StakingLimits,
 };
 use types::{
-    account::AccountHash, system_contract_errors::pos::Error, ApiError, BlockTime, CLValue, Key,
-    Phase, TransferResult, URef, U512,
+    account::AccountHash,
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter},
+    runtime_args,
+    system_contract_errors::{mint, pos::Error},
+    ApiError, BlockTime, CLType, CLValue, Key, Phase, RuntimeArgs, TransferResult, URef, U512,
 };
 
 pub const METHOD_BOND: &str = "bond";
@@ -25,13 +31,28 @@ pub const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
 pub const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
 pub const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
 pub const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
+pub const METHOD_RUN_AUCTION: &str = "run_auction";
+pub const METHOD_MINT_SEIGNIORAGE: &str = "mint_seigniorage";
+
+pub const AUCTION_CONFIG_KEY: &str = "pos_auction_config";
+pub const STAKING_LIMITS_KEY: &str = "pos_staking_limits";
+pub const INFLATION_RATE_KEY: &str = "pos_inflation_rate";
+pub const FEE_BURN_RATE_KEY: &str = "pos_fee_burn_rate";
 
 const BONDING_KEY: u8 = 1;
 const UNBONDING_KEY: u8 = 2;
+const BIDS_KEY: u8 = 3;
+const ERA_END_KEY: u8 = 4;
+const LAST_SEIGNIORAGE_KEY: u8 = 5;
+
+const MINT_METHOD_MINT_INTO: &str = "mint_into";
+const MINT_METHOD_TOTAL_SUPPLY: &str = "total_supply";
+const MINT_METHOD_BURN: &str = "burn";
 
 pub const ARG_AMOUNT: &str = "amount";
 pub const ARG_PURSE: &str = "purse";
 pub const ARG_ACCOUNT_KEY: &str = "account";
+pub const ARG_PROPOSER: &str = "proposer";
 
 pub struct ProofOfStakeContract;
 
@@ -57,6 +78,35 @@ impl MintProvider for ProofOfStakeContract {
     fn balance(&mut self, purse: URef) -> Option<U512> {
         system::get_balance(purse)
     }
+
+    fn mint_into(&mut self, purse: URef, amount: U512) -> Result<(), ()> {
+        let args = runtime_args! {
+            ARG_PURSE => purse,
+            ARG_AMOUNT => amount,
+        };
+        let result: Result<(), mint::Error> =
+            runtime::call_contract(system::get_mint(), MINT_METHOD_MINT_INTO, args);
+        result.map_err(|_| ())
+    }
+
+    fn total_supply(&mut self) -> Option<U512> {
+        let total_supply: U512 = runtime::call_contract(
+            system::get_mint(),
+            MINT_METHOD_TOTAL_SUPPLY,
+            RuntimeArgs::new(),
+        );
+        Some(total_supply)
+    }
+
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), ()> {
+        let args = runtime_args! {
+            ARG_PURSE => purse,
+            ARG_AMOUNT => amount,
+        };
+        let result: Result<(), mint::Error> =
+            runtime::call_contract(system::get_mint(), MINT_METHOD_BURN, args);
+        result.map_err(|_| ())
+    }
 }
 
 impl QueueProvider for ProofOfStakeContract {
@@ -85,6 +135,82 @@ impl QueueProvider for ProofOfStakeContract {
     }
 }
 
+impl AuctionProvider for ProofOfStakeContract {
+    /// Reads the auction configuration stored at genesis, if any.
+    fn read_auction_config(&mut self) -> Option<AuctionConfig> {
+        let uref = match runtime::get_key(AUCTION_CONFIG_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => runtime::revert(ApiError::UnexpectedKeyVariant),
+            None => return None,
+        };
+        storage::read(uref).unwrap_or_revert()
+    }
+
+    /// Reads the bids of the current era from the local state of the contract.
+    fn read_bids(&mut self) -> Bids {
+        storage::read_local(&BIDS_KEY)
+            .unwrap_or_default()
+            .unwrap_or_default()
+    }
+
+    /// Writes the bids of the current era to the local state of the contract.
+    fn write_bids(&mut self, bids: Bids) {
+        storage::write_local(BIDS_KEY, bids);
+    }
+
+    /// Reads the end of the current era from the local state of the contract.
+    fn read_era_end(&mut self) -> Option<BlockTime> {
+        storage::read_local::<u8, u64>(&ERA_END_KEY)
+            .unwrap_or_default()
+            .map(BlockTime::new)
+    }
+
+    /// Writes the end of the current era to the local state of the contract.
+    fn write_era_end(&mut self, era_end: BlockTime) {
+        let era_end: u64 = era_end.into();
+        storage::write_local(ERA_END_KEY, era_end);
+    }
+}
+
+impl SeigniorageProvider for ProofOfStakeContract {
+    /// Reads the annual inflation rate stored at genesis or by the latest upgrade, falling back to
+    /// no inflation if there is none.
+    fn read_inflation_rate(&mut self) -> u64 {
+        let uref = match runtime::get_key(INFLATION_RATE_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => runtime::revert(ApiError::UnexpectedKeyVariant),
+            None => return 0,
+        };
+        storage::read(uref).unwrap_or_revert().unwrap_or_default()
+    }
+
+    /// Reads the time of the last seigniorage mint from the local state of the contract.
+    fn read_last_seigniorage(&mut self) -> Option<BlockTime> {
+        storage::read_local::<u8, u64>(&LAST_SEIGNIORAGE_KEY)
+            .unwrap_or_default()
+            .map(BlockTime::new)
+    }
+
+    /// Writes the time of the last seigniorage mint to the local state of the contract.
+    fn write_last_seigniorage(&mut self, timestamp: BlockTime) {
+        let timestamp: u64 = timestamp.into();
+        storage::write_local(LAST_SEIGNIORAGE_KEY, timestamp);
+    }
+}
+
+impl FeeProvider for ProofOfStakeContract {
+    /// Reads the fee burn rate stored at genesis or by the latest upgrade, falling back to burning
+    /// nothing if there is none.
+    fn read_fee_burn_rate(&mut self) -> u8 {
+        let uref = match runtime::get_key(FEE_BURN_RATE_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => runtime::revert(ApiError::UnexpectedKeyVariant),
+            None => return 0,
+        };
+        storage::read(uref).unwrap_or_revert().unwrap_or_default()
+    }
+}
+
 impl RuntimeProvider for ProofOfStakeContract {
     fn get_key(&self, name: &str) -> Option<Key> {
         runtime::get_key(name)
@@ -157,6 +283,17 @@ impl StakesProvider for ProofOfStakeContract {
             runtime::put_key(&name, Key::Hash([0; 32]));
         }
     }
+
+    /// Reads the staking limits stored at genesis or by the latest upgrade, falling back to the
+    /// defaults if there are none.
+    fn read_limits(&self) -> StakingLimits {
+        let uref = match runtime::get_key(STAKING_LIMITS_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => runtime::revert(ApiError::UnexpectedKeyVariant),
+            None => return StakingLimits::default(),
+        };
+        storage::read(uref).unwrap_or_revert().unwrap_or_default()
+    }
 }
 
 impl ProofOfStake for ProofOfStakeContract {}
@@ -222,7 +359,106 @@ pub fn finalize_payment() {
 
     let amount_spent: U512 = runtime::get_named_arg(ARG_AMOUNT);
     let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT_KEY);
+    let proposer: Option<AccountHash> = runtime::get_named_arg(ARG_PROPOSER);
     pos_contract
-        .finalize_payment(amount_spent, account)
+        .finalize_payment(amount_spent, account, proposer)
         .unwrap_or_revert();
 }
+
+pub fn run_auction() {
+    let mut pos_contract = ProofOfStakeContract;
+    pos_contract.run_auction().unwrap_or_revert();
+}
+
+pub fn mint_seigniorage() {
+    let mut pos_contract = ProofOfStakeContract;
+    pos_contract.mint_seigniorage().unwrap_or_revert();
+}
+
+pub fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+
+    let bond = EntryPoint::new(
+        METHOD_BOND.to_string(),
+        vec![
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+            Parameter::new(ARG_PURSE, CLType::URef),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(bond);
+
+    let unbond = EntryPoint::new(
+        METHOD_UNBOND.to_string(),
+        vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(unbond);
+
+    let get_payment_purse = EntryPoint::new(
+        METHOD_GET_PAYMENT_PURSE.to_string(),
+        vec![],
+        CLType::URef,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(get_payment_purse);
+
+    let set_refund_purse = EntryPoint::new(
+        METHOD_SET_REFUND_PURSE.to_string(),
+        vec![Parameter::new(ARG_PURSE, CLType::URef)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(set_refund_purse);
+
+    let get_refund_purse = EntryPoint::new(
+        METHOD_GET_REFUND_PURSE.to_string(),
+        vec![],
+        CLType::Option(Box::new(CLType::URef)),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(get_refund_purse);
+
+    let finalize_payment = EntryPoint::new(
+        METHOD_FINALIZE_PAYMENT.to_string(),
+        vec![
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+            Parameter::new(ARG_ACCOUNT_KEY, CLType::FixedList(Box::new(CLType::U8), 32)),
+            Parameter::new(
+                ARG_PROPOSER,
+                CLType::Option(Box::new(CLType::FixedList(Box::new(CLType::U8), 32))),
+            ),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(finalize_payment);
+
+    let run_auction = EntryPoint::new(
+        METHOD_RUN_AUCTION.to_string(),
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(run_auction);
+
+    let mint_seigniorage = EntryPoint::new(
+        METHOD_MINT_SEIGNIORAGE.to_string(),
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(mint_seigniorage);
+
+    entry_points
+}
@@ -0,0 +1,55 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use contract::contract_api::{account, runtime};
+use types::{
+    account::{AccountHash, RecoveryConfig, Weight},
+    ApiError,
+};
+
+const ARG_STAGE: &str = "stage";
+const ARG_GUARDIANS: &str = "guardians";
+const ARG_THRESHOLD: &str = "threshold";
+const ARG_DELAY: &str = "delay";
+const ARG_ACCOUNT: &str = "account";
+const ARG_NEW_KEYS: &str = "new_keys";
+
+const STAGE_CONFIGURE: &str = "configure";
+const STAGE_CANCEL: &str = "cancel";
+const STAGE_PROPOSE: &str = "propose";
+const STAGE_FINALIZE: &str = "finalize";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let stage: String = runtime::get_named_arg(ARG_STAGE);
+
+    let result = match stage.as_str() {
+        STAGE_CONFIGURE => {
+            let guardians: Vec<AccountHash> = runtime::get_named_arg(ARG_GUARDIANS);
+            let threshold: u8 = runtime::get_named_arg(ARG_THRESHOLD);
+            let delay: u64 = runtime::get_named_arg(ARG_DELAY);
+            let config = RecoveryConfig::new(guardians.into_iter().collect(), threshold, delay);
+            account::set_recovery_config(Some(config))
+        }
+        STAGE_CANCEL => account::cancel_recovery(),
+        STAGE_PROPOSE => {
+            let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+            let new_keys: BTreeMap<AccountHash, Weight> = runtime::get_named_arg(ARG_NEW_KEYS);
+            account::propose_recovery(account, new_keys)
+        }
+        STAGE_FINALIZE => {
+            let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+            account::finalize_recovery(account)
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    };
+
+    // Reports the `RecoveryFailure` code to the test
+    if let Err(error) = result {
+        runtime::revert(ApiError::User(error as u16))
+    }
+}
@@ -0,0 +1,50 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{boxed::Box, string::ToString, vec};
+
+use contract::contract_api::{runtime, storage};
+use standard_payment::ARG_AMOUNT;
+use types::{
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys, Parameter},
+    CLType, ContractPackageHash, Phase, U512,
+};
+
+const METHOD_CALL: &str = "call";
+const ARG_PACKAGE_HASH: &str = "package_hash";
+const LAST_PAYMENT_AMOUNT_KEY: &str = "last_payment_amount";
+
+/// Adds this module as a new version of the standard payment contract package.  When run as the
+/// payment code of a deploy, e.g. as that new version, it makes the standard payment and, unlike
+/// the genesis version, records the amount paid under the "last_payment_amount" named key.
+#[no_mangle]
+pub extern "C" fn call() {
+    if runtime::get_phase() == Phase::Payment {
+        standard_payment::delegate();
+        let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+        runtime::put_key(LAST_PAYMENT_AMOUNT_KEY, storage::new_uref(amount).into());
+        return;
+    }
+
+    let package_hash: ContractPackageHash = runtime::get_named_arg(ARG_PACKAGE_HASH);
+
+    let entry_points = {
+        let mut entry_points = EntryPoints::new();
+        let entry_point = EntryPoint::new(
+            METHOD_CALL.to_string(),
+            vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
+            CLType::Result {
+                ok: Box::new(CLType::Unit),
+                err: Box::new(CLType::U32),
+            },
+            EntryPointAccess::Public,
+            EntryPointType::Session,
+        );
+        entry_points.add_entry_point(entry_point);
+        entry_points
+    };
+
+    storage::add_contract_version(package_hash, entry_points, NamedKeys::new());
+}
@@ -0,0 +1,109 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+
+use contract::contract_api::{runtime, storage};
+use types::{contracts::NamedKeys, ApiError, ContractPackageHash};
+
+const ARG_PACKAGE_HASH: &str = "package_hash";
+const ARG_SYSTEM_CONTRACT: &str = "system_contract";
+const SYSTEM_CONTRACT_MINT: &str = "mint";
+const SYSTEM_CONTRACT_POS: &str = "pos";
+const NEW_VERSION_KEY: &str = "new_version";
+
+#[no_mangle]
+pub extern "C" fn mint() {
+    mint_token::mint();
+}
+
+#[no_mangle]
+pub extern "C" fn create() {
+    mint_token::create();
+}
+
+#[no_mangle]
+pub extern "C" fn balance() {
+    mint_token::balance();
+}
+
+#[no_mangle]
+pub extern "C" fn transfer() {
+    mint_token::transfer();
+}
+
+#[no_mangle]
+pub extern "C" fn burn() {
+    mint_token::burn();
+}
+
+#[no_mangle]
+pub extern "C" fn mint_into() {
+    mint_token::mint_into();
+}
+
+#[no_mangle]
+pub extern "C" fn total_supply() {
+    mint_token::total_supply();
+}
+
+#[no_mangle]
+pub extern "C" fn bond() {
+    pos::bond();
+}
+
+#[no_mangle]
+pub extern "C" fn unbond() {
+    pos::unbond();
+}
+
+#[no_mangle]
+pub extern "C" fn get_payment_purse() {
+    pos::get_payment_purse();
+}
+
+#[no_mangle]
+pub extern "C" fn set_refund_purse() {
+    pos::set_refund_purse();
+}
+
+#[no_mangle]
+pub extern "C" fn get_refund_purse() {
+    pos::get_refund_purse();
+}
+
+#[no_mangle]
+pub extern "C" fn finalize_payment() {
+    pos::finalize_payment();
+}
+
+#[no_mangle]
+pub extern "C" fn run_auction() {
+    pos::run_auction();
+}
+
+#[no_mangle]
+pub extern "C" fn mint_seigniorage() {
+    pos::mint_seigniorage();
+}
+
+/// Adds this module as a new version of the mint or Proof of Stake contract package, marked by the
+/// "new_version" named key.  The new version keeps the named keys of the version it replaces.
+#[no_mangle]
+pub extern "C" fn call() {
+    let package_hash: ContractPackageHash = runtime::get_named_arg(ARG_PACKAGE_HASH);
+    let system_contract: String = runtime::get_named_arg(ARG_SYSTEM_CONTRACT);
+
+    let entry_points = match system_contract.as_str() {
+        SYSTEM_CONTRACT_MINT => mint_token::get_entry_points(),
+        SYSTEM_CONTRACT_POS => pos::get_entry_points(),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    };
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(NEW_VERSION_KEY.to_string(), storage::new_uref(()).into());
+
+    storage::add_contract_version(package_hash, entry_points, named_keys);
+}
@@ -0,0 +1,49 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{account, runtime},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::{AccountHash, ActionType, ContractAllowList, Weight},
+    ApiError, ContractPackageHash,
+};
+
+const ARG_STAGE: &str = "stage";
+const ARG_ACCOUNT: &str = "account";
+const ARG_CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+const ARG_ENTRY_POINT: &str = "entry_point";
+
+const STAGE_SETUP: &str = "setup";
+const STAGE_ALLOW: &str = "allow";
+const STAGE_CLEAR: &str = "clear";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let stage: String = runtime::get_named_arg(ARG_STAGE);
+
+    match stage.as_str() {
+        STAGE_SETUP => {
+            // The added key can deploy on its own, but can't manage the account's keys
+            let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+            account::add_associated_key(account, Weight::new(1)).unwrap_or_revert();
+            account::set_action_threshold(ActionType::KeyManagement, Weight::new(2))
+                .unwrap_or_revert();
+        }
+        STAGE_ALLOW => {
+            let contract_package_hash: ContractPackageHash =
+                runtime::get_named_arg(ARG_CONTRACT_PACKAGE_HASH);
+            let entry_point: String = runtime::get_named_arg(ARG_ENTRY_POINT);
+            let mut contract_allow_list = ContractAllowList::new();
+            contract_allow_list.allow(contract_package_hash, entry_point);
+            account::set_contract_allow_list(Some(contract_allow_list)).unwrap_or_revert();
+        }
+        STAGE_CLEAR => account::set_contract_allow_list(None).unwrap_or_revert(),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -0,0 +1,105 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::{
+        ContractMetadata, EntryPoint, EntryPointAccess, EntryPointDocs, EntryPointType,
+        EntryPoints, NamedKeys, NamedKeysSchema,
+    },
+    ApiError, CLType, CLTyped, Key,
+};
+
+const ARG_STAGE: &str = "stage";
+const ARG_PACKAGE_HASH: &str = "package_hash";
+
+const STAGE_INSTALL: &str = "install";
+const STAGE_SET_FOREIGN: &str = "set_foreign";
+
+const ENTRY_FUNCTION_NAME: &str = "delegate";
+const PACKAGE_HASH_KEY: &str = "metadata_package";
+const PACKAGE_ACCESS_KEY: &str = "metadata_access";
+const COUNTER_KEY: &str = "counter";
+
+#[no_mangle]
+pub extern "C" fn delegate() {}
+
+fn entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_FUNCTION_NAME,
+        Vec::new(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+fn package_metadata() -> ContractMetadata {
+    ContractMetadata::new(
+        "Metadata".to_string(),
+        "Describes itself".to_string(),
+        None,
+        EntryPointDocs::new(),
+        NamedKeysSchema::new(),
+    )
+}
+
+fn version_metadata() -> ContractMetadata {
+    let mut entry_point_docs = EntryPointDocs::new();
+    entry_point_docs.insert(ENTRY_FUNCTION_NAME.to_string(), "Does nothing".to_string());
+    let mut named_keys_schema = NamedKeysSchema::new();
+    named_keys_schema.insert(COUNTER_KEY.to_string(), u64::cl_type());
+    ContractMetadata::new(
+        "Metadata v1".to_string(),
+        "Describes its first version".to_string(),
+        Some([1; 32]),
+        entry_point_docs,
+        named_keys_schema,
+    )
+}
+
+fn install() {
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(COUNTER_KEY.to_string(), storage::new_uref(0u64).into());
+    let (contract_hash, _contract_version) = storage::new_contract(
+        entry_points(),
+        Some(named_keys),
+        Some(PACKAGE_HASH_KEY.to_string()),
+        Some(PACKAGE_ACCESS_KEY.to_string()),
+    );
+    let package_hash = runtime::get_key(PACKAGE_HASH_KEY)
+        .and_then(Key::into_hash)
+        .unwrap_or_revert_with(ApiError::GetKey);
+
+    storage::set_contract_metadata(package_hash, None, Some(package_metadata())).unwrap_or_revert();
+    storage::set_contract_metadata(package_hash, Some(contract_hash), Some(version_metadata()))
+        .unwrap_or_revert();
+}
+
+fn set_foreign() {
+    let package_hash: Key = runtime::get_named_arg(ARG_PACKAGE_HASH);
+    storage::set_contract_metadata(package_hash.into_seed(), None, None).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let stage: String = runtime::get_named_arg(ARG_STAGE);
+
+    match stage.as_str() {
+        STAGE_INSTALL => install(),
+        STAGE_SET_FOREIGN => set_foreign(),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -0,0 +1,140 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::{
+        EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys,
+        ARG_PREVIOUS_CONTRACT_HASH,
+    },
+    ApiError, CLType, ContractHash, Key,
+};
+
+const ARG_STAGE: &str = "stage";
+
+const STAGE_INSTALL: &str = "install";
+const STAGE_UPGRADE: &str = "upgrade";
+const STAGE_UPGRADE_FAILING: &str = "upgrade_failing";
+const STAGE_UPGRADE_UNKNOWN: &str = "upgrade_unknown";
+const STAGE_UPGRADE_SESSION: &str = "upgrade_session";
+const STAGE_UPGRADE_PUBLIC: &str = "upgrade_public";
+
+const ENTRY_FUNCTION_NAME: &str = "delegate";
+const MIGRATE: &str = "migrate";
+const FAIL_MIGRATION: &str = "fail_migration";
+const UNKNOWN_MIGRATION: &str = "unknown_migration";
+const ADMIN_GROUP: &str = "admin";
+const PACKAGE_HASH_KEY: &str = "migration_package";
+const PACKAGE_ACCESS_KEY: &str = "migration_access";
+const MIGRATED_FROM_KEY: &str = "migrated_from";
+const MIGRATION_FAILED: u16 = 1;
+
+#[no_mangle]
+pub extern "C" fn delegate() {}
+
+/// Records the version migrated from in the new version's named keys.
+#[no_mangle]
+pub extern "C" fn migrate() {
+    let previous_contract_hash: ContractHash = runtime::get_named_arg(ARG_PREVIOUS_CONTRACT_HASH);
+    runtime::put_key(MIGRATED_FROM_KEY, Key::Hash(previous_contract_hash));
+}
+
+#[no_mangle]
+pub extern "C" fn fail_migration() {
+    runtime::put_key(MIGRATED_FROM_KEY, Key::Hash(ContractHash::default()));
+    runtime::revert(ApiError::User(MIGRATION_FAILED))
+}
+
+/// Migrations are only meant to be called by the runtime, so nobody holds the admin group's URefs.
+fn migration_entry_point(name: &str, entry_point_type: EntryPointType) -> EntryPoint {
+    EntryPoint::new(
+        name,
+        Vec::new(),
+        CLType::Unit,
+        EntryPointAccess::groups(&[ADMIN_GROUP]),
+        entry_point_type,
+    )
+}
+
+fn entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_FUNCTION_NAME,
+        Vec::new(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+fn install() {
+    let (package_hash, access_uref) = storage::create_contract_package_at_hash();
+    runtime::put_key(PACKAGE_HASH_KEY, package_hash.into());
+    runtime::put_key(PACKAGE_ACCESS_KEY, access_uref.into());
+
+    storage::create_contract_user_group(package_hash, ADMIN_GROUP, 0, BTreeSet::new())
+        .unwrap_or_revert();
+
+    storage::add_contract_version(package_hash, entry_points(), NamedKeys::new());
+}
+
+fn upgrade(migration: &str, migration_entry_point: Option<EntryPoint>) {
+    let package_hash = runtime::get_key(PACKAGE_HASH_KEY)
+        .and_then(Key::into_hash)
+        .unwrap_or_revert_with(ApiError::GetKey);
+
+    let mut entry_points = entry_points();
+    if let Some(entry_point) = migration_entry_point {
+        entry_points.add_entry_point(entry_point);
+    }
+    storage::add_contract_version_with_migration(
+        package_hash,
+        entry_points,
+        NamedKeys::new(),
+        migration,
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let stage: String = runtime::get_named_arg(ARG_STAGE);
+
+    match stage.as_str() {
+        STAGE_INSTALL => install(),
+        STAGE_UPGRADE => upgrade(
+            MIGRATE,
+            Some(migration_entry_point(MIGRATE, EntryPointType::Contract)),
+        ),
+        STAGE_UPGRADE_FAILING => upgrade(
+            FAIL_MIGRATION,
+            Some(migration_entry_point(
+                FAIL_MIGRATION,
+                EntryPointType::Contract,
+            )),
+        ),
+        STAGE_UPGRADE_UNKNOWN => upgrade(UNKNOWN_MIGRATION, None),
+        STAGE_UPGRADE_SESSION => upgrade(
+            MIGRATE,
+            Some(migration_entry_point(MIGRATE, EntryPointType::Session)),
+        ),
+        STAGE_UPGRADE_PUBLIC => upgrade(
+            MIGRATE,
+            Some(EntryPoint::new(
+                MIGRATE,
+                Vec::new(),
+                CLType::Unit,
+                EntryPointAccess::Public,
+                EntryPointType::Contract,
+            )),
+        ),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -0,0 +1,185 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{
+    collections::BTreeSet,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use contract::{
+    contract_api::{account, runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::{AccountHash, Weight},
+    contracts::{
+        EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Group, NamedKeys, Parameter,
+    },
+    runtime_args, ApiError, CLType, CLTyped, CLValue, Key, RuntimeArgs, URef,
+};
+
+const ARG_STAGE: &str = "stage";
+const ARG_MEMBERS: &str = "members";
+const ARG_PACKAGE_HASH: &str = "package_hash";
+const ARG_MEMBER: &str = "member";
+const ARG_MEMBERSHIP: &str = "membership";
+
+const STAGE_INSTALL: &str = "install";
+const STAGE_CLAIM: &str = "claim";
+const STAGE_ADMIT: &str = "admit";
+const STAGE_EXPEL: &str = "expel";
+
+const MEMBERS_GROUP: &str = "members";
+const CLAIM_MEMBERSHIP: &str = "claim_membership";
+const SET_ASIDE_MEMBERSHIP: &str = "set_aside_membership";
+const CREATE_ACCOUNT: &str = "create_account";
+const PACKAGE_HASH_KEY: &str = "dao_package";
+const PACKAGE_ACCESS_KEY: &str = "dao_access";
+const DAO_ACCOUNT_KEY: &str = "dao_account";
+const MEMBERSHIP_KEY: &str = "dao_membership";
+
+/// Hands the URef of the members group set aside for the calling account over to it.
+#[no_mangle]
+pub extern "C" fn claim_membership() {
+    let caller = runtime::get_caller().to_string();
+    let membership = runtime::get_key(&caller).unwrap_or_revert_with(ApiError::PermissionDenied);
+    runtime::remove_key(&caller);
+    let membership = membership.into_uref().unwrap_or_revert();
+    runtime::ret(CLValue::from_t(membership).unwrap_or_revert())
+}
+
+/// Sets the given URef of the members group aside for the given account to claim.
+#[no_mangle]
+pub extern "C" fn set_aside_membership() {
+    let member: AccountHash = runtime::get_named_arg(ARG_MEMBER);
+    let membership: URef = runtime::get_named_arg(ARG_MEMBERSHIP);
+    runtime::put_key(&member.to_string(), membership.into());
+}
+
+/// Creates the DAO's account, whose deploys need two of the three members to sign, and all of them
+/// to manage it.
+#[no_mangle]
+pub extern "C" fn create_account() {
+    if runtime::has_key(DAO_ACCOUNT_KEY) {
+        runtime::revert(ApiError::PermissionDenied);
+    }
+    let account_hash =
+        account::create_contract_account(Group::new(MEMBERS_GROUP), Weight::new(2), Weight::new(3))
+            .unwrap_or_revert();
+    runtime::put_key(DAO_ACCOUNT_KEY, Key::Account(account_hash));
+    runtime::ret(CLValue::from_t(account_hash).unwrap_or_revert())
+}
+
+fn entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        CLAIM_MEMBERSHIP,
+        Vec::new(),
+        CLType::URef,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        SET_ASIDE_MEMBERSHIP,
+        vec![
+            Parameter::new(ARG_MEMBER, AccountHash::cl_type()),
+            Parameter::new(ARG_MEMBERSHIP, CLType::URef),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        CREATE_ACCOUNT,
+        Vec::new(),
+        AccountHash::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+fn install() {
+    let members: Vec<AccountHash> = runtime::get_named_arg(ARG_MEMBERS);
+
+    let (package_hash, access_uref) = storage::create_contract_package_at_hash();
+    runtime::put_key(PACKAGE_HASH_KEY, package_hash.into());
+    runtime::put_key(PACKAGE_ACCESS_KEY, access_uref.into());
+
+    let group_urefs = storage::create_contract_user_group(
+        package_hash,
+        MEMBERS_GROUP,
+        members.len() as u8,
+        BTreeSet::new(),
+    )
+    .unwrap_or_revert();
+
+    // Each member's URef is kept by the contract until the member claims it
+    let mut named_keys = NamedKeys::new();
+    for (member, group_uref) in members.iter().zip(group_urefs) {
+        named_keys.insert(member.to_string(), group_uref.into());
+    }
+    storage::add_contract_version(package_hash, entry_points(), named_keys);
+
+    let account_hash: AccountHash =
+        runtime::call_versioned_contract(package_hash, None, CREATE_ACCOUNT, runtime_args! {});
+    runtime::put_key(DAO_ACCOUNT_KEY, Key::Account(account_hash));
+}
+
+fn claim() {
+    let package_hash: Key = runtime::get_named_arg(ARG_PACKAGE_HASH);
+    let membership: URef = runtime::call_versioned_contract(
+        package_hash.into_seed(),
+        None,
+        CLAIM_MEMBERSHIP,
+        runtime_args! {},
+    );
+    // A member may hold several URefs of the group
+    let name = format!("{}_{}", MEMBERSHIP_KEY, membership.as_string());
+    runtime::put_key(&name, membership.into());
+}
+
+fn package_hash() -> Key {
+    runtime::get_key(PACKAGE_HASH_KEY).unwrap_or_revert_with(ApiError::MissingKey)
+}
+
+/// Adds a new URef to the members group and sets it aside for the given account.
+fn admit() {
+    let member: AccountHash = runtime::get_named_arg(ARG_MEMBER);
+    let package_hash = package_hash().into_seed();
+    let membership =
+        storage::provision_contract_user_group_uref(package_hash, MEMBERS_GROUP).unwrap_or_revert();
+    runtime::call_versioned_contract::<()>(
+        package_hash,
+        None,
+        SET_ASIDE_MEMBERSHIP,
+        runtime_args! { ARG_MEMBER => member, ARG_MEMBERSHIP => membership },
+    );
+}
+
+/// Removes the given URef from the members group.
+fn expel() {
+    let membership: URef = runtime::get_named_arg(ARG_MEMBERSHIP);
+    let mut urefs = BTreeSet::new();
+    urefs.insert(membership);
+    storage::remove_contract_user_group_urefs(package_hash().into_seed(), MEMBERS_GROUP, urefs)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let stage: String = runtime::get_named_arg(ARG_STAGE);
+
+    match stage.as_str() {
+        STAGE_INSTALL => install(),
+        STAGE_CLAIM => claim(),
+        STAGE_ADMIT => admit(),
+        STAGE_EXPEL => expel(),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
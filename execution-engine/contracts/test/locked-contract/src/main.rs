@@ -0,0 +1,125 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::{EntryPoint, EntryPoints, NamedKeys},
+    ApiError, CLType, EntryPointAccess, EntryPointType, Key,
+};
+
+const ARG_STAGE: &str = "stage";
+
+const STAGE_INSTALL: &str = "install";
+const STAGE_ADD_VERSION: &str = "add_version";
+const STAGE_DISABLE_VERSION: &str = "disable_version";
+const STAGE_CREATE_GROUP: &str = "create_group";
+const STAGE_REMOVE_GROUP: &str = "remove_group";
+const STAGE_PROVISION_UREF: &str = "provision_uref";
+const STAGE_REMOVE_UREFS: &str = "remove_urefs";
+const STAGE_SET_PACKAGE_METADATA: &str = "set_package_metadata";
+const STAGE_SET_VERSION_METADATA: &str = "set_version_metadata";
+
+const ENTRY_FUNCTION_NAME: &str = "delegate";
+const PACKAGE_HASH_KEY: &str = "locked_package";
+const CONTRACT_HASH_KEY: &str = "locked_contract";
+const GROUP_LABEL: &str = "group";
+
+#[no_mangle]
+pub extern "C" fn delegate() {}
+
+fn entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_FUNCTION_NAME.to_string(),
+        Vec::new(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+fn install() {
+    let (contract_hash, _contract_version) =
+        storage::new_locked_contract(entry_points(), None, Some(PACKAGE_HASH_KEY.to_string()));
+    runtime::put_key(CONTRACT_HASH_KEY, Key::Hash(contract_hash));
+}
+
+fn named_hash(name: &str) -> [u8; 32] {
+    runtime::get_key(name)
+        .and_then(Key::into_hash)
+        .unwrap_or_revert_with(ApiError::GetKey)
+}
+
+fn add_version() {
+    let package_hash = named_hash(PACKAGE_HASH_KEY);
+    storage::add_contract_version(package_hash, entry_points(), NamedKeys::new());
+}
+
+fn disable_version() {
+    let package_hash = named_hash(PACKAGE_HASH_KEY);
+    let contract_hash = named_hash(CONTRACT_HASH_KEY);
+    storage::disable_contract_version(package_hash, contract_hash).unwrap_or_revert();
+}
+
+fn create_group() {
+    let package_hash = named_hash(PACKAGE_HASH_KEY);
+    storage::create_contract_user_group(package_hash, GROUP_LABEL, 1, BTreeSet::new())
+        .unwrap_or_revert();
+}
+
+fn remove_group() {
+    let package_hash = named_hash(PACKAGE_HASH_KEY);
+    storage::remove_contract_user_group(package_hash, GROUP_LABEL).unwrap_or_revert();
+}
+
+fn provision_uref() {
+    let package_hash = named_hash(PACKAGE_HASH_KEY);
+    storage::provision_contract_user_group_uref(package_hash, GROUP_LABEL).unwrap_or_revert();
+}
+
+fn remove_urefs() {
+    let package_hash = named_hash(PACKAGE_HASH_KEY);
+    storage::remove_contract_user_group_urefs(package_hash, GROUP_LABEL, BTreeSet::new())
+        .unwrap_or_revert();
+}
+
+fn set_package_metadata() {
+    let package_hash = named_hash(PACKAGE_HASH_KEY);
+    storage::set_contract_metadata(package_hash, None, None).unwrap_or_revert();
+}
+
+fn set_version_metadata() {
+    let package_hash = named_hash(PACKAGE_HASH_KEY);
+    let contract_hash = named_hash(CONTRACT_HASH_KEY);
+    storage::set_contract_metadata(package_hash, Some(contract_hash), None).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let stage: String = runtime::get_named_arg(ARG_STAGE);
+
+    match stage.as_str() {
+        STAGE_INSTALL => install(),
+        STAGE_ADD_VERSION => add_version(),
+        STAGE_DISABLE_VERSION => disable_version(),
+        STAGE_CREATE_GROUP => create_group(),
+        STAGE_REMOVE_GROUP => remove_group(),
+        STAGE_PROVISION_UREF => provision_uref(),
+        STAGE_REMOVE_UREFS => remove_urefs(),
+        STAGE_SET_PACKAGE_METADATA => set_package_metadata(),
+        STAGE_SET_VERSION_METADATA => set_version_metadata(),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
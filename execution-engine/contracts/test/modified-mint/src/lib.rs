@@ -36,6 +36,10 @@ impl RuntimeProvider for MintContract {
         runtime::get_caller()
     }
 
+    fn get_key(&self, name: &str) -> Option<Key> {
+        runtime::get_key(name)
+    }
+
     fn put_key(&mut self, name: &str, key: Key) {
         runtime::put_key(name, key)
     }
@@ -82,7 +82,7 @@ fn upgrade_mint() -> (ContractHash, ContractVersion) {
 
 fn upgrade_proof_of_stake() -> (ContractHash, ContractVersion) {
     use pos::{
-        ARG_ACCOUNT_KEY, ARG_AMOUNT, ARG_PURSE, METHOD_BOND, METHOD_FINALIZE_PAYMENT,
+        ARG_ACCOUNT_KEY, ARG_AMOUNT, ARG_PROPOSER, ARG_PURSE, METHOD_BOND, METHOD_FINALIZE_PAYMENT,
         METHOD_GET_PAYMENT_PURSE, METHOD_GET_REFUND_PURSE, METHOD_SET_REFUND_PURSE, METHOD_UNBOND,
     };
 
@@ -154,6 +154,10 @@ fn upgrade_proof_of_stake() -> (ContractHash, ContractVersion) {
             vec![
                 Parameter::new(ARG_AMOUNT, CLType::U512),
                 Parameter::new(ARG_ACCOUNT_KEY, CLType::FixedList(Box::new(CLType::U8), 32)),
+                Parameter::new(
+                    ARG_PROPOSER,
+                    CLType::Option(Box::new(CLType::FixedList(Box::new(CLType::U8), 32))),
+                ),
             ],
             CLType::Unit,
             EntryPointAccess::Public,
@@ -0,0 +1,21 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::AccountHash, U512};
+
+const ARG_TARGET: &str = "target";
+const ARG_TRANSFER_AMOUNT: &str = "transfer_amount";
+
+/// Pays for the deploy like the standard payment, then also transfers out of the main purse.
+#[no_mangle]
+pub extern "C" fn call() {
+    standard_payment::delegate();
+
+    let target: AccountHash = runtime::get_named_arg(ARG_TARGET);
+    let transfer_amount: U512 = runtime::get_named_arg(ARG_TRANSFER_AMOUNT);
+    system::transfer_to_account(target, transfer_amount).unwrap_or_revert();
+}
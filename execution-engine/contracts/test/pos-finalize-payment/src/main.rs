@@ -12,6 +12,7 @@ pub const ARG_AMOUNT_SPENT: &str = "amount_spent";
 pub const ARG_REFUND_FLAG: &str = "refund";
 pub const ARG_PURSE: &str = "purse";
 pub const ARG_ACCOUNT_KEY: &str = "account";
+pub const ARG_PROPOSER: &str = "proposer";
 
 fn set_refund_purse(contract_hash: ContractHash, purse: URef) {
     runtime::call_contract(
@@ -40,6 +41,7 @@ fn finalize_payment(contract_hash: ContractHash, amount_spent: U512, account: Ac
         runtime_args! {
             ARG_AMOUNT => amount_spent,
             ARG_ACCOUNT_KEY => account,
+            ARG_PROPOSER => Option::<AccountHash>::None,
         },
     )
 }
@@ -0,0 +1,20 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::{runtime, system};
+use types::Key;
+
+const MINT_KEY: &str = "mint_contract_hash";
+const POS_KEY: &str = "pos_contract_hash";
+const STANDARD_PAYMENT_KEY: &str = "standard_payment_contract_hash";
+
+/// Records the system contracts as seen by session code in the account's named keys.
+#[no_mangle]
+pub extern "C" fn call() {
+    runtime::put_key(MINT_KEY, Key::Hash(system::get_mint()));
+    runtime::put_key(POS_KEY, Key::Hash(system::get_proof_of_stake()));
+    runtime::put_key(
+        STANDARD_PAYMENT_KEY,
+        Key::Hash(system::get_standard_payment()),
+    );
+}
@@ -0,0 +1,31 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{runtime_args, system_contract_errors::mint, RuntimeArgs, U512};
+
+const METHOD_TRANSFER: &str = "transfer";
+const ARG_SOURCE: &str = "source";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+
+/// Transfers out of the main purse by calling the mint directly, rather than through the transfer
+/// host functions.
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let source = account::get_main_purse();
+    let target = system::create_purse();
+
+    let args = runtime_args! {
+        ARG_SOURCE => source,
+        ARG_TARGET => target,
+        ARG_AMOUNT => amount,
+    };
+    let result: Result<(), mint::Error> =
+        runtime::call_contract(system::get_mint(), METHOD_TRANSFER, args);
+    result.unwrap_or_revert();
+}
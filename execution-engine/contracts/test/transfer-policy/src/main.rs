@@ -0,0 +1,26 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::{AccountHash, ActionType, Weight},
+    U512,
+};
+
+const ARG_ACCOUNT: &str = "account";
+const ARG_TRANSFER_THRESHOLD: &str = "transfer_threshold";
+const ARG_SPENDING_LIMIT: &str = "spending_limit";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+    let transfer_threshold: Weight = runtime::get_named_arg(ARG_TRANSFER_THRESHOLD);
+    let spending_limit: Option<U512> = runtime::get_named_arg(ARG_SPENDING_LIMIT);
+
+    account::add_associated_key(account, Weight::new(1)).unwrap_or_revert();
+    account::set_action_threshold(ActionType::Transfer, transfer_threshold).unwrap_or_revert();
+    account::set_spending_limit(spending_limit).unwrap_or_revert();
+}
@@ -0,0 +1,262 @@
+//! Detection of conflicts between deploys which were executed independently against the same
+//! parent state.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use engine_shared::{stored_value::StoredValue, transform::Transform};
+use types::Key;
+
+use super::{execution_effect::ExecutionEffect, op::Op};
+
+/// The keys which a deploy read, wrote and added to, taken from the ops of its effect.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeySets {
+    reads: BTreeSet<Key>,
+    writes: BTreeSet<Key>,
+    adds: BTreeSet<Key>,
+}
+
+impl KeySets {
+    pub fn reads(&self) -> &BTreeSet<Key> {
+        &self.reads
+    }
+
+    pub fn writes(&self) -> &BTreeSet<Key> {
+        &self.writes
+    }
+
+    pub fn adds(&self) -> &BTreeSet<Key> {
+        &self.adds
+    }
+
+    /// Returns `true` if the effects of the two deploys can be applied in either order with the
+    /// same outcome.
+    ///
+    /// That is the case unless one deploy writes a key which the other accesses in any way, or
+    /// adds to a key which the other reads.  Reads commute with reads, and adds with adds.
+    pub fn commutes_with(&self, other: &KeySets) -> bool {
+        self.writes.iter().all(|key| !other.accesses(key))
+            && other.writes.iter().all(|key| !self.accesses(key))
+            && self.adds.is_disjoint(&other.reads)
+            && other.adds.is_disjoint(&self.reads)
+    }
+
+    /// Records the accesses to those of `finalization_keys` which `effect` leaves with their given
+    /// value as reads.
+    ///
+    /// Such a deploy commutes with every other deploy which leaves the key as it was, but not with
+    /// one which changes it, since applying its effect afterwards would restore the old value.
+    fn read_unchanged_keys(
+        &mut self,
+        effect: &ExecutionEffect,
+        finalization_keys: &BTreeMap<Key, StoredValue>,
+    ) {
+        for (key, value) in finalization_keys {
+            let unchanged = match effect.transforms.get(key) {
+                None | Some(Transform::Identity) => true,
+                Some(Transform::Write(written_value)) => written_value == value,
+                Some(_) => false,
+            };
+            if !unchanged {
+                continue;
+            }
+            let written = self.writes.remove(key);
+            let added = self.adds.remove(key);
+            if written || added {
+                self.reads.insert(*key);
+            }
+        }
+    }
+
+    fn accesses(&self, key: &Key) -> bool {
+        self.reads.contains(key) || self.writes.contains(key) || self.adds.contains(key)
+    }
+}
+
+impl From<&ExecutionEffect> for KeySets {
+    fn from(effect: &ExecutionEffect) -> Self {
+        let mut key_sets = KeySets::default();
+        for (key, op) in effect.ops.iter() {
+            match op {
+                Op::Read => key_sets.reads.insert(*key),
+                Op::Write => key_sets.writes.insert(*key),
+                Op::Add => key_sets.adds.insert(*key),
+                Op::NoOp => continue,
+            };
+        }
+        key_sets
+    }
+}
+
+/// Every pair of deploys in a request, identified by their indices, split into those whose effects
+/// commute and those whose effects conflict.
+///
+/// Pairs are ordered with the lower index first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeployConflicts {
+    commuting_pairs: Vec<(usize, usize)>,
+    conflicting_pairs: Vec<(usize, usize)>,
+}
+
+impl DeployConflicts {
+    /// Checks every pair of `effects` for conflicts.
+    ///
+    /// Every deploy's payment and finalization writes `finalization_keys` (see
+    /// [`EngineState::finalization_keys`](super::EngineState::finalization_keys)), which would
+    /// otherwise make every pair of deploys conflict.  A deploy which leaves one of them with its
+    /// given value is treated as only reading it.
+    pub fn new<'a, I>(effects: I, finalization_keys: &BTreeMap<Key, StoredValue>) -> Self
+    where
+        I: IntoIterator<Item = &'a ExecutionEffect>,
+    {
+        let key_sets: Vec<KeySets> = effects
+            .into_iter()
+            .map(|effect| {
+                let mut key_sets = KeySets::from(effect);
+                key_sets.read_unchanged_keys(effect, finalization_keys);
+                key_sets
+            })
+            .collect();
+
+        let mut deploy_conflicts = DeployConflicts::default();
+        for (first, first_key_sets) in key_sets.iter().enumerate() {
+            for (second, second_key_sets) in key_sets.iter().enumerate().skip(first + 1) {
+                if first_key_sets.commutes_with(second_key_sets) {
+                    deploy_conflicts.commuting_pairs.push((first, second));
+                } else {
+                    deploy_conflicts.conflicting_pairs.push((first, second));
+                }
+            }
+        }
+        deploy_conflicts
+    }
+
+    pub fn commuting_pairs(&self) -> &[(usize, usize)] {
+        &self.commuting_pairs
+    }
+
+    pub fn conflicting_pairs(&self) -> &[(usize, usize)] {
+        &self.conflicting_pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, iter};
+
+    use engine_shared::{
+        additive_map::AdditiveMap, stored_value::StoredValue, transform::Transform,
+    };
+    use types::{CLValue, Key};
+
+    use super::{DeployConflicts, KeySets};
+    use crate::engine_state::{execution_effect::ExecutionEffect, op::Op};
+
+    const KEY_1: Key = Key::Hash([1; 32]);
+    const KEY_2: Key = Key::Hash([2; 32]);
+
+    fn effect(ops: &[(Key, Op)]) -> ExecutionEffect {
+        let ops: AdditiveMap<Key, Op> = ops.iter().cloned().collect();
+        let transforms: AdditiveMap<Key, Transform> =
+            ops.keys().map(|key| (*key, Transform::Identity)).collect();
+        ExecutionEffect::new(ops, transforms)
+    }
+
+    fn value(value: u64) -> StoredValue {
+        StoredValue::CLValue(CLValue::from_t(value).expect("should create CLValue"))
+    }
+
+    /// Returns an effect which writes `value` under `key`, besides performing `ops`.
+    fn effect_writing(key: Key, value: StoredValue, ops: &[(Key, Op)]) -> ExecutionEffect {
+        let mut effect = effect(ops);
+        effect.ops.insert(key, Op::Write);
+        effect.transforms.insert(key, Transform::Write(value));
+        effect
+    }
+
+    fn commutes(first: &[(Key, Op)], second: &[(Key, Op)]) -> bool {
+        let first = KeySets::from(&effect(first));
+        let second = KeySets::from(&effect(second));
+        let commutes = first.commutes_with(&second);
+        assert_eq!(commutes, second.commutes_with(&first));
+        commutes
+    }
+
+    #[test]
+    fn should_record_key_sets_from_ops() {
+        let key_sets = KeySets::from(&effect(&[
+            (KEY_1, Op::Read),
+            (KEY_2, Op::Add),
+            (Key::Hash([3; 32]), Op::Write),
+            (Key::Hash([4; 32]), Op::NoOp),
+        ]));
+        assert_eq!(key_sets.reads().iter().collect::<Vec<_>>(), vec![&KEY_1]);
+        assert_eq!(key_sets.adds().iter().collect::<Vec<_>>(), vec![&KEY_2]);
+        assert_eq!(
+            key_sets.writes().iter().collect::<Vec<_>>(),
+            vec![&Key::Hash([3; 32])]
+        );
+    }
+
+    #[test]
+    fn should_commute() {
+        assert!(commutes(&[(KEY_1, Op::Read)], &[(KEY_1, Op::Read)]));
+        assert!(commutes(&[(KEY_1, Op::Add)], &[(KEY_1, Op::Add)]));
+        assert!(commutes(&[(KEY_1, Op::Write)], &[(KEY_2, Op::Write)]));
+        assert!(commutes(&[(KEY_1, Op::NoOp)], &[(KEY_1, Op::Write)]));
+        assert!(commutes(&[], &[(KEY_1, Op::Write)]));
+    }
+
+    #[test]
+    fn should_conflict() {
+        assert!(!commutes(&[(KEY_1, Op::Write)], &[(KEY_1, Op::Read)]));
+        assert!(!commutes(&[(KEY_1, Op::Write)], &[(KEY_1, Op::Write)]));
+        assert!(!commutes(&[(KEY_1, Op::Write)], &[(KEY_1, Op::Add)]));
+        assert!(!commutes(&[(KEY_1, Op::Add)], &[(KEY_1, Op::Read)]));
+    }
+
+    #[test]
+    fn should_split_pairs_of_deploys() {
+        let effects = vec![
+            effect(&[(KEY_1, Op::Write)]),
+            effect(&[(KEY_1, Op::Read), (KEY_2, Op::Add)]),
+            effect(&[(KEY_2, Op::Add)]),
+        ];
+        let deploy_conflicts = DeployConflicts::new(&effects, &BTreeMap::new());
+        assert_eq!(deploy_conflicts.commuting_pairs(), &[(0, 2), (1, 2)]);
+        assert_eq!(deploy_conflicts.conflicting_pairs(), &[(0, 1)]);
+    }
+
+    #[test]
+    fn should_read_unchanged_finalization_keys() {
+        let effects = vec![
+            effect_writing(KEY_1, value(0), &[(KEY_2, Op::Write)]),
+            effect_writing(KEY_1, value(0), &[]),
+            effect(&[(KEY_1, Op::Read)]),
+        ];
+        let finalization_keys = iter::once((KEY_1, value(0))).collect();
+        let deploy_conflicts = DeployConflicts::new(&effects, &finalization_keys);
+        assert_eq!(
+            deploy_conflicts.commuting_pairs(),
+            &[(0, 1), (0, 2), (1, 2)]
+        );
+        assert!(deploy_conflicts.conflicting_pairs().is_empty());
+    }
+
+    #[test]
+    fn should_conflict_on_changed_finalization_keys() {
+        let effects = vec![
+            effect_writing(KEY_1, value(1), &[]),
+            effect_writing(KEY_1, value(0), &[]),
+            effect(&[(KEY_1, Op::Read)]),
+            effect(&[(KEY_2, Op::Write)]),
+        ];
+        let finalization_keys = iter::once((KEY_1, value(0))).collect();
+        let deploy_conflicts = DeployConflicts::new(&effects, &finalization_keys);
+        assert_eq!(
+            deploy_conflicts.commuting_pairs(),
+            &[(0, 3), (1, 2), (1, 3), (2, 3)]
+        );
+        assert_eq!(deploy_conflicts.conflicting_pairs(), &[(0, 1), (0, 2)]);
+    }
+}
@@ -1,11 +1,36 @@
 use std::collections::BTreeSet;
 
-use types::account::AccountHash;
+use types::{account::AccountHash, BlockTime};
 
 use crate::{engine_state::executable_deploy_item::ExecutableDeployItem, DeployHash};
 
 type GasPrice = u64;
 
+/// The time at which a deploy was created and how long after that it may be executed, both in
+/// milliseconds.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DeployTtl {
+    pub timestamp: BlockTime,
+    pub ttl: u64,
+}
+
+impl DeployTtl {
+    pub fn new(timestamp: BlockTime, ttl: u64) -> Self {
+        DeployTtl { timestamp, ttl }
+    }
+
+    /// Returns the last block time at which the deploy may be executed.
+    pub fn expires_at(&self) -> BlockTime {
+        let timestamp: u64 = self.timestamp.into();
+        BlockTime::new(timestamp.saturating_add(self.ttl))
+    }
+
+    /// Returns `true` if a block with the given block time can no longer include the deploy.
+    pub fn is_expired(&self, blocktime: BlockTime) -> bool {
+        blocktime > self.expires_at()
+    }
+}
+
 /// Represents a deploy to be executed.  Corresponds to the similarly-named ipc protobuf message.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct DeployItem {
@@ -15,6 +40,9 @@ pub struct DeployItem {
     pub gas_price: GasPrice,
     pub authorization_keys: BTreeSet<AccountHash>,
     pub deploy_hash: DeployHash,
+    /// If set, the deploy expires, and can't be replayed in the context of the same account before
+    /// it does when replay protection is enabled.
+    pub ttl: Option<DeployTtl>,
 }
 
 impl DeployItem {
@@ -26,6 +54,7 @@ impl DeployItem {
         gas_price: GasPrice,
         authorization_keys: BTreeSet<AccountHash>,
         deploy_hash: DeployHash,
+        ttl: Option<DeployTtl>,
     ) -> Self {
         DeployItem {
             address,
@@ -34,6 +63,7 @@ impl DeployItem {
             gas_price,
             authorization_keys,
             deploy_hash,
+            ttl,
         }
     }
 }
@@ -1,9 +1,36 @@
+use crate::execution::WasmBackend;
+
+/// The default maximum number of contract modules held in the module cache.
+pub const DEFAULT_MODULE_CACHE_CAPACITY: usize = 256;
+
+/// The default maximum TTL of a deploy in milliseconds, i.e. one day.
+pub const DEFAULT_MAX_DEPLOY_TTL: u64 = 24 * 60 * 60 * 1000;
+
 /// The runtime configuration of the execution engine
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone)]
 pub struct EngineConfig {
     // feature flags go here
     use_system_contracts: bool,
     enable_bonding: bool,
+    enable_replay_protection: bool,
+    max_deploy_ttl: u64,
+    deploy_worker_count: usize,
+    module_cache_capacity: usize,
+    wasm_backend: WasmBackend,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        EngineConfig {
+            use_system_contracts: false,
+            enable_bonding: false,
+            enable_replay_protection: false,
+            max_deploy_ttl: DEFAULT_MAX_DEPLOY_TTL,
+            deploy_worker_count: 0,
+            module_cache_capacity: DEFAULT_MODULE_CACHE_CAPACITY,
+            wasm_backend: WasmBackend::default(),
+        }
+    }
 }
 
 impl EngineConfig {
@@ -29,4 +56,58 @@ impl EngineConfig {
         self.enable_bonding = enable_bonding;
         self
     }
+
+    /// Whether the hashes of deploys with a TTL are recorded in their account until they expire,
+    /// so that replaying them is rejected.
+    pub fn enable_replay_protection(self) -> bool {
+        self.enable_replay_protection
+    }
+
+    pub fn with_enable_replay_protection(mut self, enable_replay_protection: bool) -> EngineConfig {
+        self.enable_replay_protection = enable_replay_protection;
+        self
+    }
+
+    /// The longest TTL in milliseconds a deploy may have.  Deploys with a longer TTL are rejected,
+    /// which bounds how long a recorded deploy stays in its account.
+    pub fn max_deploy_ttl(self) -> u64 {
+        self.max_deploy_ttl
+    }
+
+    pub fn with_max_deploy_ttl(mut self, max_deploy_ttl: u64) -> EngineConfig {
+        self.max_deploy_ttl = max_deploy_ttl;
+        self
+    }
+
+    /// The number of threads on which the deploys of an `ExecuteRequest` are executed.  A value of
+    /// 0 or 1 means they are executed one at a time on the calling thread.
+    pub fn deploy_worker_count(self) -> usize {
+        self.deploy_worker_count
+    }
+
+    pub fn with_deploy_worker_count(mut self, deploy_worker_count: usize) -> EngineConfig {
+        self.deploy_worker_count = deploy_worker_count;
+        self
+    }
+
+    /// The maximum number of deserialized contract modules kept in the module cache.  A value of
+    /// 0 disables the cache.
+    pub fn module_cache_capacity(self) -> usize {
+        self.module_cache_capacity
+    }
+
+    pub fn with_module_cache_capacity(mut self, module_cache_capacity: usize) -> EngineConfig {
+        self.module_cache_capacity = module_cache_capacity;
+        self
+    }
+
+    /// The backend used to execute Wasm.
+    pub fn wasm_backend(self) -> WasmBackend {
+        self.wasm_backend
+    }
+
+    pub fn with_wasm_backend(mut self, wasm_backend: WasmBackend) -> EngineConfig {
+        self.wasm_backend = wasm_backend;
+        self
+    }
 }
@@ -19,6 +19,14 @@ pub enum Error {
     InvalidProtocolVersion(ProtocolVersion),
     #[fail(display = "Invalid upgrade config")]
     InvalidUpgradeConfig,
+    #[fail(display = "Invalid auction config")]
+    InvalidAuctionConfig,
+    #[fail(display = "Invalid fee burn rate: {}%", _0)]
+    InvalidFeeBurnRate(u8),
+    #[fail(display = "Invalid maximum Wasm memory: {} pages", _0)]
+    InvalidMaxMemory(u32),
+    #[fail(display = "Governance account is not a genesis account")]
+    InvalidGovernanceAccount,
     #[fail(display = "Wasm preprocessing error: {}", _0)]
     WasmPreprocessing(engine_wasm_prep::PreprocessingError),
     #[fail(display = "Wasm serialization error: {:?}", _0)]
@@ -47,6 +55,14 @@ pub enum Error {
     InvalidUpgradeResult,
     #[fail(display = "Unsupported deploy item variant: {}", _0)]
     InvalidDeployItemVariant(String),
+    #[fail(display = "Deploy not permitted by the account's contract allow-list")]
+    ContractNotAllowed,
+    #[fail(display = "Deploy expired")]
+    DeployExpired,
+    #[fail(display = "Deploy already executed")]
+    DeployReplayed,
+    #[fail(display = "Deploy TTL exceeds the maximum of {} ms", _0)]
+    DeployTtlTooLong(u64),
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
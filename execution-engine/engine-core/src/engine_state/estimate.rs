@@ -0,0 +1,99 @@
+use engine_shared::{gas::Gas, newtypes::Blake2bHash};
+use types::ProtocolVersion;
+
+use super::{deploy_item::DeployItem, execution_effect::ExecutionEffect, Error};
+
+/// A request to execute a single deploy as a dry run, in order to estimate the gas it consumes.
+#[derive(Debug)]
+pub struct EstimateRequest {
+    pub parent_state_hash: Blake2bHash,
+    pub block_time: u64,
+    pub deploy_item: DeployItem,
+    pub protocol_version: ProtocolVersion,
+}
+
+impl EstimateRequest {
+    pub fn new(
+        parent_state_hash: Blake2bHash,
+        block_time: u64,
+        deploy_item: DeployItem,
+        protocol_version: ProtocolVersion,
+    ) -> Self {
+        EstimateRequest {
+            parent_state_hash,
+            block_time,
+            deploy_item,
+            protocol_version,
+        }
+    }
+}
+
+/// The outcome of a dry run of a deploy.
+///
+/// If the deploy couldn't be executed at all, e.g. because its Wasm is invalid, both costs are
+/// zero and the effect is empty.
+#[derive(Debug)]
+pub struct EstimateResult {
+    payment_cost: Gas,
+    session_cost: Gas,
+    effect: ExecutionEffect,
+    error: Option<Error>,
+}
+
+impl EstimateResult {
+    pub fn new(
+        payment_cost: Gas,
+        session_cost: Gas,
+        effect: ExecutionEffect,
+        error: Option<Error>,
+    ) -> Self {
+        EstimateResult {
+            payment_cost,
+            session_cost,
+            effect,
+            error,
+        }
+    }
+
+    pub fn precondition_failure(error: Error) -> Self {
+        EstimateResult::new(
+            Gas::default(),
+            Gas::default(),
+            ExecutionEffect::default(),
+            Some(error),
+        )
+    }
+
+    /// The gas consumed by the payment code.
+    pub fn payment_cost(&self) -> Gas {
+        self.payment_cost
+    }
+
+    /// The gas consumed by the session code.
+    pub fn session_cost(&self) -> Gas {
+        self.session_cost
+    }
+
+    /// The gas consumed by the payment and session code together.
+    pub fn total_cost(&self) -> Gas {
+        self.payment_cost + self.session_cost
+    }
+
+    /// The effect of the payment code, combined with that of the session code if it succeeded.
+    pub fn effect(&self) -> &ExecutionEffect {
+        &self.effect
+    }
+
+    /// The error the deploy failed with, if any.
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    pub fn take_error(self) -> Option<Error> {
+        self.error
+    }
+}
@@ -1,16 +1,40 @@
 use std::mem;
 
 use engine_shared::newtypes::Blake2bHash;
-use types::ProtocolVersion;
+use types::{account::AccountHash, ProtocolVersion};
 
 use super::{deploy_item::DeployItem, execution_result::ExecutionResult};
 
+/// Determines how the deploys of an [`ExecuteRequest`] are executed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionMode {
+    /// Every deploy is executed against the parent state, independently of the others.
+    Independent,
+    /// The deploys are executed in order, each one observing the effects of its predecessors, and
+    /// their combined effect is committed.
+    Sequential,
+}
+
+impl Default for ExecutionMode {
+    fn default() -> Self {
+        ExecutionMode::Independent
+    }
+}
+
 #[derive(Debug)]
 pub struct ExecuteRequest {
     pub parent_state_hash: Blake2bHash,
     pub block_time: u64,
     pub deploys: Vec<Result<DeployItem, ExecutionResult>>,
     pub protocol_version: ProtocolVersion,
+    /// The account which proposed the block and receives the fees, if any.
+    pub proposer: Option<AccountHash>,
+    pub execution_mode: ExecutionMode,
+    /// Whether to record an [`ExecutionTrace`](crate::execution::ExecutionTrace) of each deploy.
+    pub enable_tracing: bool,
+    /// Whether to also record the Wasm functions entered in the traces, so that a
+    /// [`GasProfile`](crate::execution::GasProfile) of each deploy can be built from them.
+    pub enable_profiling: bool,
 }
 
 impl ExecuteRequest {
@@ -19,12 +43,20 @@ impl ExecuteRequest {
         block_time: u64,
         deploys: Vec<Result<DeployItem, ExecutionResult>>,
         protocol_version: ProtocolVersion,
+        proposer: Option<AccountHash>,
+        execution_mode: ExecutionMode,
+        enable_tracing: bool,
+        enable_profiling: bool,
     ) -> Self {
         Self {
             parent_state_hash,
             block_time,
             deploys,
             protocol_version,
+            proposer,
+            execution_mode,
+            enable_tracing,
+            enable_profiling,
         }
     }
 
@@ -40,6 +72,10 @@ impl Default for ExecuteRequest {
             block_time: 0,
             deploys: vec![],
             protocol_version: Default::default(),
+            proposer: None,
+            execution_mode: Default::default(),
+            enable_tracing: false,
+            enable_profiling: false,
         }
     }
 }
@@ -1,19 +1,34 @@
+use std::collections::HashMap;
+
 use super::{error, execution_effect::ExecutionEffect, op::Op, CONV_RATE};
+use crate::execution::ExecutionTrace;
 use engine_shared::{
-    additive_map::AdditiveMap, gas::Gas, motes::Motes, newtypes::CorrelationId,
-    stored_value::StoredValue, transform::Transform,
+    additive_map::AdditiveMap,
+    gas::Gas,
+    motes::Motes,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+    TypeMismatch,
+};
+use engine_storage::global_state::{CommitResult, StateReader};
+use types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes},
+    CLTyped, CLValue, Key, U512,
 };
-use engine_storage::global_state::StateReader;
-use types::{bytesrepr::FromBytes, CLTyped, CLValue, Key};
 
 fn make_payment_error_effects(
+    base_effect: ExecutionEffect,
     max_payment_cost: Motes,
     account_main_purse_balance: Motes,
     account_main_purse: Key,
     rewards_purse: Key,
 ) -> ExecutionEffect {
-    let mut ops = AdditiveMap::new();
-    let mut transforms = AdditiveMap::new();
+    let ExecutionEffect {
+        mut ops,
+        mut transforms,
+    } = base_effect;
 
     let new_balance = account_main_purse_balance - max_payment_cost;
     // from_t for U512 is assumed to never panic
@@ -173,14 +188,18 @@ impl ExecutionResult {
         }
     }
 
+    /// Creates the result of a deploy whose payment failed, which takes the maximum payment from
+    /// the account's main purse on top of `base_effect`.
     pub fn new_payment_code_error(
         error: error::Error,
+        base_effect: ExecutionEffect,
         max_payment_cost: Motes,
         account_main_purse_balance: Motes,
         account_main_purse: Key,
         rewards_purse: Key,
     ) -> ExecutionResult {
         let effect = make_payment_error_effects(
+            base_effect,
             max_payment_cost,
             account_main_purse_balance,
             account_main_purse,
@@ -203,6 +222,50 @@ impl ExecutionResult {
     }
 }
 
+/// The outcome of executing the deploys of an `ExecuteRequest` in sequential mode.
+#[derive(Debug)]
+pub enum SequentialExecutionResult {
+    RootNotFound,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Serialization(bytesrepr::Error),
+    Success {
+        /// The result of every deploy, in the order in which they were executed, along with its
+        /// execution trace if tracing is enabled for the request.
+        execution_results: Vec<(ExecutionResult, Option<ExecutionTrace>)>,
+        /// The combined effect of all the deploys, which has been committed.
+        effect: ExecutionEffect,
+        post_state_hash: Blake2bHash,
+        bonded_validators: HashMap<AccountHash, U512>,
+    },
+}
+
+impl SequentialExecutionResult {
+    pub fn from_commit_result(
+        commit_result: CommitResult,
+        execution_results: Vec<(ExecutionResult, Option<ExecutionTrace>)>,
+        effect: ExecutionEffect,
+    ) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => SequentialExecutionResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => SequentialExecutionResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => {
+                SequentialExecutionResult::TypeMismatch(type_mismatch)
+            }
+            CommitResult::Serialization(error) => SequentialExecutionResult::Serialization(error),
+            CommitResult::Success {
+                state_root,
+                bonded_validators,
+            } => SequentialExecutionResult::Success {
+                execution_results,
+                effect,
+                post_state_hash: state_root,
+                bonded_validators,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ExecutionResultBuilderError {
     MissingPaymentExecutionResult,
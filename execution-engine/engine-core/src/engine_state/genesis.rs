@@ -8,15 +8,21 @@ use rand::{
 
 use engine_shared::{motes::Motes, newtypes::Blake2bHash, TypeMismatch};
 use engine_storage::global_state::CommitResult;
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{wasm_costs::WasmCosts, MAX_MEMORY_PAGES};
 use types::{account::AccountHash, bytesrepr, Key, ProtocolVersion, U512};
 
+pub use mint::TOTAL_SUPPLY_KEY;
+pub use proof_of_stake::{AuctionConfig, StakingLimits, MAX_FEE_BURN_RATE};
+
 use crate::engine_state::execution_effect::ExecutionEffect;
 
 pub const PLACEHOLDER_KEY: Key = Key::Hash([0u8; 32]);
 pub const POS_BONDING_PURSE: &str = "pos_bonding_purse";
 pub const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
 pub const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
+pub const POS_STAKING_LIMITS: &str = "pos_staking_limits";
+pub const POS_INFLATION_RATE: &str = "pos_inflation_rate";
+pub const POS_FEE_BURN_RATE: &str = "pos_fee_burn_rate";
 
 pub enum GenesisResult {
     RootNotFound,
@@ -188,6 +194,11 @@ pub struct ExecConfig {
     standard_payment_installer_bytes: Vec<u8>,
     accounts: Vec<GenesisAccount>,
     wasm_costs: WasmCosts,
+    auction_config: Option<AuctionConfig>,
+    staking_limits: StakingLimits,
+    annual_inflation_rate: u64,
+    fee_burn_rate: u8,
+    governance_account: Option<AccountHash>,
 }
 
 impl ExecConfig {
@@ -204,8 +215,50 @@ impl ExecConfig {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            auction_config: None,
+            staking_limits: StakingLimits::default(),
+            annual_inflation_rate: 0,
+            fee_burn_rate: 0,
+            governance_account: None,
         }
     }
+
+    /// Enables the auction mode of the Proof of Stake contract, in which the validator set is
+    /// elected anew at the end of every era.
+    pub fn with_auction_config(mut self, auction_config: AuctionConfig) -> ExecConfig {
+        self.auction_config = Some(auction_config);
+        self
+    }
+
+    /// Overrides the default limits which the Proof of Stake contract enforces when validators
+    /// bond and unbond.
+    pub fn with_staking_limits(mut self, staking_limits: StakingLimits) -> ExecConfig {
+        self.staking_limits = staking_limits;
+        self
+    }
+
+    /// Sets the annual inflation rate, in millionths of the total supply, at which seigniorage is
+    /// minted into the Proof of Stake rewards purse.  By default there is no inflation.
+    pub fn with_annual_inflation_rate(mut self, annual_inflation_rate: u64) -> ExecConfig {
+        self.annual_inflation_rate = annual_inflation_rate;
+        self
+    }
+
+    /// Sets the percentage of the transaction fees which is burned, while the rest is paid to the
+    /// block proposer.  By default nothing is burned.
+    pub fn with_fee_burn_rate(mut self, fee_burn_rate: u8) -> ExecConfig {
+        self.fee_burn_rate = fee_burn_rate;
+        self
+    }
+
+    /// Grants the genesis account `governance_account` the access keys of the system contracts'
+    /// contract packages, allowing it to add new versions of them without a protocol upgrade.  By
+    /// default no account holds them.
+    pub fn with_governance_account(mut self, governance_account: AccountHash) -> ExecConfig {
+        self.governance_account = Some(governance_account);
+        self
+    }
+
     pub fn mint_installer_bytes(&self) -> &[u8] {
         self.mint_installer_bytes.as_slice()
     }
@@ -222,6 +275,26 @@ impl ExecConfig {
         self.wasm_costs
     }
 
+    pub fn auction_config(&self) -> Option<AuctionConfig> {
+        self.auction_config
+    }
+
+    pub fn staking_limits(&self) -> StakingLimits {
+        self.staking_limits
+    }
+
+    pub fn annual_inflation_rate(&self) -> u64 {
+        self.annual_inflation_rate
+    }
+
+    pub fn fee_burn_rate(&self) -> u8 {
+        self.fee_burn_rate
+    }
+
+    pub fn governance_account(&self) -> Option<AccountHash> {
+        self.governance_account
+    }
+
     pub fn get_bonded_validators(&self) -> impl Iterator<Item = (AccountHash, Motes)> + '_ {
         let zero = Motes::zero();
         self.accounts.iter().filter_map(move |genesis_account| {
@@ -272,6 +345,37 @@ impl Distribution<ExecConfig> for Standard {
             max_stack_height: rng.gen(),
             opcodes_mul: rng.gen(),
             opcodes_div: rng.gen(),
+            max_memory: rng.gen_range(1, MAX_MEMORY_PAGES + 1),
+        };
+
+        let auction_config = if rng.gen() {
+            Some(AuctionConfig::new(
+                rng.gen(),
+                rng.gen(),
+                U512::from(rng.gen::<u64>()),
+            ))
+        } else {
+            None
+        };
+
+        let staking_limits = StakingLimits::new(
+            U512::from(rng.gen::<u64>()),
+            U512::from(rng.gen::<u64>()),
+            U512::from(rng.gen::<u64>()),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+        );
+
+        let annual_inflation_rate = rng.gen();
+
+        let fee_burn_rate = rng.gen_range(0, 101);
+
+        let governance_account = if rng.gen() {
+            Some(AccountHash::new(rng.gen()))
+        } else {
+            None
         };
 
         ExecConfig {
@@ -280,6 +384,11 @@ impl Distribution<ExecConfig> for Standard {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            auction_config,
+            staking_limits,
+            annual_inflation_rate,
+            fee_burn_rate,
+            governance_account,
         }
     }
 }
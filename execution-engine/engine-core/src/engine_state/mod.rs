@@ -1,37 +1,42 @@
+pub mod conflicts;
 pub mod deploy_item;
 pub mod engine_config;
 mod error;
+pub mod estimate;
 pub mod executable_deploy_item;
 pub mod execute_request;
 pub mod execution_effect;
 pub mod execution_result;
 pub mod genesis;
+pub mod module_cache;
 pub mod op;
 pub mod query;
 pub mod run_genesis_request;
-pub mod system_contract_cache;
 mod transfer;
 pub mod upgrade;
 pub mod utils;
 
 use std::{
     cell::RefCell,
+    cmp,
     collections::{BTreeMap, BTreeSet, HashMap},
     rc::Rc,
 };
 
+use crossbeam_utils::thread;
 use log::{debug, warn};
 use num_traits::Zero;
 use parity_wasm::elements::Module;
 
 use engine_shared::{
-    account::Account,
+    account::{Account, AccountOwner},
     additive_map::AdditiveMap,
     gas::Gas,
     motes::Motes,
     newtypes::{Blake2bHash, CorrelationId},
     stored_value::StoredValue,
     transform::Transform,
+    TypeMismatch,
 };
 use engine_storage::{
     global_state::{CommitResult, StateProvider, StateReader},
@@ -45,9 +50,9 @@ use types::{
     runtime_args,
     system_contract_errors::mint,
     system_contract_type::PROOF_OF_STAKE,
-    AccessRights, BlockTime, Contract, ContractHash, ContractPackage, ContractPackageHash,
-    ContractVersionKey, EntryPoint, EntryPointType, Key, Phase, ProtocolVersion, RuntimeArgs, URef,
-    U512,
+    AccessRights, BlockTime, CLValue, Contract, ContractHash, ContractPackage, ContractPackageHash,
+    ContractVersionKey, ContractWasmHash, EntryPoint, EntryPointType, Key, Phase, ProtocolVersion,
+    RuntimeArgs, URef, U512, UREF_ADDR_LENGTH,
 };
 
 pub use self::{
@@ -57,23 +62,31 @@ pub use self::{
 };
 use crate::{
     engine_state::{
-        deploy_item::DeployItem,
+        deploy_item::{DeployItem, DeployTtl},
         error::Error::MissingSystemContract,
+        estimate::{EstimateRequest, EstimateResult},
         executable_deploy_item::ExecutableDeployItem,
         execute_request::ExecuteRequest,
-        execution_result::{ExecutionResult, ForcedTransferResult},
+        execution_effect::ExecutionEffect,
+        execution_result::{ExecutionResult, ForcedTransferResult, SequentialExecutionResult},
         genesis::{
-            ExecConfig, GenesisAccount, GenesisResult, POS_PAYMENT_PURSE, POS_REWARDS_PURSE,
+            ExecConfig, GenesisAccount, GenesisResult, MAX_FEE_BURN_RATE, POS_FEE_BURN_RATE,
+            POS_INFLATION_RATE, POS_PAYMENT_PURSE, POS_REWARDS_PURSE, POS_STAKING_LIMITS,
+            TOTAL_SUPPLY_KEY,
         },
+        module_cache::{ModuleCache, ModuleCacheKey},
+        op::Op,
         query::{QueryRequest, QueryResult},
-        system_contract_cache::SystemContractCache,
         transfer::TransferTargetMode,
         upgrade::{UpgradeConfig, UpgradeResult},
     },
     execution::{
-        self, AddressGenerator, AddressGeneratorBuilder, DirectSystemContractCall, Executor,
+        self, AddressGenerator, AddressGeneratorBuilder, DirectSystemContractCall, ExecutionTrace,
+        Executor,
     },
-    tracking_copy::{TrackingCopy, TrackingCopyExt},
+    resolvers::create_preprocessor,
+    tracking_copy::{AddResult, TrackingCopy, TrackingCopyExt},
+    DeployHash,
 };
 
 // TODO?: MAX_PAYMENT && CONV_RATE values are currently arbitrary w/ real values
@@ -89,7 +102,7 @@ const ARG_AMOUNT: &str = "amount";
 #[derive(Debug)]
 pub struct EngineState<S> {
     config: EngineConfig,
-    system_contract_cache: SystemContractCache,
+    module_cache: ModuleCache,
     state: S,
 }
 
@@ -125,10 +138,10 @@ where
     S::Error: Into<execution::Error>,
 {
     pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
-        let system_contract_cache = Default::default();
+        let module_cache = ModuleCache::new(config.module_cache_capacity());
         EngineState {
             config,
-            system_contract_cache,
+            module_cache,
             state,
         }
     }
@@ -137,6 +150,11 @@ where
         &self.config
     }
 
+    /// Returns the cache of deserialized contract modules shared by all deploys.
+    pub fn module_cache(&self) -> &ModuleCache {
+        &self.module_cache
+    }
+
     pub fn wasm_costs(
         &self,
         protocol_version: ProtocolVersion,
@@ -158,6 +176,26 @@ where
         }
     }
 
+    /// Returns the protocol data for `protocol_version` with its system contracts resolved against
+    /// `state_hash`, or `None` if `state_hash` is not found in global state.
+    pub fn resolved_protocol_data(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        state_hash: Blake2bHash,
+    ) -> Result<Option<ProtocolData>, Error> {
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        let mut tracking_copy = match self.tracking_copy(state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(None),
+        };
+        resolve_system_contracts(correlation_id, protocol_data, &mut tracking_copy).map(Some)
+    }
+
     pub fn commit_genesis(
         &self,
         correlation_id: CorrelationId,
@@ -171,9 +209,32 @@ where
         let gas_limit = Gas::new(std::u64::MAX.into());
         let phase = Phase::System;
 
+        if let Some(auction_config) = ee_config.auction_config() {
+            if !auction_config.is_valid() {
+                return Err(Error::InvalidAuctionConfig);
+            }
+        }
+
+        if ee_config.fee_burn_rate() > MAX_FEE_BURN_RATE {
+            return Err(Error::InvalidFeeBurnRate(ee_config.fee_burn_rate()));
+        }
+
+        if let Some(governance_account) = ee_config.governance_account() {
+            let is_genesis_account = ee_config
+                .accounts()
+                .iter()
+                .any(|account| account.account_hash() == governance_account);
+            if !is_genesis_account {
+                return Err(Error::InvalidGovernanceAccount);
+            }
+        }
+
         let initial_root_hash = self.state.empty_root();
         let wasm_costs = ee_config.wasm_costs();
-        let preprocessor = Preprocessor::new(wasm_costs);
+        if !wasm_costs.has_valid_max_memory() {
+            return Err(Error::InvalidMaxMemory(wasm_costs.max_memory));
+        }
+        let preprocessor = create_preprocessor(wasm_costs, protocol_version);
 
         // Spec #3: Create "virtual system account" object.
         let mut virtual_system_account = {
@@ -222,8 +283,8 @@ where
             let hash_address_generator = Rc::clone(&hash_address_generator);
             let uref_address_generator = Rc::clone(&uref_address_generator);
             let tracking_copy = Rc::clone(&tracking_copy);
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
-            let protocol_data = ProtocolData::default();
+            let module_cache = ModuleCache::clone(&self.module_cache);
+            let protocol_data = ProtocolData::partial_with_wasm_costs(wasm_costs);
 
             executor.exec_wasm_direct(
                 mint_installer_module,
@@ -241,7 +302,7 @@ where
                 tracking_copy,
                 phase,
                 protocol_data,
-                system_contract_cache,
+                module_cache,
             )?
         };
 
@@ -262,11 +323,11 @@ where
             let hash_address_generator = Rc::clone(&hash_address_generator);
             let uref_address_generator = Rc::clone(&uref_address_generator);
             let install_deploy_hash = genesis_config_hash.into();
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let module_cache = ModuleCache::clone(&self.module_cache);
 
             // Constructs a partial protocol data with already known uref to pass the validation
             // step
-            let partial_protocol_data = ProtocolData::partial_with_mint(mint_hash);
+            let partial_protocol_data = ProtocolData::partial_with_mint(wasm_costs, mint_hash);
 
             let proof_of_stake_installer_bytes = ee_config.proof_of_stake_installer_bytes();
             let proof_of_stake_installer_module =
@@ -274,6 +335,10 @@ where
             let args = runtime_args! {
                 "mint_contract_package_hash" => mint_package_hash,
                 "genesis_validators" => bonded_validators,
+                "auction_config" => ee_config.auction_config(),
+                "staking_limits" => ee_config.staking_limits(),
+                "inflation_rate" => ee_config.annual_inflation_rate(),
+                "fee_burn_rate" => ee_config.fee_burn_rate(),
             };
             let authorization_keys: BTreeSet<AccountHash> = BTreeSet::new();
 
@@ -293,7 +358,7 @@ where
                 tracking_copy,
                 phase,
                 partial_protocol_data,
-                system_contract_cache,
+                module_cache,
             )?
         };
 
@@ -327,7 +392,7 @@ where
             let hash_address_generator = Rc::clone(&hash_address_generator);
             let uref_address_generator = Rc::clone(&uref_address_generator);
             let tracking_copy = Rc::clone(&tracking_copy);
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let module_cache = ModuleCache::clone(&self.module_cache);
 
             executor.exec_wasm_direct(
                 standard_payment_installer_module,
@@ -345,7 +410,7 @@ where
                 tracking_copy,
                 phase,
                 protocol_data,
-                system_contract_cache,
+                module_cache,
             )?
         };
 
@@ -377,13 +442,20 @@ where
         // Create accounts
         {
             // Collect chainspec accounts and their known keys with the genesis account and its
-            // known keys
+            // known keys.  The governance account, if any, is given the same known keys as the
+            // system account, i.e. the hashes and access keys of the system contract packages.
             let accounts = {
                 let mut ret: Vec<(GenesisAccount, NamedKeys)> = ee_config
                     .accounts()
                     .to_vec()
                     .into_iter()
-                    .map(|account| (account, account_named_keys.clone()))
+                    .map(|account| {
+                        if Some(account.account_hash()) == ee_config.governance_account() {
+                            (account, virtual_system_account.named_keys().clone())
+                        } else {
+                            (account, account_named_keys.clone())
+                        }
+                    })
                     .collect();
                 let system_account =
                     GenesisAccount::new(SYSTEM_ACCOUNT_ADDR, Motes::zero(), Motes::zero());
@@ -425,7 +497,7 @@ where
                         .build();
                     Rc::new(RefCell::new(generator))
                 };
-                let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+                let module_cache = ModuleCache::clone(&self.module_cache);
 
                 let mint_result: Result<URef, mint::Error> = {
                     // ...call the Mint's "mint" endpoint to create purse with tokens...
@@ -443,12 +515,13 @@ where
                         gas_limit,
                         hash_address_generator,
                         uref_address_generator,
+                        Default::default(),
                         protocol_version,
                         correlation_id,
                         tracking_copy_exec,
                         phase,
                         protocol_data,
-                        system_contract_cache,
+                        module_cache,
                     )?;
 
                     runtime
@@ -517,6 +590,12 @@ where
                 return Err(Error::Exec(error.into()));
             }
         };
+        // the new protocol version starts from the current versions of the system contracts
+        let current_protocol_data = resolve_system_contracts(
+            correlation_id,
+            current_protocol_data,
+            &mut tracking_copy.borrow_mut(),
+        )?;
 
         // 3.1.1.1.1.3 activation point is not currently used by EE; skipping
         // 3.1.1.1.1.4 upgrade point protocol version validation
@@ -529,11 +608,20 @@ where
             return Err(Error::InvalidProtocolVersion(new_protocol_version));
         }
 
+        if let Some(new_fee_burn_rate) = upgrade_config.new_fee_burn_rate() {
+            if new_fee_burn_rate > MAX_FEE_BURN_RATE {
+                return Err(Error::InvalidFeeBurnRate(new_fee_burn_rate));
+            }
+        }
+
         // 3.1.1.1.1.6 resolve wasm CostTable for new protocol version
         let new_wasm_costs = match upgrade_config.wasm_costs() {
             Some(new_wasm_costs) => new_wasm_costs,
             None => *current_protocol_data.wasm_costs(),
         };
+        if !new_wasm_costs.has_valid_max_memory() {
+            return Err(Error::InvalidMaxMemory(new_wasm_costs.max_memory));
+        }
 
         // 3.1.2.2 persist wasm CostTable
         let mut new_protocol_data = ProtocolData::new(
@@ -561,7 +649,7 @@ where
 
                 // preprocess installer module
                 let upgrade_installer_module = {
-                    let preprocessor = Preprocessor::new(new_wasm_costs);
+                    let preprocessor = create_preprocessor(new_wasm_costs, new_protocol_version);
                     preprocessor.preprocess(bytes)?
                 };
 
@@ -614,7 +702,7 @@ where
                     Rc::new(RefCell::new(generator))
                 };
                 let tracking_copy = Rc::clone(&tracking_copy);
-                let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+                let module_cache = ModuleCache::clone(&self.module_cache);
 
                 let executor = Executor::new(self.config);
 
@@ -634,7 +722,7 @@ where
                     Rc::clone(&tracking_copy),
                     phase,
                     new_protocol_data,
-                    system_contract_cache,
+                    module_cache,
                 )?;
 
                 if !new_protocol_data.update_from(result) {
@@ -647,6 +735,46 @@ where
             }
         }
 
+        // 3.1.2.4 persist new staking limits of the proof of stake contract
+        if let Some(new_staking_limits) = upgrade_config.new_staking_limits() {
+            let value = CLValue::from_t(new_staking_limits).map_err(execution::Error::from)?;
+            write_proof_of_stake_setting(
+                correlation_id,
+                &tracking_copy,
+                pre_state_hash,
+                new_protocol_data.proof_of_stake(),
+                POS_STAKING_LIMITS,
+                value,
+            )?;
+        }
+
+        // 3.1.2.5 persist new inflation rate of the proof of stake contract
+        if let Some(new_annual_inflation_rate) = upgrade_config.new_annual_inflation_rate() {
+            let value =
+                CLValue::from_t(new_annual_inflation_rate).map_err(execution::Error::from)?;
+            write_proof_of_stake_setting(
+                correlation_id,
+                &tracking_copy,
+                pre_state_hash,
+                new_protocol_data.proof_of_stake(),
+                POS_INFLATION_RATE,
+                value,
+            )?;
+        }
+
+        // 3.1.2.6 persist new fee burn rate of the proof of stake contract
+        if let Some(new_fee_burn_rate) = upgrade_config.new_fee_burn_rate() {
+            let value = CLValue::from_t(new_fee_burn_rate).map_err(execution::Error::from)?;
+            write_proof_of_stake_setting(
+                correlation_id,
+                &tracking_copy,
+                pre_state_hash,
+                new_protocol_data.proof_of_stake(),
+                POS_FEE_BURN_RATE,
+                value,
+            )?;
+        }
+
         let effects = tracking_copy.borrow().effect();
 
         // commit
@@ -691,67 +819,397 @@ where
             .into())
     }
 
+    /// Reads the total supply of motes tracked by the Mint at `state_hash`.
+    ///
+    /// Returns `None` if `state_hash` is not found in global state.
+    pub fn read_total_supply(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        state_hash: Blake2bHash,
+    ) -> Result<Option<U512>, Error> {
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        let mut tracking_copy = match self.tracking_copy(state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(None),
+        };
+        let protocol_data =
+            resolve_system_contracts(correlation_id, protocol_data, &mut tracking_copy)?;
+
+        let mint_contract = tracking_copy.get_contract(correlation_id, protocol_data.mint())?;
+        let total_supply_key = match mint_contract.named_keys().get(TOTAL_SUPPLY_KEY) {
+            Some(key @ Key::URef(_)) => *key,
+            Some(key) => return Err(Error::InvalidKeyVariant(format!("{:?}", key))),
+            None => {
+                let error = execution::Error::NamedKeyNotFound(TOTAL_SUPPLY_KEY.to_string());
+                return Err(Error::Exec(error));
+            }
+        };
+
+        match tracking_copy.read(correlation_id, &total_supply_key) {
+            Ok(Some(StoredValue::CLValue(cl_value))) => {
+                let total_supply = cl_value
+                    .into_t()
+                    .map_err(|error| Error::Exec(error.into()))?;
+                Ok(Some(total_supply))
+            }
+            Ok(Some(other)) => Err(Error::Exec(execution::Error::TypeMismatch(
+                TypeMismatch::new("CLValue".to_string(), other.type_name()),
+            ))),
+            Ok(None) => Err(Error::Exec(execution::Error::KeyNotFound(total_supply_key))),
+            Err(error) => Err(Error::Exec(error.into())),
+        }
+    }
+
+    /// Returns the ABI of the contract stored under `contract_hash` at `state_hash`, i.e. its
+    /// entry points serialized as JSON.
+    ///
+    /// Returns `None` if `state_hash` is not found in global state.
+    pub fn get_contract_abi(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        contract_hash: ContractHash,
+    ) -> Result<Option<String>, Error> {
+        let mut tracking_copy = match self.tracking_copy(state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(None),
+        };
+
+        let contract = tracking_copy.get_contract(correlation_id, contract_hash)?;
+        // Entry points only hold strings and `CLType`s, which always serialize to JSON
+        let abi = serde_json::to_string_pretty(contract.entry_points())
+            .expect("entry points should serialize to JSON");
+        Ok(Some(abi))
+    }
+
+    /// Returns the keys which the payment and finalization of every deploy executed against
+    /// `state_hash` write, along with their values under `state_hash`.
+    ///
+    /// These are the Proof of Stake contract, whose refund purse is unset at the beginning and end
+    /// of each deploy, and the balance of its payment purse, which is zero at the beginning and end
+    /// of each deploy.  A deploy which leaves one of them with its value under `state_hash` is
+    /// treated as only reading it when checking deploys for conflicts.
+    pub fn finalization_keys(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        state_hash: Blake2bHash,
+    ) -> Result<Option<BTreeMap<Key, StoredValue>>, Error> {
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        let mut tracking_copy = match self.tracking_copy(state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(None),
+        };
+
+        let protocol_data =
+            resolve_system_contracts(correlation_id, protocol_data, &mut tracking_copy)?;
+        let proof_of_stake_hash = protocol_data.proof_of_stake();
+        let proof_of_stake_contract =
+            tracking_copy.get_contract(correlation_id, proof_of_stake_hash)?;
+        let payment_purse_key = match proof_of_stake_contract.named_keys().get(POS_PAYMENT_PURSE) {
+            Some(key) => *key,
+            None => return Err(Error::Deploy),
+        };
+        let payment_purse_balance_key =
+            tracking_copy.get_purse_balance_key(correlation_id, payment_purse_key)?;
+        let payment_purse_balance = match tracking_copy
+            .read(correlation_id, &payment_purse_balance_key)
+            .map_err(|error| Error::Exec(error.into()))?
+        {
+            Some(payment_purse_balance) => payment_purse_balance,
+            None => {
+                let error = execution::Error::KeyNotFound(payment_purse_balance_key);
+                return Err(Error::Exec(error));
+            }
+        };
+
+        let mut finalization_keys = BTreeMap::new();
+        finalization_keys.insert(
+            Key::from(proof_of_stake_hash),
+            StoredValue::Contract(proof_of_stake_contract),
+        );
+        finalization_keys.insert(payment_purse_balance_key, payment_purse_balance);
+        Ok(Some(finalization_keys))
+    }
+
+    /// Executes the deploys of `exec_request` independently of each other against its parent state.
+    ///
+    /// If the engine is configured with more than one deploy worker, the deploys are spread over
+    /// that many threads.  The results are returned in the order of the deploys in the request,
+    /// each along with its execution trace if tracing is enabled for the request.
     pub fn run_execute(
         &self,
         correlation_id: CorrelationId,
         mut exec_request: ExecuteRequest,
-    ) -> Result<Vec<ExecutionResult>, RootNotFound> {
+    ) -> Result<Vec<(ExecutionResult, Option<ExecutionTrace>)>, RootNotFound>
+    where
+        S: Sync,
+    {
         // TODO: do not unwrap
         let wasm_costs = self
             .wasm_costs(exec_request.protocol_version)
             .unwrap()
             .unwrap();
-        let executor = Executor::new(self.config);
-        let preprocessor = Preprocessor::new(wasm_costs);
 
-        let mut results = Vec::new();
+        // The system contracts are resolved once for all the deploys.  If that fails, each deploy
+        // tries again on its own and reports the error.
+        let protocol_data = match self.resolved_protocol_data(
+            correlation_id,
+            exec_request.protocol_version,
+            exec_request.parent_state_hash,
+        ) {
+            Ok(Some(protocol_data)) => Some(protocol_data),
+            Ok(None) => return Err(RootNotFound::new(exec_request.parent_state_hash)),
+            Err(_) => None,
+        };
 
-        for deploy_item in exec_request.take_deploys() {
-            let result = match deploy_item {
-                Err(exec_result) => Ok(exec_result),
-                Ok(deploy_item) => match deploy_item.session {
-                    ExecutableDeployItem::Transfer { .. } => self.transfer(
-                        correlation_id,
-                        &executor,
-                        &preprocessor,
-                        exec_request.protocol_version,
-                        exec_request.parent_state_hash,
-                        BlockTime::new(exec_request.block_time),
-                        deploy_item,
-                    ),
-                    _ => self.deploy(
+        let deploy_items = exec_request.take_deploys();
+        let worker_count = cmp::min(self.config.deploy_worker_count(), deploy_items.len());
+
+        if worker_count <= 1 {
+            let executor = self.request_executor(&exec_request);
+            let preprocessor = create_preprocessor(wasm_costs, exec_request.protocol_version);
+            return deploy_items
+                .into_iter()
+                .map(|deploy_item| {
+                    self.execute_deploy_item(
                         correlation_id,
                         &executor,
                         &preprocessor,
-                        exec_request.protocol_version,
-                        exec_request.parent_state_hash,
-                        BlockTime::new(exec_request.block_time),
+                        &exec_request,
+                        protocol_data,
                         deploy_item,
-                    ),
-                },
-            };
-            match result {
-                Ok(result) => results.push(result),
-                Err(error) => {
-                    return Err(error);
+                    )
+                })
+                .collect();
+        }
+
+        // The deploys are dealt out to the workers in turn, and each worker tags its results with
+        // the index of the deploy so that the original order can be restored.
+        let mut partitions: Vec<Vec<_>> = (0..worker_count).map(|_| Vec::new()).collect();
+        for (index, deploy_item) in deploy_items.into_iter().enumerate() {
+            partitions[index % worker_count].push((index, deploy_item));
+        }
+
+        let exec_request = &exec_request;
+        let mut indexed_results = thread::scope(|scope| {
+            let workers: Vec<_> = partitions
+                .into_iter()
+                .map(|partition| {
+                    scope.spawn(move |_| {
+                        let executor = self.request_executor(exec_request);
+                        let preprocessor =
+                            create_preprocessor(wasm_costs, exec_request.protocol_version);
+                        partition
+                            .into_iter()
+                            .map(|(index, deploy_item)| {
+                                let result = self.execute_deploy_item(
+                                    correlation_id,
+                                    &executor,
+                                    &preprocessor,
+                                    exec_request,
+                                    protocol_data,
+                                    deploy_item,
+                                );
+                                (index, result)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("deploy worker should not panic"))
+                .collect::<Vec<_>>()
+        })
+        .expect("deploy workers should not panic");
+
+        indexed_results.sort_by_key(|(index, _)| *index);
+        indexed_results
+            .into_iter()
+            .map(|(_, result)| result)
+            .collect()
+    }
+
+    /// Returns an executor which records execution traces or profiles if the request asks for them.
+    fn request_executor(&self, exec_request: &ExecuteRequest) -> Executor {
+        let mut executor = Executor::new(self.config);
+        if exec_request.enable_profiling {
+            executor.enable_profiling();
+        } else if exec_request.enable_tracing {
+            executor.enable_tracing();
+        }
+        executor
+    }
+
+    fn execute_deploy_item(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        exec_request: &ExecuteRequest,
+        protocol_data: Option<ProtocolData>,
+        deploy_item: Result<DeployItem, ExecutionResult>,
+    ) -> Result<(ExecutionResult, Option<ExecutionTrace>), RootNotFound> {
+        let execution_result = match deploy_item {
+            Err(exec_result) => Ok(exec_result),
+            Ok(deploy_item) => match deploy_item.session {
+                ExecutableDeployItem::Transfer { .. } => self.transfer(
+                    correlation_id,
+                    executor,
+                    preprocessor,
+                    exec_request.protocol_version,
+                    protocol_data,
+                    exec_request.parent_state_hash,
+                    BlockTime::new(exec_request.block_time),
+                    deploy_item,
+                ),
+                _ => self.deploy(
+                    correlation_id,
+                    executor,
+                    preprocessor,
+                    exec_request.protocol_version,
+                    protocol_data,
+                    exec_request.parent_state_hash,
+                    BlockTime::new(exec_request.block_time),
+                    deploy_item,
+                    exec_request.proposer,
+                ),
+            },
+        }?;
+        Ok((execution_result, executor.take_trace()))
+    }
+
+    /// Executes the deploys of `exec_request` in order over a single tracking copy, so that every
+    /// deploy observes the effects of the ones before it, then commits their combined effect on
+    /// top of the parent state.
+    ///
+    /// The effect of a failed deploy (e.g. the payment taken from its account) is applied in the
+    /// same way as that of a successful one.
+    pub fn run_execute_sequential(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+    ) -> Result<SequentialExecutionResult, Error> {
+        let parent_state_hash = exec_request.parent_state_hash;
+        let protocol_version = exec_request.protocol_version;
+        let blocktime = BlockTime::new(exec_request.block_time);
+
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        let mut block_tracking_copy = match self.tracking_copy(parent_state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(SequentialExecutionResult::RootNotFound),
+        };
+        let protocol_data =
+            resolve_system_contracts(correlation_id, protocol_data, &mut block_tracking_copy)?;
+
+        let executor = self.request_executor(&exec_request);
+        let preprocessor = create_preprocessor(*protocol_data.wasm_costs(), protocol_version);
+
+        let mut execution_results = Vec::new();
+
+        for deploy_item in exec_request.take_deploys() {
+            let execution_result = match deploy_item {
+                Err(execution_result) => execution_result,
+                Ok(deploy_item) => {
+                    let tracking_copy = Rc::new(RefCell::new(block_tracking_copy.fork()));
+                    match deploy_item.session {
+                        ExecutableDeployItem::Transfer { .. } => self.transfer_with_tracking_copy(
+                            correlation_id,
+                            &executor,
+                            &preprocessor,
+                            protocol_version,
+                            protocol_data,
+                            tracking_copy,
+                            blocktime,
+                            deploy_item,
+                        ),
+                        _ => self.deploy_with_tracking_copy(
+                            correlation_id,
+                            &executor,
+                            &preprocessor,
+                            protocol_version,
+                            protocol_data,
+                            tracking_copy,
+                            blocktime,
+                            deploy_item,
+                            exec_request.proposer,
+                        ),
+                    }
                 }
             };
+
+            match block_tracking_copy
+                .apply_effect(correlation_id, execution_result.effect().clone())
+                .map_err(Into::into)?
+            {
+                AddResult::Success => {
+                    execution_results.push((execution_result, executor.take_trace()))
+                }
+                AddResult::KeyNotFound(key) => {
+                    return Ok(SequentialExecutionResult::KeyNotFound(key))
+                }
+                AddResult::TypeMismatch(type_mismatch) => {
+                    return Ok(SequentialExecutionResult::TypeMismatch(type_mismatch))
+                }
+                AddResult::Serialization(error) => {
+                    return Ok(SequentialExecutionResult::Serialization(error))
+                }
+            }
         }
 
-        Ok(results)
+        let effect = block_tracking_copy.effect();
+
+        let commit_result = self
+            .state
+            .commit(
+                correlation_id,
+                parent_state_hash,
+                effect.transforms.to_owned(),
+            )
+            .map_err(Into::into)?;
+
+        Ok(SequentialExecutionResult::from_commit_result(
+            commit_result,
+            execution_results,
+            effect,
+        ))
     }
 
-    pub fn get_module(
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_module<R>(
         &self,
-        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         deploy_item: &ExecutableDeployItem,
         account: &Account,
+        authorization_keys: &BTreeSet<AccountHash>,
         correlation_id: CorrelationId,
         preprocessor: &Preprocessor,
         protocol_version: &ProtocolVersion,
-    ) -> Result<GetModuleResult, error::Error> {
+    ) -> Result<GetModuleResult, error::Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
         let (contract_package, contract, base_key) = match deploy_item {
             ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
+                if !account.can_execute_module_bytes_with(authorization_keys) {
+                    return Err(error::Error::ContractNotAllowed);
+                }
                 let module = preprocessor.preprocess(&module_bytes)?;
                 return Ok(GetModuleResult::Session {
                     module,
@@ -830,6 +1288,14 @@ where
 
         let entry_point_name = deploy_item.entry_point_name();
 
+        if !account.can_call_contract_with(
+            authorization_keys,
+            &contract.contract_package_hash(),
+            entry_point_name,
+        ) {
+            return Err(error::Error::ContractNotAllowed);
+        }
+
         let entry_point = contract
             .entry_point(entry_point_name)
             .cloned()
@@ -837,11 +1303,12 @@ where
                 error::Error::Exec(execution::Error::NoSuchMethod(entry_point_name.to_owned()))
             })?;
 
-        let contract_wasm = tracking_copy
-            .borrow_mut()
-            .get_contract_wasm(correlation_id, contract.contract_wasm_hash())?;
-
-        let module = engine_wasm_prep::deserialize(contract_wasm.bytes())?;
+        let module = self.get_contract_module(
+            tracking_copy,
+            contract.contract_wasm_hash(),
+            correlation_id,
+            preprocessor,
+        )?;
 
         match entry_point.entry_point_type() {
             EntryPointType::Session => Ok(GetModuleResult::Session {
@@ -859,13 +1326,18 @@ where
         }
     }
 
-    fn get_module_from_contract_hash(
+    fn get_module_from_contract_hash<R>(
         &self,
-        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         contract_hash: ContractHash,
         correlation_id: CorrelationId,
+        preprocessor: &Preprocessor,
         protocol_version: &ProtocolVersion,
-    ) -> Result<Module, error::Error> {
+    ) -> Result<Module, error::Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
         let contract = tracking_copy
             .borrow_mut()
             .get_contract(correlation_id, contract_hash)?;
@@ -880,22 +1352,91 @@ where
             return Err(error::Error::Exec(exec_error));
         }
 
+        self.get_contract_module(
+            tracking_copy,
+            contract.contract_wasm_hash(),
+            correlation_id,
+            preprocessor,
+        )
+    }
+
+    /// Returns the deserialized module of the given contract Wasm, limited to the current maximum
+    /// memory, from the module cache if possible.
+    fn get_contract_module<R>(
+        &self,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        contract_wasm_hash: ContractWasmHash,
+        correlation_id: CorrelationId,
+        preprocessor: &Preprocessor,
+    ) -> Result<Module, error::Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        // The Wasm is read even if its module is cached, so that the tracking copy doesn't depend
+        // on the state of the cache.
         let contract_wasm = tracking_copy
             .borrow_mut()
-            .get_contract_wasm(correlation_id, contract.contract_wasm_hash())?;
-
-        let module = engine_wasm_prep::deserialize(contract_wasm.bytes())?;
+            .get_contract_wasm(correlation_id, contract_wasm_hash)?;
+
+        let wasm_costs = *preprocessor.wasm_costs();
+        let module_cache_key = ModuleCacheKey::new(contract_wasm_hash, wasm_costs);
+        self.module_cache
+            .get_or_load(module_cache_key, || -> Result<Module, error::Error> {
+                let module = engine_wasm_prep::deserialize(contract_wasm.bytes())?;
+                Ok(engine_wasm_prep::apply_memory_limit(
+                    module,
+                    wasm_costs.max_memory,
+                )?)
+            })
+    }
 
-        Ok(module)
+    /// Returns the module of a system contract.  When system contracts are in use, this is taken
+    /// from the module cache if possible, otherwise a module which does nothing is returned.
+    fn get_system_module<R>(
+        &self,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        contract_wasm_hash: ContractWasmHash,
+        correlation_id: CorrelationId,
+        preprocessor: &Preprocessor,
+    ) -> Result<Module, error::Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        if self.config.use_system_contracts() {
+            self.get_contract_module(
+                tracking_copy,
+                contract_wasm_hash,
+                correlation_id,
+                preprocessor,
+            )
+        } else {
+            let module = tracking_copy.borrow_mut().get_system_module(
+                correlation_id,
+                contract_wasm_hash,
+                false,
+                preprocessor,
+            )?;
+            Ok(module)
+        }
     }
 
-    fn get_authorized_account(
+    /// Returns the account along with the keys authorizing the deploy on its behalf.
+    ///
+    /// For a contract-owned account, every signing account has to hold a URef of the owner's
+    /// group as it is now, and the returned account has the signers as its associated keys.
+    fn get_authorized_account<R>(
         &self,
         correlation_id: CorrelationId,
         account_hash: AccountHash,
         authorization_keys: &BTreeSet<AccountHash>,
-        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
-    ) -> Result<Account, Error> {
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+    ) -> Result<(Account, BTreeSet<AccountHash>), Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
         let account: Account = match tracking_copy
             .borrow_mut()
             .get_account(correlation_id, account_hash)
@@ -906,40 +1447,169 @@ where
             }
         };
 
+        let account = match account.owner() {
+            Some(owner) => {
+                self.check_group_members(
+                    correlation_id,
+                    owner,
+                    authorization_keys,
+                    Rc::clone(&tracking_copy),
+                )?;
+                account
+                    .with_members(authorization_keys)
+                    .map_err(|_| error::Error::Authorization)?
+            }
+            None => account,
+        };
+        let authorization_keys = authorization_keys.clone();
+
         // Authorize using provided authorization keys
-        if !account.can_authorize(authorization_keys) {
+        if !account.can_authorize(&authorization_keys) {
             return Err(error::Error::Authorization);
         }
 
         // Check total key weight against deploy threshold
-        if !account.can_deploy_with(authorization_keys) {
+        if !account.can_deploy_with(&authorization_keys) {
             return Err(execution::Error::DeploymentAuthorizationFailure.into());
         }
 
-        Ok(account)
+        Ok((account, authorization_keys))
     }
 
+    /// Checks that each of the keys signing a deploy for a contract-owned account belongs to an
+    /// account holding a URef of the owner's group in its named keys.
+    ///
+    /// The group is read when the deploy is authorized, so URefs added to it since the account
+    /// was created count, and removed ones don't.
+    fn check_group_members<R>(
+        &self,
+        correlation_id: CorrelationId,
+        owner: &AccountOwner,
+        authorization_keys: &BTreeSet<AccountHash>,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+    ) -> Result<(), Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        let contract_package = tracking_copy
+            .borrow_mut()
+            .get_contract_package(correlation_id, owner.contract_package_hash())
+            .map_err(|_| error::Error::Authorization)?;
+        let group_urefs: BTreeSet<[u8; UREF_ADDR_LENGTH]> =
+            match contract_package.groups().get(owner.group()) {
+                Some(urefs) => urefs.iter().map(URef::addr).collect(),
+                None => return Err(error::Error::Authorization),
+            };
+
+        for authorization_key in authorization_keys {
+            let signer = tracking_copy
+                .borrow_mut()
+                .get_account(correlation_id, *authorization_key)
+                .map_err(|_| error::Error::Authorization)?;
+            let is_member = signer
+                .named_keys()
+                .values()
+                .filter_map(Key::as_uref)
+                .any(|uref| group_urefs.contains(&uref.addr()));
+            if !is_member {
+                return Err(error::Error::Authorization);
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects a deploy which expired before `blocktime`, or whose TTL is longer than the
+    /// configured maximum.
+    fn check_deploy_ttl(&self, ttl: Option<DeployTtl>, blocktime: BlockTime) -> Result<(), Error> {
+        let ttl = match ttl {
+            Some(ttl) => ttl,
+            None => return Ok(()),
+        };
+        let max_deploy_ttl = self.config.max_deploy_ttl();
+        if ttl.ttl > max_deploy_ttl {
+            return Err(Error::DeployTtlTooLong(max_deploy_ttl));
+        }
+        if ttl.is_expired(blocktime) {
+            return Err(Error::DeployExpired);
+        }
+        Ok(())
+    }
+
+    /// Records the deploy in `account` until it expires, if replay protection is enabled and the
+    /// deploy has a TTL, and writes the account to `tracking_copy`.
+    ///
+    /// Returns the effect of recording the deploy, which persists even if its payment fails, or
+    /// [`Error::DeployReplayed`] if the deploy was already recorded.
     #[allow(clippy::too_many_arguments)]
-    pub fn transfer(
+    fn record_executed_deploy<R>(
         &self,
         correlation_id: CorrelationId,
         executor: &Executor,
-        preprocessor: &Preprocessor,
         protocol_version: ProtocolVersion,
-        prestate_hash: Blake2bHash,
+        protocol_data: ProtocolData,
+        account: &Account,
+        authorization_keys: &BTreeSet<AccountHash>,
+        deploy_hash: DeployHash,
+        ttl: Option<DeployTtl>,
         blocktime: BlockTime,
-        deploy_item: DeployItem,
-    ) -> Result<ExecutionResult, RootNotFound> {
-        let protocol_data = match self.state.get_protocol_data(protocol_version) {
-            Ok(Some(protocol_data)) => protocol_data,
-            Ok(None) => {
-                let error = Error::InvalidProtocolVersion(protocol_version);
-                return Ok(ExecutionResult::precondition_failure(error));
-            }
-            Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(Error::Exec(
-                    error.into(),
-                )));
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+    ) -> Result<ExecutionEffect, Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        let ttl = match ttl {
+            Some(ttl) if self.config.enable_replay_protection() => ttl,
+            _ => return Ok(ExecutionEffect::default()),
+        };
+
+        let value = match executor.record_executed_deploy(
+            account,
+            authorization_keys.clone(),
+            blocktime,
+            deploy_hash,
+            ttl.expires_at(),
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            protocol_data,
+        )? {
+            Some(value) => value,
+            None => return Err(Error::DeployReplayed),
+        };
+
+        let key = Key::Account(account.account_hash());
+
+        let mut ops = AdditiveMap::new();
+        let mut transforms = AdditiveMap::new();
+        ops.insert(key, Op::Write);
+        transforms.insert(key, Transform::Write(value));
+        Ok(ExecutionEffect::new(ops, transforms))
+    }
+
+    /// `protocol_data` is the protocol data with its system contracts resolved against
+    /// `prestate_hash`, or `None` to resolve them for this deploy alone.
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        protocol_data: Option<ProtocolData>,
+        prestate_hash: Blake2bHash,
+        blocktime: BlockTime,
+        deploy_item: DeployItem,
+    ) -> Result<ExecutionResult, RootNotFound> {
+        let protocol_data = match protocol_data {
+            Some(protocol_data) => protocol_data,
+            None => {
+                match self.resolved_protocol_data(correlation_id, protocol_version, prestate_hash) {
+                    Ok(Some(protocol_data)) => protocol_data,
+                    Ok(None) => return Err(RootNotFound::new(prestate_hash)),
+                    Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+                }
             }
         };
 
@@ -949,52 +1619,101 @@ where
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
 
+        Ok(self.transfer_with_tracking_copy(
+            correlation_id,
+            executor,
+            preprocessor,
+            protocol_version,
+            protocol_data,
+            tracking_copy,
+            blocktime,
+            deploy_item,
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn transfer_with_tracking_copy<R>(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        blocktime: BlockTime,
+        deploy_item: DeployItem,
+    ) -> ExecutionResult
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        if let Err(error) = self.check_deploy_ttl(deploy_item.ttl, blocktime) {
+            return ExecutionResult::precondition_failure(error);
+        }
+
         let base_key = Key::Account(deploy_item.address);
 
         let account_public_key = match base_key.into_account() {
             Some(account_addr) => account_addr,
             None => {
-                return Ok(ExecutionResult::precondition_failure(
-                    error::Error::Authorization,
-                ));
+                return ExecutionResult::precondition_failure(error::Error::Authorization);
             }
         };
 
         let authorization_keys = deploy_item.authorization_keys;
 
-        let account = match self.get_authorized_account(
+        let (account, authorization_keys) = match self.get_authorized_account(
             correlation_id,
             account_public_key,
             &authorization_keys,
             Rc::clone(&tracking_copy),
         ) {
-            Ok(account) => account,
-            Err(e) => return Ok(ExecutionResult::precondition_failure(e)),
+            Ok(authorized) => authorized,
+            Err(e) => return ExecutionResult::precondition_failure(e),
         };
 
+        // Check total key weight against transfer threshold
+        if !account.can_transfer_with(&authorization_keys) {
+            return ExecutionResult::precondition_failure(
+                execution::Error::TransferAuthorizationFailure.into(),
+            );
+        }
+
+        // The record is written to the tracking copy, so it's part of the transfer's effect
+        if let Err(error) = self.record_executed_deploy(
+            correlation_id,
+            executor,
+            protocol_version,
+            protocol_data,
+            &account,
+            &authorization_keys,
+            deploy_item.deploy_hash,
+            deploy_item.ttl,
+            blocktime,
+            Rc::clone(&tracking_copy),
+        ) {
+            return ExecutionResult::precondition_failure(error);
+        }
+
         let mint_contract = match tracking_copy
             .borrow_mut()
             .get_contract(correlation_id, protocol_data.mint())
         {
             Ok(contract) => contract,
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error.into()));
+                return ExecutionResult::precondition_failure(error.into());
             }
         };
 
-        let mint_module = {
-            let contract_wasm_hash = mint_contract.contract_wasm_hash();
-            let use_system_contracts = self.config.use_system_contracts();
-            match tracking_copy.borrow_mut().get_system_module(
-                correlation_id,
-                contract_wasm_hash,
-                use_system_contracts,
-                preprocessor,
-            ) {
-                Ok(module) => module,
-                Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
-                }
+        let mint_module = match self.get_system_module(
+            Rc::clone(&tracking_copy),
+            mint_contract.contract_wasm_hash(),
+            correlation_id,
+            preprocessor,
+        ) {
+            Ok(module) => module,
+            Err(error) => {
+                return ExecutionResult::precondition_failure(error);
             }
         };
 
@@ -1005,7 +1724,7 @@ where
 
         let input_runtime_args = match deploy_item.session.into_runtime_args() {
             Ok(runtime_args) => runtime_args,
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+            Err(error) => return ExecutionResult::precondition_failure(error.into()),
         };
 
         let mut runtime_args_builder = TransferRuntimeArgsBuilder::new(input_runtime_args);
@@ -1031,7 +1750,7 @@ where
                             Rc::clone(&tracking_copy),
                             Phase::Session,
                             protocol_data,
-                            SystemContractCache::clone(&self.system_contract_cache),
+                            ModuleCache::clone(&self.module_cache),
                         );
                     match maybe_uref {
                         Some(main_purse) => {
@@ -1044,17 +1763,17 @@ where
                                 .write(Key::Account(public_key), StoredValue::Account(new_account))
                         }
                         None => {
-                            return Ok(execution_result);
+                            return execution_result;
                         }
                     }
                 }
             },
             Err(error) => {
-                return Ok(ExecutionResult::Failure {
+                return ExecutionResult::Failure {
                     error,
                     effect: Default::default(),
                     cost: Gas::default(),
-                });
+                };
             }
         }
 
@@ -1062,11 +1781,11 @@ where
             match runtime_args_builder.build(&account, correlation_id, Rc::clone(&tracking_copy)) {
                 Ok(runtime_args) => runtime_args,
                 Err(error) => {
-                    return Ok(ExecutionResult::Failure {
+                    return ExecutionResult::Failure {
                         error,
                         effect: Default::default(),
                         cost: Gas::default(),
-                    });
+                    };
                 }
             };
 
@@ -1088,12 +1807,14 @@ where
                 tracking_copy,
                 Phase::Session,
                 protocol_data,
-                SystemContractCache::clone(&self.system_contract_cache),
+                ModuleCache::clone(&self.module_cache),
             );
 
-        Ok(execution_result)
+        execution_result
     }
 
+    /// `protocol_data` is the protocol data with its system contracts resolved against
+    /// `prestate_hash`, or `None` to resolve them for this deploy alone.
     #[allow(clippy::too_many_arguments)]
     pub fn deploy(
         &self,
@@ -1101,24 +1822,24 @@ where
         executor: &Executor,
         preprocessor: &Preprocessor,
         protocol_version: ProtocolVersion,
+        protocol_data: Option<ProtocolData>,
         prestate_hash: Blake2bHash,
         blocktime: BlockTime,
         deploy_item: DeployItem,
+        proposer: Option<AccountHash>,
     ) -> Result<ExecutionResult, RootNotFound> {
         // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
 
         // Obtain current protocol data for given version
         // do this first, as there is no reason to proceed if protocol version is invalid
-        let protocol_data = match self.state.get_protocol_data(protocol_version) {
-            Ok(Some(protocol_data)) => protocol_data,
-            Ok(None) => {
-                let error = Error::InvalidProtocolVersion(protocol_version);
-                return Ok(ExecutionResult::precondition_failure(error));
-            }
-            Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(Error::Exec(
-                    error.into(),
-                )));
+        let protocol_data = match protocol_data {
+            Some(protocol_data) => protocol_data,
+            None => {
+                match self.resolved_protocol_data(correlation_id, protocol_version, prestate_hash) {
+                    Ok(Some(protocol_data)) => protocol_data,
+                    Ok(None) => return Err(RootNotFound::new(prestate_hash)),
+                    Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+                }
             }
         };
 
@@ -1131,6 +1852,40 @@ where
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
 
+        Ok(self.deploy_with_tracking_copy(
+            correlation_id,
+            executor,
+            preprocessor,
+            protocol_version,
+            protocol_data,
+            tracking_copy,
+            blocktime,
+            deploy_item,
+            proposer,
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn deploy_with_tracking_copy<R>(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        blocktime: BlockTime,
+        deploy_item: DeployItem,
+        proposer: Option<AccountHash>,
+    ) -> ExecutionResult
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        if let Err(error) = self.check_deploy_ttl(deploy_item.ttl, blocktime) {
+            return ExecutionResult::precondition_failure(error);
+        }
+
         let base_key = Key::Account(deploy_item.address);
 
         // Get addr bytes from `address` (which is actually a Key)
@@ -1138,9 +1893,7 @@ where
         let account_public_key = match base_key.into_account() {
             Some(account_addr) => account_addr,
             None => {
-                return Ok(ExecutionResult::precondition_failure(
-                    error::Error::Authorization,
-                ));
+                return ExecutionResult::precondition_failure(error::Error::Authorization);
             }
         };
 
@@ -1148,14 +1901,32 @@ where
 
         // Get account from tracking copy
         // validation_spec_3: account validity
-        let account = match self.get_authorized_account(
+        let (account, authorization_keys) = match self.get_authorized_account(
             correlation_id,
             account_public_key,
             &authorization_keys,
             Rc::clone(&tracking_copy),
         ) {
-            Ok(account) => account,
-            Err(e) => return Ok(ExecutionResult::precondition_failure(e)),
+            Ok(authorized) => authorized,
+            Err(e) => return ExecutionResult::precondition_failure(e),
+        };
+
+        // The record is written to the tracking copy ahead of payment, so it's part of the
+        // payment's effect, which is kept even if session code fails
+        let record_effect = match self.record_executed_deploy(
+            correlation_id,
+            executor,
+            protocol_version,
+            protocol_data,
+            &account,
+            &authorization_keys,
+            deploy_item.deploy_hash,
+            deploy_item.ttl,
+            blocktime,
+            Rc::clone(&tracking_copy),
+        ) {
+            Ok(effect) => effect,
+            Err(error) => return ExecutionResult::precondition_failure(error),
         };
 
         let session = deploy_item.session;
@@ -1169,13 +1940,14 @@ where
             Rc::clone(&tracking_copy),
             &session,
             &account,
+            &authorization_keys,
             correlation_id,
             preprocessor,
             &protocol_version,
         ) {
             Ok(module) => module,
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error));
+                return ExecutionResult::precondition_failure(error);
             }
         };
 
@@ -1189,25 +1961,18 @@ where
         {
             Ok(contract) => contract,
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error.into()));
+                return ExecutionResult::precondition_failure(error.into());
             }
         };
 
         // cache mint module
-        if !self.system_contract_cache.has(mint_hash) {
-            let mint_module = match tracking_copy.borrow_mut().get_system_module(
-                correlation_id,
-                mint_contract.contract_wasm_hash(),
-                self.config.use_system_contracts(),
-                preprocessor,
-            ) {
-                Ok(contract) => contract,
-                Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
-                }
-            };
-
-            self.system_contract_cache.insert(mint_hash, mint_module);
+        if let Err(error) = self.get_system_module(
+            Rc::clone(&tracking_copy),
+            mint_contract.contract_wasm_hash(),
+            correlation_id,
+            preprocessor,
+        ) {
+            return ExecutionResult::precondition_failure(error);
         }
 
         // Get proof of stake system contract URef from account (an account on a
@@ -1223,28 +1988,22 @@ where
         {
             Ok(contract) => contract,
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error.into()));
+                return ExecutionResult::precondition_failure(error.into());
             }
         };
 
-        let proof_of_stake_module = match tracking_copy.borrow_mut().get_system_module(
-            correlation_id,
+        let proof_of_stake_module = match self.get_system_module(
+            Rc::clone(&tracking_copy),
             proof_of_stake_contract.contract_wasm_hash(),
-            self.config.use_system_contracts(),
+            correlation_id,
             preprocessor,
         ) {
             Ok(module) => module,
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error.into()));
+                return ExecutionResult::precondition_failure(error);
             }
         };
 
-        // cache proof_of_stake module
-        if !self.system_contract_cache.has(proof_of_stake_hash) {
-            self.system_contract_cache
-                .insert(proof_of_stake_hash, proof_of_stake_module.clone());
-        }
-
         // Get account main purse balance key
         // validation_spec_5: account main purse minimum balance
         let account_main_purse_balance_key: Key = {
@@ -1255,7 +2014,7 @@ where
             {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             }
         };
@@ -1267,7 +2026,7 @@ where
             .get_purse_balance(correlation_id, account_main_purse_balance_key)
         {
             Ok(balance) => balance,
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+            Err(error) => return ExecutionResult::precondition_failure(error.into()),
         };
 
         let max_payment_cost: Motes = Motes::new(U512::from(MAX_PAYMENT));
@@ -1275,9 +2034,7 @@ where
         // Enforce minimum main purse balance validation
         // validation_spec_5: account main purse minimum balance
         if account_main_purse_balance < max_payment_cost {
-            return Ok(ExecutionResult::precondition_failure(
-                Error::InsufficientPayment,
-            ));
+            return ExecutionResult::precondition_failure(Error::InsufficientPayment);
         }
 
         // Finalization is executed by system account (currently genesis account)
@@ -1294,180 +2051,28 @@ where
         let mut execution_result_builder = execution_result::ExecutionResultBuilder::new();
 
         // Execute provided payment code
-        let payment_result = {
-            // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost /
-            // conv_rate)
-            let pay_gas_limit = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
-
-            let module_bytes_is_empty = match payment {
-                ExecutableDeployItem::ModuleBytes {
-                    ref module_bytes, ..
-                } => module_bytes.is_empty(),
-                _ => false,
-            };
-
-            // Create payment code module from bytes
-            // validation_spec_1: valid wasm bytes
-            let maybe_payment_module = if module_bytes_is_empty {
-                let standard_payment_hash: ContractHash =
-                    match self.state.get_protocol_data(protocol_version) {
-                        Ok(Some(protocol_data)) => protocol_data.standard_payment(),
-                        Ok(None) => {
-                            return Ok(ExecutionResult::precondition_failure(
-                                Error::InvalidProtocolVersion(protocol_version),
-                            ));
-                        }
-                        Err(_) => return Ok(ExecutionResult::precondition_failure(Error::Deploy)),
-                    };
-
-                // if "use-system-contracts" is false, "do_nothing" wasm is returned
-                self.get_module_from_contract_hash(
-                    Rc::clone(&tracking_copy),
-                    standard_payment_hash,
-                    correlation_id,
-                    &protocol_version,
-                )
-                .map(|module| GetModuleResult::Session {
-                    module,
-                    contract_package: ContractPackage::default(),
-                    entry_point: EntryPoint::default(),
-                })
-            } else {
-                self.get_module(
-                    Rc::clone(&tracking_copy),
-                    &payment,
-                    &account,
-                    correlation_id,
-                    preprocessor,
-                    &protocol_version,
-                )
-            };
-
-            let payment_module = match maybe_payment_module {
-                Ok(module) => module,
-                Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error));
-                }
-            };
-
-            // payment_code_spec_2: execute payment code
-            let phase = Phase::Payment;
-            let (
-                payment_module,
-                payment_base_key,
-                mut payment_named_keys,
-                payment_package,
-                payment_entry_point,
-            ) = match payment_module {
-                GetModuleResult::Session {
-                    module,
-                    contract_package,
-                    entry_point,
-                } => (
-                    module,
-                    base_key,
-                    account.named_keys().clone(),
-                    contract_package,
-                    entry_point,
-                ),
-                GetModuleResult::Contract {
-                    module,
-                    base_key,
-                    contract,
-                    contract_package,
-                    entry_point,
-                } => (
-                    module,
-                    base_key,
-                    contract.named_keys().clone(),
-                    contract_package,
-                    entry_point,
-                ),
-            };
-
-            let payment_args = match payment.into_runtime_args() {
-                Ok(args) => args,
-                Err(e) => {
-                    let exec_err: crate::execution::Error = e.into();
-                    warn!("Unable to deserialize arguments: {:?}", exec_err);
-                    return Ok(ExecutionResult::precondition_failure(exec_err.into()));
-                }
-            };
-
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
-
-            if self.config.use_system_contracts() || !module_bytes_is_empty {
-                executor.exec(
-                    payment_module,
-                    payment_entry_point,
-                    payment_args,
-                    payment_base_key,
-                    &account,
-                    &mut payment_named_keys,
-                    authorization_keys.clone(),
-                    blocktime,
-                    deploy_hash,
-                    pay_gas_limit,
-                    protocol_version,
-                    correlation_id,
-                    Rc::clone(&tracking_copy),
-                    phase,
-                    protocol_data,
-                    system_contract_cache,
-                    &payment_package,
-                )
-            } else {
-                // use host side standard payment
-                let hash_address_generator = {
-                    let generator = AddressGenerator::new(&deploy_hash, phase);
-                    Rc::new(RefCell::new(generator))
-                };
-                let uref_address_generator = {
-                    let generator = AddressGenerator::new(&deploy_hash, phase);
-                    Rc::new(RefCell::new(generator))
-                };
-
-                let mut runtime = match executor.create_runtime(
-                    payment_module,
-                    EntryPointType::Session,
-                    payment_args,
-                    &mut payment_named_keys,
-                    Default::default(),
-                    payment_base_key,
-                    &account,
-                    authorization_keys.clone(),
-                    blocktime,
-                    deploy_hash,
-                    pay_gas_limit,
-                    hash_address_generator,
-                    uref_address_generator,
-                    protocol_version,
-                    correlation_id,
-                    Rc::clone(&tracking_copy),
-                    phase,
-                    protocol_data,
-                    system_contract_cache,
-                ) {
-                    Ok((_instance, runtime)) => runtime,
-                    Err(error) => {
-                        return Ok(ExecutionResult::precondition_failure(Error::Exec(error)));
-                    }
-                };
-
-                let effects_snapshot = tracking_copy.borrow().effect();
-
-                match runtime.call_host_standard_payment() {
-                    Ok(()) => ExecutionResult::Success {
-                        effect: runtime.context().effect(),
-                        cost: runtime.context().gas_counter(),
-                    },
-                    Err(error) => ExecutionResult::Failure {
-                        error: error.into(),
-                        effect: effects_snapshot,
-                        cost: runtime.context().gas_counter(),
-                    },
-                }
-            }
+        // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost /
+        // conv_rate)
+        let pay_gas_limit = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
+        // Both phases count towards the account's spending limit.
+        let transferred = Rc::new(RefCell::new(U512::zero()));
+        let payment_result = match self.exec_payment_code(
+            correlation_id,
+            executor,
+            preprocessor,
+            protocol_version,
+            protocol_data,
+            Rc::clone(&tracking_copy),
+            blocktime,
+            &account,
+            payment,
+            authorization_keys.clone(),
+            deploy_hash,
+            pay_gas_limit,
+            Rc::clone(&transferred),
+        ) {
+            Ok(payment_result) => payment_result,
+            Err(error) => return ExecutionResult::precondition_failure(error),
         };
 
         debug!("Payment result: {:?}", payment_result);
@@ -1481,7 +2086,7 @@ where
             let payment_purse_key: Key =
                 match proof_of_stake_contract.named_keys().get(POS_PAYMENT_PURSE) {
                     Some(key) => *key,
-                    None => return Ok(ExecutionResult::precondition_failure(Error::Deploy)),
+                    None => return ExecutionResult::precondition_failure(Error::Deploy),
                 };
 
             let purse_balance_key = match tracking_copy
@@ -1490,7 +2095,7 @@ where
             {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             };
 
@@ -1500,7 +2105,7 @@ where
             {
                 Ok(balance) => balance,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             }
         };
@@ -1515,7 +2120,7 @@ where
                     match proof_of_stake_contract.named_keys().get(POS_REWARDS_PURSE) {
                         Some(key) => *key,
                         None => {
-                            return Ok(ExecutionResult::precondition_failure(Error::Deploy));
+                            return ExecutionResult::precondition_failure(Error::Deploy);
                         }
                     };
 
@@ -1525,7 +2130,7 @@ where
                 {
                     Ok(key) => key,
                     Err(error) => {
-                        return Ok(ExecutionResult::precondition_failure(error.into()));
+                        return ExecutionResult::precondition_failure(error.into());
                     }
                 }
             };
@@ -1534,13 +2139,14 @@ where
                 ForcedTransferResult::InsufficientPayment => Error::InsufficientPayment,
                 ForcedTransferResult::PaymentFailure => payment_result.take_error().unwrap(),
             };
-            return Ok(ExecutionResult::new_payment_code_error(
+            return ExecutionResult::new_payment_code_error(
                 error,
+                record_effect,
                 max_payment_cost,
                 account_main_purse_balance,
                 account_main_purse_balance_key,
                 rewards_purse_balance_key,
-            ));
+            );
         }
 
         execution_result_builder.set_payment_execution_result(payment_result);
@@ -1549,76 +2155,30 @@ where
         let session_tracking_copy = Rc::new(RefCell::new(post_payment_tracking_copy.fork()));
 
         // session_code_spec_2: execute session code
-        let (
+        // payment_code_spec_3_b_i: if (balance of PoS pay purse) >= (gas spent during
+        // payment code execution) * conv_rate, yes session
+        // session_code_spec_1: gas limit = ((balance of PoS payment purse) / conv_rate)
+        // - (gas spent during payment execution)
+        let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, CONV_RATE)
+            .unwrap_or_default()
+            - payment_result_cost;
+        let session_result = match self.exec_session_code(
+            correlation_id,
+            executor,
+            protocol_version,
+            protocol_data,
+            Rc::clone(&session_tracking_copy),
+            blocktime,
+            &account,
             session_module,
-            session_base_key,
-            mut session_named_keys,
-            session_package,
-            session_entry_point,
-        ) = match session_module {
-            GetModuleResult::Session {
-                module,
-                contract_package,
-                entry_point,
-            } => (
-                module,
-                base_key,
-                account.named_keys().clone(),
-                contract_package,
-                entry_point,
-            ),
-            GetModuleResult::Contract {
-                module,
-                base_key,
-                contract,
-                contract_package,
-                entry_point,
-            } => (
-                module,
-                base_key,
-                contract.named_keys().clone(),
-                contract_package,
-                entry_point,
-            ),
-        };
-
-        let session_args = match session.into_runtime_args() {
-            Ok(args) => args,
-            Err(e) => {
-                let exec_err: crate::execution::Error = e.into();
-                warn!("Unable to deserialize session arguments: {:?}", exec_err);
-                return Ok(ExecutionResult::precondition_failure(exec_err.into()));
-            }
-        };
-        let session_result = {
-            // payment_code_spec_3_b_i: if (balance of PoS pay purse) >= (gas spent during
-            // payment code execution) * conv_rate, yes session
-            // session_code_spec_1: gas limit = ((balance of PoS payment purse) / conv_rate)
-            // - (gas spent during payment execution)
-            let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, CONV_RATE)
-                .unwrap_or_default()
-                - payment_result_cost;
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
-
-            executor.exec(
-                session_module,
-                session_entry_point,
-                session_args,
-                session_base_key,
-                &account,
-                &mut session_named_keys,
-                authorization_keys.clone(),
-                blocktime,
-                deploy_hash,
-                session_gas_limit,
-                protocol_version,
-                correlation_id,
-                Rc::clone(&session_tracking_copy),
-                Phase::Session,
-                protocol_data,
-                system_contract_cache,
-                &session_package,
-            )
+            session,
+            authorization_keys.clone(),
+            deploy_hash,
+            session_gas_limit,
+            transferred,
+        ) {
+            Ok(session_result) => session_result,
+            Err(error) => return ExecutionResult::precondition_failure(error),
         };
         debug!("Session result: {:?}", session_result);
 
@@ -1644,9 +2204,11 @@ where
                 let finalize_cost_motes: Motes = Motes::from_gas(execution_result_builder.total_cost(), CONV_RATE).expect("motes overflow");
                 const ARG_AMOUNT: &str = "amount";
                 const ARG_ACCOUNT_KEY: &str = "account";
+                const ARG_PROPOSER: &str = "proposer";
                 runtime_args! {
                     ARG_AMOUNT => finalize_cost_motes.value(),
                     ARG_ACCOUNT_KEY => account_public_key,
+                    ARG_PROPOSER => proposer,
                 }
             };
 
@@ -1657,13 +2219,13 @@ where
                 .get_contract(correlation_id, proof_of_stake_hash)
             {
                 Ok(info) => info,
-                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                Err(error) => return ExecutionResult::precondition_failure(error.into()),
             };
 
             let mut proof_of_stake_keys = proof_of_stake_contract.named_keys().to_owned();
 
             let gas_limit = Gas::new(U512::from(std::u64::MAX));
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let module_cache = ModuleCache::clone(&self.module_cache);
 
             executor.exec_system_contract(
                 DirectSystemContractCall::FinalizePayment,
@@ -1682,7 +2244,7 @@ where
                 finalization_tc,
                 Phase::FinalizePayment,
                 protocol_data,
-                system_contract_cache,
+                module_cache,
             )
         };
 
@@ -1696,31 +2258,613 @@ where
         // NOTE: payment_code_spec_5_a is enforced in execution_result_builder.build()
         // payment_code_spec_6: return properly combined set of transforms and
         // appropriate error
-        Ok(ret)
+        ret
     }
 
-    pub fn apply_effect(
+    /// Executes `estimate_request`'s deploy as a dry run, returning the gas consumed by its
+    /// payment and session code along with their effect.
+    ///
+    /// Unlike [`EngineState::deploy`], both payment and session code run with an effectively
+    /// unlimited gas limit, and under all the keys associated with the account regardless of the
+    /// deploy's authorization keys, so that neither the payment amount nor the account's action
+    /// thresholds affect the estimate.  Payment is not finalized and nothing is committed.
+    ///
+    /// Wasm-less transfers have a fixed cost and can't be estimated.
+    pub fn run_estimate(
         &self,
         correlation_id: CorrelationId,
-        protocol_version: ProtocolVersion,
-        pre_state_hash: Blake2bHash,
-        effects: AdditiveMap<Key, Transform>,
-    ) -> Result<CommitResult, Error>
-    where
-        Error: From<S::Error>,
-    {
-        match self.state.commit(correlation_id, pre_state_hash, effects)? {
-            CommitResult::Success { state_root, .. } => {
-                let bonded_validators =
-                    self.get_bonded_validators(correlation_id, protocol_version, state_root)?;
-                Ok(CommitResult::Success {
-                    state_root,
-                    bonded_validators,
-                })
-            }
-            commit_result => Ok(commit_result),
+        estimate_request: EstimateRequest,
+    ) -> Result<EstimateResult, RootNotFound> {
+        let EstimateRequest {
+            parent_state_hash,
+            block_time,
+            deploy_item,
+            protocol_version,
+        } = estimate_request;
+        let blocktime = BlockTime::new(block_time);
+
+        if let ExecutableDeployItem::Transfer { .. } = deploy_item.session {
+            let error = Error::InvalidDeployItemVariant("Transfer".to_string());
+            return Ok(EstimateResult::precondition_failure(error));
         }
-    }
+
+        let protocol_data = match self.state.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => {
+                let error = Error::InvalidProtocolVersion(protocol_version);
+                return Ok(EstimateResult::precondition_failure(error));
+            }
+            Err(error) => {
+                let error = Error::Exec(error.into());
+                return Ok(EstimateResult::precondition_failure(error));
+            }
+        };
+
+        let tracking_copy = match self.tracking_copy(parent_state_hash) {
+            Err(error) => return Ok(EstimateResult::precondition_failure(error)),
+            Ok(None) => return Err(RootNotFound::new(parent_state_hash)),
+            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
+        };
+
+        let protocol_data = match resolve_system_contracts(
+            correlation_id,
+            protocol_data,
+            &mut tracking_copy.borrow_mut(),
+        ) {
+            Ok(protocol_data) => protocol_data,
+            Err(error) => return Ok(EstimateResult::precondition_failure(error)),
+        };
+
+        let account = match tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, deploy_item.address)
+        {
+            Ok(account) => account,
+            Err(_) => return Ok(EstimateResult::precondition_failure(Error::Authorization)),
+        };
+        let authorization_keys: BTreeSet<AccountHash> = account
+            .get_associated_keys()
+            .map(|(account_hash, _)| *account_hash)
+            .collect();
+
+        let executor = Executor::new(self.config);
+        let preprocessor = create_preprocessor(*protocol_data.wasm_costs(), protocol_version);
+        let gas_limit = Gas::new(U512::from(std::u64::MAX));
+
+        let session_module = match self.get_module(
+            Rc::clone(&tracking_copy),
+            &deploy_item.session,
+            &account,
+            &authorization_keys,
+            correlation_id,
+            &preprocessor,
+            &protocol_version,
+        ) {
+            Ok(module) => module,
+            Err(error) => return Ok(EstimateResult::precondition_failure(error)),
+        };
+
+        let transferred = Rc::new(RefCell::new(U512::zero()));
+        let payment_result = match self.exec_payment_code(
+            correlation_id,
+            &executor,
+            &preprocessor,
+            protocol_version,
+            protocol_data,
+            Rc::clone(&tracking_copy),
+            blocktime,
+            &account,
+            deploy_item.payment,
+            authorization_keys.clone(),
+            deploy_item.deploy_hash,
+            gas_limit,
+            Rc::clone(&transferred),
+        ) {
+            Ok(payment_result) => payment_result,
+            Err(error) => return Ok(EstimateResult::precondition_failure(error)),
+        };
+        debug!("Estimated payment result: {:?}", payment_result);
+
+        let payment_cost = payment_result.cost();
+        let mut effect = payment_result.effect().clone();
+        if payment_result.is_failure() {
+            let error = payment_result.take_error();
+            return Ok(EstimateResult::new(
+                payment_cost,
+                Gas::default(),
+                effect,
+                error,
+            ));
+        }
+
+        let post_payment_tracking_copy = tracking_copy.borrow();
+        let session_tracking_copy = Rc::new(RefCell::new(post_payment_tracking_copy.fork()));
+        let session_result = match self.exec_session_code(
+            correlation_id,
+            &executor,
+            protocol_version,
+            protocol_data,
+            session_tracking_copy,
+            blocktime,
+            &account,
+            session_module,
+            deploy_item.session,
+            authorization_keys,
+            deploy_item.deploy_hash,
+            gas_limit,
+            transferred,
+        ) {
+            Ok(session_result) => session_result,
+            Err(error) => return Ok(EstimateResult::precondition_failure(error)),
+        };
+        debug!("Estimated session result: {:?}", session_result);
+
+        let session_cost = session_result.cost();
+        if session_result.is_failure() {
+            let error = session_result.take_error();
+            return Ok(EstimateResult::new(
+                payment_cost,
+                session_cost,
+                effect,
+                error,
+            ));
+        }
+
+        let session_effect = session_result.effect();
+        for (key, op) in session_effect.ops.iter() {
+            effect.ops.insert_add(*key, op.clone());
+        }
+        for (key, transform) in session_effect.transforms.iter() {
+            effect.transforms.insert_add(*key, transform.clone());
+        }
+
+        Ok(EstimateResult::new(
+            payment_cost,
+            session_cost,
+            effect,
+            None,
+        ))
+    }
+
+    /// Executes the payment code of a deploy, returning an error if it can't be executed at all.
+    #[allow(clippy::too_many_arguments)]
+    fn exec_payment_code<R>(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        blocktime: BlockTime,
+        account: &Account,
+        payment: ExecutableDeployItem,
+        authorization_keys: BTreeSet<AccountHash>,
+        deploy_hash: [u8; 32],
+        pay_gas_limit: Gas,
+        transferred: Rc<RefCell<U512>>,
+    ) -> Result<ExecutionResult, Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        let base_key = Key::Account(account.account_hash());
+
+        let module_bytes_is_empty = match payment {
+            ExecutableDeployItem::ModuleBytes {
+                ref module_bytes, ..
+            } => module_bytes.is_empty(),
+            _ => false,
+        };
+
+        // Create payment code module from bytes
+        // validation_spec_1: valid wasm bytes
+        let payment_module = if module_bytes_is_empty {
+            // if "use-system-contracts" is false, "do_nothing" wasm is returned
+            self.get_module_from_contract_hash(
+                Rc::clone(&tracking_copy),
+                protocol_data.standard_payment(),
+                correlation_id,
+                preprocessor,
+                &protocol_version,
+            )
+            .map(|module| GetModuleResult::Session {
+                module,
+                contract_package: ContractPackage::default(),
+                entry_point: EntryPoint::default(),
+            })?
+        } else {
+            self.get_module(
+                Rc::clone(&tracking_copy),
+                &payment,
+                account,
+                &authorization_keys,
+                correlation_id,
+                preprocessor,
+                &protocol_version,
+            )?
+        };
+
+        // payment_code_spec_2: execute payment code
+        let phase = Phase::Payment;
+        let (
+            payment_module,
+            payment_base_key,
+            mut payment_named_keys,
+            payment_package,
+            payment_entry_point,
+        ) = match payment_module {
+            GetModuleResult::Session {
+                module,
+                contract_package,
+                entry_point,
+            } => (
+                module,
+                base_key,
+                account.named_keys().clone(),
+                contract_package,
+                entry_point,
+            ),
+            GetModuleResult::Contract {
+                module,
+                base_key,
+                contract,
+                contract_package,
+                entry_point,
+            } => (
+                module,
+                base_key,
+                contract.named_keys().clone(),
+                contract_package,
+                entry_point,
+            ),
+        };
+
+        let payment_args = match payment.into_runtime_args() {
+            Ok(args) => args,
+            Err(e) => {
+                let exec_err: crate::execution::Error = e.into();
+                warn!("Unable to deserialize arguments: {:?}", exec_err);
+                return Err(exec_err.into());
+            }
+        };
+
+        let module_cache = ModuleCache::clone(&self.module_cache);
+
+        if self.config.use_system_contracts() || !module_bytes_is_empty {
+            return Ok(executor.exec(
+                payment_module,
+                payment_entry_point,
+                payment_args,
+                payment_base_key,
+                account,
+                &mut payment_named_keys,
+                authorization_keys,
+                blocktime,
+                deploy_hash,
+                pay_gas_limit,
+                transferred,
+                protocol_version,
+                correlation_id,
+                tracking_copy,
+                phase,
+                protocol_data,
+                module_cache,
+                &payment_package,
+            ));
+        }
+
+        // use host side standard payment
+        let hash_address_generator = {
+            let generator = AddressGenerator::new(&deploy_hash, phase);
+            Rc::new(RefCell::new(generator))
+        };
+        let uref_address_generator = {
+            let generator = AddressGenerator::new(&deploy_hash, phase);
+            Rc::new(RefCell::new(generator))
+        };
+
+        let (_instance, mut runtime) = executor
+            .create_runtime(
+                payment_module,
+                EntryPointType::Session,
+                payment_args,
+                &mut payment_named_keys,
+                Default::default(),
+                payment_base_key,
+                account,
+                authorization_keys,
+                blocktime,
+                deploy_hash,
+                pay_gas_limit,
+                hash_address_generator,
+                uref_address_generator,
+                transferred,
+                protocol_version,
+                correlation_id,
+                Rc::clone(&tracking_copy),
+                phase,
+                protocol_data,
+                module_cache,
+            )
+            .map_err(Error::Exec)?;
+
+        let effects_snapshot = tracking_copy.borrow().effect();
+
+        let payment_result = match runtime.call_host_standard_payment() {
+            Ok(()) => ExecutionResult::Success {
+                effect: runtime.context().effect(),
+                cost: runtime.context().gas_counter(),
+            },
+            Err(error) => ExecutionResult::Failure {
+                error: error.into(),
+                effect: effects_snapshot,
+                cost: runtime.context().gas_counter(),
+            },
+        };
+        Ok(payment_result)
+    }
+
+    /// Executes the session code of a deploy, returning an error if it can't be executed at all.
+    #[allow(clippy::too_many_arguments)]
+    fn exec_session_code<R>(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        blocktime: BlockTime,
+        account: &Account,
+        session_module: GetModuleResult,
+        session: ExecutableDeployItem,
+        authorization_keys: BTreeSet<AccountHash>,
+        deploy_hash: [u8; 32],
+        session_gas_limit: Gas,
+        transferred: Rc<RefCell<U512>>,
+    ) -> Result<ExecutionResult, Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        let (
+            session_module,
+            session_base_key,
+            mut session_named_keys,
+            session_package,
+            session_entry_point,
+        ) = match session_module {
+            GetModuleResult::Session {
+                module,
+                contract_package,
+                entry_point,
+            } => (
+                module,
+                Key::Account(account.account_hash()),
+                account.named_keys().clone(),
+                contract_package,
+                entry_point,
+            ),
+            GetModuleResult::Contract {
+                module,
+                base_key,
+                contract,
+                contract_package,
+                entry_point,
+            } => (
+                module,
+                base_key,
+                contract.named_keys().clone(),
+                contract_package,
+                entry_point,
+            ),
+        };
+
+        let session_args = match session.into_runtime_args() {
+            Ok(args) => args,
+            Err(e) => {
+                let exec_err: crate::execution::Error = e.into();
+                warn!("Unable to deserialize session arguments: {:?}", exec_err);
+                return Err(exec_err.into());
+            }
+        };
+
+        let module_cache = ModuleCache::clone(&self.module_cache);
+
+        Ok(executor.exec(
+            session_module,
+            session_entry_point,
+            session_args,
+            session_base_key,
+            account,
+            &mut session_named_keys,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            session_gas_limit,
+            transferred,
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            Phase::Session,
+            protocol_data,
+            module_cache,
+            &session_package,
+        ))
+    }
+
+    /// Runs the Proof of Stake auction at the end of an era and commits its effects.
+    ///
+    /// The auction elects the validators for the next era from the current stakes and the bids
+    /// placed during the era, and pays back the bidders who lost.  It is executed by the system
+    /// account and fails if the auction mode was not enabled at genesis, or if `blocktime` is
+    /// before the end of the current era.
+    pub fn run_auction(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        blocktime: BlockTime,
+    ) -> Result<CommitResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        self.run_proof_of_stake_step(
+            DirectSystemContractCall::RunAuction,
+            correlation_id,
+            protocol_version,
+            pre_state_hash,
+            blocktime,
+        )
+    }
+
+    /// Mints the seigniorage accrued since the previous call into the Proof of Stake rewards purse
+    /// and commits its effects.
+    ///
+    /// The amount is pro-rated from the annual inflation rate set at genesis or by the latest
+    /// upgrade, so the system is expected to call this at the end of every era.  It is executed by
+    /// the system account; the first call only records `blocktime` as the start of the period.
+    pub fn mint_seigniorage(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        blocktime: BlockTime,
+    ) -> Result<CommitResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        self.run_proof_of_stake_step(
+            DirectSystemContractCall::MintSeigniorage,
+            correlation_id,
+            protocol_version,
+            pre_state_hash,
+            blocktime,
+        )
+    }
+
+    /// Calls the Proof of Stake contract's entry point for `direct_system_contract_call` as the
+    /// system account and commits its effects.
+    fn run_proof_of_stake_step(
+        &self,
+        direct_system_contract_call: DirectSystemContractCall,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        blocktime: BlockTime,
+    ) -> Result<CommitResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        let tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(CommitResult::RootNotFound),
+        };
+        let protocol_data = resolve_system_contracts(
+            correlation_id,
+            protocol_data,
+            &mut tracking_copy.borrow_mut(),
+        )?;
+
+        let system_account = {
+            let key = Key::Account(SYSTEM_ACCOUNT_ADDR);
+            match tracking_copy.borrow_mut().read(correlation_id, &key) {
+                Ok(Some(StoredValue::Account(account))) => account,
+                Ok(_) => panic!("system account must exist"),
+                Err(error) => return Err(Error::Exec(error.into())),
+            }
+        };
+
+        let authorization_keys = {
+            let mut ret = BTreeSet::new();
+            ret.insert(SYSTEM_ACCOUNT_ADDR);
+            ret
+        };
+
+        let proof_of_stake_hash = protocol_data.proof_of_stake();
+        let proof_of_stake_contract = tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, proof_of_stake_hash)?;
+        let mut proof_of_stake_keys = proof_of_stake_contract.named_keys().to_owned();
+
+        let preprocessor = create_preprocessor(*protocol_data.wasm_costs(), protocol_version);
+        let proof_of_stake_module = self.get_system_module(
+            Rc::clone(&tracking_copy),
+            proof_of_stake_contract.contract_wasm_hash(),
+            correlation_id,
+            &preprocessor,
+        )?;
+
+        let deploy_hash = {
+            // seeds address generator w/ the end of the era and the called entry point
+            let mut bytes: Vec<u8> = blocktime.into_bytes()?;
+            bytes.extend(direct_system_contract_call.entry_point_name().as_bytes());
+            Blake2bHash::new(&bytes).into()
+        };
+
+        // the auction has no gas limit; approximating with MAX
+        let gas_limit = Gas::new(std::u64::MAX.into());
+        let module_cache = ModuleCache::clone(&self.module_cache);
+        let executor = Executor::new(self.config);
+
+        let (_, execution_result): (Option<()>, ExecutionResult) = executor.exec_system_contract(
+            direct_system_contract_call,
+            proof_of_stake_module,
+            RuntimeArgs::new(),
+            &mut proof_of_stake_keys,
+            Default::default(),
+            Key::from(proof_of_stake_hash),
+            &system_account,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            Rc::clone(&tracking_copy),
+            Phase::System,
+            protocol_data,
+            module_cache,
+        );
+
+        match execution_result {
+            ExecutionResult::Success { effect, .. } => self.apply_effect(
+                correlation_id,
+                protocol_version,
+                pre_state_hash,
+                effect.transforms,
+            ),
+            ExecutionResult::Failure { error, .. } => Err(error),
+        }
+    }
+
+    pub fn apply_effect(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+    ) -> Result<CommitResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        match self.state.commit(correlation_id, pre_state_hash, effects)? {
+            CommitResult::Success { state_root, .. } => {
+                let bonded_validators =
+                    self.get_bonded_validators(correlation_id, protocol_version, state_root)?;
+                Ok(CommitResult::Success {
+                    state_root,
+                    bonded_validators,
+                })
+            }
+            commit_result => Ok(commit_result),
+        }
+    }
 
     /// Calculates bonded validators at `root_hash` state.
     ///
@@ -1740,14 +2884,19 @@ where
             None => return Err(Error::InvalidProtocolVersion(protocol_version)),
         };
 
-        let proof_of_stake_key = protocol_data.proof_of_stake().into();
-
-        let reader = match self.state.checkout(root_hash)? {
-            Some(reader) => reader,
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
             None => panic!("get_bonded_validators called with an invalid root hash"),
         };
+        let protocol_data =
+            resolve_system_contracts(correlation_id, protocol_data, &mut tracking_copy)?;
 
-        let contract = match reader.read(correlation_id, &proof_of_stake_key)? {
+        let proof_of_stake_key = protocol_data.proof_of_stake().into();
+
+        let contract = match tracking_copy
+            .read(correlation_id, &proof_of_stake_key)
+            .map_err(|error| Error::Exec(error.into()))?
+        {
             Some(StoredValue::Contract(contract)) => contract,
             _ => return Err(MissingSystemContract(PROOF_OF_STAKE.to_string())),
         };
@@ -1761,3 +2910,83 @@ where
         Ok(bonded_validators)
     }
 }
+
+/// Resolves each system contract of `protocol_data` to the current enabled version of its contract
+/// package in `tracking_copy`.
+///
+/// New versions of the system contracts can be added to their packages by the holder of the
+/// packages' access keys without a protocol upgrade, so this has to be done against the state the
+/// system contracts are about to be used in.
+fn resolve_system_contracts<R>(
+    correlation_id: CorrelationId,
+    protocol_data: ProtocolData,
+    tracking_copy: &mut TrackingCopy<R>,
+) -> Result<ProtocolData, Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    protocol_data.resolve_current_versions(|contract_hash| {
+        let contract_package_hash = tracking_copy
+            .get_contract(correlation_id, contract_hash)?
+            .contract_package_hash();
+        tracking_copy
+            .get_contract_package(correlation_id, contract_package_hash)?
+            .current_contract_hash()
+            .ok_or_else(|| {
+                Error::MissingSystemContract(format!(
+                    "no enabled version of contract package {}",
+                    base16::encode_lower(&contract_package_hash)
+                ))
+            })
+    })
+}
+
+/// Writes `value` under the URef stored in the named key `name` of the Proof of Stake contract,
+/// creating the URef if the contract was installed before the named key existed.
+fn write_proof_of_stake_setting<R>(
+    correlation_id: CorrelationId,
+    tracking_copy: &Rc<RefCell<TrackingCopy<R>>>,
+    pre_state_hash: Blake2bHash,
+    proof_of_stake_hash: ContractHash,
+    name: &str,
+    value: CLValue,
+) -> Result<(), Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    let mut proof_of_stake_contract = tracking_copy
+        .borrow_mut()
+        .get_contract(correlation_id, proof_of_stake_hash)?;
+    let value = StoredValue::CLValue(value);
+
+    match proof_of_stake_contract.named_keys().get(name) {
+        Some(Key::URef(uref)) => {
+            tracking_copy.borrow_mut().write(Key::URef(*uref), value);
+        }
+        Some(_) => return Err(Error::InvalidUpgradeConfig),
+        None => {
+            let mut address_generator = AddressGeneratorBuilder::new()
+                .seed_with(&pre_state_hash.value())
+                .seed_with(name.as_bytes())
+                .seed_with(&[Phase::System as u8])
+                .build();
+            let uref = URef::new(
+                address_generator.create_address(),
+                AccessRights::READ_ADD_WRITE,
+            );
+            tracking_copy.borrow_mut().write(Key::URef(uref), value);
+
+            let mut named_keys = NamedKeys::new();
+            named_keys.insert(name.to_string(), Key::URef(uref));
+            proof_of_stake_contract.named_keys_append(&mut named_keys);
+            tracking_copy.borrow_mut().write(
+                proof_of_stake_hash.into(),
+                StoredValue::Contract(proof_of_stake_contract),
+            );
+        }
+    }
+
+    Ok(())
+}
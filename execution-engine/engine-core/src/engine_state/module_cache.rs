@@ -0,0 +1,307 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use parity_wasm::elements::Module;
+
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::ContractWasmHash;
+
+/// Identifies a cached module.
+///
+/// The same contract Wasm is cached separately per set of [`WasmCosts`], as a protocol upgrade
+/// which changes them invalidates any module prepared under the previous costs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ModuleCacheKey {
+    contract_wasm_hash: ContractWasmHash,
+    wasm_costs: WasmCosts,
+}
+
+impl ModuleCacheKey {
+    pub fn new(contract_wasm_hash: ContractWasmHash, wasm_costs: WasmCosts) -> Self {
+        ModuleCacheKey {
+            contract_wasm_hash,
+            wasm_costs,
+        }
+    }
+
+    pub fn contract_wasm_hash(&self) -> ContractWasmHash {
+        self.contract_wasm_hash
+    }
+
+    pub fn wasm_costs(&self) -> &WasmCosts {
+        &self.wasm_costs
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    capacity: usize,
+    /// Cached modules along with the tick at which each was last used.
+    modules: HashMap<ModuleCacheKey, (Module, u64)>,
+    /// Keys of the cached modules ordered from least to most recently used.
+    recency: BTreeMap<u64, ModuleCacheKey>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl Inner {
+    fn touch(&mut self, key: ModuleCacheKey, last_used: u64) -> u64 {
+        self.recency.remove(&last_used);
+        self.tick += 1;
+        self.recency.insert(self.tick, key);
+        self.tick
+    }
+}
+
+/// A bounded, least-recently-used cache of deserialized contract modules, shared between all
+/// deploys executed by an `EngineState`.
+///
+/// A capacity of 0 disables caching.
+#[derive(Clone, Default, Debug)]
+pub struct ModuleCache(Arc<Mutex<Inner>>);
+
+impl ModuleCache {
+    pub fn new(capacity: usize) -> Self {
+        ModuleCache(Arc::new(Mutex::new(Inner {
+            capacity,
+            ..Default::default()
+        })))
+    }
+
+    /// Returns the maximum number of modules held by the cache.
+    pub fn capacity(&self) -> usize {
+        self.0.lock().unwrap().capacity
+    }
+
+    /// Returns the number of modules currently held by the cache.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().modules.len()
+    }
+
+    /// Returns `true` if the cache holds no modules.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the cache has a module corresponding to `key`.
+    ///
+    /// This does not count as a use of the module, nor as a hit or miss.
+    pub fn has(&self, key: &ModuleCacheKey) -> bool {
+        self.0.lock().unwrap().modules.contains_key(key)
+    }
+
+    /// Returns a clone of the module corresponding to `key`, marking it as the most recently used.
+    pub fn get(&self, key: &ModuleCacheKey) -> Option<Module> {
+        let mut guarded_inner = self.0.lock().unwrap();
+        let last_used = match guarded_inner.modules.get(key) {
+            Some((_, last_used)) => *last_used,
+            None => {
+                guarded_inner.misses += 1;
+                return None;
+            }
+        };
+        guarded_inner.hits += 1;
+        let tick = guarded_inner.touch(*key, last_used);
+        let (module, last_used) = guarded_inner
+            .modules
+            .get_mut(key)
+            .expect("module should be cached");
+        *last_used = tick;
+        Some(module.clone())
+    }
+
+    /// Inserts `module` into the cache under `key`, evicting the least recently used module if the
+    /// cache is full.
+    ///
+    /// If the cache did have this key present, the module is updated.
+    pub fn insert(&self, key: ModuleCacheKey, module: Module) {
+        let mut guarded_inner = self.0.lock().unwrap();
+        if guarded_inner.capacity == 0 {
+            return;
+        }
+        let last_used = guarded_inner
+            .modules
+            .get(&key)
+            .map(|(_, last_used)| *last_used);
+        match last_used {
+            Some(last_used) => {
+                guarded_inner.recency.remove(&last_used);
+            }
+            None if guarded_inner.modules.len() >= guarded_inner.capacity => {
+                let (&oldest, _) = guarded_inner
+                    .recency
+                    .iter()
+                    .next()
+                    .expect("full cache should have a least recently used module");
+                let evicted_key = guarded_inner
+                    .recency
+                    .remove(&oldest)
+                    .expect("should remove least recently used key");
+                guarded_inner.modules.remove(&evicted_key);
+            }
+            None => (),
+        }
+        guarded_inner.tick += 1;
+        let tick = guarded_inner.tick;
+        guarded_inner.recency.insert(tick, key);
+        guarded_inner.modules.insert(key, (module, tick));
+    }
+
+    /// Returns a clone of the module corresponding to `key`, or if it isn't cached, calls `load`
+    /// and caches the module it returns.
+    pub fn get_or_load<E, F>(&self, key: ModuleCacheKey, load: F) -> Result<Module, E>
+    where
+        F: FnOnce() -> Result<Module, E>,
+    {
+        if let Some(module) = self.get(&key) {
+            return Ok(module);
+        }
+        let module = load()?;
+        self.insert(key, module.clone());
+        Ok(module)
+    }
+
+    /// Returns the number of lookups which found a cached module.
+    pub fn hits(&self) -> u64 {
+        self.0.lock().unwrap().hits
+    }
+
+    /// Returns the number of lookups which didn't find a cached module.
+    pub fn misses(&self) -> u64 {
+        self.0.lock().unwrap().misses
+    }
+
+    /// Returns the fraction of lookups which found a cached module, or 0 if there have been no
+    /// lookups.
+    pub fn hit_rate(&self) -> f64 {
+        let guarded_inner = self.0.lock().unwrap();
+        let lookups = guarded_inner.hits + guarded_inner.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+        guarded_inner.hits as f64 / lookups as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::{Module, ModuleNameSubsection, NameSection, Section};
+
+    use engine_wasm_prep::wasm_costs::WasmCosts;
+
+    use super::{ModuleCache, ModuleCacheKey};
+
+    fn key(seed: u8) -> ModuleCacheKey {
+        ModuleCacheKey::new([seed; 32], WasmCosts::default())
+    }
+
+    fn named_module(name: &str) -> Module {
+        let section = Section::Name(NameSection::new(
+            Some(ModuleNameSubsection::new(name)),
+            None,
+            None,
+        ));
+        Module::new(vec![section])
+    }
+
+    #[test]
+    fn should_not_have_module_which_was_not_inserted() {
+        let cache = ModuleCache::new(2);
+        assert!(!cache.has(&key(1)));
+        assert!(cache.get(&key(1)).is_none());
+    }
+
+    #[test]
+    fn should_get_inserted_module_repeatedly() {
+        let cache = ModuleCache::new(2);
+        let module = named_module("a");
+        cache.insert(key(1), module.clone());
+        assert!(cache.has(&key(1)));
+        assert!(!cache.has(&key(2)));
+        assert_eq!(cache.get(&key(1)), Some(module.clone()));
+        assert_eq!(cache.get(&key(1)), Some(module));
+        assert_eq!(cache.get(&key(2)), None);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.hits(), 2);
+        assert_eq!(cache.misses(), 1);
+        assert!((cache.hit_rate() - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn should_update_module() {
+        let cache = ModuleCache::new(2);
+        let initial_module = Module::default();
+        let updated_module = named_module("a_mod");
+        assert_ne!(initial_module, updated_module);
+
+        cache.insert(key(1), initial_module);
+        cache.insert(key(1), updated_module.clone());
+        assert_eq!(cache.get(&key(1)), Some(updated_module));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn should_not_evict_when_updating_module_in_full_cache() {
+        let cache = ModuleCache::new(2);
+        cache.insert(key(1), named_module("a"));
+        cache.insert(key(2), named_module("b"));
+        cache.insert(key(1), named_module("c"));
+        assert!(cache.has(&key(1)));
+        assert!(cache.has(&key(2)));
+    }
+
+    #[test]
+    fn should_key_by_wasm_costs() {
+        let cache = ModuleCache::new(2);
+        cache.insert(key(1), named_module("a"));
+        let other_costs = WasmCosts {
+            regular: 2,
+            ..WasmCosts::default()
+        };
+        assert!(!cache.has(&ModuleCacheKey::new([1; 32], other_costs)));
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_module() {
+        let cache = ModuleCache::new(2);
+        cache.insert(key(1), named_module("a"));
+        cache.insert(key(2), named_module("b"));
+        // Using the first module makes the second the least recently used.
+        assert!(cache.get(&key(1)).is_some());
+        cache.insert(key(3), named_module("c"));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.has(&key(1)));
+        assert!(!cache.has(&key(2)));
+        assert!(cache.has(&key(3)));
+    }
+
+    #[test]
+    fn should_not_cache_with_zero_capacity() {
+        let cache = ModuleCache::new(0);
+        let module = cache
+            .get_or_load::<(), _>(key(1), || Ok(named_module("a")))
+            .expect("should load module");
+        assert_eq!(module, named_module("a"));
+        assert!(cache.is_empty());
+        assert_eq!(cache.misses(), 1);
+    }
+
+    #[test]
+    fn should_load_module_once() {
+        let cache = ModuleCache::new(1);
+        let mut loads = 0;
+        for _ in 0..3 {
+            cache
+                .get_or_load::<(), _>(key(1), || {
+                    loads += 1;
+                    Ok(named_module("a"))
+                })
+                .expect("should load module");
+        }
+        assert_eq!(loads, 1);
+        assert_eq!(cache.hits(), 2);
+    }
+}
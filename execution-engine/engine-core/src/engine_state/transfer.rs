@@ -271,6 +271,14 @@ impl TransferRuntimeArgsBuilder {
 
         let amount = self.resolve_amount()?;
 
+        // A wasmless transfer is the only transfer of its deploy, so it's checked against the
+        // spending limit on its own.
+        if source_uref.addr() == account.main_purse().addr()
+            && !account.can_spend(U512::zero(), amount)
+        {
+            return Err(ExecError::Revert(ApiError::SpendingLimitExceeded).into());
+        }
+
         let runtime_args = {
             let mut runtime_args = RuntimeArgs::new();
 
@@ -3,6 +3,7 @@ use std::fmt;
 use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
 use engine_storage::global_state::CommitResult;
 use engine_wasm_prep::wasm_costs::WasmCosts;
+use proof_of_stake::StakingLimits;
 use types::{bytesrepr, Key, ProtocolVersion};
 
 use crate::engine_state::execution_effect::ExecutionEffect;
@@ -61,9 +62,13 @@ pub struct UpgradeConfig {
     upgrade_installer_bytes: Option<Vec<u8>>,
     wasm_costs: Option<WasmCosts>,
     activation_point: Option<ActivationPoint>,
+    new_staking_limits: Option<StakingLimits>,
+    new_annual_inflation_rate: Option<u64>,
+    new_fee_burn_rate: Option<u8>,
 }
 
 impl UpgradeConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pre_state_hash: Blake2bHash,
         current_protocol_version: ProtocolVersion,
@@ -72,6 +77,9 @@ impl UpgradeConfig {
         upgrade_installer_bytes: Option<Vec<u8>>,
         wasm_costs: Option<WasmCosts>,
         activation_point: Option<ActivationPoint>,
+        new_staking_limits: Option<StakingLimits>,
+        new_annual_inflation_rate: Option<u64>,
+        new_fee_burn_rate: Option<u8>,
     ) -> Self {
         UpgradeConfig {
             pre_state_hash,
@@ -81,6 +89,9 @@ impl UpgradeConfig {
             upgrade_installer_bytes,
             wasm_costs,
             activation_point,
+            new_staking_limits,
+            new_annual_inflation_rate,
+            new_fee_burn_rate,
         }
     }
 
@@ -113,4 +124,16 @@ impl UpgradeConfig {
     pub fn activation_point(&self) -> Option<u64> {
         self.activation_point
     }
+
+    pub fn new_staking_limits(&self) -> Option<StakingLimits> {
+        self.new_staking_limits
+    }
+
+    pub fn new_annual_inflation_rate(&self) -> Option<u64> {
+        self.new_annual_inflation_rate
+    }
+
+    pub fn new_fee_burn_rate(&self) -> Option<u8> {
+        self.new_fee_burn_rate
+    }
 }
@@ -3,7 +3,9 @@ use parity_wasm::elements;
 
 use engine_shared::TypeMismatch;
 use types::{
-    account::{AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure},
+    account::{
+        AddKeyFailure, RecoveryFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure,
+    },
     bytesrepr, system_contract_errors, AccessRights, ApiError, CLType, CLValueError,
     ContractPackageHash, ContractVersionKey, Key, URef,
 };
@@ -56,9 +58,13 @@ pub enum Error {
     #[fail(display = "{}", _0)]
     SetThresholdFailure(SetThresholdFailure),
     #[fail(display = "{}", _0)]
+    RecoveryFailure(RecoveryFailure),
+    #[fail(display = "{}", _0)]
     SystemContract(system_contract_errors::Error),
     #[fail(display = "Deployment authorization failure")]
     DeploymentAuthorizationFailure,
+    #[fail(display = "Transfer authorization failure")]
+    TransferAuthorizationFailure,
     #[fail(display = "Expected return value")]
     ExpectedReturnValue,
     #[fail(display = "Unexpected return value")]
@@ -168,6 +174,12 @@ impl From<SetThresholdFailure> for Error {
     }
 }
 
+impl From<RecoveryFailure> for Error {
+    fn from(err: RecoveryFailure) -> Self {
+        Error::RecoveryFailure(err)
+    }
+}
+
 impl From<system_contract_errors::Error> for Error {
     fn from(error: system_contract_errors::Error) -> Self {
         Error::SystemContract(error)
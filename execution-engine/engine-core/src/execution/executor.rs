@@ -2,7 +2,6 @@ use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
 
 use log::warn;
 use parity_wasm::elements::Module;
-use wasmi::ModuleRef;
 
 use engine_shared::{
     account::Account, gas::Gas, newtypes::CorrelationId, stored_value::StoredValue,
@@ -11,15 +10,15 @@ use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use types::{
     account::AccountHash, bytesrepr::FromBytes, contracts::NamedKeys, AccessRights, BlockTime,
     CLTyped, CLValue, ContractPackage, EntryPoint, EntryPointType, Key, Phase, ProtocolVersion,
-    RuntimeArgs,
+    RuntimeArgs, U512,
 };
 
 use crate::{
     engine_state::{
         execution_effect::ExecutionEffect, execution_result::ExecutionResult,
-        system_contract_cache::SystemContractCache, EngineConfig,
+        module_cache::ModuleCache, EngineConfig,
     },
-    execution::{address_generator::AddressGenerator, Error},
+    execution::{address_generator::AddressGenerator, Error, ExecutionTrace, WasmInstance},
     runtime::{
         extract_access_rights_from_keys, extract_access_rights_from_urefs, instance_and_memory,
         Runtime,
@@ -73,18 +72,50 @@ macro_rules! on_fail_charge {
 
 pub struct Executor {
     config: EngineConfig,
+    trace: Option<Rc<RefCell<ExecutionTrace>>>,
+    profiling: bool,
 }
 
 #[allow(clippy::too_many_arguments)]
 impl Executor {
     pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+        Executor {
+            config,
+            trace: None,
+            profiling: false,
+        }
     }
 
     pub fn config(&self) -> EngineConfig {
         self.config
     }
 
+    /// Makes [`exec`](Executor::exec) record the host functions called by the code it runs.
+    ///
+    /// The trace accumulates over successive calls until it is taken with
+    /// [`take_trace`](Executor::take_trace).
+    pub fn enable_tracing(&mut self) {
+        self.trace = Some(Default::default());
+    }
+
+    /// Makes [`exec`](Executor::exec) record entry to and return from the Wasm functions of the
+    /// code it runs, as well as its host function calls, so the trace can be turned into a
+    /// [`GasProfile`](crate::execution::GasProfile).
+    ///
+    /// Profiling enables tracing.  The code run is instrumented when instantiated, without
+    /// affecting the gas it costs.
+    pub fn enable_profiling(&mut self) {
+        self.enable_tracing();
+        self.profiling = true;
+    }
+
+    /// Returns the trace recorded since the last call, or `None` if tracing is not enabled.
+    pub fn take_trace(&self) -> Option<ExecutionTrace> {
+        self.trace
+            .as_ref()
+            .map(|trace| trace.replace(ExecutionTrace::new()))
+    }
+
     pub fn exec<R>(
         &self,
         module: Module,
@@ -97,12 +128,13 @@ impl Executor {
         blocktime: BlockTime,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
+        transferred: Rc<RefCell<U512>>,
         protocol_version: ProtocolVersion,
         correlation_id: CorrelationId,
         tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         protocol_data: ProtocolData,
-        system_contract_cache: SystemContractCache,
+        module_cache: ModuleCache,
         contract_package: &ContractPackage,
     ) -> ExecutionResult
     where
@@ -113,8 +145,13 @@ impl Executor {
         let entry_point_type = entry_point.entry_point_type();
         let entry_point_access = entry_point.access();
 
-        let (instance, memory) =
-            on_fail_charge!(instance_and_memory(module.clone(), protocol_version));
+        let (instance, memory) = on_fail_charge!(instance_and_memory(
+            module.clone(),
+            protocol_version,
+            protocol_data.wasm_costs().max_memory,
+            self.config.wasm_backend(),
+            self.profiling
+        ));
 
         let access_rights = {
             let keys: Vec<Key> = named_keys.values().cloned().collect();
@@ -150,13 +187,20 @@ impl Executor {
             gas_counter,
             hash_address_generator,
             uref_address_generator,
+            transferred,
             protocol_version,
             correlation_id,
             phase,
             protocol_data,
         );
 
-        let mut runtime = Runtime::new(self.config, system_contract_cache, memory, module, context);
+        let mut runtime = Runtime::new(self.config, module_cache, memory, module, context);
+        if let Some(trace) = &self.trace {
+            runtime.set_trace(Rc::clone(trace));
+        }
+        if self.profiling {
+            runtime.enable_profiling();
+        }
 
         let accounts_access_rights = {
             let keys: Vec<Key> = account.named_keys().values().cloned().collect();
@@ -217,11 +261,21 @@ impl Executor {
             }
         }
 
-        on_fail_charge!(
-            instance.invoke_export(entry_point_name, &[], &mut runtime),
-            runtime.context().gas_counter(),
-            effects_snapshot
-        );
+        if let Some(trace) = &self.trace {
+            trace
+                .borrow_mut()
+                .enter(phase, base_key, entry_point_name, Gas::default());
+        }
+        let result = instance.invoke_export(entry_point_name, &mut runtime);
+        if let Some(trace) = &self.trace {
+            let error = result.as_ref().err().map(ToString::to_string);
+            let gas = runtime.context().gas_counter();
+            trace
+                .borrow_mut()
+                .exit(base_key, entry_point_name, error, gas);
+        }
+
+        on_fail_charge!(result, runtime.context().gas_counter(), effects_snapshot);
 
         ExecutionResult::Success {
             effect: runtime.context().effect(),
@@ -247,7 +301,7 @@ impl Executor {
         tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         protocol_data: ProtocolData,
-        system_contract_cache: SystemContractCache,
+        module_cache: ModuleCache,
     ) -> (Option<T>, ExecutionResult)
     where
         R: StateReader<Key, StoredValue>,
@@ -255,7 +309,9 @@ impl Executor {
         T: FromBytes + CLTyped,
     {
         match direct_system_contract_call {
-            DirectSystemContractCall::FinalizePayment => {
+            DirectSystemContractCall::FinalizePayment
+            | DirectSystemContractCall::RunAuction
+            | DirectSystemContractCall::MintSeigniorage => {
                 if protocol_data.proof_of_stake() != base_key.into_seed() {
                     panic!(
                         "{} should only be called with the proof of stake contract",
@@ -302,12 +358,13 @@ impl Executor {
                 gas_limit,
                 hash_address_generator,
                 uref_address_generator,
+                Default::default(),
                 protocol_version,
                 correlation_id,
                 tracking_copy,
                 phase,
                 protocol_data,
-                system_contract_cache,
+                module_cache,
             )
             .map_err(|e| {
                 ExecutionResult::Failure {
@@ -334,11 +391,9 @@ impl Executor {
         }
 
         let (maybe_ret, maybe_error, revert_effect): (Option<T>, Option<Error>, bool) = {
-            match instance.invoke_export(
-                direct_system_contract_call.entry_point_name(),
-                &[],
-                &mut runtime,
-            ) {
+            match instance
+                .invoke_export(direct_system_contract_call.entry_point_name(), &mut runtime)
+            {
                 Err(error) => match error.as_host_error() {
                     Some(host_error) => match host_error.downcast_ref::<Error>().unwrap() {
                         Error::Ret(ref ret_urefs) => match runtime.take_host_buffer() {
@@ -415,7 +470,7 @@ impl Executor {
         tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         protocol_data: ProtocolData,
-        system_contract_cache: SystemContractCache,
+        module_cache: ModuleCache,
     ) -> Result<T, Error>
     where
         R: StateReader<Key, StoredValue>,
@@ -439,16 +494,16 @@ impl Executor {
             gas_limit,
             hash_address_generator,
             uref_address_generator,
+            Default::default(),
             protocol_version,
             correlation_id,
             tracking_copy,
             phase,
             protocol_data,
-            system_contract_cache,
+            module_cache,
         )?;
 
-        let error: wasmi::Error = match instance.invoke_export(entry_point_name, &[], &mut runtime)
-        {
+        let error: wasmi::Error = match instance.invoke_export(entry_point_name, &mut runtime) {
             Err(error) => error,
             Ok(_) => {
                 // This duplicates the behavior of runtime sub_call.
@@ -480,6 +535,67 @@ impl Executor {
         Ok(ret)
     }
 
+    /// Records the deploy in `account` until `expires_at`, going through a runtime context of the
+    /// account so the written account is validated like any other.
+    ///
+    /// Returns the account written, or `None` if the deploy was already recorded and hasn't
+    /// expired yet.
+    pub fn record_executed_deploy<R>(
+        &self,
+        account: &Account,
+        authorization_keys: BTreeSet<AccountHash>,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        expires_at: BlockTime,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        protocol_data: ProtocolData,
+    ) -> Result<Option<StoredValue>, Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+    {
+        let mut named_keys = account.named_keys().clone();
+        let access_rights = {
+            let keys: Vec<Key> = named_keys.values().cloned().collect();
+            extract_access_rights_from_keys(keys)
+        };
+        let phase = Phase::Payment;
+        let hash_address_generator = {
+            let generator = AddressGenerator::new(&deploy_hash, phase);
+            Rc::new(RefCell::new(generator))
+        };
+        let uref_address_generator = {
+            let generator = AddressGenerator::new(&deploy_hash, phase);
+            Rc::new(RefCell::new(generator))
+        };
+
+        let mut context = RuntimeContext::new(
+            tracking_copy,
+            EntryPointType::Session,
+            &mut named_keys,
+            access_rights,
+            RuntimeArgs::new(),
+            authorization_keys,
+            account,
+            Key::Account(account.account_hash()),
+            blocktime,
+            deploy_hash,
+            Gas::default(),
+            Gas::default(),
+            hash_address_generator,
+            uref_address_generator,
+            Default::default(),
+            protocol_version,
+            correlation_id,
+            phase,
+            protocol_data,
+        );
+
+        context.record_executed_deploy(expires_at)
+    }
+
     pub fn create_runtime<'a, R>(
         &self,
         module: Module,
@@ -495,13 +611,14 @@ impl Executor {
         gas_limit: Gas,
         hash_address_generator: Rc<RefCell<AddressGenerator>>,
         uref_address_generator: Rc<RefCell<AddressGenerator>>,
+        transferred: Rc<RefCell<U512>>,
         protocol_version: ProtocolVersion,
         correlation_id: CorrelationId,
         tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         protocol_data: ProtocolData,
-        system_contract_cache: SystemContractCache,
-    ) -> Result<(ModuleRef, Runtime<'a, R>), Error>
+        module_cache: ModuleCache,
+    ) -> Result<(Box<dyn WasmInstance>, Runtime<'a, R>), Error>
     where
         R: StateReader<Key, StoredValue>,
         R::Error: Into<Error>,
@@ -529,21 +646,22 @@ impl Executor {
             gas_counter,
             hash_address_generator,
             uref_address_generator,
+            transferred,
             protocol_version,
             correlation_id,
             phase,
             protocol_data,
         );
 
-        let (instance, memory) = instance_and_memory(module.clone(), protocol_version)?;
+        let (instance, memory) = instance_and_memory(
+            module.clone(),
+            protocol_version,
+            protocol_data.wasm_costs().max_memory,
+            self.config.wasm_backend(),
+            false,
+        )?;
 
-        let runtime = Runtime::new(
-            self.config,
-            system_contract_cache,
-            memory,
-            module,
-            runtime_context,
-        );
+        let runtime = Runtime::new(self.config, module_cache, memory, module, runtime_context);
 
         Ok((instance, runtime))
     }
@@ -553,14 +671,18 @@ pub enum DirectSystemContractCall {
     FinalizePayment,
     CreatePurse,
     Transfer,
+    RunAuction,
+    MintSeigniorage,
 }
 
 impl DirectSystemContractCall {
-    fn entry_point_name(&self) -> &str {
+    pub(crate) fn entry_point_name(&self) -> &str {
         match self {
             DirectSystemContractCall::FinalizePayment => "finalize_payment",
             DirectSystemContractCall::CreatePurse => "create",
             DirectSystemContractCall::Transfer => "transfer",
+            DirectSystemContractCall::RunAuction => "run_auction",
+            DirectSystemContractCall::MintSeigniorage => "mint_seigniorage",
         }
     }
 
@@ -580,7 +702,9 @@ impl DirectSystemContractCall {
     {
         let entry_point_name = self.entry_point_name();
         let result = match self {
-            DirectSystemContractCall::FinalizePayment => runtime.call_host_proof_of_stake(
+            DirectSystemContractCall::FinalizePayment
+            | DirectSystemContractCall::RunAuction
+            | DirectSystemContractCall::MintSeigniorage => runtime.call_host_proof_of_stake(
                 protocol_version,
                 entry_point_name,
                 named_keys,
@@ -0,0 +1,303 @@
+//! Attribution of the gas consumed by a deploy to the code it was consumed by.
+//!
+//! A [`GasProfile`] is built from an [`ExecutionTrace`].  The gas charged between two entries of
+//! the trace is attributed to the stack of frames open at that point: the stored contracts
+//! entered, the Wasm functions called if the trace was recorded while profiling, and the host
+//! function being called, if any.  Gas charged by the `gas` host function, i.e. for executing Wasm
+//! instructions, is attributed to the calling function itself.
+//!
+//! The profile can be written in the folded-stack format read by flamegraph tools, e.g.
+//! `inferno-flamegraph` or `flamegraph.pl`.
+
+use std::{cmp, collections::BTreeMap};
+
+use engine_shared::gas::Gas;
+use types::Key;
+
+use super::trace::{ExecutionTrace, TraceEntry};
+use crate::resolvers::v1_function_index::FunctionIndex;
+
+const FRAME_SEPARATOR: char = ';';
+const ROOT_FRAME: &str = "[root]";
+
+/// Gas consumed per stack of frames.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasProfile {
+    stacks: BTreeMap<String, Gas>,
+}
+
+impl GasProfile {
+    /// Returns the gas consumed per stack, each stack being a list of frames separated by `;`,
+    /// outermost first.
+    pub fn stacks(&self) -> &BTreeMap<String, Gas> {
+        &self.stacks
+    }
+
+    /// Returns the total gas consumed.
+    pub fn total(&self) -> Gas {
+        self.stacks
+            .values()
+            .fold(Gas::default(), |total, gas| total + *gas)
+    }
+
+    /// Returns the gas consumed by `frame` itself, excluding that consumed by the frames it called.
+    pub fn self_cost(&self, frame: &str) -> Gas {
+        self.stacks
+            .iter()
+            .filter(|(stack, _)| stack.rsplit(FRAME_SEPARATOR).next() == Some(frame))
+            .fold(Gas::default(), |total, (_, gas)| total + *gas)
+    }
+
+    /// Returns the gas consumed by `frame` and by the frames it called.
+    pub fn total_cost(&self, frame: &str) -> Gas {
+        self.stacks
+            .iter()
+            .filter(|(stack, _)| stack.split(FRAME_SEPARATOR).any(|name| name == frame))
+            .fold(Gas::default(), |total, (_, gas)| total + *gas)
+    }
+
+    /// Returns the profile in the folded-stack format, one `<stack> <gas>` line per stack.
+    pub fn to_folded(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|(stack, gas)| format!("{} {}\n", stack, gas.value()))
+            .collect()
+    }
+}
+
+enum Frame {
+    Contract(String),
+    Function(String),
+    Host {
+        name: String,
+        gas_after: Gas,
+        /// Whether the host function has called a contract, in which case the call has returned.
+        called_contract: bool,
+    },
+}
+
+impl Frame {
+    fn name(&self) -> &str {
+        match self {
+            Frame::Contract(name) | Frame::Function(name) | Frame::Host { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Folder {
+    stacks: BTreeMap<String, Gas>,
+    frames: Vec<Frame>,
+    gas: Option<Gas>,
+}
+
+impl Folder {
+    /// Attributes the gas charged since the last entry to the current stack.
+    fn charge(&mut self, gas: Gas) {
+        let previous = self.gas.unwrap_or(gas);
+        if gas > previous {
+            let stack = if self.frames.is_empty() {
+                ROOT_FRAME.to_string()
+            } else {
+                self.frames
+                    .iter()
+                    .map(Frame::name)
+                    .collect::<Vec<_>>()
+                    .join(&FRAME_SEPARATOR.to_string())
+            };
+            let total = self.stacks.entry(stack).or_default();
+            *total = *total + (gas - previous);
+        }
+        self.gas = Some(cmp::max(previous, gas));
+    }
+
+    /// Pops the host calls which have returned, given that `entering_contract` is whether the next
+    /// entry is the start of a contract called by the innermost host call.
+    fn close_host_calls(&mut self, entering_contract: bool) {
+        while let Some(Frame::Host {
+            gas_after,
+            called_contract,
+            ..
+        }) = self.frames.last_mut()
+        {
+            if entering_contract && !*called_contract {
+                *called_contract = true;
+                return;
+            }
+            let gas_after = *gas_after;
+            self.charge(gas_after);
+            self.frames.pop();
+        }
+    }
+
+    fn fold(&mut self, entry: &TraceEntry) {
+        match entry {
+            TraceEntry::HostCall {
+                function,
+                gas_before,
+                gas_after,
+                ..
+            } => {
+                self.close_host_calls(false);
+                if *function == FunctionIndex::GasFuncIndex {
+                    self.charge(*gas_after);
+                } else {
+                    self.charge(*gas_before);
+                    self.frames.push(Frame::Host {
+                        name: frame_name(&format!("{:?}", function)),
+                        gas_after: *gas_after,
+                        called_contract: false,
+                    });
+                }
+            }
+            TraceEntry::Enter {
+                base_key,
+                entry_point,
+                gas,
+                ..
+            } => {
+                self.close_host_calls(true);
+                if self.frames.is_empty() {
+                    // Each phase of a deploy is charged from zero.
+                    self.gas = None;
+                }
+                self.charge(*gas);
+                self.frames
+                    .push(Frame::Contract(contract_frame_name(*base_key, entry_point)));
+            }
+            TraceEntry::Exit { gas, .. } => {
+                // Host calls and functions which trapped don't return, e.g. those calling `ret`.
+                self.close_host_calls(false);
+                self.charge(*gas);
+                while let Some(frame) = self.frames.pop() {
+                    if let Frame::Contract(_) = frame {
+                        break;
+                    }
+                }
+            }
+            TraceEntry::FunctionEnter { name, gas, .. } => {
+                self.close_host_calls(false);
+                self.charge(*gas);
+                self.frames.push(Frame::Function(frame_name(name)));
+            }
+            TraceEntry::FunctionExit { gas, .. } => {
+                self.close_host_calls(false);
+                self.charge(*gas);
+                if let Some(Frame::Function(_)) = self.frames.last() {
+                    self.frames.pop();
+                }
+            }
+        }
+    }
+}
+
+impl From<&ExecutionTrace> for GasProfile {
+    fn from(trace: &ExecutionTrace) -> Self {
+        let mut folder = Folder::default();
+        for entry in trace.entries() {
+            folder.fold(entry);
+        }
+        folder.close_host_calls(false);
+        GasProfile {
+            stacks: folder.stacks,
+        }
+    }
+}
+
+fn contract_frame_name(base_key: Key, entry_point: &str) -> String {
+    frame_name(&format!("{}@{}", entry_point, base_key.as_string()))
+}
+
+/// Makes `name` usable as a frame of a folded stack.
+fn frame_name(name: &str) -> String {
+    name.replace(FRAME_SEPARATOR, ":")
+        .replace(char::is_whitespace, "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::gas::Gas;
+    use types::{Key, Phase, U512};
+
+    use super::GasProfile;
+    use crate::{execution::ExecutionTrace, resolvers::v1_function_index::FunctionIndex};
+
+    fn gas(value: u64) -> Gas {
+        Gas::new(U512::from(value))
+    }
+
+    #[test]
+    fn should_attribute_gas_to_stacks() {
+        let session_key = Key::Hash([1; 32]);
+        let contract_key = Key::Hash([2; 32]);
+        let session = format!("call@{}", session_key.as_string());
+        let contract = format!("inc@{}", contract_key.as_string());
+
+        let mut trace = ExecutionTrace::new();
+        trace.enter(Phase::Session, session_key, "call", gas(0));
+        trace.enter_function(1, "call", gas(0));
+        trace.begin_host_call(FunctionIndex::GasFuncIndex, gas(0));
+        trace.end_host_call(None, None, gas(10));
+        trace.enter_function(2, "helper", gas(10));
+        trace.begin_host_call(FunctionIndex::GasFuncIndex, gas(10));
+        trace.end_host_call(None, None, gas(15));
+        trace.begin_host_call(FunctionIndex::WriteFuncIndex, gas(15));
+        trace.end_host_call(None, None, gas(115));
+        trace.exit_function(2, gas(115));
+        trace.begin_host_call(FunctionIndex::CallContractFuncIndex, gas(115));
+        trace.enter(Phase::Session, contract_key, "inc", gas(120));
+        trace.begin_host_call(FunctionIndex::GasFuncIndex, gas(120));
+        trace.end_host_call(None, None, gas(150));
+        trace.exit(contract_key, "inc", None, gas(150));
+        trace.end_host_call(Some(0), None, gas(152));
+        trace.begin_host_call(FunctionIndex::RetFuncIndex, gas(152));
+        trace.end_host_call(None, Some("Ret".to_string()), gas(153));
+        trace.exit(session_key, "call", Some("Ret".to_string()), gas(153));
+        trace.enter(Phase::FinalizePayment, session_key, "call", gas(0));
+        trace.begin_host_call(FunctionIndex::GasFuncIndex, gas(0));
+        trace.end_host_call(None, None, gas(3));
+        trace.exit(session_key, "call", None, gas(3));
+
+        let profile = GasProfile::from(&trace);
+
+        let stack = |frames: &[&str]| frames.join(";");
+        let expected = vec![
+            (stack(&[&session, "call"]), gas(10)),
+            (stack(&[&session, "call", "helper"]), gas(5)),
+            (
+                stack(&[&session, "call", "helper", "WriteFuncIndex"]),
+                gas(100),
+            ),
+            (stack(&[&session, "call", "CallContractFuncIndex"]), gas(7)),
+            (
+                stack(&[&session, "call", "CallContractFuncIndex", &contract]),
+                gas(30),
+            ),
+            (stack(&[&session, "call", "RetFuncIndex"]), gas(1)),
+            (session.clone(), gas(3)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(profile.stacks(), &expected);
+
+        assert_eq!(profile.total(), gas(156));
+        assert_eq!(profile.self_cost("helper"), gas(5));
+        assert_eq!(profile.total_cost("helper"), gas(105));
+        assert_eq!(profile.total_cost(&contract), gas(30));
+    }
+
+    #[test]
+    fn should_write_folded_stacks() {
+        let mut trace = ExecutionTrace::new();
+        trace.enter(Phase::Payment, Key::Hash([0; 32]), "call", gas(0));
+        trace.enter_function(0, "my fn;1", gas(0));
+        trace.begin_host_call(FunctionIndex::GasFuncIndex, gas(0));
+        trace.end_host_call(None, None, gas(42));
+
+        let folded = GasProfile::from(&trace).to_folded();
+        assert_eq!(
+            folded,
+            format!("call@{};my_fn:1 42\n", Key::Hash([0; 32]).as_string())
+        );
+    }
+}
@@ -2,11 +2,19 @@ mod address_generator;
 mod error;
 #[macro_use]
 mod executor;
+pub mod gas_profile;
 #[cfg(test)]
 mod tests;
+pub mod trace;
+pub mod wasm_backend;
+#[cfg(feature = "wasmtime-backend")]
+pub(crate) mod wasmtime_backend;
 
 pub use self::{
     address_generator::{AddressGenerator, AddressGeneratorBuilder},
     error::Error,
     executor::{DirectSystemContractCall, Executor},
+    gas_profile::GasProfile,
+    trace::{ExecutionTrace, TraceArg, TraceEntry},
+    wasm_backend::{InstanceMemory, WasmBackend, WasmInstance},
 };
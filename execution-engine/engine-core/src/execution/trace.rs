@@ -0,0 +1,337 @@
+//! Recording of the host functions called while executing a deploy.
+//!
+//! A trace is an ordered list of [`TraceEntry`]s.  Every host function call is recorded along with
+//! the keys, values and names it decoded from Wasm memory, its return code and the gas counter
+//! before and after the call.  Calls to stored contracts are delimited by
+//! [`TraceEntry::Enter`] and [`TraceEntry::Exit`] frames, so the host calls made by a nested
+//! contract appear between the frames of that contract.  When profiling, calls to the functions
+//! defined by the Wasm modules are delimited in the same way by [`TraceEntry::FunctionEnter`] and
+//! [`TraceEntry::FunctionExit`].
+
+use serde_json::{json, Value};
+
+use engine_shared::{gas::Gas, utils::jsonify};
+use types::{CLValue, Key, Phase};
+
+use crate::resolvers::v1_function_index::FunctionIndex;
+
+/// An argument of a host function call, as decoded from Wasm memory.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TraceArg {
+    Key(Key),
+    CLValue(CLValue),
+    Name(String),
+}
+
+impl TraceArg {
+    fn to_json(&self) -> Value {
+        match self {
+            TraceArg::Key(key) => json!({ "key": key.as_string() }),
+            TraceArg::CLValue(cl_value) => json!({
+                "cl_value": {
+                    "cl_type": format!("{:?}", cl_value.cl_type()),
+                    "bytes": base16::encode_lower(cl_value.inner_bytes()),
+                }
+            }),
+            TraceArg::Name(name) => json!({ "name": name }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TraceEntry {
+    /// A call to a host function.
+    HostCall {
+        function: FunctionIndex,
+        args: Vec<TraceArg>,
+        /// The value returned to the Wasm caller, if the host function returns one.
+        return_code: Option<i32>,
+        /// The error the host function trapped with, if any.  Note that a call to `ret` always
+        /// traps in order to stop execution.
+        error: Option<String>,
+        gas_before: Gas,
+        gas_after: Gas,
+    },
+    /// Start of the execution of an entry point, either of the deploy itself or of a stored
+    /// contract it calls.
+    Enter {
+        phase: Phase,
+        base_key: Key,
+        entry_point: String,
+        gas: Gas,
+    },
+    /// End of the execution of the entry point entered by the matching [`TraceEntry::Enter`].
+    Exit {
+        base_key: Key,
+        entry_point: String,
+        error: Option<String>,
+        gas: Gas,
+    },
+    /// Call to a function defined by the Wasm module being executed, only recorded when
+    /// profiling.
+    FunctionEnter { index: u32, name: String, gas: Gas },
+    /// Return from the function called by the matching [`TraceEntry::FunctionEnter`].  There is
+    /// none if the function trapped.
+    FunctionExit { index: u32, gas: Gas },
+}
+
+impl TraceEntry {
+    fn to_json(&self) -> Value {
+        match self {
+            TraceEntry::HostCall {
+                function,
+                args,
+                return_code,
+                error,
+                gas_before,
+                gas_after,
+            } => json!({
+                "type": "host_call",
+                "function": format!("{:?}", function),
+                "args": args.iter().map(TraceArg::to_json).collect::<Vec<_>>(),
+                "return_code": return_code,
+                "error": error,
+                "gas_before": gas_before.value().to_string(),
+                "gas_after": gas_after.value().to_string(),
+            }),
+            TraceEntry::Enter {
+                phase,
+                base_key,
+                entry_point,
+                gas,
+            } => json!({
+                "type": "enter",
+                "phase": format!("{:?}", phase),
+                "base_key": base_key.as_string(),
+                "entry_point": entry_point,
+                "gas": gas.value().to_string(),
+            }),
+            TraceEntry::Exit {
+                base_key,
+                entry_point,
+                error,
+                gas,
+            } => json!({
+                "type": "exit",
+                "base_key": base_key.as_string(),
+                "entry_point": entry_point,
+                "error": error,
+                "gas": gas.value().to_string(),
+            }),
+            TraceEntry::FunctionEnter { index, name, gas } => json!({
+                "type": "function_enter",
+                "index": index,
+                "name": name,
+                "gas": gas.value().to_string(),
+            }),
+            TraceEntry::FunctionExit { index, gas } => json!({
+                "type": "function_exit",
+                "index": index,
+                "gas": gas.value().to_string(),
+            }),
+        }
+    }
+}
+
+/// The ordered trace of the execution of a deploy.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExecutionTrace {
+    entries: Vec<TraceEntry>,
+    /// Indices into `entries` of the host calls which haven't returned yet, innermost last.
+    open_host_calls: Vec<usize>,
+}
+
+impl ExecutionTrace {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the host functions called, in order.
+    pub fn host_calls(&self) -> impl Iterator<Item = FunctionIndex> + '_ {
+        self.entries.iter().filter_map(|entry| match entry {
+            TraceEntry::HostCall { function, .. } => Some(*function),
+            _ => None,
+        })
+    }
+
+    /// Returns the trace as a JSON document of the form `{"entries": [...]}`.
+    pub fn to_json(&self, pretty_print: bool) -> String {
+        let entries: Vec<Value> = self.entries.iter().map(TraceEntry::to_json).collect();
+        jsonify(json!({ "entries": entries }), pretty_print)
+    }
+
+    pub(crate) fn begin_host_call(&mut self, function: FunctionIndex, gas_before: Gas) {
+        self.open_host_calls.push(self.entries.len());
+        self.entries.push(TraceEntry::HostCall {
+            function,
+            args: Vec::new(),
+            return_code: None,
+            error: None,
+            gas_before,
+            gas_after: gas_before,
+        });
+    }
+
+    /// Adds `arg` to the innermost host call which hasn't returned yet.
+    pub(crate) fn record_arg(&mut self, arg: TraceArg) {
+        let entries = &mut self.entries;
+        if let Some(TraceEntry::HostCall { args, .. }) = self
+            .open_host_calls
+            .last()
+            .and_then(|index| entries.get_mut(*index))
+        {
+            args.push(arg);
+        }
+    }
+
+    pub(crate) fn end_host_call(
+        &mut self,
+        result_code: Option<i32>,
+        result_error: Option<String>,
+        gas: Gas,
+    ) {
+        if let Some(TraceEntry::HostCall {
+            return_code,
+            error,
+            gas_after,
+            ..
+        }) = self
+            .open_host_calls
+            .pop()
+            .and_then(|index| self.entries.get_mut(index))
+        {
+            *return_code = result_code;
+            *error = result_error;
+            *gas_after = gas;
+        }
+    }
+
+    pub(crate) fn enter(&mut self, phase: Phase, base_key: Key, entry_point: &str, gas: Gas) {
+        self.entries.push(TraceEntry::Enter {
+            phase,
+            base_key,
+            entry_point: entry_point.to_string(),
+            gas,
+        });
+    }
+
+    pub(crate) fn exit(
+        &mut self,
+        base_key: Key,
+        entry_point: &str,
+        error: Option<String>,
+        gas: Gas,
+    ) {
+        self.entries.push(TraceEntry::Exit {
+            base_key,
+            entry_point: entry_point.to_string(),
+            error,
+            gas,
+        });
+    }
+
+    pub(crate) fn enter_function(&mut self, index: u32, name: &str, gas: Gas) {
+        self.entries.push(TraceEntry::FunctionEnter {
+            index,
+            name: name.to_string(),
+            gas,
+        });
+    }
+
+    pub(crate) fn exit_function(&mut self, index: u32, gas: Gas) {
+        self.entries.push(TraceEntry::FunctionExit { index, gas });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{CLValue, Key, Phase, U512};
+
+    use super::{ExecutionTrace, TraceArg, TraceEntry};
+    use crate::resolvers::v1_function_index::FunctionIndex;
+    use engine_shared::gas::Gas;
+
+    fn gas(value: u64) -> Gas {
+        Gas::new(U512::from(value))
+    }
+
+    #[test]
+    fn should_record_args_to_innermost_open_host_call() {
+        let contract_key = Key::Hash([1; 32]);
+        let value_key = Key::Hash([2; 32]);
+
+        let mut trace = ExecutionTrace::new();
+        trace.begin_host_call(FunctionIndex::CallContractFuncIndex, gas(10));
+        trace.record_arg(TraceArg::Name("counter".to_string()));
+        trace.enter(Phase::Session, contract_key, "inc", gas(12));
+        trace.begin_host_call(FunctionIndex::ReadFuncIndex, gas(13));
+        trace.record_arg(TraceArg::Key(value_key));
+        trace.end_host_call(Some(0), None, gas(15));
+        trace.exit(contract_key, "inc", None, gas(16));
+        trace.end_host_call(Some(0), None, gas(17));
+
+        assert_eq!(
+            trace.host_calls().collect::<Vec<_>>(),
+            vec![
+                FunctionIndex::CallContractFuncIndex,
+                FunctionIndex::ReadFuncIndex
+            ]
+        );
+        assert_eq!(
+            trace.entries()[0],
+            TraceEntry::HostCall {
+                function: FunctionIndex::CallContractFuncIndex,
+                args: vec![TraceArg::Name("counter".to_string())],
+                return_code: Some(0),
+                error: None,
+                gas_before: gas(10),
+                gas_after: gas(17),
+            }
+        );
+        assert_eq!(
+            trace.entries()[2],
+            TraceEntry::HostCall {
+                function: FunctionIndex::ReadFuncIndex,
+                args: vec![TraceArg::Key(value_key)],
+                return_code: Some(0),
+                error: None,
+                gas_before: gas(13),
+                gas_after: gas(15),
+            }
+        );
+    }
+
+    #[test]
+    fn should_dump_trace_as_json() {
+        let mut trace = ExecutionTrace::new();
+        trace.enter(Phase::Payment, Key::Hash([0; 32]), "call", gas(0));
+        trace.begin_host_call(FunctionIndex::WriteFuncIndex, gas(1));
+        trace.record_arg(TraceArg::CLValue(CLValue::from_t(7u8).unwrap()));
+        trace.end_host_call(None, Some("Out of gas error".to_string()), gas(2));
+        trace.exit(
+            Key::Hash([0; 32]),
+            "call",
+            Some("Out of gas error".to_string()),
+            gas(2),
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&trace.to_json(false)).expect("should parse trace json");
+        let entries = json["entries"].as_array().expect("should have entries");
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0]["type"], "enter");
+        assert_eq!(entries[0]["phase"], "Payment");
+        assert_eq!(entries[1]["function"], "WriteFuncIndex");
+        assert_eq!(entries[1]["args"][0]["cl_value"]["bytes"], "07");
+        assert_eq!(entries[1]["gas_after"], "2");
+        assert_eq!(entries[2]["error"], "Out of gas error");
+    }
+}
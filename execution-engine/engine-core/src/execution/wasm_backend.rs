@@ -0,0 +1,147 @@
+//! Abstraction over the engine which executes Wasm.
+//!
+//! Host functions are dispatched through [`wasmi::Externals`] and errors are reported as
+//! [`wasmi::Error`] whichever backend runs the code, so that the runtime and the executor handle
+//! both identically.
+
+use std::{fmt, str::FromStr};
+
+use wasmi::{Externals, MemoryRef, ModuleInstance, ModuleRef, RuntimeArgs, RuntimeValue, Trap};
+
+/// The backend used to execute Wasm.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WasmBackend {
+    /// The `wasmi` interpreter.
+    Interpreted,
+    /// The `wasmtime` JIT compiler.
+    #[cfg(feature = "wasmtime-backend")]
+    Compiled,
+}
+
+impl Default for WasmBackend {
+    fn default() -> Self {
+        WasmBackend::Interpreted
+    }
+}
+
+impl fmt::Display for WasmBackend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WasmBackend::Interpreted => write!(f, "wasmi"),
+            #[cfg(feature = "wasmtime-backend")]
+            WasmBackend::Compiled => write!(f, "wasmtime"),
+        }
+    }
+}
+
+impl FromStr for WasmBackend {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "wasmi" => Ok(WasmBackend::Interpreted),
+            #[cfg(feature = "wasmtime-backend")]
+            "wasmtime" => Ok(WasmBackend::Compiled),
+            #[cfg(not(feature = "wasmtime-backend"))]
+            "wasmtime" => {
+                Err("the wasmtime backend requires the 'wasmtime-backend' feature".into())
+            }
+            _ => Err(format!("unknown Wasm backend: {}", input)),
+        }
+    }
+}
+
+/// An instantiated Wasm module.
+pub trait WasmInstance {
+    /// Invokes the exported function `name` without arguments, dispatching its host function calls
+    /// to `externals`.
+    fn invoke_export(
+        &self,
+        name: &str,
+        externals: &mut dyn Externals,
+    ) -> Result<Option<RuntimeValue>, wasmi::Error>;
+}
+
+impl WasmInstance for ModuleRef {
+    fn invoke_export(
+        &self,
+        name: &str,
+        externals: &mut dyn Externals,
+    ) -> Result<Option<RuntimeValue>, wasmi::Error> {
+        ModuleInstance::invoke_export(self, name, &[], &mut DynExternals(externals))
+    }
+}
+
+/// Adapts a trait object to `wasmi`'s APIs, which take `Externals` by sized type parameter.
+pub(crate) struct DynExternals<'a>(pub(crate) &'a mut dyn Externals);
+
+impl Externals for DynExternals<'_> {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        self.0.invoke_index(index, args)
+    }
+}
+
+/// The linear memory of an instantiated Wasm module.
+#[derive(Clone, Debug)]
+pub enum InstanceMemory {
+    Interpreted(MemoryRef),
+    #[cfg(feature = "wasmtime-backend")]
+    Compiled(wasmtime::Memory),
+}
+
+impl InstanceMemory {
+    /// Returns a copy of `size` bytes of memory starting at `offset`.
+    pub fn get(&self, offset: u32, size: usize) -> Result<Vec<u8>, wasmi::Error> {
+        match self {
+            InstanceMemory::Interpreted(memory) => memory.get(offset, size),
+            #[cfg(feature = "wasmtime-backend")]
+            InstanceMemory::Compiled(memory) => {
+                // Safe as the host only accesses memory while no Wasm code of this instance runs.
+                let data = unsafe { memory.data_unchecked() };
+                let region = checked_region(offset, size, data.len())?;
+                Ok(data[region].to_vec())
+            }
+        }
+    }
+
+    /// Copies `value` into memory starting at `offset`.
+    pub fn set(&self, offset: u32, value: &[u8]) -> Result<(), wasmi::Error> {
+        match self {
+            InstanceMemory::Interpreted(memory) => memory.set(offset, value),
+            #[cfg(feature = "wasmtime-backend")]
+            InstanceMemory::Compiled(memory) => {
+                // Safe as the host only accesses memory while no Wasm code of this instance runs.
+                let data = unsafe { memory.data_unchecked_mut() };
+                let region = checked_region(offset, value.len(), data.len())?;
+                data[region].copy_from_slice(value);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Returns the range of `size` bytes starting at `offset`, or the same error as `wasmi` if it
+/// doesn't fit in a memory of `memory_size` bytes.
+#[cfg(feature = "wasmtime-backend")]
+fn checked_region(
+    offset: u32,
+    size: usize,
+    memory_size: usize,
+) -> Result<std::ops::Range<usize>, wasmi::Error> {
+    let start = offset as usize;
+    match start.checked_add(size) {
+        Some(end) if end <= memory_size => Ok(start..end),
+        Some(end) => Err(wasmi::Error::Memory(format!(
+            "trying to access region [{}..{}] in memory [0..{}]",
+            start, end, memory_size
+        ))),
+        None => Err(wasmi::Error::Memory(format!(
+            "trying to access memory block of size {} from offset {}",
+            size, offset
+        ))),
+    }
+}
@@ -0,0 +1,314 @@
+//! Executes Wasm using the `wasmtime` JIT compiler.
+//!
+//! Imported host functions are resolved by the same resolver as for `wasmi`, and are invoked
+//! through it, so the injected `gas` import and every other host function behave exactly as they
+//! do when interpreted.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use parity_wasm::elements::Module;
+use wasmi::{
+    Externals, FuncInstance, ModuleImportResolver, RuntimeValue, Signature, Trap, TrapKind,
+    ValueType,
+};
+use wasmtime::{
+    Extern, ExternType, Func, FuncType, Instance, Limits, Memory, MemoryType, Store, Val, ValType,
+};
+
+use types::ProtocolVersion;
+
+use super::{
+    wasm_backend::{DynExternals, InstanceMemory, WasmInstance},
+    Error,
+};
+use crate::resolvers::{
+    create_module_resolver, error::ResolverError, memory_resolver::MemoryResolver,
+};
+
+const HOST_MODULE_NAME: &str = "env";
+const MEMORY_NAME: &str = "memory";
+
+/// State shared between a [`WasmtimeInstance`] and the host functions it imports.
+#[derive(Default)]
+struct HostState {
+    /// The externals handling host function calls, only set while an export is being invoked.
+    externals: Cell<Option<*mut (dyn Externals + 'static)>>,
+    /// The trap raised by the most recent failing host function call.
+    trap: RefCell<Option<Trap>>,
+}
+
+/// A module instantiated by `wasmtime`.
+pub struct WasmtimeInstance {
+    instance: Instance,
+    host_state: Rc<HostState>,
+}
+
+impl WasmInstance for WasmtimeInstance {
+    fn invoke_export(
+        &self,
+        name: &str,
+        externals: &mut dyn Externals,
+    ) -> Result<Option<RuntimeValue>, wasmi::Error> {
+        let func = self
+            .instance
+            .get_export(name)
+            .and_then(Extern::into_func)
+            .ok_or_else(|| {
+                wasmi::Error::Function(format!("Module doesn't have export {}", name))
+            })?;
+
+        // The pointer is only dereferenced by host functions called from within `func.call` below,
+        // while `externals` is still mutably borrowed by this function.  Nested invocations, e.g.
+        // from calling a stored contract, use their own instance and `HostState`.
+        let externals: *mut (dyn Externals + '_) = externals;
+        let externals: *mut (dyn Externals + 'static) = unsafe { std::mem::transmute(externals) };
+        let previous_externals = self.host_state.externals.replace(Some(externals));
+        let result = func.call(&[]);
+        self.host_state.externals.set(previous_externals);
+
+        match result {
+            Ok(values) => values.first().map(runtime_value_from_val).transpose(),
+            Err(wasmtime_trap) => {
+                let trap = match self.host_state.trap.borrow_mut().take() {
+                    Some(trap) => trap,
+                    None => Trap::new(trap_kind_from_message(wasmtime_trap.message())),
+                };
+                Err(wasmi::Error::Trap(trap))
+            }
+        }
+    }
+}
+
+/// Instantiates `parity_module` with `wasmtime`, returning the instance and its imported memory.
+pub fn instance_and_memory(
+    parity_module: Module,
+    protocol_version: ProtocolVersion,
+    max_memory: u32,
+    profiling: bool,
+) -> Result<(WasmtimeInstance, InstanceMemory), Error> {
+    if parity_module.start_section().is_some() {
+        return Err(Error::UnsupportedWasmStart);
+    }
+    let module_bytes = parity_wasm::serialize(parity_module)?;
+
+    let store = Store::default();
+    let module = wasmtime::Module::new(&store, &module_bytes)
+        .map_err(|error| Error::Interpreter(error.to_string()))?;
+    let resolver = create_module_resolver(protocol_version, max_memory, profiling)?;
+    let host_state = Rc::new(HostState::default());
+
+    let mut memory = None;
+    let mut imports = Vec::new();
+    for import in module.imports() {
+        if import.module() != HOST_MODULE_NAME {
+            return Err(instantiation_error(format!(
+                "Module {} not found",
+                import.module()
+            )));
+        }
+        match import.ty() {
+            ExternType::Func(func_type) => {
+                let func = host_func(
+                    &store,
+                    &resolver,
+                    import.name(),
+                    func_type.clone(),
+                    Rc::clone(&host_state),
+                )?;
+                imports.push(Extern::from(func));
+            }
+            ExternType::Memory(memory_type) if import.name() == MEMORY_NAME => {
+                let limits = memory_type.limits();
+                resolver.check_memory_limits(limits.min(), limits.max())?;
+                let imported_memory = Memory::new(
+                    &store,
+                    MemoryType::new(Limits::new(limits.min(), limits.max())),
+                );
+                memory = Some(imported_memory.clone());
+                imports.push(Extern::from(imported_memory));
+            }
+            ExternType::Memory(_) => {
+                return Err(instantiation_error(
+                    "Memory imported under unknown name".to_string(),
+                ))
+            }
+            other => {
+                return Err(instantiation_error(format!(
+                    "Unsupported import {}: {:?}",
+                    import.name(),
+                    other
+                )))
+            }
+        }
+    }
+
+    let instance =
+        Instance::new(&module, &imports).map_err(|error| Error::Interpreter(error.to_string()))?;
+    let memory = memory.ok_or(ResolverError::NoImportedMemory)?;
+
+    Ok((
+        WasmtimeInstance {
+            instance,
+            host_state,
+        },
+        InstanceMemory::Compiled(memory),
+    ))
+}
+
+/// Creates the host function imported as `name`, which forwards its calls to the host function
+/// resolved for `wasmi`.
+fn host_func<T: ModuleImportResolver>(
+    store: &Store,
+    resolver: &T,
+    name: &str,
+    func_type: FuncType,
+    host_state: Rc<HostState>,
+) -> Result<Func, Error> {
+    let signature = signature_from_func_type(&func_type)?;
+    let func_ref = resolver.resolve_func(name, &signature)?;
+    if func_ref.signature() != &signature {
+        return Err(instantiation_error(format!(
+            "Export {} has different signature {:?}",
+            name,
+            func_ref.signature()
+        )));
+    }
+
+    let func = Func::new(store, func_type, move |_caller, params, results| {
+        let externals = match host_state.externals.get() {
+            Some(externals) => externals,
+            None => {
+                return Err(wasmtime::Trap::new(
+                    "host function called outside invocation",
+                ))
+            }
+        };
+        let args = params
+            .iter()
+            .map(runtime_value_from_val)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|error| wasmtime::Trap::new(error.to_string()))?;
+
+        // See `WasmtimeInstance::invoke_export` for why this is safe.
+        let externals = unsafe { &mut *externals };
+        match FuncInstance::invoke(&func_ref, &args, &mut DynExternals(externals)) {
+            Ok(Some(value)) => {
+                results[0] = val_from_runtime_value(value);
+                Ok(())
+            }
+            Ok(None) => Ok(()),
+            Err(trap) => {
+                let message = trap.to_string();
+                *host_state.trap.borrow_mut() = Some(trap);
+                Err(wasmtime::Trap::new(message))
+            }
+        }
+    });
+    Ok(func)
+}
+
+fn instantiation_error(message: String) -> Error {
+    Error::Interpreter(wasmi::Error::Instantiation(message).into())
+}
+
+fn value_type_from_val_type(val_type: &ValType) -> Result<ValueType, Error> {
+    match val_type {
+        ValType::I32 => Ok(ValueType::I32),
+        ValType::I64 => Ok(ValueType::I64),
+        ValType::F32 => Ok(ValueType::F32),
+        ValType::F64 => Ok(ValueType::F64),
+        other => Err(instantiation_error(format!(
+            "Unsupported value type {:?}",
+            other
+        ))),
+    }
+}
+
+fn signature_from_func_type(func_type: &FuncType) -> Result<Signature, Error> {
+    let params = func_type
+        .params()
+        .iter()
+        .map(value_type_from_val_type)
+        .collect::<Result<Vec<_>, _>>()?;
+    let return_type = match func_type.results() {
+        [] => None,
+        [result] => Some(value_type_from_val_type(result)?),
+        _ => {
+            return Err(instantiation_error(
+                "Multiple return values are not supported".to_string(),
+            ))
+        }
+    };
+    Ok(Signature::new(params, return_type))
+}
+
+fn runtime_value_from_val(val: &Val) -> Result<RuntimeValue, wasmi::Error> {
+    match val {
+        Val::I32(value) => Ok(RuntimeValue::I32(*value)),
+        Val::I64(value) => Ok(RuntimeValue::I64(*value)),
+        Val::F32(bits) => Ok(RuntimeValue::F32(f32::from_bits(*bits).into())),
+        Val::F64(bits) => Ok(RuntimeValue::F64(f64::from_bits(*bits).into())),
+        other => Err(wasmi::Error::Value(format!(
+            "Unsupported value {:?}",
+            other
+        ))),
+    }
+}
+
+fn val_from_runtime_value(value: RuntimeValue) -> Val {
+    match value {
+        RuntimeValue::I32(value) => Val::I32(value),
+        RuntimeValue::I64(value) => Val::I64(value),
+        RuntimeValue::F32(value) => Val::F32(value.to_bits()),
+        RuntimeValue::F64(value) => Val::F64(value.to_bits()),
+    }
+}
+
+/// Maps the message of a trap raised by compiled code to the equivalent `wasmi` trap.
+fn trap_kind_from_message(message: &str) -> TrapKind {
+    if message.contains("out of bounds memory access") {
+        TrapKind::MemoryAccessOutOfBounds
+    } else if message.contains("undefined element") || message.contains("uninitialized element") {
+        TrapKind::ElemUninitialized
+    } else if message.contains("out of bounds table access") {
+        TrapKind::TableAccessOutOfBounds
+    } else if message.contains("integer divide by zero") {
+        TrapKind::DivisionByZero
+    } else if message.contains("integer overflow")
+        || message.contains("invalid conversion to integer")
+    {
+        TrapKind::InvalidConversionToInt
+    } else if message.contains("call stack exhausted") {
+        TrapKind::StackOverflow
+    } else if message.contains("indirect call type mismatch") {
+        TrapKind::UnexpectedSignature
+    } else {
+        TrapKind::Unreachable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasmi::TrapKind;
+
+    use super::trap_kind_from_message;
+
+    #[test]
+    fn should_map_trap_messages() {
+        assert!(matches!(
+            trap_kind_from_message("wasm trap: out of bounds memory access"),
+            TrapKind::MemoryAccessOutOfBounds
+        ));
+        assert!(matches!(
+            trap_kind_from_message("wasm trap: integer divide by zero"),
+            TrapKind::DivisionByZero
+        ));
+        assert!(matches!(
+            trap_kind_from_message("wasm trap: unreachable"),
+            TrapKind::Unreachable
+        ));
+    }
+}
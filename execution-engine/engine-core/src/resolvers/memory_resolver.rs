@@ -1,4 +1,4 @@
-use wasmi::MemoryRef;
+use wasmi::{Error as InterpreterError, MemoryRef};
 
 use super::error::ResolverError;
 
@@ -8,4 +8,11 @@ use super::error::ResolverError;
 /// contract didn't request a memory this method should return an Error.
 pub trait MemoryResolver {
     fn memory_ref(&self) -> Result<MemoryRef, ResolverError>;
+
+    /// Returns an error if the imported memory may not have the given limits, in 64 KiB pages.
+    fn check_memory_limits(
+        &self,
+        initial: u32,
+        maximum: Option<u32>,
+    ) -> Result<(), InterpreterError>;
 }
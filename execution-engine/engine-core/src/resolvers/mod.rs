@@ -5,6 +5,7 @@ mod v1_resolver;
 
 use wasmi::ModuleImportResolver;
 
+use engine_wasm_prep::{wasm_costs::WasmCosts, HostFunctions, Preprocessor};
 use types::ProtocolVersion;
 
 use self::error::ResolverError;
@@ -13,22 +14,73 @@ use crate::resolvers::memory_resolver::MemoryResolver;
 /// Creates a module resolver for given protocol version.
 ///
 /// * `protocol_version` Version of the protocol. Can't be lower than 1.
+/// * `max_memory` Maximum number of memory pages a module can have.
+/// * `profiling` Whether to resolve the host functions imported by modules instrumented for gas
+///   profiling.
 pub fn create_module_resolver(
     protocol_version: ProtocolVersion,
-) -> Result<impl ModuleImportResolver + MemoryResolver, ResolverError> {
+    max_memory: u32,
+    profiling: bool,
+) -> Result<impl ModuleImportResolver + MemoryResolver + HostFunctions, ResolverError> {
     // TODO: revisit how protocol_version check here is meant to combine with upgrade
     if protocol_version >= ProtocolVersion::V1_0_0 {
-        return Ok(v1_resolver::RuntimeModuleImportResolver::default());
+        return Ok(v1_resolver::RuntimeModuleImportResolver::new(
+            max_memory, profiling,
+        ));
     }
     Err(ResolverError::UnknownProtocolVersion(protocol_version))
 }
 
+/// Creates a preprocessor which checks the functions imported by modules against the host
+/// functions of given protocol version.
+///
+/// If the protocol version is unknown, imported functions are left to be checked by the module
+/// resolver, which rejects the protocol version.
+pub fn create_preprocessor(
+    wasm_costs: WasmCosts,
+    protocol_version: ProtocolVersion,
+) -> Preprocessor {
+    let preprocessor = Preprocessor::new(wasm_costs);
+    match create_module_resolver(protocol_version, wasm_costs.max_memory, false) {
+        Ok(resolver) => preprocessor.with_host_functions(resolver),
+        Err(_) => preprocessor,
+    }
+}
+
 #[test]
 fn resolve_invalid_module() {
-    assert!(create_module_resolver(ProtocolVersion::default()).is_err());
+    assert!(create_module_resolver(
+        ProtocolVersion::default(),
+        engine_wasm_prep::MEM_PAGES,
+        false
+    )
+    .is_err());
 }
 
 #[test]
 fn protocol_version_1_always_resolves() {
-    assert!(create_module_resolver(ProtocolVersion::V1_0_0).is_ok());
+    assert!(
+        create_module_resolver(ProtocolVersion::V1_0_0, engine_wasm_prep::MEM_PAGES, false).is_ok()
+    );
+}
+
+#[test]
+fn host_functions_have_resolver_signatures() {
+    use parity_wasm::elements::{FunctionType, ValueType};
+
+    let resolver =
+        create_module_resolver(ProtocolVersion::V1_0_0, engine_wasm_prep::MEM_PAGES, false)
+            .unwrap();
+    assert_eq!(
+        resolver.function_type("write"),
+        Some(FunctionType::new(vec![ValueType::I32; 4], None))
+    );
+    assert_eq!(
+        resolver.function_type("has_key"),
+        Some(FunctionType::new(
+            vec![ValueType::I32; 2],
+            Some(ValueType::I32)
+        ))
+    );
+    assert!(resolver.function_type("profile_enter").is_none());
 }
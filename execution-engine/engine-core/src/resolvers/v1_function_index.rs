@@ -49,6 +49,18 @@ pub enum FunctionIndex {
     RemoveContractUserGroupIndex,
     ExtendContractUserGroupURefsIndex,
     RemoveContractUserGroupURefsIndex,
+    SetSpendingLimitIndex,
+    SetRecoveryConfigIndex,
+    CancelRecoveryIndex,
+    ProposeRecoveryIndex,
+    FinalizeRecoveryIndex,
+    SetContractAllowListIndex,
+    CreateContractAccountIndex,
+    NewLockedContract,
+    AddContractVersionWithMigration,
+    SetContractMetadata,
+    ProfileEnterIndex,
+    ProfileExitIndex,
 }
 
 impl Into<usize> for FunctionIndex {
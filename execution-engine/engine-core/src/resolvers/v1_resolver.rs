@@ -5,6 +5,12 @@ use wasmi::{
     MemoryInstance, MemoryRef, ModuleImportResolver, Signature, ValueType,
 };
 
+use engine_wasm_prep::{
+    profiling::{PROFILE_ENTER_FUNC, PROFILE_EXIT_FUNC},
+    HostFunctions, MEM_PAGES,
+};
+use parity_wasm::elements::{self, FunctionType};
+
 use super::{
     error::ResolverError, memory_resolver::MemoryResolver, v1_function_index::FunctionIndex,
 };
@@ -12,13 +18,25 @@ use super::{
 pub struct RuntimeModuleImportResolver {
     memory: RefCell<Option<MemoryRef>>,
     max_memory: u32,
+    profiling: bool,
+}
+
+impl RuntimeModuleImportResolver {
+    pub fn new(max_memory: u32, profiling: bool) -> Self {
+        RuntimeModuleImportResolver {
+            max_memory,
+            profiling,
+            ..Default::default()
+        }
+    }
 }
 
 impl Default for RuntimeModuleImportResolver {
     fn default() -> Self {
         RuntimeModuleImportResolver {
             memory: RefCell::new(None),
-            max_memory: 64,
+            max_memory: MEM_PAGES,
+            profiling: false,
         }
     }
 }
@@ -31,6 +49,21 @@ impl MemoryResolver for RuntimeModuleImportResolver {
             .map(Clone::clone)
             .ok_or(ResolverError::NoImportedMemory)
     }
+
+    fn check_memory_limits(
+        &self,
+        initial: u32,
+        maximum: Option<u32>,
+    ) -> Result<(), InterpreterError> {
+        let effective_max = maximum.unwrap_or(self.max_memory + 1);
+        if initial > self.max_memory || effective_max > self.max_memory {
+            Err(InterpreterError::Instantiation(
+                "Module requested too much memory".to_owned(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl ModuleImportResolver for RuntimeModuleImportResolver {
@@ -204,6 +237,54 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
                 FunctionIndex::RemoveContractUserGroupURefsIndex.into(),
             ),
+            "set_spending_limit" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::SetSpendingLimitIndex.into(),
+            ),
+            "set_recovery_config" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::SetRecoveryConfigIndex.into(),
+            ),
+            "cancel_recovery" => FuncInstance::alloc_host(
+                Signature::new(&[][..], Some(ValueType::I32)),
+                FunctionIndex::CancelRecoveryIndex.into(),
+            ),
+            "propose_recovery" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::ProposeRecoveryIndex.into(),
+            ),
+            "finalize_recovery" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::FinalizeRecoveryIndex.into(),
+            ),
+            "set_contract_allow_list" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::SetContractAllowListIndex.into(),
+            ),
+            "create_contract_account" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
+                FunctionIndex::CreateContractAccountIndex.into(),
+            ),
+            "new_locked_contract" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 7][..], Some(ValueType::I32)),
+                FunctionIndex::NewLockedContract.into(),
+            ),
+            "add_contract_version_with_migration" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 12][..], Some(ValueType::I32)),
+                FunctionIndex::AddContractVersionWithMigration.into(),
+            ),
+            "set_contract_metadata" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
+                FunctionIndex::SetContractMetadata.into(),
+            ),
+            PROFILE_ENTER_FUNC if self.profiling => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], None),
+                FunctionIndex::ProfileEnterIndex.into(),
+            ),
+            PROFILE_EXIT_FUNC if self.profiling => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], None),
+                FunctionIndex::ProfileExitIndex.into(),
+            ),
             #[cfg(feature = "test-support")]
             "print" => FuncInstance::alloc_host(
                 Signature::new(&[ValueType::I32; 2][..], None),
@@ -225,20 +306,14 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
         descriptor: &MemoryDescriptor,
     ) -> Result<MemoryRef, InterpreterError> {
         if field_name == "memory" {
-            let effective_max = descriptor.maximum().unwrap_or(self.max_memory + 1);
-            if descriptor.initial() > self.max_memory || effective_max > self.max_memory {
-                Err(InterpreterError::Instantiation(
-                    "Module requested too much memory".to_owned(),
-                ))
-            } else {
-                // Note: each "page" is 64 KiB
-                let mem = MemoryInstance::alloc(
-                    Pages(descriptor.initial() as usize),
-                    descriptor.maximum().map(|x| Pages(x as usize)),
-                )?;
-                *self.memory.borrow_mut() = Some(mem.clone());
-                Ok(mem)
-            }
+            self.check_memory_limits(descriptor.initial(), descriptor.maximum())?;
+            // Note: each "page" is 64 KiB
+            let mem = MemoryInstance::alloc(
+                Pages(descriptor.initial() as usize),
+                descriptor.maximum().map(|x| Pages(x as usize)),
+            )?;
+            *self.memory.borrow_mut() = Some(mem.clone());
+            Ok(mem)
         } else {
             Err(InterpreterError::Instantiation(
                 "Memory imported under unknown name".to_owned(),
@@ -246,3 +321,26 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
         }
     }
 }
+
+impl HostFunctions for RuntimeModuleImportResolver {
+    fn function_type(&self, name: &str) -> Option<FunctionType> {
+        // The signature passed in is ignored, the host function is resolved with its own.
+        let func_ref = self
+            .resolve_func(name, &Signature::new(&[][..], None))
+            .ok()?;
+        let signature = func_ref.signature();
+        Some(FunctionType::new(
+            signature.params().iter().copied().map(value_type).collect(),
+            signature.return_type().map(value_type),
+        ))
+    }
+}
+
+fn value_type(value_type: ValueType) -> elements::ValueType {
+    match value_type {
+        ValueType::I32 => elements::ValueType::I32,
+        ValueType::I64 => elements::ValueType::I64,
+        ValueType::F32 => elements::ValueType::F32,
+        ValueType::F64 => elements::ValueType::F64,
+    }
+}
@@ -1,12 +1,12 @@
 use std::{collections::BTreeSet, convert::TryFrom};
 
-use wasmi::{Externals, RuntimeArgs, RuntimeValue, Trap};
+use wasmi::{Externals, RuntimeArgs, RuntimeValue, Trap, TrapKind};
 
 use types::{
     account::AccountHash,
     api_error,
     bytesrepr::{self, ToBytes},
-    contracts::{EntryPoints, NamedKeys},
+    contracts::{ContractMetadata, EntryPoints, NamedKeys},
     ContractHash, ContractPackageHash, ContractVersion, Group, Key, TransferredTo, URef, U512,
 };
 
@@ -27,6 +27,47 @@ where
         args: RuntimeArgs,
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
+        let is_profiling_call = matches!(
+            func,
+            FunctionIndex::ProfileEnterIndex | FunctionIndex::ProfileExitIndex
+        );
+        if self.trace.is_none() || is_profiling_call {
+            return self.invoke_function(func, args);
+        }
+
+        let gas_before = self.context.gas_counter();
+        self.trace(|trace| trace.begin_host_call(func, gas_before));
+        let result = self.invoke_function(func, args);
+        let (return_code, error) = match &result {
+            Ok(Some(RuntimeValue::I32(return_code))) => (Some(*return_code), None),
+            Ok(_) => (None, None),
+            Err(trap) => {
+                let error = match trap.kind() {
+                    TrapKind::Host(host_error) => match host_error.downcast_ref::<Error>() {
+                        Some(error) => error.to_string(),
+                        None => trap.to_string(),
+                    },
+                    _ => trap.to_string(),
+                };
+                (None, Some(error))
+            }
+        };
+        let gas_after = self.context.gas_counter();
+        self.trace(|trace| trace.end_host_call(return_code, error, gas_after));
+        result
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    fn invoke_function(
+        &mut self,
+        func: FunctionIndex,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
         let mut scoped_instrumenter = ScopedInstrumenter::new(func);
         match func {
             FunctionIndex::ReadFuncIndex => {
@@ -185,6 +226,20 @@ where
                 Ok(None)
             }
 
+            FunctionIndex::ProfileEnterIndex => {
+                // args(0) = index of the function entered
+                let function_index: u32 = Args::parse(args)?;
+                self.profile_enter(function_index);
+                Ok(None)
+            }
+
+            FunctionIndex::ProfileExitIndex => {
+                // args(0) = index of the function returning
+                let function_index: u32 = Args::parse(args)?;
+                self.profile_exit(function_index);
+                Ok(None)
+            }
+
             FunctionIndex::IsValidURefFnIndex => {
                 // args(0) = pointer to value to validate
                 // args(1) = size of value
@@ -247,6 +302,87 @@ where
                 Ok(Some(RuntimeValue::I32(value)))
             }
 
+            FunctionIndex::SetSpendingLimitIndex => {
+                // args(0) = pointer to the serialized optional spending limit
+                // args(1) = size of the serialized optional spending limit
+                let (spending_limit_ptr, spending_limit_size): (u32, u32) = Args::parse(args)?;
+                let value = self.set_spending_limit(spending_limit_ptr, spending_limit_size)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::SetRecoveryConfigIndex => {
+                // args(0) = pointer to the serialized optional recovery config
+                // args(1) = size of the serialized optional recovery config
+                let (config_ptr, config_size): (u32, u32) = Args::parse(args)?;
+                let value = self.set_recovery_config(config_ptr, config_size)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::CancelRecoveryIndex => {
+                let value = self.cancel_recovery()?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::ProposeRecoveryIndex => {
+                // args(0) = pointer to the serialized account hash of the recovered account
+                // args(1) = size of the serialized account hash
+                // args(2) = pointer to the serialized map of new associated keys
+                // args(3) = size of the serialized map of new associated keys
+                let (account_hash_ptr, account_hash_size, new_keys_ptr, new_keys_size): (
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                ) = Args::parse(args)?;
+                let value = self.propose_recovery(
+                    account_hash_ptr,
+                    account_hash_size,
+                    new_keys_ptr,
+                    new_keys_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::FinalizeRecoveryIndex => {
+                // args(0) = pointer to the serialized account hash of the recovered account
+                // args(1) = size of the serialized account hash
+                let (account_hash_ptr, account_hash_size): (u32, u32) = Args::parse(args)?;
+                let value = self.finalize_recovery(account_hash_ptr, account_hash_size)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::SetContractAllowListIndex => {
+                // args(0) = pointer to the serialized optional allow-list
+                // args(1) = size of the serialized optional allow-list
+                let (allow_list_ptr, allow_list_size): (u32, u32) = Args::parse(args)?;
+                let value = self.set_contract_allow_list(allow_list_ptr, allow_list_size)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::CreateContractAccountIndex => {
+                // args(0) = pointer to the serialized group of the calling contract's package
+                // args(1) = size of the serialized group
+                // args(2) = deployment threshold of the new account
+                // args(3) = key management threshold of the new account
+                // args(4) = pointer to array for the account hash of the new account
+                let (
+                    group_ptr,
+                    group_size,
+                    deployment_threshold,
+                    key_management_threshold,
+                    dest_ptr,
+                ): (u32, u32, u8, u8, u32) = Args::parse(args)?;
+                scoped_instrumenter.add_property("group_size", group_size.to_string());
+                let group: Group = self.t_from_mem(group_ptr, group_size)?;
+                let ret = self.create_contract_account(
+                    group,
+                    deployment_threshold,
+                    key_management_threshold,
+                    dest_ptr,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::CreatePurseIndex => {
                 // args(0) = pointer to array for return value
                 // args(1) = length of array for return value
@@ -457,6 +593,59 @@ where
                     contract_package_hash,
                     entry_points,
                     named_keys,
+                    None,
+                    output_ptr,
+                    output_size as usize,
+                    bytes_written_ptr,
+                    version_ptr,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::AddContractVersionWithMigration => {
+                // args(0) = pointer to package key in wasm memory
+                // args(1) = size of package key in wasm memory
+                // args(2) = pointer to the version assigned to the new contract
+                // args(3) = pointer to entrypoints in wasm memory
+                // args(4) = size of entrypoints in wasm memory
+                // args(5) = pointer to named keys in wasm memory
+                // args(6) = size of named keys in wasm memory
+                // args(7) = pointer to migration entry point name in wasm memory
+                // args(8) = size of migration entry point name in wasm memory
+                // args(9) = pointer to output buffer for serialized key
+                // args(10) = size of output buffer
+                // args(11) = pointer to bytes written
+                let (
+                    contract_package_hash_ptr,
+                    contract_package_hash_size,
+                    version_ptr,
+                    entry_points_ptr,
+                    entry_points_size,
+                    named_keys_ptr,
+                    named_keys_size,
+                    migration_ptr,
+                    migration_size,
+                    output_ptr,
+                    output_size,
+                    bytes_written_ptr,
+                ): (u32, u32, u32, u32, u32, u32, u32, u32, u32, u32, u32, u32) =
+                    Args::parse(args)?;
+
+                scoped_instrumenter
+                    .add_property("entry_points_size", entry_points_size.to_string());
+                scoped_instrumenter.add_property("named_keys_size", named_keys_size.to_string());
+
+                let contract_package_hash: ContractPackageHash =
+                    self.t_from_mem(contract_package_hash_ptr, contract_package_hash_size)?;
+                let entry_points: EntryPoints =
+                    self.t_from_mem(entry_points_ptr, entry_points_size)?;
+                let named_keys: NamedKeys = self.t_from_mem(named_keys_ptr, named_keys_size)?;
+                let migration: String = self.t_from_mem(migration_ptr, migration_size)?;
+                let ret = self.add_contract_version(
+                    contract_package_hash,
+                    entry_points,
+                    named_keys,
+                    Some(migration),
                     output_ptr,
                     output_size as usize,
                     bytes_written_ptr,
@@ -465,6 +654,69 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
+            FunctionIndex::SetContractMetadata => {
+                // args(0) = pointer to package hash in wasm memory
+                // args(1) = size of package hash in wasm memory
+                // args(2) = pointer to the serialized optional contract hash in wasm memory
+                // args(3) = size of the serialized optional contract hash in wasm memory
+                // args(4) = pointer to the serialized optional metadata in wasm memory
+                // args(5) = size of the serialized optional metadata in wasm memory
+                let (
+                    contract_package_hash_ptr,
+                    contract_package_hash_size,
+                    contract_hash_ptr,
+                    contract_hash_size,
+                    metadata_ptr,
+                    metadata_size,
+                ): (u32, u32, u32, u32, u32, u32) = Args::parse(args)?;
+                scoped_instrumenter.add_property("metadata_size", metadata_size.to_string());
+
+                let contract_package_hash: ContractPackageHash =
+                    self.t_from_mem(contract_package_hash_ptr, contract_package_hash_size)?;
+                let contract_hash: Option<ContractHash> =
+                    self.t_from_mem(contract_hash_ptr, contract_hash_size)?;
+                let metadata: Option<ContractMetadata> =
+                    self.t_from_mem(metadata_ptr, metadata_size)?;
+                let ret =
+                    self.set_contract_metadata(contract_package_hash, contract_hash, metadata)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::NewLockedContract => {
+                // args(0) = pointer to entrypoints in wasm memory
+                // args(1) = size of entrypoints in wasm memory
+                // args(2) = pointer to named keys in wasm memory
+                // args(3) = size of named keys in wasm memory
+                // args(4) = pointer to array for the hash of the new package
+                // args(5) = pointer to array for the hash of the new contract
+                // args(6) = pointer to the version assigned to the new contract
+                let (
+                    entry_points_ptr,
+                    entry_points_size,
+                    named_keys_ptr,
+                    named_keys_size,
+                    contract_package_hash_ptr,
+                    contract_hash_ptr,
+                    version_ptr,
+                ): (u32, u32, u32, u32, u32, u32, u32) = Args::parse(args)?;
+
+                scoped_instrumenter
+                    .add_property("entry_points_size", entry_points_size.to_string());
+                scoped_instrumenter.add_property("named_keys_size", named_keys_size.to_string());
+
+                let entry_points: EntryPoints =
+                    self.t_from_mem(entry_points_ptr, entry_points_size)?;
+                let named_keys: NamedKeys = self.t_from_mem(named_keys_ptr, named_keys_size)?;
+                let ret = self.new_locked_contract(
+                    entry_points,
+                    named_keys,
+                    contract_package_hash_ptr,
+                    contract_hash_ptr,
+                    version_ptr,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::DisableContractVersion => {
                 // args(0) = pointer to package hash in wasm memory
                 // args(1) = size of package hash in wasm memory
@@ -6,10 +6,12 @@ mod scoped_instrumenter;
 mod standard_payment_internal;
 
 use std::{
+    cell::RefCell,
     cmp,
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::TryFrom,
     iter::IntoIterator,
+    rc::Rc,
 };
 
 use itertools::Itertools;
@@ -17,15 +19,21 @@ use parity_wasm::elements::Module;
 use wasmi::{ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef, Trap, TrapKind};
 
 use ::mint::Mint;
-use engine_shared::{account::Account, gas::Gas, stored_value::StoredValue};
+use engine_shared::{
+    account::{Account, AccountOwner, ActionThresholds},
+    gas::Gas,
+    stored_value::StoredValue,
+};
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
+use engine_wasm_prep::profiling;
 use proof_of_stake::ProofOfStake;
 use standard_payment::StandardPayment;
 use types::{
-    account::{AccountHash, ActionType, Weight},
+    account::{AccountHash, ActionType, ContractAllowList, RecoveryConfig, Weight},
     bytesrepr::{self, FromBytes, ToBytes},
     contracts::{
-        self, Contract, ContractPackage, EntryPoint, EntryPointAccess, EntryPoints, Group,
+        self, Contract, ContractPackage, ContractPackageStatus, EntryPoint, EntryPointAccess,
+        EntryPoints, Group,
     },
     runtime_args, system_contract_errors,
     system_contract_errors::mint,
@@ -35,22 +43,30 @@ use types::{
 };
 
 use crate::{
-    engine_state::{system_contract_cache::SystemContractCache, EngineConfig},
-    execution::Error,
+    engine_state::{
+        module_cache::{ModuleCache, ModuleCacheKey},
+        EngineConfig,
+    },
+    execution::{Error, ExecutionTrace, InstanceMemory, TraceArg, WasmBackend, WasmInstance},
     resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
     runtime_context::{self, RuntimeContext},
     Address,
 };
-use contracts::{ContractVersion, ContractVersions, DisabledVersions, Groups, NamedKeys};
+use contracts::{
+    ContractMetadata, ContractVersion, ContractVersions, DisabledVersions, Groups, NamedKeys,
+};
 use scoped_instrumenter::ScopedInstrumenter;
 
 pub struct Runtime<'a, R> {
-    system_contract_cache: SystemContractCache,
+    module_cache: ModuleCache,
     config: EngineConfig,
-    memory: MemoryRef,
+    memory: InstanceMemory,
     module: Module,
     host_buffer: Option<CLValue>,
     context: RuntimeContext<'a, R>,
+    trace: Option<Rc<RefCell<ExecutionTrace>>>,
+    /// The names of the functions of `module`, only set when profiling.
+    function_names: Option<BTreeMap<u32, String>>,
 }
 
 /// Rename function called `name` in the `module` to `call`.
@@ -69,12 +85,52 @@ pub fn rename_export_to_call(module: &mut Module, name: String) {
     main_export.push_str("call");
 }
 
+/// Instantiates `parity_module`, instrumented for gas profiling if `profiling` is set.
+///
+/// The module can't have more than `max_memory` pages of memory.
 pub fn instance_and_memory(
     parity_module: Module,
     protocol_version: ProtocolVersion,
+    max_memory: u32,
+    wasm_backend: WasmBackend,
+    profiling: bool,
+) -> Result<(Box<dyn WasmInstance>, InstanceMemory), Error> {
+    let parity_module = if profiling {
+        profiling::inject_profiling(parity_module)
+    } else {
+        parity_module
+    };
+    match wasm_backend {
+        WasmBackend::Interpreted => {
+            let (instance, memory) = interpreted_instance_and_memory(
+                parity_module,
+                protocol_version,
+                max_memory,
+                profiling,
+            )?;
+            Ok((Box::new(instance), InstanceMemory::Interpreted(memory)))
+        }
+        #[cfg(feature = "wasmtime-backend")]
+        WasmBackend::Compiled => {
+            let (instance, memory) = crate::execution::wasmtime_backend::instance_and_memory(
+                parity_module,
+                protocol_version,
+                max_memory,
+                profiling,
+            )?;
+            Ok((Box::new(instance), memory))
+        }
+    }
+}
+
+fn interpreted_instance_and_memory(
+    parity_module: Module,
+    protocol_version: ProtocolVersion,
+    max_memory: u32,
+    profiling: bool,
 ) -> Result<(ModuleRef, MemoryRef), Error> {
     let module = wasmi::Module::from_parity_wasm_module(parity_module)?;
-    let resolver = create_module_resolver(protocol_version)?;
+    let resolver = create_module_resolver(protocol_version, max_memory, profiling)?;
     let mut imports = ImportsBuilder::new();
     imports.push_resolver("env", &resolver);
     let not_started_module = ModuleInstance::new(&module, &imports)?;
@@ -1344,6 +1400,18 @@ fn extract_urefs(cl_value: &CLValue) -> Result<Vec<URef>, Error> {
     }
 }
 
+/// Converts the result of a recovery action into the value returned by its host function.
+///
+/// This relies on the fact that `RecoveryFailure` is represented as i32 and its first variant
+/// starts with `1`, so `0` can be used to indicate success.
+fn recovery_result(result: Result<(), Error>) -> Result<i32, Trap> {
+    match result {
+        Ok(_) => Ok(0),
+        Err(Error::RecoveryFailure(e)) => Ok(e as i32),
+        Err(e) => Err(e.into()),
+    }
+}
+
 impl<'a, R> Runtime<'a, R>
 where
     R: StateReader<Key, StoredValue>,
@@ -1351,22 +1419,38 @@ where
 {
     pub fn new(
         config: EngineConfig,
-        system_contract_cache: SystemContractCache,
-        memory: MemoryRef,
+        module_cache: ModuleCache,
+        memory: InstanceMemory,
         module: Module,
         context: RuntimeContext<'a, R>,
     ) -> Self {
         Runtime {
             config,
-            system_contract_cache,
+            module_cache,
             memory,
             module,
             host_buffer: None,
             context,
+            trace: None,
+            function_names: None,
         }
     }
 
-    pub fn memory(&self) -> &MemoryRef {
+    /// Records the host functions called by this runtime, and by the runtimes of any contracts it
+    /// calls, to `trace`.
+    pub fn set_trace(&mut self, trace: Rc<RefCell<ExecutionTrace>>) {
+        self.trace = Some(trace);
+    }
+
+    /// Records the calls to the functions of the module to the trace, as well as those of any
+    /// contracts called.
+    ///
+    /// The module has to be instantiated with profiling enabled, see [`instance_and_memory`].
+    pub fn enable_profiling(&mut self) {
+        self.function_names = Some(profiling::function_names(&self.module));
+    }
+
+    pub fn memory(&self) -> &InstanceMemory {
         &self.memory
     }
 
@@ -1400,6 +1484,29 @@ where
         }
     }
 
+    /// Applies `f` to the execution trace, if tracing is enabled.
+    fn trace<F: FnOnce(&mut ExecutionTrace)>(&self, f: F) {
+        if let Some(trace) = &self.trace {
+            f(&mut trace.borrow_mut())
+        }
+    }
+
+    fn profile_enter(&self, index: u32) {
+        if let Some(function_names) = &self.function_names {
+            let name = function_names
+                .get(&index)
+                .map(String::as_str)
+                .unwrap_or_default();
+            let gas = self.context.gas_counter();
+            self.trace(|trace| trace.enter_function(index, name, gas));
+        }
+    }
+
+    fn profile_exit(&self, index: u32) {
+        let gas = self.context.gas_counter();
+        self.trace(|trace| trace.exit_function(index, gas));
+    }
+
     fn gas(&mut self, amount: Gas) -> Result<(), Trap> {
         if self.charge_gas(amount) {
             Ok(())
@@ -1420,7 +1527,9 @@ where
     /// Reads key (defined as `key_ptr` and `key_size` tuple) from Wasm memory.
     fn key_from_mem(&mut self, key_ptr: u32, key_size: u32) -> Result<Key, Error> {
         let bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
-        bytesrepr::deserialize(bytes).map_err(Into::into)
+        let key: Key = bytesrepr::deserialize(bytes)?;
+        self.trace(|trace| trace.record_arg(TraceArg::Key(key)));
+        Ok(key)
     }
 
     /// Reads `CLValue` (defined as `cl_value_ptr` and `cl_value_size` tuple) from Wasm memory.
@@ -1430,12 +1539,16 @@ where
         cl_value_size: u32,
     ) -> Result<CLValue, Error> {
         let bytes = self.bytes_from_mem(cl_value_ptr, cl_value_size as usize)?;
-        bytesrepr::deserialize(bytes).map_err(Into::into)
+        let cl_value: CLValue = bytesrepr::deserialize(bytes)?;
+        self.trace(|trace| trace.record_arg(TraceArg::CLValue(cl_value.clone())));
+        Ok(cl_value)
     }
 
     fn string_from_mem(&self, ptr: u32, size: u32) -> Result<String, Trap> {
         let bytes = self.bytes_from_mem(ptr, size as usize)?;
-        bytesrepr::deserialize(bytes).map_err(|e| Error::BytesRepr(e).into())
+        let string: String = bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?;
+        self.trace(|trace| trace.record_arg(TraceArg::Name(string.clone())));
+        Ok(string)
     }
 
     fn get_module_from_entry_points(
@@ -1676,6 +1789,9 @@ where
         const METHOD_CREATE: &str = "create";
         const METHOD_BALANCE: &str = "balance";
         const METHOD_TRANSFER: &str = "transfer";
+        const METHOD_BURN: &str = "burn";
+        const METHOD_MINT_INTO: &str = "mint_into";
+        const METHOD_TOTAL_SUPPLY: &str = "total_supply";
 
         let state = self.context.state();
         let access_rights = {
@@ -1713,6 +1829,7 @@ where
             gas_counter,
             hash_address_generator,
             uref_address_generator,
+            self.context.transferred(),
             protocol_version,
             correlation_id,
             phase,
@@ -1746,6 +1863,25 @@ where
                 let result: Result<(), mint::Error> = mint_context.transfer(source, target, amount);
                 CLValue::from_t(result).map_err(Self::reverter)?
             }
+            // Type: `fn burn(purse: URef, amount: U512) -> Result<(), Error>`
+            METHOD_BURN => {
+                let purse: URef = Self::get_named_argument(&runtime_args, "purse")?;
+                let amount: U512 = Self::get_named_argument(&runtime_args, "amount")?;
+                let result: Result<(), mint::Error> = mint_context.burn(purse, amount);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn mint_into(purse: URef, amount: U512) -> Result<(), Error>`
+            METHOD_MINT_INTO => {
+                let purse: URef = Self::get_named_argument(&runtime_args, "purse")?;
+                let amount: U512 = Self::get_named_argument(&runtime_args, "amount")?;
+                let result: Result<(), mint::Error> = mint_context.mint_into(purse, amount);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn total_supply() -> U512`
+            METHOD_TOTAL_SUPPLY => {
+                let total_supply: U512 = mint_context.total_supply().map_err(Self::reverter)?;
+                CLValue::from_t(total_supply).map_err(Self::reverter)?
+            }
             _ => CLValue::from_t(()).map_err(Self::reverter)?,
         };
         let urefs = extract_urefs(&ret)?;
@@ -1768,6 +1904,8 @@ where
         const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
         const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
         const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
+        const METHOD_RUN_AUCTION: &str = "run_auction";
+        const METHOD_MINT_SEIGNIORAGE: &str = "mint_seigniorage";
         const ARG_AMOUNT: &str = "amount";
         const ARG_PURSE: &str = "purse";
 
@@ -1807,6 +1945,7 @@ where
             gas_counter,
             fn_store_id,
             address_generator,
+            self.context.transferred(),
             protocol_version,
             correlation_id,
             phase,
@@ -1815,7 +1954,7 @@ where
 
         let mut runtime = Runtime::new(
             self.config,
-            SystemContractCache::clone(&self.system_contract_cache),
+            ModuleCache::clone(&self.module_cache),
             self.memory.clone(),
             self.module.clone(),
             runtime_context,
@@ -1866,11 +2005,21 @@ where
             METHOD_FINALIZE_PAYMENT => {
                 let amount_spent: U512 = Self::get_named_argument(&runtime_args, "amount")?;
                 let account: AccountHash = Self::get_named_argument(&runtime_args, "account")?;
+                let proposer: Option<AccountHash> =
+                    Self::get_named_argument(&runtime_args, "proposer")?;
                 runtime
-                    .finalize_payment(amount_spent, account)
+                    .finalize_payment(amount_spent, account, proposer)
                     .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
+            METHOD_RUN_AUCTION => {
+                runtime.run_auction().map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_MINT_SEIGNIORAGE => {
+                runtime.mint_seigniorage().map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
             _ => CLValue::from_t(()).map_err(Self::reverter)?,
         };
         let urefs = extract_urefs(&ret)?;
@@ -2038,6 +2187,78 @@ where
         args: RuntimeArgs,
         entry_point: EntryPoint,
         protocol_version: ProtocolVersion,
+    ) -> Result<CLValue, Error> {
+        // Transfers out of the account's main purse are limited however the mint is reached,
+        // including by calling it directly.
+        let mint_transfer = self.authorize_mint_transfer(key, &entry_point, &args)?;
+
+        let result = if self.trace.is_none() {
+            self.execute_contract_frame(
+                key,
+                base_key,
+                contract,
+                args,
+                entry_point,
+                protocol_version,
+            )
+        } else {
+            let phase = self.context.phase();
+            let entry_point_name = entry_point.name().to_string();
+            let gas = self.context.gas_counter();
+            self.trace(|trace| trace.enter(phase, key, &entry_point_name, gas));
+            let result = self.execute_contract_frame(
+                key,
+                base_key,
+                contract,
+                args,
+                entry_point,
+                protocol_version,
+            );
+            let error = result.as_ref().err().map(ToString::to_string);
+            let gas = self.context.gas_counter();
+            self.trace(|trace| trace.exit(key, &entry_point_name, error, gas));
+            result
+        };
+
+        if let (Some((source, target, amount)), Ok(cl_value)) = (mint_transfer, &result) {
+            let transfer_result: Result<(), mint::Error> = cl_value.clone().into_t()?;
+            if transfer_result.is_ok() {
+                self.context.record_transfer(source, target, amount)?;
+            }
+        }
+        result
+    }
+
+    /// If `entry_point` of the contract under `key` is the mint's `transfer`, checks the transfer
+    /// against the account's transfer threshold and spending limit, and returns its source, target
+    /// and amount.
+    fn authorize_mint_transfer(
+        &self,
+        key: Key,
+        entry_point: &EntryPoint,
+        args: &RuntimeArgs,
+    ) -> Result<Option<(URef, URef, U512)>, Error> {
+        const METHOD_TRANSFER: &str = "transfer";
+
+        if !self.is_mint(key) || entry_point.name() != METHOD_TRANSFER {
+            return Ok(None);
+        }
+        let source: URef = Self::get_named_argument(args, "source")?;
+        let target: URef = Self::get_named_argument(args, "target")?;
+        let amount: U512 = Self::get_named_argument(args, "amount")?;
+        self.context
+            .authorize_transfer(source, Some(target), amount)?;
+        Ok(Some((source, target, amount)))
+    }
+
+    fn execute_contract_frame(
+        &mut self,
+        key: Key,
+        base_key: Key,
+        contract: Contract,
+        args: RuntimeArgs,
+        entry_point: EntryPoint,
+        protocol_version: ProtocolVersion,
     ) -> Result<CLValue, Error> {
         // Check for major version compatibility before calling
         if !contract.is_compatible_protocol_version(protocol_version) {
@@ -2091,7 +2312,8 @@ where
         };
 
         let module = {
-            let maybe_module = self.system_contract_cache.get(key.into_seed());
+            // The Wasm is read even if its module is cached, so that the read is part of the
+            // effects regardless of the state of the cache.
             let wasm_key = contract.contract_wasm_key();
 
             let contract_wasm: ContractWasm = match self.context.read_gs(&wasm_key)? {
@@ -2104,15 +2326,33 @@ where
                 }
                 None => return Err(Error::KeyNotFound(key)),
             };
-            match maybe_module {
-                Some(module) => module,
-                None => parity_wasm::deserialize_buffer(contract_wasm.bytes())?,
-            }
+            let wasm_costs = *self.context.protocol_data().wasm_costs();
+            let module_cache_key = ModuleCacheKey::new(contract.contract_wasm_hash(), wasm_costs);
+            self.module_cache
+                .get_or_load(module_cache_key, || -> Result<Module, Error> {
+                    let module = parity_wasm::deserialize_buffer(contract_wasm.bytes())?;
+                    Ok(engine_wasm_prep::apply_memory_limit(
+                        module,
+                        wasm_costs.max_memory,
+                    )?)
+                })?
         };
 
         let entry_point_name = entry_point.name();
 
-        let (instance, memory) = instance_and_memory(module.clone(), protocol_version)?;
+        let profiling = self.function_names.is_some();
+        let (instance, memory) = instance_and_memory(
+            module.clone(),
+            protocol_version,
+            self.context.protocol_data().wasm_costs().max_memory,
+            self.config.wasm_backend(),
+            profiling,
+        )?;
+        let function_names = if profiling {
+            Some(profiling::function_names(&module))
+        } else {
+            None
+        };
 
         let access_rights = {
             let mut keys: Vec<Key> = named_keys.values().cloned().collect();
@@ -2122,7 +2362,7 @@ where
             extract_access_rights_from_keys(keys)
         };
 
-        let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+        let module_cache = ModuleCache::clone(&self.module_cache);
 
         let config = self.config;
 
@@ -2143,6 +2383,7 @@ where
             self.context.gas_counter(),
             self.context.hash_address_generator(),
             self.context.uref_address_generator(),
+            self.context.transferred(),
             protocol_version,
             self.context.correlation_id(),
             self.context.phase(),
@@ -2150,15 +2391,17 @@ where
         );
 
         let mut runtime = Runtime {
-            system_contract_cache,
+            module_cache,
             config,
             memory,
             module,
             host_buffer,
             context,
+            trace: self.trace.clone(),
+            function_names,
         };
 
-        let result = instance.invoke_export(entry_point_name, &[], &mut runtime);
+        let result = instance.invoke_export(entry_point_name, &mut runtime);
 
         // The `runtime`'s context was initialized with our counter from before the call and any gas
         // charged by the sub-call was added to its counter - so let's copy the correct value of the
@@ -2339,6 +2582,7 @@ where
             ContractVersions::default(),
             DisabledVersions::default(),
             Groups::default(),
+            ContractPackageStatus::Unlocked,
         );
 
         let value = StoredValue::ContractPackage(contract_package);
@@ -2364,10 +2608,13 @@ where
         output_size_ptr: u32,
     ) -> Result<Result<(), ApiError>, Error> {
         let contract_package_key = contract_package_hash.into();
-
-        let mut contract_package: ContractPackage = self
+        let mut contract_package = match self
             .context
-            .get_validated_contract_package(contract_package_hash)?;
+            .get_validated_contract_package(contract_package_hash)?
+        {
+            Ok(contract_package) => contract_package,
+            Err(error) => return Ok(Err(error)),
+        };
 
         let groups = contract_package.groups_mut();
         let new_group = Group::new(label);
@@ -2435,33 +2682,45 @@ where
         contract_package_hash: ContractPackageHash,
         entry_points: EntryPoints,
         mut named_keys: NamedKeys,
+        migration: Option<String>,
         output_ptr: u32,
         output_size: usize,
         bytes_written_ptr: u32,
         version_ptr: u32,
     ) -> Result<Result<(), ApiError>, Error> {
         let contract_package_key = contract_package_hash.into();
-        self.context.validate_key(&contract_package_key)?;
-
-        let mut contract_package: ContractPackage = self
+        let mut contract_package = match self
             .context
-            .get_validated_contract_package(contract_package_hash)?;
-
-        let contract_wasm_hash = self.context.new_hash_address()?;
-        let contract_wasm_key = Key::Hash(contract_wasm_hash);
-        let contract_wasm = {
-            let module_bytes = self.get_module_from_entry_points(&entry_points)?;
-            ContractWasm::new(module_bytes)
+            .get_validated_contract_package(contract_package_hash)?
+        {
+            Ok(contract_package) => contract_package,
+            Err(error) => return Ok(Err(error)),
         };
 
-        let contract_hash = self.context.new_hash_address()?;
-        let contract_key = Key::Hash(contract_hash);
+        let previous_contract_hash = contract_package.current_contract_hash();
 
-        let protocol_version = self.context.protocol_version();
-        let major = protocol_version.value().major;
+        let migration_entry_point = match migration {
+            Some(migration) => {
+                if previous_contract_hash.is_none() {
+                    return Ok(Err(contracts::Error::NoPreviousVersion.into()));
+                }
+                let entry_point = match entry_points.get(&migration) {
+                    Some(entry_point) => entry_point.clone(),
+                    None => return Ok(Err(contracts::Error::MigrationEntryPointNotFound.into())),
+                };
+                // A public migration could be called again later with any previous contract hash
+                if entry_point.entry_point_type() != EntryPointType::Contract
+                    || *entry_point.access() == EntryPointAccess::Public
+                {
+                    return Ok(Err(contracts::Error::InvalidMigrationEntryPoint.into()));
+                }
+                Some(entry_point)
+            }
+            None => None,
+        };
 
         // TODO: EE-1032 - Implement different ways of carrying on existing named keys
-        if let Some(previous_contract_hash) = contract_package.current_contract_hash() {
+        if let Some(previous_contract_hash) = previous_contract_hash {
             let previous_contract: Contract =
                 self.context.read_gs_typed(&previous_contract_hash.into())?;
 
@@ -2469,31 +2728,36 @@ where
             named_keys.append(&mut previous_named_keys);
         }
 
-        let contract = Contract::new(
+        let (contract_hash, insert_contract_result) = self.write_contract_version(
             contract_package_hash,
-            contract_wasm_hash,
-            named_keys,
+            &mut contract_package,
             entry_points,
-            protocol_version,
-        );
-
-        let insert_contract_result = contract_package.insert_contract_version(major, contract_hash);
-
-        self.context
-            .state()
-            .borrow_mut()
-            .write(contract_wasm_key, StoredValue::ContractWasm(contract_wasm));
-
-        self.context
-            .state()
-            .borrow_mut()
-            .write(contract_key, StoredValue::Contract(contract));
+            named_keys,
+        )?;
 
         self.context.state().borrow_mut().write(
             contract_package_key,
             StoredValue::ContractPackage(contract_package),
         );
 
+        // Any failure of the migration fails the whole deploy, taking the new version with it
+        if let (Some(entry_point), Some(previous_contract_hash)) =
+            (migration_entry_point, previous_contract_hash)
+        {
+            let contract: Contract = self.context.read_gs_typed(&contract_hash.into())?;
+            let args = runtime_args! {
+                contracts::ARG_PREVIOUS_CONTRACT_HASH => previous_contract_hash,
+            };
+            self.execute_contract(
+                contract_hash.into(),
+                contract_hash.into(),
+                contract,
+                args,
+                entry_point,
+                self.context.protocol_version(),
+            )?;
+        }
+
         // return contract key to caller
         {
             let key_bytes = match contract_hash.to_bytes() {
@@ -2528,17 +2792,163 @@ where
         Ok(Ok(()))
     }
 
+    /// Writes the wasm and the header of a new version of the given package, and inserts it into
+    /// the package's versions. Writing the package itself is left to the caller.
+    fn write_contract_version(
+        &mut self,
+        contract_package_hash: ContractPackageHash,
+        contract_package: &mut ContractPackage,
+        entry_points: EntryPoints,
+        named_keys: NamedKeys,
+    ) -> Result<(ContractHash, ContractVersionKey), Error> {
+        let contract_wasm_hash = self.context.new_hash_address()?;
+        let contract_wasm_key = Key::Hash(contract_wasm_hash);
+        let contract_wasm = {
+            let module_bytes = self.get_module_from_entry_points(&entry_points)?;
+            ContractWasm::new(module_bytes)
+        };
+
+        let contract_hash = self.context.new_hash_address()?;
+        let contract_key = Key::Hash(contract_hash);
+
+        let protocol_version = self.context.protocol_version();
+        let major = protocol_version.value().major;
+
+        let contract = Contract::new(
+            contract_package_hash,
+            contract_wasm_hash,
+            named_keys,
+            entry_points,
+            protocol_version,
+        );
+
+        let contract_version_key = contract_package.insert_contract_version(major, contract_hash);
+
+        self.context
+            .state()
+            .borrow_mut()
+            .write(contract_wasm_key, StoredValue::ContractWasm(contract_wasm));
+
+        self.context
+            .state()
+            .borrow_mut()
+            .write(contract_key, StoredValue::Contract(contract));
+
+        Ok((contract_hash, contract_version_key))
+    }
+
+    /// Creates a locked package holding a single version of a contract; neither can ever change.
+    fn new_locked_contract(
+        &mut self,
+        entry_points: EntryPoints,
+        named_keys: NamedKeys,
+        contract_package_hash_ptr: u32,
+        contract_hash_ptr: u32,
+        version_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let contract_package_hash = self.context.new_hash_address()?;
+        // Nothing may be done with a locked package, so its access key is neither granted to
+        // anyone nor stored in global state
+        let access_key = URef::new(self.context.new_hash_address()?, AccessRights::NONE);
+        let mut contract_package = ContractPackage::new(
+            access_key,
+            ContractVersions::default(),
+            DisabledVersions::default(),
+            Groups::default(),
+            ContractPackageStatus::Locked,
+        );
+
+        let (contract_hash, contract_version_key) = self.write_contract_version(
+            contract_package_hash,
+            &mut contract_package,
+            entry_points,
+            named_keys,
+        )?;
+
+        self.context.state().borrow_mut().write(
+            Key::Hash(contract_package_hash),
+            StoredValue::ContractPackage(contract_package),
+        );
+
+        if let Err(error) = self
+            .memory
+            .set(contract_package_hash_ptr, &contract_package_hash)
+        {
+            return Err(Error::Interpreter(error.into()));
+        }
+
+        if let Err(error) = self.memory.set(contract_hash_ptr, &contract_hash) {
+            return Err(Error::Interpreter(error.into()));
+        }
+
+        let version_bytes = contract_version_key.contract_version().to_le_bytes();
+        if let Err(error) = self.memory.set(version_ptr, &version_bytes) {
+            return Err(Error::Interpreter(error.into()));
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Sets or clears the metadata of the given package, or of one of its versions if
+    /// `contract_hash` is given. Like any other change, this is refused for a locked package.
+    fn set_contract_metadata(
+        &mut self,
+        contract_package_hash: ContractPackageHash,
+        contract_hash: Option<ContractHash>,
+        metadata: Option<ContractMetadata>,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let contract_package_key = contract_package_hash.into();
+        let mut contract_package = match self
+            .context
+            .get_validated_contract_package(contract_package_hash)?
+        {
+            Ok(contract_package) => contract_package,
+            Err(error) => return Ok(Err(error)),
+        };
+
+        match contract_hash {
+            None => {
+                contract_package.set_metadata(metadata);
+                self.context.state().borrow_mut().write(
+                    contract_package_key,
+                    StoredValue::ContractPackage(contract_package),
+                );
+            }
+            Some(contract_hash) => {
+                if !contract_package
+                    .versions()
+                    .values()
+                    .any(|version_hash| *version_hash == contract_hash)
+                {
+                    return Ok(Err(contracts::Error::ContractNotFound.into()));
+                }
+
+                let contract_key = contract_hash.into();
+                let mut contract: Contract = self.context.read_gs_typed(&contract_key)?;
+                contract.set_metadata(metadata);
+                self.context
+                    .state()
+                    .borrow_mut()
+                    .write(contract_key, StoredValue::Contract(contract));
+            }
+        }
+
+        Ok(Ok(()))
+    }
+
     fn disable_contract_version(
         &mut self,
         contract_package_hash: ContractPackageHash,
         contract_hash: ContractHash,
     ) -> Result<Result<(), ApiError>, Error> {
         let contract_package_key = contract_package_hash.into();
-        self.context.validate_key(&contract_package_key)?;
-
-        let mut contract_package: ContractPackage = self
+        let mut contract_package = match self
             .context
-            .get_validated_contract_package(contract_package_hash)?;
+            .get_validated_contract_package(contract_package_hash)?
+        {
+            Ok(contract_package) => contract_package,
+            Err(error) => return Ok(Err(error)),
+        };
 
         if let Err(err) = contract_package.disable_contract_version(contract_hash) {
             return Ok(Err(err.into()));
@@ -2783,6 +3193,132 @@ where
         }
     }
 
+    fn set_spending_limit(
+        &mut self,
+        spending_limit_ptr: u32,
+        spending_limit_size: u32,
+    ) -> Result<i32, Trap> {
+        let spending_limit: Option<U512> = {
+            let bytes = self.bytes_from_mem(spending_limit_ptr, spending_limit_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        match self.context.set_spending_limit(spending_limit) {
+            Ok(_) => Ok(0),
+            Err(Error::SetThresholdFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_contract_allow_list(
+        &mut self,
+        allow_list_ptr: u32,
+        allow_list_size: u32,
+    ) -> Result<i32, Trap> {
+        let contract_allow_list: Option<ContractAllowList> = {
+            let bytes = self.bytes_from_mem(allow_list_ptr, allow_list_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        match self.context.set_contract_allow_list(contract_allow_list) {
+            Ok(_) => Ok(0),
+            Err(Error::SetThresholdFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Creates an account owned by the package of the calling contract, whose deploys are
+    /// authorized by the holders of URefs of `group`, and writes its account hash to `dest_ptr`.
+    fn create_contract_account(
+        &mut self,
+        group: Group,
+        deployment_threshold: u8,
+        key_management_threshold: u8,
+        dest_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let contract_hash = match self.context.base_key() {
+            Key::Hash(contract_hash)
+                if self.context.entry_point_type() == EntryPointType::Contract =>
+            {
+                contract_hash
+            }
+            _ => return Err(Error::InvalidContext),
+        };
+        let contract: Contract = self.context.read_gs_typed(&Key::Hash(contract_hash))?;
+        let contract_package_hash = contract.contract_package_hash();
+        let contract_package: ContractPackage = self
+            .context
+            .read_gs_typed(&Key::from(contract_package_hash))?;
+
+        if !contract_package.groups().contains_key(&group) {
+            return Ok(Err(contracts::Error::GroupDoesNotExist.into()));
+        }
+
+        let action_thresholds = match ActionThresholds::new(
+            Weight::new(deployment_threshold),
+            Weight::new(key_management_threshold),
+        ) {
+            Ok(action_thresholds) => action_thresholds,
+            Err(error) => return Ok(Err(error.into())),
+        };
+
+        let main_purse = self.create_purse()?;
+        let account_hash = AccountHash::new(self.context.new_hash_address()?);
+        let account = Account::create_contract_owned(
+            account_hash,
+            main_purse,
+            AccountOwner::new(contract_package_hash, group),
+            action_thresholds,
+        );
+        self.context
+            .write_account(Key::Account(account_hash), account)?;
+
+        self.memory
+            .set(dest_ptr, account_hash.as_bytes())
+            .map_err(|e| Error::Interpreter(e.into()))?;
+        Ok(Ok(()))
+    }
+
+    fn set_recovery_config(&mut self, config_ptr: u32, config_size: u32) -> Result<i32, Trap> {
+        let recovery_config: Option<RecoveryConfig> = {
+            let bytes = self.bytes_from_mem(config_ptr, config_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        recovery_result(self.context.set_recovery_config(recovery_config))
+    }
+
+    fn cancel_recovery(&mut self) -> Result<i32, Trap> {
+        recovery_result(self.context.cancel_recovery())
+    }
+
+    fn propose_recovery(
+        &mut self,
+        account_hash_ptr: u32,
+        account_hash_size: u32,
+        new_keys_ptr: u32,
+        new_keys_size: u32,
+    ) -> Result<i32, Trap> {
+        let account_hash: AccountHash = {
+            let bytes = self.bytes_from_mem(account_hash_ptr, account_hash_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        let new_keys: BTreeMap<AccountHash, Weight> = {
+            let bytes = self.bytes_from_mem(new_keys_ptr, new_keys_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        recovery_result(self.context.propose_recovery(account_hash, new_keys))
+    }
+
+    fn finalize_recovery(
+        &mut self,
+        account_hash_ptr: u32,
+        account_hash_size: u32,
+    ) -> Result<i32, Trap> {
+        let account_hash: AccountHash = {
+            let bytes = self.bytes_from_mem(account_hash_ptr, account_hash_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        recovery_result(self.context.finalize_recovery(account_hash))
+    }
+
     /// Looks up the public mint contract key in the context's protocol data.
     ///
     /// Returned URef is already attenuated depending on the calling account.
@@ -2841,6 +3377,55 @@ where
         Ok(result.map_err(system_contract_errors::Error::from)?)
     }
 
+    /// Calls the "mint_into" method on the mint contract at the given mint
+    /// contract key
+    fn mint_into_purse(
+        &mut self,
+        mint_contract_hash: ContractHash,
+        purse: URef,
+        amount: U512,
+    ) -> Result<(), Error> {
+        const ARG_PURSE: &str = "purse";
+        const ARG_AMOUNT: &str = "amount";
+
+        let args_values: RuntimeArgs = runtime_args! {
+            ARG_PURSE => purse,
+            ARG_AMOUNT => amount,
+        };
+
+        let result = self.call_contract(mint_contract_hash, "mint_into", args_values)?;
+        let result: Result<(), mint::Error> = result.into_t()?;
+        Ok(result.map_err(system_contract_errors::Error::from)?)
+    }
+
+    /// Calls the "burn" method on the mint contract at the given mint
+    /// contract key
+    fn mint_burn(
+        &mut self,
+        mint_contract_hash: ContractHash,
+        purse: URef,
+        amount: U512,
+    ) -> Result<(), Error> {
+        const ARG_PURSE: &str = "purse";
+        const ARG_AMOUNT: &str = "amount";
+
+        let args_values: RuntimeArgs = runtime_args! {
+            ARG_PURSE => purse,
+            ARG_AMOUNT => amount,
+        };
+
+        let result = self.call_contract(mint_contract_hash, "burn", args_values)?;
+        let result: Result<(), mint::Error> = result.into_t()?;
+        Ok(result.map_err(system_contract_errors::Error::from)?)
+    }
+
+    /// Calls the "total_supply" method on the mint contract at the given mint
+    /// contract key
+    fn mint_total_supply(&mut self, mint_contract_hash: ContractHash) -> Result<U512, Error> {
+        let result = self.call_contract(mint_contract_hash, "total_supply", RuntimeArgs::new())?;
+        Ok(result.into_t()?)
+    }
+
     /// Creates a new account at a given public key, transferring a given amount
     /// of motes from the given source purse to the new account's purse.
     fn transfer_to_new_account(
@@ -2914,6 +3499,12 @@ where
         target: AccountHash,
         amount: U512,
     ) -> Result<TransferResult, Error> {
+        match self.context.authorize_transfer(source, None, amount) {
+            Ok(()) => (),
+            Err(Error::Revert(error)) => return Ok(Err(error)),
+            Err(error) => return Err(error),
+        }
+
         let target_key = Key::Account(target);
         // Look up the account at the given public key's address
         match self.context.read_account(&target_key)? {
@@ -2962,6 +3553,15 @@ where
             bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
         };
 
+        match self
+            .context
+            .authorize_transfer(source, Some(target), amount)
+        {
+            Ok(()) => (),
+            Err(Error::Revert(error)) => return Ok(Err(error)),
+            Err(error) => return Err(error),
+        }
+
         let mint_contract_key = self.get_mint_contract();
 
         if self
@@ -3201,8 +3801,10 @@ where
         package_key: ContractPackageHash,
         label: Group,
     ) -> Result<Result<(), ApiError>, Error> {
-        let mut package: ContractPackage =
-            self.context.get_validated_contract_package(package_key)?;
+        let mut package = match self.context.get_validated_contract_package(package_key)? {
+            Ok(package) => package,
+            Err(error) => return Ok(Err(error)),
+        };
 
         let group_to_remove = Group::new(label);
         let groups = package.groups_mut();
@@ -3256,9 +3858,13 @@ where
     ) -> Result<Result<(), ApiError>, Error> {
         let contract_package_hash = self.t_from_mem(package_ptr, package_size)?;
         let label: String = self.t_from_mem(label_ptr, label_size)?;
-        let mut contract_package = self
+        let mut contract_package = match self
             .context
-            .get_validated_contract_package(contract_package_hash)?;
+            .get_validated_contract_package(contract_package_hash)?
+        {
+            Ok(contract_package) => contract_package,
+            Err(error) => return Ok(Err(error)),
+        };
         let groups = contract_package.groups_mut();
 
         let group_label = Group::new(label);
@@ -3327,9 +3933,13 @@ where
         let label: String = self.t_from_mem(label_ptr, label_size)?;
         let urefs: BTreeSet<URef> = self.t_from_mem(urefs_ptr, urefs_size)?;
 
-        let mut contract_package = self
+        let mut contract_package = match self
             .context
-            .get_validated_contract_package(contract_package_hash)?;
+            .get_validated_contract_package(contract_package_hash)?
+        {
+            Ok(contract_package) => contract_package,
+            Err(error) => return Ok(Err(error)),
+        };
 
         let groups = contract_package.groups_mut();
         let group_label = Group::new(label);
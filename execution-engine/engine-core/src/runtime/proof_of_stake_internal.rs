@@ -6,7 +6,8 @@ use std::{
 use engine_shared::stored_value::StoredValue;
 use engine_storage::global_state::StateReader;
 use proof_of_stake::{
-    MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, Stakes, StakesProvider,
+    AuctionConfig, AuctionProvider, Bids, FeeProvider, MintProvider, ProofOfStake, Queue,
+    QueueProvider, RuntimeProvider, SeigniorageProvider, Stakes, StakesProvider, StakingLimits,
 };
 use types::{
     account::AccountHash, bytesrepr::ToBytes, system_contract_errors::pos::Error, ApiError,
@@ -27,6 +28,32 @@ const UNBONDING_KEY: [u8; 32] = {
     result
 };
 
+const BIDS_KEY: [u8; 32] = {
+    let mut result = [0; 32];
+    result[31] = 3;
+    result
+};
+
+const ERA_END_KEY: [u8; 32] = {
+    let mut result = [0; 32];
+    result[31] = 4;
+    result
+};
+
+const LAST_SEIGNIORAGE_KEY: [u8; 32] = {
+    let mut result = [0; 32];
+    result[31] = 5;
+    result
+};
+
+const AUCTION_CONFIG_KEY: &str = "pos_auction_config";
+
+const STAKING_LIMITS_KEY: &str = "pos_staking_limits";
+
+const INFLATION_RATE_KEY: &str = "pos_inflation_rate";
+
+const FEE_BURN_RATE_KEY: &str = "pos_fee_burn_rate";
+
 // TODO: Update MintProvider to better handle errors
 impl<'a, R> MintProvider for Runtime<'a, R>
 where
@@ -63,6 +90,23 @@ where
     fn balance(&mut self, purse: URef) -> Option<U512> {
         self.get_balance(purse).expect("should get balance")
     }
+
+    fn mint_into(&mut self, purse: URef, amount: U512) -> Result<(), ()> {
+        let mint_contract_key = self.get_mint_contract();
+        self.mint_into_purse(mint_contract_key, purse, amount)
+            .map_err(|_| ())
+    }
+
+    fn total_supply(&mut self) -> Option<U512> {
+        let mint_contract_key = self.get_mint_contract();
+        self.mint_total_supply(mint_contract_key).ok()
+    }
+
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), ()> {
+        let mint_contract_key = self.get_mint_contract();
+        self.mint_burn(mint_contract_key, purse, amount)
+            .map_err(|_| ())
+    }
 }
 
 // TODO: Update QueueProvider to better handle errors
@@ -104,6 +148,109 @@ where
     }
 }
 
+// TODO: Update AuctionProvider to better handle errors
+impl<'a, R> AuctionProvider for Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    fn read_auction_config(&mut self) -> Option<AuctionConfig> {
+        let key = self.context.named_keys_get(AUCTION_CONFIG_KEY).cloned()?;
+        let cl_value: CLValue = self
+            .context
+            .read_gs_typed(&key)
+            .expect("should read auction config");
+        Some(cl_value.into_t().expect("should convert"))
+    }
+
+    fn read_bids(&mut self) -> Bids {
+        let key = BIDS_KEY.to_bytes().expect("should serialize");
+        match self.context.read_ls(&key) {
+            Ok(Some(cl_value)) => cl_value.into_t().expect("should convert"),
+            _ => Bids::default(),
+        }
+    }
+
+    fn write_bids(&mut self, bids: Bids) {
+        let key = BIDS_KEY.to_bytes().expect("should serialize");
+        let value = CLValue::from_t(bids).expect("should convert");
+        self.context
+            .write_ls(&key, value)
+            .expect("should write local state")
+    }
+
+    fn read_era_end(&mut self) -> Option<BlockTime> {
+        let key = ERA_END_KEY.to_bytes().expect("should serialize");
+        match self.context.read_ls(&key) {
+            Ok(Some(cl_value)) => Some(BlockTime::new(cl_value.into_t().expect("should convert"))),
+            _ => None,
+        }
+    }
+
+    fn write_era_end(&mut self, era_end: BlockTime) {
+        let key = ERA_END_KEY.to_bytes().expect("should serialize");
+        let era_end: u64 = era_end.into();
+        let value = CLValue::from_t(era_end).expect("should convert");
+        self.context
+            .write_ls(&key, value)
+            .expect("should write local state")
+    }
+}
+
+// TODO: Update SeigniorageProvider to better handle errors
+impl<'a, R> SeigniorageProvider for Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    fn read_inflation_rate(&mut self) -> u64 {
+        let key = match self.context.named_keys_get(INFLATION_RATE_KEY).cloned() {
+            Some(key) => key,
+            None => return 0,
+        };
+        let cl_value: CLValue = self
+            .context
+            .read_gs_typed(&key)
+            .expect("should read inflation rate");
+        cl_value.into_t().expect("should convert")
+    }
+
+    fn read_last_seigniorage(&mut self) -> Option<BlockTime> {
+        let key = LAST_SEIGNIORAGE_KEY.to_bytes().expect("should serialize");
+        match self.context.read_ls(&key) {
+            Ok(Some(cl_value)) => Some(BlockTime::new(cl_value.into_t().expect("should convert"))),
+            _ => None,
+        }
+    }
+
+    fn write_last_seigniorage(&mut self, timestamp: BlockTime) {
+        let key = LAST_SEIGNIORAGE_KEY.to_bytes().expect("should serialize");
+        let timestamp: u64 = timestamp.into();
+        let value = CLValue::from_t(timestamp).expect("should convert");
+        self.context
+            .write_ls(&key, value)
+            .expect("should write local state")
+    }
+}
+
+impl<'a, R> FeeProvider for Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    fn read_fee_burn_rate(&mut self) -> u8 {
+        let key = match self.context.named_keys_get(FEE_BURN_RATE_KEY).cloned() {
+            Some(key) => key,
+            None => return 0,
+        };
+        let cl_value: CLValue = self
+            .context
+            .read_gs_typed(&key)
+            .expect("should read fee burn rate");
+        cl_value.into_t().expect("should convert")
+    }
+}
+
 // TODO: Update RuntimeProvider to better handle errors
 impl<'a, R> RuntimeProvider for Runtime<'a, R>
 where
@@ -205,6 +352,18 @@ where
                 .expect("should put key")
         }
     }
+
+    fn read_limits(&self) -> StakingLimits {
+        let key = match self.context.named_keys_get(STAKING_LIMITS_KEY) {
+            Some(key) => key,
+            None => return StakingLimits::default(),
+        };
+        let cl_value: CLValue = self
+            .context
+            .read_gs_typed(key)
+            .expect("should read staking limits");
+        cl_value.into_t().expect("should convert")
+    }
 }
 
 impl<'a, R> ProofOfStake for Runtime<'a, R>
@@ -86,7 +86,9 @@ impl Drop for ScopedInstrumenter {
     fn drop(&mut self) {
         let duration = self.duration();
         let host_function = match self.function_index {
-            FunctionIndex::GasFuncIndex => return,
+            FunctionIndex::GasFuncIndex
+            | FunctionIndex::ProfileEnterIndex
+            | FunctionIndex::ProfileExitIndex => return,
             FunctionIndex::WriteFuncIndex => "host_function_write",
             FunctionIndex::WriteLocalFuncIndex => "host_function_write_local",
             FunctionIndex::ReadFuncIndex => "host_function_read_value",
@@ -139,6 +141,18 @@ impl Drop for ScopedInstrumenter {
             FunctionIndex::RemoveContractUserGroupURefsIndex => {
                 "host_remove_contract_user_group_urefs"
             }
+            FunctionIndex::SetSpendingLimitIndex => "host_function_set_spending_limit",
+            FunctionIndex::SetRecoveryConfigIndex => "host_function_set_recovery_config",
+            FunctionIndex::CancelRecoveryIndex => "host_function_cancel_recovery",
+            FunctionIndex::ProposeRecoveryIndex => "host_function_propose_recovery",
+            FunctionIndex::FinalizeRecoveryIndex => "host_function_finalize_recovery",
+            FunctionIndex::SetContractAllowListIndex => "host_function_set_contract_allow_list",
+            FunctionIndex::CreateContractAccountIndex => "host_function_create_contract_account",
+            FunctionIndex::NewLockedContract => "host_function_new_locked_contract",
+            FunctionIndex::AddContractVersionWithMigration => {
+                "host_function_add_contract_version_with_migration"
+            }
+            FunctionIndex::SetContractMetadata => "host_function_set_contract_metadata",
         };
 
         let mut properties = mem::take(&mut self.properties);
@@ -1,6 +1,6 @@
 use std::{
     cell::RefCell,
-    collections::{BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::{TryFrom, TryInto},
     fmt::Debug,
     rc::Rc,
@@ -17,18 +17,18 @@ use engine_shared::{
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use types::{
     account::{
-        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        AccountHash, ActionType, AddKeyFailure, ContractAllowList, RecoveryConfig, RecoveryFailure,
+        RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure, Weight,
     },
     bytesrepr,
-    contracts::NamedKeys,
-    AccessRights, BlockTime, CLType, CLValue, Contract, ContractPackage, ContractPackageHash,
-    EntryPointAccess, EntryPointType, Key, Phase, ProtocolVersion, RuntimeArgs, URef,
-    KEY_HASH_LENGTH,
+    contracts::{self, NamedKeys},
+    AccessRights, ApiError, BlockTime, CLType, CLValue, Contract, ContractPackage,
+    ContractPackageHash, EntryPointAccess, EntryPointType, Key, Phase, ProtocolVersion,
+    RuntimeArgs, URef, KEY_HASH_LENGTH, U512,
 };
 
 use crate::{
-    engine_state::execution_effect::ExecutionEffect,
+    engine_state::{execution_effect::ExecutionEffect, genesis::POS_PAYMENT_PURSE},
     execution::{AddressGenerator, Error},
     tracking_copy::{AddResult, TrackingCopy},
     Address,
@@ -101,6 +101,8 @@ pub struct RuntimeContext<'a, R> {
     gas_counter: Gas,
     hash_address_generator: Rc<RefCell<AddressGenerator>>,
     uref_address_generator: Rc<RefCell<AddressGenerator>>,
+    // Motes transferred out of the account's main purse so far by the deploy
+    transferred: Rc<RefCell<U512>>,
     protocol_version: ProtocolVersion,
     correlation_id: CorrelationId,
     phase: Phase,
@@ -129,6 +131,7 @@ where
         gas_counter: Gas,
         hash_address_generator: Rc<RefCell<AddressGenerator>>,
         uref_address_generator: Rc<RefCell<AddressGenerator>>,
+        transferred: Rc<RefCell<U512>>,
         protocol_version: ProtocolVersion,
         correlation_id: CorrelationId,
         phase: Phase,
@@ -149,6 +152,7 @@ where
             gas_counter,
             hash_address_generator,
             uref_address_generator,
+            transferred,
             protocol_version,
             correlation_id,
             phase,
@@ -265,6 +269,10 @@ where
         Rc::clone(&self.hash_address_generator)
     }
 
+    pub fn transferred(&self) -> Rc<RefCell<U512>> {
+        Rc::clone(&self.transferred)
+    }
+
     pub fn state(&self) -> Rc<RefCell<TrackingCopy<R>>> {
         Rc::clone(&self.tracking_copy)
     }
@@ -374,7 +382,7 @@ where
         Ok(())
     }
 
-    pub fn read_gs(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
+    pub fn read_gs(&self, key: &Key) -> Result<Option<StoredValue>, Error> {
         self.validate_readable(key)?;
         self.validate_key(key)?;
 
@@ -396,7 +404,7 @@ where
     /// `StoredValue` stored in the global state in a type safe manner.
     ///
     /// This is useful if you want to get the exact type from global state.
-    pub fn read_gs_typed<T>(&mut self, key: &Key) -> Result<T, Error>
+    pub fn read_gs_typed<T>(&self, key: &Key) -> Result<T, Error>
     where
         T: TryFrom<StoredValue>,
         T::Error: Debug,
@@ -814,6 +822,225 @@ where
         Ok(())
     }
 
+    pub fn set_spending_limit(&mut self, spending_limit: Option<U512>) -> Result<(), Error> {
+        // Check permission to modify the spending limit
+        if !self.is_valid_context() {
+            // Exit early with error to avoid mutations
+            return Err(SetThresholdFailure::PermissionDeniedError.into());
+        }
+
+        if !self
+            .account()
+            .can_manage_keys_with(&self.authorization_keys)
+        {
+            // Exit early if authorization keys weight doesn't exceed required
+            // key management threshold
+            return Err(SetThresholdFailure::PermissionDeniedError.into());
+        }
+
+        // Converts an account's public key into a URef
+        let key = Key::Account(self.account().account_hash());
+
+        // Take an account out of the global state
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        account.set_spending_limit(spending_limit);
+
+        let account_value = self.account_to_validated_value(account)?;
+
+        self.tracking_copy.borrow_mut().write(key, account_value);
+
+        Ok(())
+    }
+
+    pub fn set_contract_allow_list(
+        &mut self,
+        contract_allow_list: Option<ContractAllowList>,
+    ) -> Result<(), Error> {
+        // Changing the allow-list requires the same permissions as changing the keys, otherwise
+        // the keys it restricts could lift the restriction
+        if !self.is_valid_context()
+            || !self
+                .account()
+                .can_manage_keys_with(&self.authorization_keys)
+        {
+            return Err(SetThresholdFailure::PermissionDeniedError.into());
+        }
+
+        let key = Key::Account(self.account().account_hash());
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        account.set_contract_allow_list(contract_allow_list);
+
+        let account_value = self.account_to_validated_value(account)?;
+        self.tracking_copy.borrow_mut().write(key, account_value);
+        Ok(())
+    }
+
+    /// Records the deploy in the context account until `expires_at`, and returns the account
+    /// written, or `None` if the deploy was already recorded and hasn't expired yet.
+    pub fn record_executed_deploy(
+        &mut self,
+        expires_at: BlockTime,
+    ) -> Result<Option<StoredValue>, Error> {
+        let key = Key::Account(self.account().account_hash());
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        if !account.record_executed_deploy(self.deploy_hash, expires_at, self.blocktime) {
+            return Ok(None);
+        }
+
+        let account_value = self.account_to_validated_value(account)?;
+        self.tracking_copy
+            .borrow_mut()
+            .write(key, account_value.clone());
+        Ok(Some(account_value))
+    }
+
+    pub fn set_recovery_config(
+        &mut self,
+        recovery_config: Option<RecoveryConfig>,
+    ) -> Result<(), Error> {
+        self.check_recovery_permission()?;
+
+        let key = Key::Account(self.account().account_hash());
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        account
+            .set_recovery_config(recovery_config)
+            .map_err(Error::from)?;
+
+        let account_value = self.account_to_validated_value(account)?;
+        self.tracking_copy.borrow_mut().write(key, account_value);
+        Ok(())
+    }
+
+    pub fn cancel_recovery(&mut self) -> Result<(), Error> {
+        self.check_recovery_permission()?;
+
+        let key = Key::Account(self.account().account_hash());
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        account.cancel_recovery().map_err(Error::from)?;
+
+        let account_value = self.account_to_validated_value(account)?;
+        self.tracking_copy.borrow_mut().write(key, account_value);
+        Ok(())
+    }
+
+    /// Proposes or approves replacing the associated keys of `account_hash` with `new_keys`, with
+    /// the current account acting as a guardian.
+    pub fn propose_recovery(
+        &mut self,
+        account_hash: AccountHash,
+        new_keys: BTreeMap<AccountHash, Weight>,
+    ) -> Result<(), Error> {
+        self.check_recovery_permission()?;
+
+        let guardian = self.account().account_hash();
+        let blocktime = self.blocktime;
+        self.modify_recovered_account(account_hash, |account| {
+            account.propose_recovery(guardian, new_keys, blocktime)
+        })
+    }
+
+    /// Finalizes the pending recovery of `account_hash`, with the current account acting as a
+    /// guardian.
+    pub fn finalize_recovery(&mut self, account_hash: AccountHash) -> Result<(), Error> {
+        self.check_recovery_permission()?;
+
+        let guardian = self.account().account_hash();
+        let blocktime = self.blocktime;
+        self.modify_recovered_account(account_hash, |account| {
+            account.finalize_recovery(guardian, blocktime)
+        })
+    }
+
+    /// Recovery actions affect the keys of an account, so they require the key-management
+    /// threshold, and can't be performed by stored contracts on behalf of their caller.
+    fn check_recovery_permission(&self) -> Result<(), Error> {
+        if !self.is_valid_context()
+            || !self
+                .account()
+                .can_manage_keys_with(&self.authorization_keys)
+        {
+            return Err(RecoveryFailure::PermissionDenied.into());
+        }
+        Ok(())
+    }
+
+    fn modify_recovered_account<F>(&mut self, account_hash: AccountHash, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Account) -> Result<(), RecoveryFailure>,
+    {
+        let key = Key::Account(account_hash);
+        let mut account: Account = match self.read_account(&key)? {
+            Some(value) => value.try_into().map_err(Error::TypeMismatch)?,
+            None => return Err(RecoveryFailure::NotConfigured.into()),
+        };
+
+        f(&mut account).map_err(Error::from)?;
+
+        self.write_account(key, account)
+    }
+
+    /// Checks whether the deploy may transfer `amount` motes from `source` to `target`, if known.
+    ///
+    /// Only transfers out of the account's main purse are checked, in every phase: the keys
+    /// authorizing the deploy have to meet the account's transfer threshold, and the deploy's
+    /// transfers in total have to stay within the account's spending limit.  A denied transfer is
+    /// reported as [`Error::Revert`].
+    pub fn authorize_transfer(
+        &self,
+        source: URef,
+        target: Option<URef>,
+        amount: U512,
+    ) -> Result<(), Error> {
+        if !self.is_limited_transfer(source, target)? {
+            return Ok(());
+        }
+        if !self.account.can_transfer_with(&self.authorization_keys) {
+            return Err(Error::Revert(ApiError::PermissionDenied));
+        }
+        if !self.account.can_spend(*self.transferred.borrow(), amount) {
+            return Err(Error::Revert(ApiError::SpendingLimitExceeded));
+        }
+        Ok(())
+    }
+
+    /// Records a successful transfer of `amount` motes from `source` to `target`, to be counted
+    /// against the account's spending limit.
+    pub fn record_transfer(&self, source: URef, target: URef, amount: U512) -> Result<(), Error> {
+        if self.is_limited_transfer(source, Some(target))? {
+            let mut transferred = self.transferred.borrow_mut();
+            *transferred = transferred.saturating_add(amount);
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if a transfer from `source` to `target` is subject to the account's transfer
+    /// threshold and spending limit.
+    ///
+    /// That is any transfer out of the account's main purse, except for paying for the deploy: a
+    /// transfer into the Proof of Stake payment purse during the payment phase is authorized by
+    /// the deployment threshold, and whatever isn't spent on gas is refunded to the account.
+    fn is_limited_transfer(&self, source: URef, target: Option<URef>) -> Result<bool, Error> {
+        if source.addr() != self.account.main_purse().addr() {
+            return Ok(false);
+        }
+        let target = match target {
+            Some(target) if self.phase == Phase::Payment => target,
+            _ => return Ok(true),
+        };
+        let proof_of_stake_key = Key::from(self.protocol_data.proof_of_stake());
+        let proof_of_stake: Contract = self.read_gs_typed(&proof_of_stake_key)?;
+        let is_payment = match proof_of_stake.named_keys().get(POS_PAYMENT_PURSE) {
+            Some(Key::URef(payment_purse)) => payment_purse.addr() == target.addr(),
+            _ => false,
+        };
+        Ok(!is_payment)
+    }
+
     pub fn protocol_data(&self) -> ProtocolData {
         self.protocol_data
     }
@@ -843,15 +1070,20 @@ where
         self.entry_point_type
     }
 
-    /// Gets given contract package with its access_key validated against current context.
+    /// Gets given contract package with its access_key validated against current context, for
+    /// modification. A locked package can't be modified and yields `LockedContract`; its lock is
+    /// checked first as its access key is never handed out.
     pub(crate) fn get_validated_contract_package(
         &mut self,
         package_hash: ContractPackageHash,
-    ) -> Result<ContractPackage, Error> {
+    ) -> Result<Result<ContractPackage, ApiError>, Error> {
         let package_hash_key = Key::from(package_hash);
         self.validate_key(&package_hash_key)?;
-        let contract_package: ContractPackage = self.read_gs_typed(&Key::from(package_hash))?;
+        let contract_package: ContractPackage = self.read_gs_typed(&package_hash_key)?;
+        if contract_package.is_locked() {
+            return Ok(Err(contracts::Error::LockedContract.into()));
+        }
         self.validate_uref(&contract_package.access_key())?;
-        Ok(contract_package)
+        Ok(Ok(contract_package))
     }
 }
@@ -133,6 +133,7 @@ fn mock_runtime_context<'a>(
         Gas::default(),
         Rc::new(RefCell::new(hash_address_generator)),
         Rc::new(RefCell::new(uref_address_generator)),
+        Default::default(),
         ProtocolVersion::V1_0_0,
         CorrelationId::new(),
         Phase::Session,
@@ -361,6 +362,7 @@ fn contract_key_addable_valid() {
         Gas::default(),
         Rc::new(RefCell::new(hash_address_generator)),
         Rc::new(RefCell::new(uref_address_generator)),
+        Default::default(),
         ProtocolVersion::V1_0_0,
         CorrelationId::new(),
         PHASE,
@@ -431,6 +433,7 @@ fn contract_key_addable_invalid() {
         Gas::default(),
         Rc::new(RefCell::new(hash_address_generator)),
         Rc::new(RefCell::new(uref_address_generator)),
+        Default::default(),
         ProtocolVersion::V1_0_0,
         CorrelationId::new(),
         PHASE,
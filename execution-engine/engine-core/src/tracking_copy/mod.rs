@@ -201,11 +201,9 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
     /// allows isolating a specific set of changes (those in the new
     /// `TrackingCopy`) from existing changes. Note that mutations to state
     /// caused by new changes (i.e. writes and adds) only impact the new
-    /// `TrackingCopy`, not this one. Note that currently there is no `join` /
-    /// `merge` function to bring changes from a fork back to the main
-    /// `TrackingCopy`. this means the current usage requires repeated
-    /// forking, however we recognize this is sub-optimal and will revisit
-    /// in the future.
+    /// `TrackingCopy`, not this one. The changes made in a fork can be
+    /// brought back to this `TrackingCopy` by passing the fork's effect to
+    /// `TrackingCopy::apply_effect`.
     pub fn fork(&self) -> TrackingCopy<&TrackingCopy<R>> {
         TrackingCopy::new(self)
     }
@@ -325,6 +323,49 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    /// Applies `effect`, typically taken from a fork of this `TrackingCopy`, on top of the changes
+    /// already held here, so that later reads observe it and it is included in `effect()`.
+    ///
+    /// Transforms are applied one key at a time, so if anything other than `AddResult::Success` is
+    /// returned this `TrackingCopy` is left partially updated and should be discarded.
+    pub fn apply_effect(
+        &mut self,
+        correlation_id: CorrelationId,
+        effect: ExecutionEffect,
+    ) -> Result<AddResult, R::Error> {
+        for (key, transform) in effect.transforms {
+            let normalized_key = key.normalize();
+            match transform {
+                Transform::Identity => (),
+                Transform::Write(ref value) => {
+                    self.cache.insert_write(normalized_key, value.clone());
+                }
+                _ => {
+                    let current_value = match self.get(correlation_id, &normalized_key)? {
+                        None => return Ok(AddResult::KeyNotFound(normalized_key)),
+                        Some(current_value) => current_value,
+                    };
+                    match transform.clone().apply(current_value) {
+                        Ok(new_value) => self.cache.insert_write(normalized_key, new_value),
+                        Err(transform::Error::TypeMismatch(type_mismatch)) => {
+                            return Ok(AddResult::TypeMismatch(type_mismatch))
+                        }
+                        Err(transform::Error::Serialization(error)) => {
+                            return Ok(AddResult::Serialization(error))
+                        }
+                    }
+                }
+            }
+            self.fns.insert_add(normalized_key, transform);
+        }
+
+        for (key, op) in effect.ops {
+            self.ops.insert_add(key.normalize(), op);
+        }
+
+        Ok(AddResult::Success)
+    }
+
     pub fn effect(&self) -> ExecutionEffect {
         ExecutionEffect::new(self.ops.clone(), self.fns.clone())
     }
@@ -285,6 +285,49 @@ fn tracking_copy_aw() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Write));
 }
 
+#[test]
+fn tracking_copy_apply_fork_effect() {
+    let correlation_id = CorrelationId::new();
+    let db = CountingDb::new_init(StoredValue::CLValue(CLValue::from_t(5_i32).unwrap()));
+    let mut tc = TrackingCopy::new(db);
+    let k1 = Key::Hash([1u8; 32]);
+    let k2 = Key::Hash([2u8; 32]);
+
+    let effect = {
+        let mut fork = tc.fork();
+        let write_value = StoredValue::CLValue(CLValue::from_t(7_i32).unwrap());
+        fork.write(k1, write_value);
+        let add_value = StoredValue::CLValue(CLValue::from_t(3_i32).unwrap());
+        assert_matches!(
+            fork.add(correlation_id, k2, add_value),
+            Ok(AddResult::Success)
+        );
+        fork.effect()
+    };
+
+    // applying the fork's effect should make its changes visible in the original
+    assert_matches!(
+        tc.apply_effect(correlation_id, effect.clone()),
+        Ok(AddResult::Success)
+    );
+    assert_eq!(
+        tc.get(correlation_id, &k1).unwrap(),
+        Some(StoredValue::CLValue(CLValue::from_t(7_i32).unwrap()))
+    );
+    assert_eq!(
+        tc.get(correlation_id, &k2).unwrap(),
+        Some(StoredValue::CLValue(CLValue::from_t(8_i32).unwrap()))
+    );
+    assert_eq!(tc.effect(), effect);
+
+    // a second fork should read against the applied changes
+    let mut fork = tc.fork();
+    assert_eq!(
+        fork.read(correlation_id, &k2).unwrap(),
+        Some(StoredValue::CLValue(CLValue::from_t(8_i32).unwrap()))
+    );
+}
+
 proptest! {
     #[test]
     fn query_empty_path(k in key_arb(), missing_key in key_arb(), v in stored_value_arb()) {
@@ -0,0 +1,772 @@
+//! Capture and replay of the requests handled by the engine server.
+//!
+//! In capture mode every request, and the response to it, is written in protobuf bytes to a
+//! journal directory, one file each, named after the sequence number of the request and the method
+//! called, e.g. `0000000042-execute.request` and `0000000042-execute.response`.
+//!
+//! A journal can be re-applied to a copy of the global state the server was started with, and the
+//! responses compared with the recorded ones in order to find where a divergence first occurs.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug, Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use grpc::{RequestOptions, SingleResponse};
+use log::warn;
+use protobuf::{Message, ProtobufError};
+
+use super::{
+    ipc::{
+        self, BidStateRequest, BidStateResponse, CommitRequest, CommitResponse,
+        DistributeRewardsRequest, DistributeRewardsResponse, EstimateResponse, ExecuteResponse,
+        GenesisResponse, GetContractAbiRequest, GetContractAbiResponse, ProofOfStakeStepRequest,
+        ProofOfStakeStepResponse, QueryResponse, ReadTotalSupplyRequest, ReadTotalSupplyResponse,
+        SlashRequest, SlashResponse, UnbondPayoutRequest, UnbondPayoutResponse, UpgradeRequest,
+        UpgradeResponse,
+    },
+    ipc_grpc::ExecutionEngineService,
+};
+
+const REQUEST_EXTENSION: &str = "request";
+const RESPONSE_EXTENSION: &str = "response";
+
+/// The methods of the engine service which are recorded in a journal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum JournalMethod {
+    Query,
+    ReadTotalSupply,
+    GetContractAbi,
+    Execute,
+    Estimate,
+    Commit,
+    RunGenesis,
+    Upgrade,
+    RunAuction,
+    MintSeigniorage,
+}
+
+impl JournalMethod {
+    fn as_str(&self) -> &'static str {
+        match self {
+            JournalMethod::Query => "query",
+            JournalMethod::ReadTotalSupply => "read_total_supply",
+            JournalMethod::GetContractAbi => "get_contract_abi",
+            JournalMethod::Execute => "execute",
+            JournalMethod::Estimate => "estimate",
+            JournalMethod::Commit => "commit",
+            JournalMethod::RunGenesis => "run_genesis",
+            JournalMethod::Upgrade => "upgrade",
+            JournalMethod::RunAuction => "run_auction",
+            JournalMethod::MintSeigniorage => "mint_seigniorage",
+        }
+    }
+}
+
+impl Display for JournalMethod {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for JournalMethod {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "query" => Ok(JournalMethod::Query),
+            "read_total_supply" => Ok(JournalMethod::ReadTotalSupply),
+            "get_contract_abi" => Ok(JournalMethod::GetContractAbi),
+            "execute" => Ok(JournalMethod::Execute),
+            "estimate" => Ok(JournalMethod::Estimate),
+            "commit" => Ok(JournalMethod::Commit),
+            "run_genesis" => Ok(JournalMethod::RunGenesis),
+            "upgrade" => Ok(JournalMethod::Upgrade),
+            "run_auction" => Ok(JournalMethod::RunAuction),
+            "mint_seigniorage" => Ok(JournalMethod::MintSeigniorage),
+            _ => Err(format!("unknown journal method: {}", input)),
+        }
+    }
+}
+
+/// A request read from a journal, along with the response to it if one was recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalEntry {
+    pub sequence: u64,
+    pub method: JournalMethod,
+    pub request: Vec<u8>,
+    pub response: Option<Vec<u8>>,
+}
+
+/// A journal directory which requests and responses are written to.
+#[derive(Debug)]
+pub struct Journal {
+    dir: PathBuf,
+    next_sequence: AtomicU64,
+}
+
+impl Journal {
+    /// Opens the journal in `dir`, creating the directory if needed.  New entries are numbered
+    /// after any already in the journal.
+    pub fn open<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let next_sequence = Self::read_entries(&dir)?
+            .last()
+            .map(|entry| entry.sequence + 1)
+            .unwrap_or_default();
+        Ok(Journal {
+            dir,
+            next_sequence: AtomicU64::new(next_sequence),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Reads the entries of the journal in `dir`, in the order in which the requests were
+    /// received.
+    pub fn read_entries<P: AsRef<Path>>(dir: P) -> io::Result<Vec<JournalEntry>> {
+        let mut requests = BTreeMap::new();
+        let mut responses = BTreeMap::new();
+        for dir_entry in fs::read_dir(dir)? {
+            let path = dir_entry?.path();
+            let (sequence, method, extension) = match parse_file_name(&path) {
+                Some(parsed) => parsed,
+                None => continue,
+            };
+            match extension {
+                REQUEST_EXTENSION => {
+                    requests.insert(sequence, (method, fs::read(&path)?));
+                }
+                RESPONSE_EXTENSION => {
+                    responses.insert(sequence, fs::read(&path)?);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(requests
+            .into_iter()
+            .map(|(sequence, (method, request))| JournalEntry {
+                sequence,
+                method,
+                request,
+                response: responses.remove(&sequence),
+            })
+            .collect())
+    }
+
+    fn next_sequence(&self) -> u64 {
+        self.next_sequence.fetch_add(1, Ordering::SeqCst)
+    }
+
+    fn path(&self, sequence: u64, method: JournalMethod, extension: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:010}-{}.{}", sequence, method, extension))
+    }
+
+    fn write<M: Message>(
+        &self,
+        sequence: u64,
+        method: JournalMethod,
+        extension: &str,
+        message: &M,
+    ) {
+        let path = self.path(sequence, method, extension);
+        let result = message
+            .write_to_bytes()
+            .map_err(|error| error.to_string())
+            .and_then(|bytes| fs::write(&path, bytes).map_err(|error| error.to_string()));
+        if let Err(error) = result {
+            warn!("failed to write journal file {:?}: {}", path, error);
+        }
+    }
+}
+
+/// Parses a journal file name of the form `<sequence>-<method>.<extension>`.
+fn parse_file_name(path: &Path) -> Option<(u64, JournalMethod, &str)> {
+    let extension = path.extension()?.to_str()?;
+    let stem = path.file_stem()?.to_str()?;
+    let mut parts = stem.splitn(2, '-');
+    let sequence = parts.next()?.parse().ok()?;
+    let method = parts.next()?.parse().ok()?;
+    Some((sequence, method, extension))
+}
+
+/// Wraps an engine service, recording each request it handles, and the response to it, to a
+/// journal.
+pub struct JournalingService<E> {
+    inner: E,
+    journal: Journal,
+}
+
+impl<E> JournalingService<E> {
+    pub fn new(inner: E, journal: Journal) -> Self {
+        JournalingService { inner, journal }
+    }
+
+    fn record<Req, Resp, F>(
+        &self,
+        method: JournalMethod,
+        request: Req,
+        handle: F,
+    ) -> SingleResponse<Resp>
+    where
+        Req: Message,
+        Resp: Message,
+        F: FnOnce(&E, Req) -> SingleResponse<Resp>,
+    {
+        let sequence = self.journal.next_sequence();
+        self.journal
+            .write(sequence, method, REQUEST_EXTENSION, &request);
+        match handle(&self.inner, request).wait_drop_metadata() {
+            Ok(response) => {
+                self.journal
+                    .write(sequence, method, RESPONSE_EXTENSION, &response);
+                SingleResponse::completed(response)
+            }
+            Err(error) => SingleResponse::err(error),
+        }
+    }
+}
+
+impl<E: ExecutionEngineService> ExecutionEngineService for JournalingService<E> {
+    fn commit(&self, o: RequestOptions, p: CommitRequest) -> SingleResponse<CommitResponse> {
+        self.record(JournalMethod::Commit, p, |inner, p| inner.commit(o, p))
+    }
+
+    fn query(&self, o: RequestOptions, p: ipc::QueryRequest) -> SingleResponse<QueryResponse> {
+        self.record(JournalMethod::Query, p, |inner, p| inner.query(o, p))
+    }
+
+    fn read_total_supply(
+        &self,
+        o: RequestOptions,
+        p: ReadTotalSupplyRequest,
+    ) -> SingleResponse<ReadTotalSupplyResponse> {
+        self.record(JournalMethod::ReadTotalSupply, p, |inner, p| {
+            inner.read_total_supply(o, p)
+        })
+    }
+
+    fn get_contract_abi(
+        &self,
+        o: RequestOptions,
+        p: GetContractAbiRequest,
+    ) -> SingleResponse<GetContractAbiResponse> {
+        self.record(JournalMethod::GetContractAbi, p, |inner, p| {
+            inner.get_contract_abi(o, p)
+        })
+    }
+
+    fn execute(
+        &self,
+        o: RequestOptions,
+        p: ipc::ExecuteRequest,
+    ) -> SingleResponse<ExecuteResponse> {
+        self.record(JournalMethod::Execute, p, |inner, p| inner.execute(o, p))
+    }
+
+    fn estimate(
+        &self,
+        o: RequestOptions,
+        p: ipc::EstimateRequest,
+    ) -> SingleResponse<EstimateResponse> {
+        self.record(JournalMethod::Estimate, p, |inner, p| inner.estimate(o, p))
+    }
+
+    fn run_genesis(
+        &self,
+        o: RequestOptions,
+        p: ipc::RunGenesisRequest,
+    ) -> SingleResponse<GenesisResponse> {
+        self.record(JournalMethod::RunGenesis, p, |inner, p| {
+            inner.run_genesis(o, p)
+        })
+    }
+
+    fn upgrade(&self, o: RequestOptions, p: UpgradeRequest) -> SingleResponse<UpgradeResponse> {
+        self.record(JournalMethod::Upgrade, p, |inner, p| inner.upgrade(o, p))
+    }
+
+    fn bid_state(&self, o: RequestOptions, p: BidStateRequest) -> SingleResponse<BidStateResponse> {
+        self.inner.bid_state(o, p)
+    }
+
+    fn distribute_rewards(
+        &self,
+        o: RequestOptions,
+        p: DistributeRewardsRequest,
+    ) -> SingleResponse<DistributeRewardsResponse> {
+        self.inner.distribute_rewards(o, p)
+    }
+
+    fn slash(&self, o: RequestOptions, p: SlashRequest) -> SingleResponse<SlashResponse> {
+        self.inner.slash(o, p)
+    }
+
+    fn unbond_payout(
+        &self,
+        o: RequestOptions,
+        p: UnbondPayoutRequest,
+    ) -> SingleResponse<UnbondPayoutResponse> {
+        self.inner.unbond_payout(o, p)
+    }
+
+    fn run_auction(
+        &self,
+        o: RequestOptions,
+        p: ProofOfStakeStepRequest,
+    ) -> SingleResponse<ProofOfStakeStepResponse> {
+        self.record(JournalMethod::RunAuction, p, |inner, p| {
+            inner.run_auction(o, p)
+        })
+    }
+
+    fn mint_seigniorage(
+        &self,
+        o: RequestOptions,
+        p: ProofOfStakeStepRequest,
+    ) -> SingleResponse<ProofOfStakeStepResponse> {
+        self.record(JournalMethod::MintSeigniorage, p, |inner, p| {
+            inner.mint_seigniorage(o, p)
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Protobuf(ProtobufError),
+    Grpc(grpc::Error),
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Protobuf(error) => write!(f, "protobuf error: {}", error),
+            ReplayError::Grpc(error) => write!(f, "grpc error: {}", error),
+        }
+    }
+}
+
+impl From<ProtobufError> for ReplayError {
+    fn from(error: ProtobufError) -> Self {
+        ReplayError::Protobuf(error)
+    }
+}
+
+impl From<grpc::Error> for ReplayError {
+    fn from(error: grpc::Error) -> Self {
+        ReplayError::Grpc(error)
+    }
+}
+
+/// Re-applies the request of `entry` to `service`, returning the response in protobuf bytes.
+pub fn replay_entry<E: ExecutionEngineService>(
+    service: &E,
+    entry: &JournalEntry,
+) -> Result<Vec<u8>, ReplayError> {
+    fn call<Req, Resp, F>(request: &[u8], handle: F) -> Result<Vec<u8>, ReplayError>
+    where
+        Req: Message,
+        Resp: Message,
+        F: FnOnce(RequestOptions, Req) -> SingleResponse<Resp>,
+    {
+        let request = protobuf::parse_from_bytes(request)?;
+        let response = handle(RequestOptions::new(), request).wait_drop_metadata()?;
+        Ok(response.write_to_bytes()?)
+    }
+
+    let request = entry.request.as_slice();
+    match entry.method {
+        JournalMethod::Query => call(request, |o, p| service.query(o, p)),
+        JournalMethod::ReadTotalSupply => call(request, |o, p| service.read_total_supply(o, p)),
+        JournalMethod::GetContractAbi => call(request, |o, p| service.get_contract_abi(o, p)),
+        JournalMethod::Execute => call(request, |o, p| service.execute(o, p)),
+        JournalMethod::Estimate => call(request, |o, p| service.estimate(o, p)),
+        JournalMethod::Commit => call(request, |o, p| service.commit(o, p)),
+        JournalMethod::RunGenesis => call(request, |o, p| service.run_genesis(o, p)),
+        JournalMethod::Upgrade => call(request, |o, p| service.upgrade(o, p)),
+        JournalMethod::RunAuction => call(request, |o, p| service.run_auction(o, p)),
+        JournalMethod::MintSeigniorage => call(request, |o, p| service.mint_seigniorage(o, p)),
+    }
+}
+
+/// The first part of a replayed response found to differ from the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    pub field: String,
+    pub recorded: String,
+    pub replayed: String,
+}
+
+impl Difference {
+    fn new<T: Debug>(field: impl Into<String>, recorded: T, replayed: T) -> Self {
+        Difference {
+            field: field.into(),
+            recorded: format!("{:?}", recorded),
+            replayed: format!("{:?}", replayed),
+        }
+    }
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} differs\n  recorded: {}\n  replayed: {}",
+            self.field, self.recorded, self.replayed
+        )
+    }
+}
+
+/// Compares a recorded response with a replayed one, both in protobuf bytes.
+///
+/// The order of the entries of execution effects and of bonded validators is not significant, as
+/// they're built from hash maps.
+pub fn compare_responses(
+    method: JournalMethod,
+    recorded: &[u8],
+    replayed: &[u8],
+) -> Result<Option<Difference>, ProtobufError> {
+    match method {
+        JournalMethod::Execute => {
+            let recorded = normalized_execute_response(protobuf::parse_from_bytes(recorded)?);
+            let replayed = normalized_execute_response(protobuf::parse_from_bytes(replayed)?);
+            Ok(compare_execute_responses(&recorded, &replayed))
+        }
+        JournalMethod::Commit => {
+            let mut recorded: CommitResponse = protobuf::parse_from_bytes(recorded)?;
+            let mut replayed: CommitResponse = protobuf::parse_from_bytes(replayed)?;
+            if recorded.has_success() {
+                sort_bonds(recorded.mut_success());
+            }
+            if replayed.has_success() {
+                sort_bonds(replayed.mut_success());
+            }
+            Ok(
+                compare_commit_results(recorded.get_success(), replayed.get_success())
+                    .or_else(|| compare_messages("response", &recorded, &replayed)),
+            )
+        }
+        JournalMethod::RunGenesis => {
+            let mut recorded: GenesisResponse = protobuf::parse_from_bytes(recorded)?;
+            let mut replayed: GenesisResponse = protobuf::parse_from_bytes(replayed)?;
+            if recorded.has_success() {
+                sort_effect(recorded.mut_success().mut_effect());
+            }
+            if replayed.has_success() {
+                sort_effect(replayed.mut_success().mut_effect());
+            }
+            let (recorded_hash, replayed_hash) = (
+                recorded.get_success().get_poststate_hash(),
+                replayed.get_success().get_poststate_hash(),
+            );
+            Ok(compare_hashes(recorded_hash, replayed_hash)
+                .or_else(|| compare_messages("response", &recorded, &replayed)))
+        }
+        JournalMethod::Upgrade => {
+            let mut recorded: UpgradeResponse = protobuf::parse_from_bytes(recorded)?;
+            let mut replayed: UpgradeResponse = protobuf::parse_from_bytes(replayed)?;
+            if recorded.has_success() {
+                sort_effect(recorded.mut_success().mut_effect());
+            }
+            if replayed.has_success() {
+                sort_effect(replayed.mut_success().mut_effect());
+            }
+            let (recorded_hash, replayed_hash) = (
+                recorded.get_success().get_post_state_hash(),
+                replayed.get_success().get_post_state_hash(),
+            );
+            Ok(compare_hashes(recorded_hash, replayed_hash)
+                .or_else(|| compare_messages("response", &recorded, &replayed)))
+        }
+        JournalMethod::RunAuction | JournalMethod::MintSeigniorage => {
+            let mut recorded: ProofOfStakeStepResponse = protobuf::parse_from_bytes(recorded)?;
+            let mut replayed: ProofOfStakeStepResponse = protobuf::parse_from_bytes(replayed)?;
+            if recorded.has_success() {
+                sort_bonds(recorded.mut_success());
+            }
+            if replayed.has_success() {
+                sort_bonds(replayed.mut_success());
+            }
+            Ok(
+                compare_commit_results(recorded.get_success(), replayed.get_success())
+                    .or_else(|| compare_messages("response", &recorded, &replayed)),
+            )
+        }
+        JournalMethod::Estimate => {
+            let mut recorded: EstimateResponse = protobuf::parse_from_bytes(recorded)?;
+            let mut replayed: EstimateResponse = protobuf::parse_from_bytes(replayed)?;
+            if recorded.has_success() {
+                sort_deploy_result(recorded.mut_success().mut_deploy_result());
+            }
+            if replayed.has_success() {
+                sort_deploy_result(replayed.mut_success().mut_deploy_result());
+            }
+            Ok(compare_messages("response", &recorded, &replayed))
+        }
+        JournalMethod::Query => {
+            let recorded: QueryResponse = protobuf::parse_from_bytes(recorded)?;
+            let replayed: QueryResponse = protobuf::parse_from_bytes(replayed)?;
+            Ok(compare_messages("response", &recorded, &replayed))
+        }
+        JournalMethod::ReadTotalSupply => {
+            let recorded: ReadTotalSupplyResponse = protobuf::parse_from_bytes(recorded)?;
+            let replayed: ReadTotalSupplyResponse = protobuf::parse_from_bytes(replayed)?;
+            Ok(compare_messages("response", &recorded, &replayed))
+        }
+        JournalMethod::GetContractAbi => {
+            let recorded: GetContractAbiResponse = protobuf::parse_from_bytes(recorded)?;
+            let replayed: GetContractAbiResponse = protobuf::parse_from_bytes(replayed)?;
+            Ok(compare_messages("response", &recorded, &replayed))
+        }
+    }
+}
+
+fn compare_messages<M: Message + PartialEq>(
+    field: &str,
+    recorded: &M,
+    replayed: &M,
+) -> Option<Difference> {
+    if recorded == replayed {
+        None
+    } else {
+        Some(Difference::new(field, recorded, replayed))
+    }
+}
+
+fn compare_hashes(recorded: &[u8], replayed: &[u8]) -> Option<Difference> {
+    if recorded == replayed {
+        None
+    } else {
+        Some(Difference {
+            field: "post-state hash".to_string(),
+            recorded: base16::encode_lower(recorded),
+            replayed: base16::encode_lower(replayed),
+        })
+    }
+}
+
+fn compare_commit_results(
+    recorded: &ipc::CommitResult,
+    replayed: &ipc::CommitResult,
+) -> Option<Difference> {
+    compare_hashes(recorded.get_poststate_hash(), replayed.get_poststate_hash())
+        .or_else(|| compare_messages("bonded validators", recorded, replayed))
+}
+
+fn compare_execute_responses(
+    recorded: &ExecuteResponse,
+    replayed: &ExecuteResponse,
+) -> Option<Difference> {
+    if recorded.has_success() && replayed.has_success() {
+        let (recorded, replayed) = (recorded.get_success(), replayed.get_success());
+        let (recorded_results, replayed_results) =
+            (recorded.get_deploy_results(), replayed.get_deploy_results());
+        if recorded_results.len() != replayed_results.len() {
+            return Some(Difference::new(
+                "deploy result count",
+                recorded_results.len(),
+                replayed_results.len(),
+            ));
+        }
+        for (index, (recorded, replayed)) in recorded_results
+            .iter()
+            .zip(replayed_results.iter())
+            .enumerate()
+        {
+            if let Some(difference) = compare_deploy_results(index, recorded, replayed) {
+                return Some(difference);
+            }
+        }
+        return compare_commit_results(recorded.get_commit_result(), replayed.get_commit_result())
+            .or_else(|| compare_messages("effect", recorded.get_effect(), replayed.get_effect()))
+            .or_else(|| compare_messages("response", recorded, replayed));
+    }
+    compare_messages("response", recorded, replayed)
+}
+
+fn compare_deploy_results(
+    index: usize,
+    recorded: &ipc::DeployResult,
+    replayed: &ipc::DeployResult,
+) -> Option<Difference> {
+    if recorded.has_execution_result() && replayed.has_execution_result() {
+        let (recorded, replayed) = (
+            recorded.get_execution_result(),
+            replayed.get_execution_result(),
+        );
+        let field = |name: &str| format!("deploy {} {}", index, name);
+        return compare_messages(&field("cost"), recorded.get_cost(), replayed.get_cost())
+            .or_else(|| {
+                compare_messages(&field("error"), recorded.get_error(), replayed.get_error())
+            })
+            .or_else(|| {
+                compare_messages(
+                    &field("effect"),
+                    recorded.get_effects(),
+                    replayed.get_effects(),
+                )
+            });
+    }
+    compare_messages(&format!("deploy {} result", index), recorded, replayed)
+}
+
+fn normalized_execute_response(mut response: ExecuteResponse) -> ExecuteResponse {
+    if response.has_success() {
+        let success = response.mut_success();
+        for deploy_result in success.mut_deploy_results().iter_mut() {
+            sort_deploy_result(deploy_result);
+        }
+        sort_effect(success.mut_effect());
+        sort_bonds(success.mut_commit_result());
+    }
+    response
+}
+
+fn sort_deploy_result(deploy_result: &mut ipc::DeployResult) {
+    if deploy_result.has_execution_result() {
+        sort_effect(deploy_result.mut_execution_result().mut_effects());
+    }
+}
+
+fn sort_effect(effect: &mut ipc::ExecutionEffect) {
+    effect
+        .mut_op_map()
+        .sort_by_key(|entry| key_bytes(entry.get_key()));
+    effect
+        .mut_transform_map()
+        .sort_by_key(|entry| key_bytes(entry.get_key()));
+}
+
+fn sort_bonds(commit_result: &mut ipc::CommitResult) {
+    commit_result.mut_bonded_validators().sort_by(|a, b| {
+        a.get_validator_account_hash()
+            .cmp(b.get_validator_account_hash())
+    });
+}
+
+fn key_bytes<M: Message>(key: &M) -> Vec<u8> {
+    key.write_to_bytes().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use protobuf::Message;
+    use tempfile::TempDir;
+
+    use super::{compare_responses, Journal, JournalEntry, JournalMethod};
+    use crate::engine_server::{ipc, state, transforms};
+
+    fn transform_entry(key_hash: u8, value: i32) -> transforms::TransformEntry {
+        let mut key = state::Key::new();
+        key.mut_hash().set_hash(vec![key_hash; 32]);
+        let mut entry = transforms::TransformEntry::new();
+        entry.set_key(key);
+        entry.mut_transform().mut_add_i32().set_value(value);
+        entry
+    }
+
+    fn execute_response(entries: Vec<transforms::TransformEntry>, cost: &str) -> Vec<u8> {
+        let mut deploy_result = ipc::DeployResult::new();
+        let execution_result = deploy_result.mut_execution_result();
+        execution_result.mut_cost().set_value(cost.to_string());
+        execution_result.mut_cost().set_bit_width(512);
+        execution_result
+            .mut_effects()
+            .set_transform_map(entries.into());
+        let mut response = ipc::ExecuteResponse::new();
+        response
+            .mut_success()
+            .set_deploy_results(vec![deploy_result].into());
+        response.write_to_bytes().unwrap()
+    }
+
+    #[test]
+    fn should_read_back_written_entries_in_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let journal = Journal::open(dir).unwrap();
+
+        let mut commit_request = ipc::CommitRequest::new();
+        commit_request.set_prestate_hash(vec![1; 32]);
+        let sequence = journal.next_sequence();
+        journal.write(
+            sequence,
+            JournalMethod::Commit,
+            super::REQUEST_EXTENSION,
+            &commit_request,
+        );
+        let query_request = ipc::QueryRequest::new();
+        let sequence = journal.next_sequence();
+        journal.write(
+            sequence,
+            JournalMethod::Query,
+            super::REQUEST_EXTENSION,
+            &query_request,
+        );
+        let query_response = ipc::QueryResponse::new();
+        journal.write(
+            sequence,
+            JournalMethod::Query,
+            super::RESPONSE_EXTENSION,
+            &query_response,
+        );
+
+        let entries = Journal::read_entries(dir).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                JournalEntry {
+                    sequence: 0,
+                    method: JournalMethod::Commit,
+                    request: commit_request.write_to_bytes().unwrap(),
+                    response: None,
+                },
+                JournalEntry {
+                    sequence: 1,
+                    method: JournalMethod::Query,
+                    request: query_request.write_to_bytes().unwrap(),
+                    response: Some(query_response.write_to_bytes().unwrap()),
+                },
+            ]
+        );
+
+        // Reopening the journal continues the numbering.
+        let journal = Journal::open(dir).unwrap();
+        assert_eq!(journal.next_sequence(), 2);
+    }
+
+    #[test]
+    fn should_ignore_order_of_effect_entries() {
+        let recorded = execute_response(vec![transform_entry(1, 1), transform_entry(2, 2)], "10");
+        let replayed = execute_response(vec![transform_entry(2, 2), transform_entry(1, 1)], "10");
+        assert_eq!(
+            compare_responses(JournalMethod::Execute, &recorded, &replayed).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn should_report_first_divergence() {
+        let recorded = execute_response(vec![transform_entry(1, 1)], "10");
+        let replayed = execute_response(vec![transform_entry(1, 2)], "11");
+        let difference = compare_responses(JournalMethod::Execute, &recorded, &replayed)
+            .unwrap()
+            .expect("should differ");
+        assert_eq!(difference.field, "deploy 0 cost");
+
+        let replayed = execute_response(vec![transform_entry(1, 2)], "10");
+        let difference = compare_responses(JournalMethod::Execute, &recorded, &replayed)
+            .unwrap()
+            .expect("should differ");
+        assert_eq!(difference.field, "deploy 0 effect");
+    }
+}
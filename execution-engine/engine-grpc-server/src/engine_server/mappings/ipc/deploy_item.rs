@@ -3,8 +3,8 @@ use std::{
     convert::{TryFrom, TryInto},
 };
 
-use engine_core::engine_state::deploy_item::DeployItem;
-use types::account::AccountHash;
+use engine_core::engine_state::deploy_item::{DeployItem, DeployTtl};
+use types::{account::AccountHash, BlockTime};
 
 use crate::engine_server::{ipc, mappings::MappingError};
 
@@ -42,6 +42,14 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
             MappingError::invalid_deploy_hash_length(pb_deploy_item.deploy_hash.len())
         })?;
 
+        let ttl = match pb_deploy_item.get_ttl_millis() {
+            0 => None,
+            ttl_millis => Some(DeployTtl::new(
+                BlockTime::new(pb_deploy_item.get_timestamp()),
+                ttl_millis,
+            )),
+        };
+
         Ok(DeployItem::new(
             address,
             session,
@@ -49,6 +57,7 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
             gas_price,
             authorization_keys,
             deploy_hash,
+            ttl,
         ))
     }
 }
@@ -68,6 +77,10 @@ impl From<DeployItem> for ipc::DeployItem {
                 .collect(),
         );
         result.set_deploy_hash(deploy_item.deploy_hash.to_vec());
+        if let Some(ttl) = deploy_item.ttl {
+            result.set_timestamp(ttl.timestamp.into());
+            result.set_ttl_millis(ttl.ttl);
+        }
         result
     }
 }
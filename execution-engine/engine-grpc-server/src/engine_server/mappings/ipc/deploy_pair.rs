@@ -0,0 +1,10 @@
+use crate::engine_server::ipc::DeployPair;
+
+impl From<(usize, usize)> for DeployPair {
+    fn from((first, second): (usize, usize)) -> Self {
+        let mut pb_deploy_pair = DeployPair::new();
+        pb_deploy_pair.set_first(first as u32);
+        pb_deploy_pair.set_second(second as u32);
+        pb_deploy_pair
+    }
+}
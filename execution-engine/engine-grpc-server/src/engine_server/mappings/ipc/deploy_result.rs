@@ -3,7 +3,7 @@ use engine_core::{
         execution_effect::ExecutionEffect, execution_result::ExecutionResult,
         Error as EngineStateError,
     },
-    execution::Error as ExecutionError,
+    execution::{Error as ExecutionError, ExecutionTrace, GasProfile},
 };
 use engine_shared::gas::Gas;
 
@@ -22,6 +22,19 @@ impl From<ExecutionResult> for DeployResult {
     }
 }
 
+impl From<(ExecutionResult, Option<ExecutionTrace>)> for DeployResult {
+    fn from(
+        (execution_result, execution_trace): (ExecutionResult, Option<ExecutionTrace>),
+    ) -> DeployResult {
+        let mut deploy_result: DeployResult = execution_result.into();
+        if let Some(execution_trace) = execution_trace {
+            deploy_result.set_gas_profile(GasProfile::from(&execution_trace).to_folded());
+            deploy_result.set_trace(execution_trace.to_json(false));
+        }
+        deploy_result
+    }
+}
+
 impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
     fn from((engine_state_error, effect, cost): (EngineStateError, ExecutionEffect, Gas)) -> Self {
         match engine_state_error {
@@ -32,12 +45,21 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
             | error @ EngineStateError::InvalidAccountHashLength { .. }
             | error @ EngineStateError::InvalidProtocolVersion { .. }
             | error @ EngineStateError::InvalidUpgradeConfig
+            | error @ EngineStateError::InvalidAuctionConfig
+            | error @ EngineStateError::InvalidFeeBurnRate(_)
+            | error @ EngineStateError::InvalidMaxMemory(_)
+            | error @ EngineStateError::InvalidGovernanceAccount
             | error @ EngineStateError::WasmPreprocessing(_)
             | error @ EngineStateError::WasmSerialization(_)
             | error @ EngineStateError::Exec(ExecutionError::DeploymentAuthorizationFailure)
+            | error @ EngineStateError::Exec(ExecutionError::TransferAuthorizationFailure)
             | error @ EngineStateError::InvalidKeyVariant(_)
             | error @ EngineStateError::Authorization
             | error @ EngineStateError::InvalidDeployItemVariant(_)
+            | error @ EngineStateError::ContractNotAllowed
+            | error @ EngineStateError::DeployExpired
+            | error @ EngineStateError::DeployReplayed
+            | error @ EngineStateError::DeployTtlTooLong(_)
             | error @ EngineStateError::InvalidUpgradeResult => {
                 detail::precondition_error(error.to_string())
             }
@@ -253,4 +275,21 @@ mod tests {
             expected_revert.to_string(),
         );
     }
+
+    #[test]
+    fn execution_trace_maps_to_json() {
+        let execution_result = || ExecutionResult::Success {
+            effect: Default::default(),
+            cost: Gas::default(),
+        };
+
+        let ipc_deploy_result: DeployResult = (execution_result(), None).into();
+        assert!(ipc_deploy_result.get_trace().is_empty());
+        assert!(ipc_deploy_result.get_gas_profile().is_empty());
+
+        let ipc_deploy_result: DeployResult =
+            (execution_result(), Some(ExecutionTrace::new())).into();
+        assert!(ipc_deploy_result.has_execution_result());
+        assert_eq!(ipc_deploy_result.get_trace(), r#"{"entries":[]}"#);
+    }
 }
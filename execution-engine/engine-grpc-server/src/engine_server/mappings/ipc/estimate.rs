@@ -0,0 +1,125 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::{
+    estimate::{EstimateRequest, EstimateResult},
+    execution_result::ExecutionResult,
+};
+use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+
+use crate::engine_server::{ipc, mappings::MappingError};
+
+impl TryFrom<ipc::EstimateRequest> for EstimateRequest {
+    type Error = MappingError;
+
+    fn try_from(mut request: ipc::EstimateRequest) -> Result<Self, Self::Error> {
+        let parent_state_hash = {
+            let parent_state_hash = request.get_parent_state_hash();
+            let length = parent_state_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                return Err(MappingError::InvalidStateHashLength {
+                    expected: BLAKE2B_DIGEST_LENGTH,
+                    actual: length,
+                });
+            }
+            parent_state_hash
+                .try_into()
+                .map_err(|_| MappingError::TryFromSlice)?
+        };
+
+        let block_time = request.get_block_time();
+
+        let deploy_item = request.take_deploy().try_into()?;
+
+        let protocol_version = request.take_protocol_version().into();
+
+        Ok(EstimateRequest::new(
+            parent_state_hash,
+            block_time,
+            deploy_item,
+            protocol_version,
+        ))
+    }
+}
+
+impl From<EstimateRequest> for ipc::EstimateRequest {
+    fn from(request: EstimateRequest) -> Self {
+        let mut result = ipc::EstimateRequest::new();
+        result.set_parent_state_hash(request.parent_state_hash.to_vec());
+        result.set_block_time(request.block_time);
+        result.set_deploy(request.deploy_item.into());
+        result.set_protocol_version(request.protocol_version.into());
+        result
+    }
+}
+
+impl From<EstimateResult> for ipc::EstimateResult {
+    fn from(estimate_result: EstimateResult) -> Self {
+        let mut result = ipc::EstimateResult::new();
+        result.set_payment_cost(estimate_result.payment_cost().value().into());
+        result.set_session_cost(estimate_result.session_cost().value().into());
+
+        let cost = estimate_result.total_cost();
+        let effect = estimate_result.effect().clone();
+        let execution_result = match estimate_result.take_error() {
+            Some(error) => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+            },
+            None => ExecutionResult::Success { effect, cost },
+        };
+        result.set_deploy_result(execution_result.into());
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use engine_core::engine_state::{
+        estimate::EstimateResult, execution_effect::ExecutionEffect, Error,
+    };
+    use engine_shared::gas::Gas;
+    use types::U512;
+
+    use crate::engine_server::ipc;
+
+    #[test]
+    fn estimate_result_should_map_costs() {
+        let payment_cost = Gas::new(U512::from(3));
+        let session_cost = Gas::new(U512::from(5));
+        let estimate_result =
+            EstimateResult::new(payment_cost, session_cost, ExecutionEffect::default(), None);
+
+        let mut ipc_estimate_result: ipc::EstimateResult = estimate_result.into();
+        let ipc_payment_cost: U512 = ipc_estimate_result
+            .take_payment_cost()
+            .try_into()
+            .expect("should map to U512");
+        let ipc_session_cost: U512 = ipc_estimate_result
+            .take_session_cost()
+            .try_into()
+            .expect("should map to U512");
+        assert_eq!(ipc_payment_cost, payment_cost.value());
+        assert_eq!(ipc_session_cost, session_cost.value());
+
+        let mut ipc_deploy_result = ipc_estimate_result.take_deploy_result();
+        assert!(!ipc_deploy_result.get_execution_result().has_error());
+        let total_cost: U512 = ipc_deploy_result
+            .mut_execution_result()
+            .take_cost()
+            .try_into()
+            .expect("should map to U512");
+        assert_eq!(total_cost, U512::from(8));
+    }
+
+    #[test]
+    fn estimate_result_should_map_precondition_failure() {
+        let estimate_result = EstimateResult::precondition_failure(Error::Authorization);
+        let ipc_estimate_result: ipc::EstimateResult = estimate_result.into();
+        assert!(ipc_estimate_result
+            .get_deploy_result()
+            .has_precondition_failure());
+    }
+}
@@ -1,7 +1,9 @@
 use std::convert::{TryFrom, TryInto};
 
-use engine_core::engine_state::genesis::{ExecConfig, GenesisAccount};
+use engine_core::engine_state::genesis::{AuctionConfig, ExecConfig, GenesisAccount};
+use types::account::AccountHash;
 
+use super::fee_burn_rate;
 use crate::engine_server::{ipc, mappings::MappingError};
 
 impl TryFrom<ipc::ChainSpec_GenesisConfig_ExecConfig> for ExecConfig {
@@ -19,13 +21,34 @@ impl TryFrom<ipc::ChainSpec_GenesisConfig_ExecConfig> for ExecConfig {
         let mint_initializer_bytes = pb_exec_config.take_mint_installer();
         let proof_of_stake_initializer_bytes = pb_exec_config.take_pos_installer();
         let standard_payment_installer_bytes = pb_exec_config.take_standard_payment_installer();
-        Ok(ExecConfig::new(
+        let mut exec_config = ExecConfig::new(
             mint_initializer_bytes,
             proof_of_stake_initializer_bytes,
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
-        ))
+        )
+        .with_annual_inflation_rate(pb_exec_config.get_annual_inflation_rate())
+        .with_fee_burn_rate(fee_burn_rate::parse_fee_burn_rate(
+            pb_exec_config.get_fee_burn_rate(),
+        )?);
+        if !pb_exec_config.get_governance_account().is_empty() {
+            let pb_governance_account = pb_exec_config.get_governance_account();
+            let governance_account =
+                AccountHash::try_from(pb_governance_account).map_err(|_| {
+                    MappingError::invalid_account_hash_length(pb_governance_account.len())
+                })?;
+            exec_config = exec_config.with_governance_account(governance_account);
+        }
+        if pb_exec_config.has_staking_limits() {
+            let staking_limits = pb_exec_config.take_staking_limits().try_into()?;
+            exec_config = exec_config.with_staking_limits(staking_limits);
+        }
+        if !pb_exec_config.has_auction_config() {
+            return Ok(exec_config);
+        }
+        let auction_config = pb_exec_config.take_auction_config().try_into()?;
+        Ok(exec_config.with_auction_config(auction_config))
     }
 }
 
@@ -49,10 +72,44 @@ impl From<ExecConfig> for ipc::ChainSpec_GenesisConfig_ExecConfig {
         pb_exec_config
             .mut_costs()
             .set_wasm(exec_config.wasm_costs().into());
+        pb_exec_config.set_staking_limits(exec_config.staking_limits().into());
+        pb_exec_config.set_annual_inflation_rate(exec_config.annual_inflation_rate());
+        pb_exec_config.set_fee_burn_rate(exec_config.fee_burn_rate().into());
+        if let Some(governance_account) = exec_config.governance_account() {
+            pb_exec_config.set_governance_account(governance_account.as_bytes().to_vec());
+        }
+        if let Some(auction_config) = exec_config.auction_config() {
+            pb_exec_config.set_auction_config(auction_config.into());
+        }
         pb_exec_config
     }
 }
 
+impl TryFrom<ipc::ChainSpec_GenesisConfig_ExecConfig_AuctionConfig> for AuctionConfig {
+    type Error = MappingError;
+
+    fn try_from(
+        mut pb_auction_config: ipc::ChainSpec_GenesisConfig_ExecConfig_AuctionConfig,
+    ) -> Result<Self, Self::Error> {
+        let minimum_bid = pb_auction_config.take_minimum_bid().try_into()?;
+        Ok(AuctionConfig::new(
+            pb_auction_config.get_validator_slots(),
+            pb_auction_config.get_era_length_millis(),
+            minimum_bid,
+        ))
+    }
+}
+
+impl From<AuctionConfig> for ipc::ChainSpec_GenesisConfig_ExecConfig_AuctionConfig {
+    fn from(auction_config: AuctionConfig) -> Self {
+        let mut pb_auction_config = ipc::ChainSpec_GenesisConfig_ExecConfig_AuctionConfig::new();
+        pb_auction_config.set_validator_slots(auction_config.validator_slots());
+        pb_auction_config.set_era_length_millis(auction_config.era_length());
+        pb_auction_config.set_minimum_bid(auction_config.minimum_bid().into());
+        pb_auction_config
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,11 @@
 use std::convert::{TryFrom, TryInto};
 
 use engine_core::engine_state::{
-    execute_request::ExecuteRequest, execution_result::ExecutionResult,
+    execute_request::{ExecuteRequest, ExecutionMode},
+    execution_result::ExecutionResult,
 };
 use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+use types::account::AccountHash;
 
 use crate::engine_server::{ipc, mappings::MappingError};
 
@@ -30,9 +32,22 @@ impl TryFrom<ipc::ExecuteRequest> for ExecuteRequest {
 
         let block_time = request.get_block_time();
 
+        // An empty proposer means that the fees are not paid to any account.
+        let proposer = match request.get_proposer() {
+            [] => Ok(None),
+            proposer => AccountHash::try_from(proposer)
+                .map(Some)
+                .map_err(|_| proposer.len()),
+        };
+
+        // None of the deploys can be executed if the proposer is malformed.
         let deploys = Into::<Vec<_>>::into(request.take_deploys())
             .into_iter()
             .map(|deploy_item| {
+                if let Err(length) = proposer {
+                    let error = MappingError::invalid_account_hash_length(length);
+                    return Err(ExecutionResult::precondition_failure(error.into()));
+                }
                 deploy_item
                     .try_into()
                     .map_err(|err: MappingError| ExecutionResult::precondition_failure(err.into()))
@@ -41,11 +56,21 @@ impl TryFrom<ipc::ExecuteRequest> for ExecuteRequest {
 
         let protocol_version = request.take_protocol_version().into();
 
+        let execution_mode = request.get_execution_mode().into();
+
+        let enable_tracing = request.get_enable_tracing();
+
+        let enable_profiling = request.get_enable_profiling();
+
         Ok(ExecuteRequest::new(
             parent_state_hash,
             block_time,
             deploys,
             protocol_version,
+            proposer.unwrap_or_default(),
+            execution_mode,
+            enable_tracing,
+            enable_profiling,
         ))
     }
 }
@@ -65,6 +90,30 @@ impl From<ExecuteRequest> for ipc::ExecuteRequest {
                 .collect(),
         );
         result.set_protocol_version(req.protocol_version.into());
+        if let Some(proposer) = req.proposer {
+            result.set_proposer(proposer.as_bytes().to_vec());
+        }
+        result.set_execution_mode(req.execution_mode.into());
+        result.set_enable_tracing(req.enable_tracing);
+        result.set_enable_profiling(req.enable_profiling);
         result
     }
 }
+
+impl From<ipc::ExecuteRequest_ExecutionMode> for ExecutionMode {
+    fn from(pb_execution_mode: ipc::ExecuteRequest_ExecutionMode) -> Self {
+        match pb_execution_mode {
+            ipc::ExecuteRequest_ExecutionMode::INDEPENDENT => ExecutionMode::Independent,
+            ipc::ExecuteRequest_ExecutionMode::SEQUENTIAL => ExecutionMode::Sequential,
+        }
+    }
+}
+
+impl From<ExecutionMode> for ipc::ExecuteRequest_ExecutionMode {
+    fn from(execution_mode: ExecutionMode) -> Self {
+        match execution_mode {
+            ExecutionMode::Independent => ipc::ExecuteRequest_ExecutionMode::INDEPENDENT,
+            ExecutionMode::Sequential => ipc::ExecuteRequest_ExecutionMode::SEQUENTIAL,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::genesis::MAX_FEE_BURN_RATE;
+
+use crate::engine_server::mappings::{MappingError, ParsingError};
+
+/// Parses the percentage of the transaction fees which is burned.
+pub(crate) fn parse_fee_burn_rate(pb_fee_burn_rate: u32) -> Result<u8, MappingError> {
+    match u8::try_from(pb_fee_burn_rate) {
+        Ok(fee_burn_rate) if fee_burn_rate <= MAX_FEE_BURN_RATE => Ok(fee_burn_rate),
+        _ => Err(ParsingError(format!(
+            "Fee burn rate must be between 0 and {}%, got {}%",
+            MAX_FEE_BURN_RATE, pb_fee_burn_rate
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_percentages() {
+        assert_eq!(parse_fee_burn_rate(0).unwrap(), 0);
+        assert_eq!(parse_fee_burn_rate(100).unwrap(), 100);
+    }
+
+    #[test]
+    fn should_reject_rates_above_100_percent() {
+        assert!(parse_fee_burn_rate(101).is_err());
+        assert!(parse_fee_burn_rate(256).is_err());
+    }
+}
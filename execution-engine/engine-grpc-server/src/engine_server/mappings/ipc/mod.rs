@@ -3,14 +3,18 @@
 
 mod bond;
 mod deploy_item;
+mod deploy_pair;
 mod deploy_result;
+mod estimate;
 mod exec_config;
 mod executable_deploy_item;
 mod execute_request;
 mod execution_effect;
+mod fee_burn_rate;
 mod genesis_account;
 mod genesis_config;
 mod query_request;
 mod run_genesis_request;
+mod staking_limits;
 mod upgrade_request;
 mod wasm_costs;
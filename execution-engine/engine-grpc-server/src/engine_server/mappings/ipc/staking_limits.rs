@@ -0,0 +1,64 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::genesis::StakingLimits;
+
+use crate::engine_server::{ipc::ChainSpec_StakingLimits, mappings::MappingError};
+
+impl From<StakingLimits> for ChainSpec_StakingLimits {
+    fn from(staking_limits: StakingLimits) -> Self {
+        let mut pb_staking_limits = ChainSpec_StakingLimits::new();
+        pb_staking_limits.set_max_spread(staking_limits.max_spread().into());
+        pb_staking_limits.set_max_increase(staking_limits.max_increase().into());
+        pb_staking_limits.set_max_decrease(staking_limits.max_decrease().into());
+        pb_staking_limits.set_max_rel_increase(staking_limits.max_rel_increase());
+        pb_staking_limits.set_max_rel_decrease(staking_limits.max_rel_decrease());
+        pb_staking_limits.set_max_bond_len(staking_limits.max_bond_len());
+        pb_staking_limits.set_max_unbond_len(staking_limits.max_unbond_len());
+        pb_staking_limits
+    }
+}
+
+impl TryFrom<ChainSpec_StakingLimits> for StakingLimits {
+    type Error = MappingError;
+
+    fn try_from(mut pb_staking_limits: ChainSpec_StakingLimits) -> Result<Self, Self::Error> {
+        let max_spread = pb_staking_limits.take_max_spread().try_into()?;
+        let max_increase = pb_staking_limits.take_max_increase().try_into()?;
+        let max_decrease = pb_staking_limits.take_max_decrease().try_into()?;
+        Ok(StakingLimits::new(
+            max_spread,
+            max_increase,
+            max_decrease,
+            pb_staking_limits.get_max_rel_increase(),
+            pb_staking_limits.get_max_rel_decrease(),
+            pb_staking_limits.get_max_bond_len(),
+            pb_staking_limits.get_max_unbond_len(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::U512;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    #[test]
+    fn round_trip() {
+        test_utils::protobuf_round_trip::<StakingLimits, ChainSpec_StakingLimits>(
+            StakingLimits::default(),
+        );
+        test_utils::protobuf_round_trip::<StakingLimits, ChainSpec_StakingLimits>(
+            StakingLimits::new(
+                U512::from(1_000),
+                U512::from(2_000),
+                U512::from(3_000),
+                500_000,
+                400_000,
+                10,
+                20,
+            ),
+        );
+    }
+}
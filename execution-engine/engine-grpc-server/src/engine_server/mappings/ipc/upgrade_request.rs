@@ -3,6 +3,7 @@ use std::convert::{TryFrom, TryInto};
 use engine_core::engine_state::upgrade::UpgradeConfig;
 use types::ProtocolVersion;
 
+use super::fee_burn_rate;
 use crate::engine_server::{ipc::UpgradeRequest, mappings::MappingError};
 
 impl TryFrom<UpgradeRequest> for UpgradeConfig {
@@ -40,6 +41,22 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
         } else {
             Some(upgrade_point.get_activation_point().rank)
         };
+        let new_staking_limits = if !upgrade_point.has_new_staking_limits() {
+            None
+        } else {
+            Some(upgrade_point.take_new_staking_limits().try_into()?)
+        };
+        let new_annual_inflation_rate = if !upgrade_point.has_new_inflation_rate() {
+            None
+        } else {
+            Some(upgrade_point.get_new_inflation_rate().get_annual_rate())
+        };
+        let new_fee_burn_rate = if !upgrade_point.has_new_fee_burn_rate() {
+            None
+        } else {
+            let percentage = upgrade_point.get_new_fee_burn_rate().get_percentage();
+            Some(fee_burn_rate::parse_fee_burn_rate(percentage)?)
+        };
 
         Ok(UpgradeConfig::new(
             pre_state_hash,
@@ -49,6 +66,9 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
             upgrade_installer_bytes,
             wasm_costs,
             activation_point,
+            new_staking_limits,
+            new_annual_inflation_rate,
+            new_fee_burn_rate,
         ))
     }
 }
@@ -1,4 +1,4 @@
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{wasm_costs::WasmCosts, MEM_PAGES};
 
 use crate::engine_server::ipc::ChainSpec_CostTable_WasmCosts;
 
@@ -15,6 +15,7 @@ impl From<WasmCosts> for ChainSpec_CostTable_WasmCosts {
             max_stack_height: wasm_costs.max_stack_height,
             opcodes_mul: wasm_costs.opcodes_mul,
             opcodes_div: wasm_costs.opcodes_div,
+            max_memory: wasm_costs.max_memory,
             ..Default::default()
         }
     }
@@ -33,6 +34,12 @@ impl From<ChainSpec_CostTable_WasmCosts> for WasmCosts {
             max_stack_height: pb_wasm_costs.max_stack_height,
             opcodes_mul: pb_wasm_costs.opcodes_mul,
             opcodes_div: pb_wasm_costs.opcodes_div,
+            // Unset by chain specs predating the configurable maximum.
+            max_memory: if pb_wasm_costs.max_memory == 0 {
+                MEM_PAGES
+            } else {
+                pb_wasm_costs.max_memory
+            },
         }
     }
 }
@@ -52,4 +59,11 @@ mod tests {
             test_utils::protobuf_round_trip::<WasmCosts, ChainSpec_CostTable_WasmCosts>(wasm_costs);
         }
     }
+
+    #[test]
+    fn unset_max_memory_maps_to_default() {
+        let pb_wasm_costs = ChainSpec_CostTable_WasmCosts::new();
+        let wasm_costs = WasmCosts::from(pb_wasm_costs);
+        assert_eq!(wasm_costs.max_memory, MEM_PAGES);
+    }
 }
@@ -1,16 +1,26 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     convert::{TryFrom, TryInto},
     mem,
 };
 
-use engine_shared::account::{Account, ActionThresholds, AssociatedKeys};
-use types::account::{AccountHash, Weight};
+use engine_shared::account::{
+    Account, AccountOwner, ActionThresholds, AssociatedKeys, PendingRecovery,
+};
+use types::{
+    account::{AccountHash, ContractAllowList, RecoveryConfig, Weight},
+    contracts::Group,
+    BlockTime,
+};
 
 use super::NamedKeyMap;
 use crate::engine_server::{
     mappings::{self, ParsingError},
-    state::{self, Account_AssociatedKey, NamedKey},
+    state::{
+        self, Account_AssociatedKey, Account_ContractAllowList,
+        Account_ContractAllowList_AllowedContract, Account_ExecutedDeploy, Account_Owner,
+        Account_PendingRecovery, Account_RecoveryConfig, NamedKey,
+    },
 };
 
 impl From<Account> for state::Account {
@@ -32,9 +42,46 @@ impl From<Account> for state::Account {
         {
             let deployment = u32::from(account.action_thresholds().deployment().value());
             let key_management = u32::from(account.action_thresholds().key_management().value());
+            let transfer = u32::from(account.action_thresholds().transfer().value());
             let pb_action_thresholds = pb_account.mut_action_thresholds();
             pb_action_thresholds.set_deployment_threshold(deployment);
-            pb_action_thresholds.set_key_management_threshold(key_management)
+            pb_action_thresholds.set_key_management_threshold(key_management);
+            pb_action_thresholds.set_transfer_threshold(transfer);
+        }
+
+        if let Some(spending_limit) = account.spending_limit() {
+            pb_account.set_spending_limit(spending_limit.into());
+        }
+
+        if let Some(recovery_config) = account.recovery_config() {
+            pb_account.set_recovery_config(recovery_config.into());
+        }
+
+        let pending_recoveries: Vec<Account_PendingRecovery> = account
+            .pending_recoveries()
+            .iter()
+            .map(Into::into)
+            .collect();
+        pb_account.set_pending_recoveries(pending_recoveries.into());
+
+        if let Some(contract_allow_list) = account.contract_allow_list() {
+            pb_account.set_contract_allow_list(contract_allow_list.into());
+        }
+
+        let executed_deploys: Vec<Account_ExecutedDeploy> = account
+            .executed_deploys()
+            .iter()
+            .map(|(deploy_hash, expires_at)| {
+                let mut pb_executed_deploy = Account_ExecutedDeploy::new();
+                pb_executed_deploy.set_deploy_hash(deploy_hash.to_vec());
+                pb_executed_deploy.set_expires_at((*expires_at).into());
+                pb_executed_deploy
+            })
+            .collect();
+        pb_account.set_executed_deploys(executed_deploys.into());
+
+        if let Some(owner) = account.owner() {
+            pb_account.set_owner(owner.into());
         }
 
         pb_account
@@ -78,7 +125,7 @@ impl TryFrom<state::Account> for Account {
                     ParsingError::from("Protobuf Account missing ActionThresholds field")
                 })?;
 
-            ActionThresholds::new(
+            let mut action_thresholds = ActionThresholds::new(
                 weight_from(
                     pb_action_thresholds.deployment_threshold,
                     "Protobuf DeploymentThreshold",
@@ -88,20 +135,219 @@ impl TryFrom<state::Account> for Account {
                     "Protobuf KeyManagementThreshold",
                 )?,
             )
-            .map_err(ParsingError::from)?
+            .map_err(ParsingError::from)?;
+            action_thresholds.set_transfer_threshold(weight_from(
+                pb_action_thresholds.transfer_threshold,
+                "Protobuf TransferThreshold",
+            )?);
+            action_thresholds
+        };
+
+        let spending_limit = match pb_account.spending_limit.into_option() {
+            Some(pb_spending_limit) => Some(pb_spending_limit.try_into()?),
+            None => None,
         };
 
-        let account = Account::new(
+        let mut account = Account::new(
             AccountHash::new(account_hash),
             named_keys.into_inner(),
             main_purse,
             associated_keys,
             action_thresholds,
         );
+        account.set_spending_limit(spending_limit);
+
+        let recovery_config = match pb_account.recovery_config.into_option() {
+            Some(pb_recovery_config) => Some(pb_recovery_config.try_into()?),
+            None => None,
+        };
+        let pending_recoveries = pb_account
+            .pending_recoveries
+            .into_vec()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<_>, ParsingError>>()?;
+        account.restore_recovery(recovery_config, pending_recoveries);
+
+        let contract_allow_list = match pb_account.contract_allow_list.into_option() {
+            Some(pb_contract_allow_list) => Some(pb_contract_allow_list.try_into()?),
+            None => None,
+        };
+        account.set_contract_allow_list(contract_allow_list);
+
+        let executed_deploys = pb_account
+            .executed_deploys
+            .into_vec()
+            .into_iter()
+            .map(|pb_executed_deploy| {
+                let deploy_hash = mappings::vec_to_array(
+                    pb_executed_deploy.deploy_hash,
+                    "Protobuf ExecutedDeploy::DeployHash",
+                )?;
+                Ok((deploy_hash, BlockTime::new(pb_executed_deploy.expires_at)))
+            })
+            .collect::<Result<BTreeMap<_, _>, ParsingError>>()?;
+        account.restore_executed_deploys(executed_deploys);
+
+        let owner = match pb_account.owner.into_option() {
+            Some(pb_owner) => Some(pb_owner.try_into()?),
+            None => None,
+        };
+        account.restore_owner(owner);
+
         Ok(account)
     }
 }
 
+impl From<&RecoveryConfig> for Account_RecoveryConfig {
+    fn from(recovery_config: &RecoveryConfig) -> Self {
+        let mut pb_recovery_config = Account_RecoveryConfig::new();
+        pb_recovery_config.set_guardians(
+            recovery_config
+                .guardians()
+                .iter()
+                .map(|guardian| guardian.as_bytes().to_vec())
+                .collect(),
+        );
+        pb_recovery_config.set_threshold(recovery_config.threshold().into());
+        pb_recovery_config.set_delay(recovery_config.delay());
+        pb_recovery_config
+    }
+}
+
+impl TryFrom<Account_RecoveryConfig> for RecoveryConfig {
+    type Error = ParsingError;
+
+    fn try_from(pb_recovery_config: Account_RecoveryConfig) -> Result<Self, Self::Error> {
+        let guardians = account_hashes_from(
+            pb_recovery_config.guardians.into_vec(),
+            "Protobuf RecoveryConfig::Guardians",
+        )?;
+        let threshold = u8::try_from(pb_recovery_config.threshold).map_err(|_| {
+            ParsingError(format!(
+                "Unable to convert {} to u8 while parsing Protobuf RecoveryConfig::Threshold",
+                pb_recovery_config.threshold
+            ))
+        })?;
+        Ok(RecoveryConfig::new(
+            guardians,
+            threshold,
+            pb_recovery_config.delay,
+        ))
+    }
+}
+
+impl From<&PendingRecovery> for Account_PendingRecovery {
+    fn from(pending_recovery: &PendingRecovery) -> Self {
+        let mut pb_pending_recovery = Account_PendingRecovery::new();
+        let new_keys: Vec<Account_AssociatedKey> =
+            pending_recovery.new_keys().iter().map(Into::into).collect();
+        pb_pending_recovery.set_new_keys(new_keys.into());
+        pb_pending_recovery.set_approvals(
+            pending_recovery
+                .approvals()
+                .iter()
+                .map(|approval| approval.as_bytes().to_vec())
+                .collect(),
+        );
+        pb_pending_recovery.set_proposed_at(pending_recovery.proposed_at().into());
+        pb_pending_recovery
+    }
+}
+
+impl TryFrom<Account_PendingRecovery> for PendingRecovery {
+    type Error = ParsingError;
+
+    fn try_from(pb_pending_recovery: Account_PendingRecovery) -> Result<Self, Self::Error> {
+        let mut new_keys = AssociatedKeys::default();
+        for pb_associated_key in pb_pending_recovery.new_keys.into_vec() {
+            let (key, weight) = pb_associated_key.try_into()?;
+            new_keys.add_key(key, weight).map_err(|error| {
+                ParsingError(format!(
+                    "Error parsing Protobuf PendingRecovery::NewKeys: {:?}",
+                    error
+                ))
+            })?;
+        }
+
+        let mut approvals = account_hashes_from(
+            pb_pending_recovery.approvals.into_vec(),
+            "Protobuf PendingRecovery::Approvals",
+        )?
+        .into_iter();
+        let proposer = approvals.next().ok_or_else(|| {
+            ParsingError::from("Protobuf PendingRecovery missing Approvals field")
+        })?;
+
+        let mut pending_recovery = PendingRecovery::new(
+            proposer,
+            new_keys,
+            BlockTime::new(pb_pending_recovery.proposed_at),
+        );
+        approvals.for_each(|approval| pending_recovery.approve(approval));
+        Ok(pending_recovery)
+    }
+}
+
+impl From<&ContractAllowList> for Account_ContractAllowList {
+    fn from(contract_allow_list: &ContractAllowList) -> Self {
+        let mut pb_contract_allow_list = Account_ContractAllowList::new();
+        let contracts: Vec<Account_ContractAllowList_AllowedContract> = contract_allow_list
+            .iter()
+            .map(|(contract_package_hash, entry_points)| {
+                let mut pb_allowed_contract = Account_ContractAllowList_AllowedContract::new();
+                pb_allowed_contract.set_contract_package_hash(contract_package_hash.to_vec());
+                pb_allowed_contract.set_entry_points(entry_points.iter().cloned().collect());
+                pb_allowed_contract
+            })
+            .collect();
+        pb_contract_allow_list.set_contracts(contracts.into());
+        pb_contract_allow_list
+    }
+}
+
+impl TryFrom<Account_ContractAllowList> for ContractAllowList {
+    type Error = ParsingError;
+
+    fn try_from(pb_contract_allow_list: Account_ContractAllowList) -> Result<Self, Self::Error> {
+        let mut contract_allow_list = ContractAllowList::new();
+        for pb_allowed_contract in pb_contract_allow_list.contracts.into_vec() {
+            let contract_package_hash = mappings::vec_to_array(
+                pb_allowed_contract.contract_package_hash,
+                "Protobuf ContractAllowList::ContractPackageHash",
+            )?;
+            for entry_point in pb_allowed_contract.entry_points.into_vec() {
+                contract_allow_list.allow(contract_package_hash, entry_point);
+            }
+        }
+        Ok(contract_allow_list)
+    }
+}
+
+impl From<&AccountOwner> for Account_Owner {
+    fn from(owner: &AccountOwner) -> Self {
+        let mut pb_owner = Account_Owner::new();
+        pb_owner.set_contract_package_hash(owner.contract_package_hash().to_vec());
+        pb_owner.set_group(owner.group().value().to_string());
+        pb_owner
+    }
+}
+
+impl TryFrom<Account_Owner> for AccountOwner {
+    type Error = ParsingError;
+
+    fn try_from(pb_owner: Account_Owner) -> Result<Self, Self::Error> {
+        let contract_package_hash = mappings::vec_to_array(
+            pb_owner.contract_package_hash,
+            "Protobuf Owner::ContractPackageHash",
+        )?;
+        Ok(AccountOwner::new(
+            contract_package_hash,
+            Group::new(pb_owner.group),
+        ))
+    }
+}
+
 impl From<(&AccountHash, &Weight)> for Account_AssociatedKey {
     fn from((account_hash, weight): (&AccountHash, &Weight)) -> Self {
         let mut pb_associated_key = Account_AssociatedKey::new();
@@ -126,6 +372,16 @@ impl TryFrom<Account_AssociatedKey> for (AccountHash, Weight) {
     }
 }
 
+fn account_hashes_from(
+    values: Vec<Vec<u8>>,
+    value_name: &str,
+) -> Result<BTreeSet<AccountHash>, ParsingError> {
+    values
+        .into_iter()
+        .map(|value| mappings::vec_to_array(value, value_name).map(AccountHash::new))
+        .collect()
+}
+
 fn weight_from(value: u32, value_name: &str) -> Result<Weight, ParsingError> {
     let weight = u8::try_from(value).map_err(|_| {
         ParsingError(format!(
@@ -9,6 +9,7 @@ use std::convert::{TryFrom, TryInto};
 
 impl From<Contract> for state::Contract {
     fn from(contract: Contract) -> Self {
+        let metadata = contract.metadata().cloned();
         let (contract_package_hash, contract_wasm_hash, named_keys, entry_points, protocol_version) =
             contract.into();
         let mut pb_contract = state::Contract::new();
@@ -23,6 +24,9 @@ impl From<Contract> for state::Contract {
         pb_contract.set_named_keys(named_keys.into());
         pb_contract.set_entry_points(entry_points.into());
         pb_contract.set_protocol_version(protocol_version.into());
+        if let Some(metadata) = metadata {
+            pb_contract.set_metadata(metadata.into());
+        }
         pb_contract
     }
 }
@@ -55,13 +59,17 @@ impl TryFrom<state::Contract> for Contract {
             entry_points.add_entry_point(entry_point.try_into()?);
         }
 
-        Ok(Contract::new(
+        let mut contract = Contract::new(
             contract_package_hash,
             contract_wasm_hash,
             named_keys,
             entry_points,
             value.take_protocol_version().try_into()?,
-        ))
+        );
+        if value.has_metadata() {
+            contract.set_metadata(Some(value.take_metadata().try_into()?));
+        }
+        Ok(contract)
     }
 }
 
@@ -3,9 +3,12 @@ use std::{
     convert::{TryFrom, TryInto},
 };
 use types::{
-    contracts::{ContractVersions, DisabledVersions, Groups},
-    ContractPackage, ContractVersionKey, EntryPoint, EntryPointAccess, EntryPointType, Group,
-    Parameter,
+    contracts::{
+        ContractMetadata, ContractVersions, DisabledVersions, EntryPointDocs, Groups,
+        NamedKeysSchema,
+    },
+    ContractPackage, ContractPackageStatus, ContractVersionKey, EntryPoint, EntryPointAccess,
+    EntryPointType, Group, Parameter,
 };
 
 use crate::engine_server::{mappings::ParsingError, state};
@@ -14,6 +17,10 @@ impl From<ContractPackage> for state::ContractPackage {
     fn from(value: ContractPackage) -> state::ContractPackage {
         let mut contract_package = state::ContractPackage::new();
         contract_package.set_access_key(value.access_key().into());
+        contract_package.set_locked(value.is_locked());
+        if let Some(metadata) = value.metadata() {
+            contract_package.set_metadata(metadata.clone().into());
+        }
 
         for &disabled_version in value.disabled_versions().iter() {
             contract_package
@@ -50,12 +57,21 @@ impl TryFrom<state::ContractPackage> for ContractPackage {
     type Error = ParsingError;
     fn try_from(mut value: state::ContractPackage) -> Result<ContractPackage, Self::Error> {
         let access_uref = value.take_access_key().try_into()?;
+        let status = if value.get_locked() {
+            ContractPackageStatus::Locked
+        } else {
+            ContractPackageStatus::Unlocked
+        };
         let mut contract_package = ContractPackage::new(
             access_uref,
             ContractVersions::default(),
             DisabledVersions::default(),
             Groups::default(),
+            status,
         );
+        if value.has_metadata() {
+            contract_package.set_metadata(Some(value.take_metadata().try_into()?));
+        }
         for mut active_version in value.take_active_versions().into_iter() {
             let version = active_version.take_version().try_into()?;
             let header = active_version.take_contract_hash().as_slice().try_into()?;
@@ -170,6 +186,72 @@ impl TryFrom<state::Contract_EntryPoint> for EntryPoint {
     }
 }
 
+impl From<ContractMetadata> for state::ContractMetadata {
+    fn from(value: ContractMetadata) -> Self {
+        let mut metadata = state::ContractMetadata::new();
+        metadata.set_name(value.name().to_string());
+        metadata.set_description(value.description().to_string());
+        if let Some(source_hash) = value.source_hash() {
+            metadata.set_source_hash(source_hash.to_vec());
+        }
+
+        for (entry_point, doc) in value.entry_point_docs() {
+            let mut entry_point_doc = state::ContractMetadata_EntryPointDoc::new();
+            entry_point_doc.set_entry_point(entry_point.clone());
+            entry_point_doc.set_doc(doc.clone());
+            metadata.mut_entry_point_docs().push(entry_point_doc);
+        }
+
+        for (name, cl_type) in value.named_keys_schema() {
+            let mut named_key_schema = state::ContractMetadata_NamedKeySchema::new();
+            named_key_schema.set_name(name.clone());
+            named_key_schema.set_cl_type(cl_type.clone().into());
+            metadata.mut_named_keys_schema().push(named_key_schema);
+        }
+
+        metadata
+    }
+}
+
+impl TryFrom<state::ContractMetadata> for ContractMetadata {
+    type Error = ParsingError;
+    fn try_from(mut value: state::ContractMetadata) -> Result<Self, Self::Error> {
+        let source_hash = if value.get_source_hash().is_empty() {
+            None
+        } else {
+            let source_hash = value
+                .get_source_hash()
+                .try_into()
+                .map_err(|_| ParsingError::from("Unable to parse contract source hash"))?;
+            Some(source_hash)
+        };
+
+        let mut entry_point_docs = EntryPointDocs::new();
+        for mut entry_point_doc in value.take_entry_point_docs().into_iter() {
+            entry_point_docs.insert(
+                entry_point_doc.take_entry_point(),
+                entry_point_doc.take_doc(),
+            );
+        }
+
+        let mut named_keys_schema = NamedKeysSchema::new();
+        for mut named_key_schema in value.take_named_keys_schema().into_iter() {
+            named_keys_schema.insert(
+                named_key_schema.take_name(),
+                named_key_schema.take_cl_type().try_into()?,
+            );
+        }
+
+        Ok(ContractMetadata::new(
+            value.take_name(),
+            value.take_description(),
+            source_hash,
+            entry_point_docs,
+            named_keys_schema,
+        ))
+    }
+}
+
 impl From<ContractVersionKey> for state::ContractVersionKey {
     fn from(version: ContractVersionKey) -> Self {
         let mut contract_version_key = state::ContractVersionKey::new();
@@ -14,6 +14,7 @@ include!(concat!(
     env!("OUT_DIR"),
     "/../../../../generated_protobuf/transforms.rs"
 ));
+pub mod journal;
 pub mod mappings;
 
 use std::{
@@ -30,7 +31,10 @@ use grpc::{Error as GrpcError, RequestOptions, ServerBuilder, SingleResponse};
 use log::{info, warn, Level};
 
 use engine_core::engine_state::{
-    execute_request::ExecuteRequest,
+    conflicts::DeployConflicts,
+    estimate::EstimateRequest,
+    execute_request::{ExecuteRequest, ExecutionMode},
+    execution_result::SequentialExecutionResult,
     genesis::GenesisResult,
     query::{QueryRequest, QueryResult},
     run_genesis_request::RunGenesisRequest,
@@ -38,17 +42,20 @@ use engine_core::engine_state::{
     EngineState, Error as EngineError,
 };
 use engine_shared::{
-    logging::{self, log_duration},
+    logging::{self, log_duration, log_metric},
     newtypes::{Blake2bHash, CorrelationId},
 };
 use engine_storage::global_state::{CommitResult, StateProvider};
-use types::{bytesrepr::ToBytes, ProtocolVersion};
+use types::{bytesrepr::ToBytes, BlockTime, ContractHash, ProtocolVersion};
 
 use self::{
     ipc::{
         BidStateRequest, BidStateResponse, CommitRequest, CommitResponse, DistributeRewardsRequest,
-        DistributeRewardsResponse, ExecuteResponse, GenesisResponse, QueryResponse, SlashRequest,
-        SlashResponse, UnbondPayoutRequest, UnbondPayoutResponse, UpgradeRequest, UpgradeResponse,
+        DistributeRewardsResponse, EstimateResponse, ExecuteResponse, GenesisResponse,
+        GetContractAbiRequest, GetContractAbiResponse, ProofOfStakeStepRequest,
+        ProofOfStakeStepResponse, QueryResponse, ReadTotalSupplyRequest, ReadTotalSupplyResponse,
+        SlashRequest, SlashResponse, UnbondPayoutRequest, UnbondPayoutResponse, UpgradeRequest,
+        UpgradeResponse,
     },
     ipc_grpc::{ExecutionEngineService, ExecutionEngineServiceServer},
     mappings::{ParsingError, TransformMap},
@@ -56,15 +63,26 @@ use self::{
 
 const METRIC_DURATION_COMMIT: &str = "commit_duration";
 const METRIC_DURATION_EXEC: &str = "exec_duration";
+const METRIC_DURATION_ESTIMATE: &str = "estimate_duration";
 const METRIC_DURATION_QUERY: &str = "query_duration";
+const METRIC_DURATION_READ_TOTAL_SUPPLY: &str = "read_total_supply_duration";
+const METRIC_DURATION_GET_CONTRACT_ABI: &str = "get_contract_abi_duration";
 const METRIC_DURATION_GENESIS: &str = "genesis_duration";
 const METRIC_DURATION_UPGRADE: &str = "upgrade_duration";
+const METRIC_DURATION_RUN_AUCTION: &str = "run_auction_duration";
+const METRIC_DURATION_MINT_SEIGNIORAGE: &str = "mint_seigniorage_duration";
+const METRIC_MODULE_CACHE_HIT_RATE: &str = "module_cache_hit_rate";
 
 const TAG_RESPONSE_COMMIT: &str = "commit_response";
 const TAG_RESPONSE_EXEC: &str = "exec_response";
+const TAG_RESPONSE_ESTIMATE: &str = "estimate_response";
 const TAG_RESPONSE_QUERY: &str = "query_response";
+const TAG_RESPONSE_READ_TOTAL_SUPPLY: &str = "read_total_supply_response";
+const TAG_RESPONSE_GET_CONTRACT_ABI: &str = "get_contract_abi_response";
 const TAG_RESPONSE_GENESIS: &str = "genesis_response";
 const TAG_RESPONSE_UPGRADE: &str = "upgrade_response";
+const TAG_RESPONSE_RUN_AUCTION: &str = "run_auction_response";
+const TAG_RESPONSE_MINT_SEIGNIORAGE: &str = "mint_seigniorage_response";
 
 const UNIMPLEMENTED: &str = "unimplemented";
 
@@ -77,7 +95,7 @@ const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 // (outer layer) leading to cleaner design.
 impl<S> ExecutionEngineService for EngineState<S>
 where
-    S: StateProvider,
+    S: StateProvider + Sync,
     EngineError: From<S::Error>,
     S::Error: Into<engine_core::execution::Error> + Debug,
 {
@@ -162,6 +180,122 @@ where
         SingleResponse::completed(response)
     }
 
+    fn read_total_supply(
+        &self,
+        _request_options: RequestOptions,
+        mut read_total_supply_request: ReadTotalSupplyRequest,
+    ) -> SingleResponse<ReadTotalSupplyResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let protocol_version = read_total_supply_request.take_protocol_version().into();
+
+        let state_hash: Blake2bHash = match read_total_supply_request.get_state_hash().try_into() {
+            Ok(hash) => hash,
+            Err(_) => {
+                let log_message = "Could not parse state hash".to_string();
+                warn!("{}", log_message);
+                let mut result = ReadTotalSupplyResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_READ_TOTAL_SUPPLY,
+                    TAG_RESPONSE_READ_TOTAL_SUPPLY,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+
+        let mut response = ReadTotalSupplyResponse::new();
+        match self.read_total_supply(correlation_id, protocol_version, state_hash) {
+            Ok(Some(total_supply)) => {
+                info!("total supply read; correlation_id: {}", correlation_id);
+                response.set_success(total_supply.into());
+            }
+            Ok(None) => {
+                info!("Root not found");
+                response.mut_missing_state().set_hash(state_hash.to_vec());
+            }
+            Err(error) => {
+                let log_message = format!("{:?}", error);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_READ_TOTAL_SUPPLY,
+            TAG_RESPONSE_READ_TOTAL_SUPPLY,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn get_contract_abi(
+        &self,
+        _request_options: RequestOptions,
+        get_contract_abi_request: GetContractAbiRequest,
+    ) -> SingleResponse<GetContractAbiResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let parsed_hashes = get_contract_abi_request
+            .get_state_hash()
+            .try_into()
+            .map_err(|_| "Could not parse state hash")
+            .and_then(|state_hash: Blake2bHash| {
+                get_contract_abi_request
+                    .get_contract_hash()
+                    .try_into()
+                    .map(|contract_hash: ContractHash| (state_hash, contract_hash))
+                    .map_err(|_| "Could not parse contract hash")
+            });
+        let (state_hash, contract_hash) = match parsed_hashes {
+            Ok(hashes) => hashes,
+            Err(log_message) => {
+                warn!("{}", log_message);
+                let mut result = GetContractAbiResponse::new();
+                result.set_failure(log_message.to_string());
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_GET_CONTRACT_ABI,
+                    TAG_RESPONSE_GET_CONTRACT_ABI,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+
+        let mut response = GetContractAbiResponse::new();
+        match self.get_contract_abi(correlation_id, state_hash, contract_hash) {
+            Ok(Some(abi)) => {
+                info!("contract abi read; correlation_id: {}", correlation_id);
+                response.set_success(abi);
+            }
+            Ok(None) => {
+                info!("Root not found");
+                response.mut_missing_state().set_hash(state_hash.to_vec());
+            }
+            Err(error) => {
+                let log_message = format!("{:?}", error);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_CONTRACT_ABI,
+            TAG_RESPONSE_GET_CONTRACT_ABI,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
     fn execute(
         &self,
         _request_options: RequestOptions,
@@ -179,6 +313,58 @@ where
 
         let mut exec_response = ExecuteResponse::new();
 
+        if exec_request.execution_mode == ExecutionMode::Sequential {
+            let parent_state_hash = exec_request.parent_state_hash;
+            match self.run_execute_sequential(correlation_id, exec_request) {
+                Ok(SequentialExecutionResult::Success {
+                    execution_results,
+                    effect,
+                    post_state_hash,
+                    bonded_validators,
+                }) => {
+                    info!(
+                        "deploys executed sequentially; new state hash is: {:?}",
+                        post_state_hash
+                    );
+                    let exec_result = exec_response.mut_success();
+                    let protobuf_results_iter = execution_results.into_iter().map(Into::into);
+                    exec_result.set_deploy_results(FromIterator::from_iter(protobuf_results_iter));
+                    exec_result.set_effect(effect.into());
+                    let bonds = bonded_validators.into_iter().map(Into::into).collect();
+                    let commit_result = exec_result.mut_commit_result();
+                    commit_result.set_poststate_hash(post_state_hash.to_vec());
+                    commit_result.set_bonded_validators(bonds);
+                }
+                Ok(SequentialExecutionResult::RootNotFound) => {
+                    info!("deploy results error: RootNotFound");
+                    exec_response
+                        .mut_missing_parent()
+                        .set_hash(parent_state_hash.to_vec());
+                }
+                Ok(sequential_execution_result) => {
+                    warn!("{:?}", sequential_execution_result);
+                    exec_response
+                        .mut_failed_commit()
+                        .set_message(format!("{:?}", sequential_execution_result));
+                }
+                Err(error) => {
+                    warn!("Error {:?} when executing deploys sequentially", error);
+                    exec_response
+                        .mut_failed_commit()
+                        .set_message(error.to_string());
+                }
+            }
+            log_duration(
+                correlation_id,
+                METRIC_DURATION_EXEC,
+                TAG_RESPONSE_EXEC,
+                start.elapsed(),
+            );
+            return SingleResponse::completed(exec_response);
+        }
+
+        let parent_state_hash = exec_request.parent_state_hash;
+        let protocol_version = exec_request.protocol_version;
         let results = match self.run_execute(correlation_id, exec_request) {
             Ok(results) => results,
             Err(error) => {
@@ -194,10 +380,33 @@ where
             }
         };
 
+        let finalization_keys =
+            match self.finalization_keys(correlation_id, protocol_version, parent_state_hash) {
+                Ok(finalization_keys) => finalization_keys.unwrap_or_default(),
+                Err(error) => {
+                    // Without them, every pair of deploys is reported as conflicting.
+                    warn!("Error {:?} when reading the finalization keys", error);
+                    BTreeMap::new()
+                }
+            };
+        let deploy_conflicts = DeployConflicts::new(
+            results.iter().map(|(result, _)| result.effect()),
+            &finalization_keys,
+        );
+        let exec_result = exec_response.mut_success();
+        let commuting_deploys = deploy_conflicts.commuting_pairs().iter().copied();
+        exec_result.set_commuting_deploys(commuting_deploys.map(Into::into).collect());
+        let conflicting_deploys = deploy_conflicts.conflicting_pairs().iter().copied();
+        exec_result.set_conflicting_deploys(conflicting_deploys.map(Into::into).collect());
         let protobuf_results_iter = results.into_iter().map(Into::into);
-        exec_response
-            .mut_success()
-            .set_deploy_results(FromIterator::from_iter(protobuf_results_iter));
+        exec_result.set_deploy_results(FromIterator::from_iter(protobuf_results_iter));
+        log_metric(
+            correlation_id,
+            METRIC_MODULE_CACHE_HIT_RATE,
+            TAG_RESPONSE_EXEC,
+            "hit_rate",
+            self.module_cache().hit_rate(),
+        );
         log_duration(
             correlation_id,
             METRIC_DURATION_EXEC,
@@ -207,6 +416,58 @@ where
         SingleResponse::completed(exec_response)
     }
 
+    fn estimate(
+        &self,
+        _request_options: RequestOptions,
+        estimate_request: ipc::EstimateRequest,
+    ) -> SingleResponse<EstimateResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut estimate_response = EstimateResponse::new();
+
+        let estimate_request: EstimateRequest = match estimate_request.try_into() {
+            Ok(estimate_request) => estimate_request,
+            Err(error) => {
+                let log_message = format!("{}", error);
+                warn!("{}", log_message);
+                estimate_response.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_ESTIMATE,
+                    TAG_RESPONSE_ESTIMATE,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(estimate_response);
+            }
+        };
+
+        match self.run_estimate(correlation_id, estimate_request) {
+            Ok(estimate_result) => {
+                info!(
+                    "deploy estimated; correlation_id: {} total cost: {}",
+                    correlation_id,
+                    estimate_result.total_cost()
+                );
+                estimate_response.set_success(estimate_result.into());
+            }
+            Err(error) => {
+                info!("estimate error: RootNotFound");
+                estimate_response
+                    .mut_missing_parent()
+                    .set_hash(error.to_vec());
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_ESTIMATE,
+            TAG_RESPONSE_ESTIMATE,
+            start.elapsed(),
+        );
+        SingleResponse::completed(estimate_response)
+    }
+
     fn commit(
         &self,
         _request_options: RequestOptions,
@@ -484,6 +745,112 @@ where
     ) -> SingleResponse<UnbondPayoutResponse> {
         SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()))
     }
+
+    fn run_auction(
+        &self,
+        _request_options: RequestOptions,
+        run_auction_request: ProofOfStakeStepRequest,
+    ) -> SingleResponse<ProofOfStakeStepResponse> {
+        run_proof_of_stake_step(
+            run_auction_request,
+            "running auction",
+            METRIC_DURATION_RUN_AUCTION,
+            TAG_RESPONSE_RUN_AUCTION,
+            |correlation_id, protocol_version, pre_state_hash, block_time| {
+                self.run_auction(correlation_id, protocol_version, pre_state_hash, block_time)
+            },
+        )
+    }
+
+    fn mint_seigniorage(
+        &self,
+        _request_options: RequestOptions,
+        mint_seigniorage_request: ProofOfStakeStepRequest,
+    ) -> SingleResponse<ProofOfStakeStepResponse> {
+        run_proof_of_stake_step(
+            mint_seigniorage_request,
+            "minting seigniorage",
+            METRIC_DURATION_MINT_SEIGNIORAGE,
+            TAG_RESPONSE_MINT_SEIGNIORAGE,
+            |correlation_id, protocol_version, pre_state_hash, block_time| {
+                self.mint_seigniorage(correlation_id, protocol_version, pre_state_hash, block_time)
+            },
+        )
+    }
+}
+
+/// Runs a Proof of Stake step at the end of an era with `run_step`, and builds the response from
+/// its commit result.  `step_name` describes the step in log messages.
+fn run_proof_of_stake_step<F>(
+    mut request: ProofOfStakeStepRequest,
+    step_name: &str,
+    metric: &str,
+    tag: &str,
+    run_step: F,
+) -> SingleResponse<ProofOfStakeStepResponse>
+where
+    F: FnOnce(
+        CorrelationId,
+        ProtocolVersion,
+        Blake2bHash,
+        BlockTime,
+    ) -> Result<CommitResult, EngineError>,
+{
+    let start = Instant::now();
+    let correlation_id = CorrelationId::new();
+
+    let protocol_version = request.take_protocol_version().into();
+    let block_time = BlockTime::new(request.get_block_time());
+
+    let pre_state_hash: Blake2bHash = match request.get_parent_state_hash().try_into() {
+        Ok(hash) => hash,
+        Err(_) => {
+            let error_message = "Could not parse parent state hash".to_string();
+            warn!("{}", error_message);
+            let mut response = ProofOfStakeStepResponse::new();
+            response.mut_error().set_message(error_message);
+            log_duration(correlation_id, metric, tag, start.elapsed());
+            return SingleResponse::completed(response);
+        }
+    };
+
+    let response = {
+        let mut ret = ProofOfStakeStepResponse::new();
+
+        match run_step(correlation_id, protocol_version, pre_state_hash, block_time) {
+            Ok(CommitResult::Success {
+                state_root,
+                bonded_validators,
+            }) => {
+                info!(
+                    "finished {}; new state hash is: {:?}",
+                    step_name, state_root
+                );
+                let bonds = bonded_validators.into_iter().map(Into::into).collect();
+                let commit_result = ret.mut_success();
+                commit_result.set_poststate_hash(state_root.to_vec());
+                commit_result.set_bonded_validators(bonds);
+            }
+            Ok(CommitResult::RootNotFound) => {
+                warn!("RootNotFound");
+                ret.mut_missing_parent().set_hash(pre_state_hash.to_vec());
+            }
+            Ok(commit_result) => {
+                warn!("{:?}", commit_result);
+                ret.mut_error().set_message(format!("{:?}", commit_result));
+            }
+            Err(error) => {
+                warn!("Error {:?} when {}", error, step_name);
+                ret.mut_error().set_message(error.to_string());
+            }
+        }
+
+        ret
+    };
+
+    log_duration(correlation_id, metric, tag, start.elapsed());
+
+    SingleResponse::completed(response)
 }
 
 // Helper method which returns single DeployResult that is set to be a
@@ -12,7 +12,13 @@ use std::{
 
 use clap::{App, Arg, ArgMatches};
 use dirs::home_dir;
-use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_core::{
+    engine_state::{
+        engine_config::{DEFAULT_MAX_DEPLOY_TTL, DEFAULT_MODULE_CACHE_CAPACITY},
+        EngineConfig, EngineState,
+    },
+    execution::WasmBackend,
+};
 use lmdb::DatabaseFlags;
 use log::{error, info, Level, LevelFilter};
 
@@ -26,7 +32,10 @@ use engine_storage::{
     trie_store::lmdb::LmdbTrieStore,
 };
 
-use casperlabs_engine_grpc_server::engine_server;
+use casperlabs_engine_grpc_server::engine_server::{
+    self,
+    journal::{Journal, JournalingService},
+};
 use engine_storage::protocol_data_store::lmdb::LmdbProtocolDataStore;
 
 // exe / proc
@@ -96,6 +105,27 @@ const ARG_THREAD_COUNT_VALUE: &str = "NUM";
 const ARG_THREAD_COUNT_HELP: &str = "Worker thread count";
 const ARG_THREAD_COUNT_EXPECT: &str = "expected valid thread count";
 
+// deploy thread count
+const ARG_DEPLOY_THREAD_COUNT: &str = "deploy-threads";
+const ARG_DEPLOY_THREAD_COUNT_DEFAULT: &str = "1";
+const ARG_DEPLOY_THREAD_COUNT_VALUE: &str = "NUM";
+const ARG_DEPLOY_THREAD_COUNT_HELP: &str = "Deploy worker thread count per execute request";
+const ARG_DEPLOY_THREAD_COUNT_EXPECT: &str = "expected valid deploy thread count";
+
+// module cache
+const ARG_MODULE_CACHE_CAPACITY: &str = "module-cache-capacity";
+const ARG_MODULE_CACHE_CAPACITY_VALUE: &str = "NUM";
+const ARG_MODULE_CACHE_CAPACITY_HELP: &str =
+    "Sets the max number of contract modules to keep deserialized, or 0 to disable the cache";
+const ARG_MODULE_CACHE_CAPACITY_EXPECT: &str = "expected valid module cache capacity";
+
+// wasm backend
+const ARG_WASM_BACKEND: &str = "wasm-backend";
+const ARG_WASM_BACKEND_DEFAULT: &str = "wasmi";
+const ARG_WASM_BACKEND_VALUE: &str = "BACKEND";
+const ARG_WASM_BACKEND_HELP: &str =
+    "Sets the Wasm execution backend: wasmi, or wasmtime if built with the wasmtime-backend feature";
+
 // use system contracts
 const ARG_USE_SYSTEM_CONTRACTS: &str = "use-system-contracts";
 const ARG_USE_SYSTEM_CONTRACTS_SHORT: &str = "z";
@@ -107,6 +137,25 @@ const ARG_ENABLE_BONDING: &str = "enable-bonding";
 const ARG_ENABLE_BONDING_SHORT: &str = "b";
 const ARG_ENABLE_BONDING_HELP: &str = "Enable bonding";
 
+// replay protection
+const ARG_ENABLE_REPLAY_PROTECTION: &str = "enable-replay-protection";
+const ARG_ENABLE_REPLAY_PROTECTION_HELP: &str =
+    "Record the hashes of deploys with a TTL in their account until they expire, rejecting replays";
+
+// max deploy TTL
+const ARG_MAX_DEPLOY_TTL: &str = "max-deploy-ttl";
+const ARG_MAX_DEPLOY_TTL_VALUE: &str = "MILLIS";
+const ARG_MAX_DEPLOY_TTL_HELP: &str =
+    "Sets the longest TTL in milliseconds a deploy may have; deploys with a longer TTL are rejected";
+const ARG_MAX_DEPLOY_TTL_EXPECT: &str = "expected valid max deploy TTL";
+
+// journal
+const ARG_JOURNAL_DIR: &str = "journal-dir";
+const ARG_JOURNAL_DIR_VALUE: &str = "DIR";
+const ARG_JOURNAL_DIR_HELP: &str =
+    "Records every request, and the response to it, to the given directory for later replay";
+const JOURNAL_DIR_EXPECT: &str = "Could not open journal directory";
+
 // runnable
 const SIGINT_HANDLE_EXPECT: &str = "Error setting Ctrl-C handler";
 const RUNNABLE_CHECK_INTERVAL_SECONDS: u64 = 3;
@@ -135,7 +184,16 @@ fn main() {
 
     let engine_config: EngineConfig = get_engine_config(&arg_matches);
 
-    let _server = get_grpc_server(&socket, data_dir, map_size, thread_count, engine_config);
+    let journal = get_journal(&arg_matches);
+
+    let _server = get_grpc_server(
+        &socket,
+        data_dir,
+        map_size,
+        thread_count,
+        engine_config,
+        journal,
+    );
 
     log_listening_message(&socket);
 
@@ -227,6 +285,29 @@ fn get_args() -> ArgMatches<'static> {
                 .value_name(ARG_THREAD_COUNT_VALUE)
                 .help(ARG_THREAD_COUNT_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_DEPLOY_THREAD_COUNT)
+                .long(ARG_DEPLOY_THREAD_COUNT)
+                .takes_value(true)
+                .default_value(ARG_DEPLOY_THREAD_COUNT_DEFAULT)
+                .value_name(ARG_DEPLOY_THREAD_COUNT_VALUE)
+                .help(ARG_DEPLOY_THREAD_COUNT_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MODULE_CACHE_CAPACITY)
+                .long(ARG_MODULE_CACHE_CAPACITY)
+                .takes_value(true)
+                .value_name(ARG_MODULE_CACHE_CAPACITY_VALUE)
+                .help(ARG_MODULE_CACHE_CAPACITY_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_WASM_BACKEND)
+                .long(ARG_WASM_BACKEND)
+                .takes_value(true)
+                .default_value(ARG_WASM_BACKEND_DEFAULT)
+                .value_name(ARG_WASM_BACKEND_VALUE)
+                .help(ARG_WASM_BACKEND_HELP),
+        )
         .arg(
             Arg::with_name(ARG_USE_SYSTEM_CONTRACTS)
                 .short(ARG_USE_SYSTEM_CONTRACTS_SHORT)
@@ -239,6 +320,25 @@ fn get_args() -> ArgMatches<'static> {
                 .long(ARG_ENABLE_BONDING)
                 .help(ARG_ENABLE_BONDING_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_ENABLE_REPLAY_PROTECTION)
+                .long(ARG_ENABLE_REPLAY_PROTECTION)
+                .help(ARG_ENABLE_REPLAY_PROTECTION_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_DEPLOY_TTL)
+                .long(ARG_MAX_DEPLOY_TTL)
+                .takes_value(true)
+                .value_name(ARG_MAX_DEPLOY_TTL_VALUE)
+                .help(ARG_MAX_DEPLOY_TTL_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_JOURNAL_DIR)
+                .long(ARG_JOURNAL_DIR)
+                .takes_value(true)
+                .value_name(ARG_JOURNAL_DIR_VALUE)
+                .help(ARG_JOURNAL_DIR_HELP),
+        )
         .arg(
             Arg::with_name(ARG_SOCKET)
                 .required(true)
@@ -299,14 +399,63 @@ fn get_thread_count(arg_matches: &ArgMatches) -> usize {
         .expect(ARG_THREAD_COUNT_EXPECT)
 }
 
+fn get_deploy_thread_count(arg_matches: &ArgMatches) -> usize {
+    arg_matches
+        .value_of(ARG_DEPLOY_THREAD_COUNT)
+        .map(str::parse)
+        .expect(ARG_DEPLOY_THREAD_COUNT_EXPECT)
+        .expect(ARG_DEPLOY_THREAD_COUNT_EXPECT)
+}
+
+fn get_module_cache_capacity(arg_matches: &ArgMatches) -> usize {
+    arg_matches
+        .value_of(ARG_MODULE_CACHE_CAPACITY)
+        .map_or(Ok(DEFAULT_MODULE_CACHE_CAPACITY), usize::from_str)
+        .expect(ARG_MODULE_CACHE_CAPACITY_EXPECT)
+}
+
+fn get_max_deploy_ttl(arg_matches: &ArgMatches) -> u64 {
+    arg_matches
+        .value_of(ARG_MAX_DEPLOY_TTL)
+        .map_or(Ok(DEFAULT_MAX_DEPLOY_TTL), u64::from_str)
+        .expect(ARG_MAX_DEPLOY_TTL_EXPECT)
+}
+
+fn get_wasm_backend(arg_matches: &ArgMatches) -> WasmBackend {
+    let wasm_backend = arg_matches
+        .value_of(ARG_WASM_BACKEND)
+        .expect("should have default value if not explicitly set");
+    WasmBackend::from_str(wasm_backend).unwrap_or_else(|error| panic!("{}", error))
+}
+
 /// Returns an [`EngineConfig`].
 fn get_engine_config(arg_matches: &ArgMatches) -> EngineConfig {
     // feature flags go here
     let use_system_contracts = arg_matches.is_present(ARG_USE_SYSTEM_CONTRACTS);
     let enable_bonding = arg_matches.is_present(ARG_ENABLE_BONDING);
+    let enable_replay_protection = arg_matches.is_present(ARG_ENABLE_REPLAY_PROTECTION);
+    let max_deploy_ttl = get_max_deploy_ttl(arg_matches);
+    let deploy_thread_count = get_deploy_thread_count(arg_matches);
+    let module_cache_capacity = get_module_cache_capacity(arg_matches);
+    let wasm_backend = get_wasm_backend(arg_matches);
     EngineConfig::new()
         .with_use_system_contracts(use_system_contracts)
         .with_enable_bonding(enable_bonding)
+        .with_enable_replay_protection(enable_replay_protection)
+        .with_max_deploy_ttl(max_deploy_ttl)
+        .with_deploy_worker_count(deploy_thread_count)
+        .with_module_cache_capacity(module_cache_capacity)
+        .with_wasm_backend(wasm_backend)
+}
+
+/// Opens the journal to record requests to, if the journal-dir argument is given.
+fn get_journal(arg_matches: &ArgMatches) -> Option<Journal> {
+    arg_matches.value_of(ARG_JOURNAL_DIR).map(|dir| {
+        let journal = Journal::open(dir)
+            .unwrap_or_else(|error| panic!("{}: {:?}: {}", JOURNAL_DIR_EXPECT, dir, error));
+        info!("recording requests to journal {:?}", journal.dir());
+        journal
+    })
 }
 
 /// Builds and returns a gRPC server.
@@ -316,12 +465,20 @@ fn get_grpc_server(
     map_size: usize,
     thread_count: usize,
     engine_config: EngineConfig,
+    journal: Option<Journal>,
 ) -> grpc::Server {
     let engine_state = get_engine_state(data_dir, map_size, engine_config);
 
-    engine_server::new(socket.as_str(), thread_count, engine_state)
-        .build()
-        .expect(SERVER_START_EXPECT)
+    let server_builder = match journal {
+        Some(journal) => engine_server::new(
+            socket.as_str(),
+            thread_count,
+            JournalingService::new(engine_state, journal),
+        ),
+        None => engine_server::new(socket.as_str(), thread_count, engine_state),
+    };
+
+    server_builder.build().expect(SERVER_START_EXPECT)
 }
 
 /// Builds and returns engine global state
@@ -0,0 +1,368 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    sync::Arc,
+};
+
+use clap::{App, Arg, ArgMatches};
+use engine_core::{
+    engine_state::{
+        engine_config::{DEFAULT_MAX_DEPLOY_TTL, DEFAULT_MODULE_CACHE_CAPACITY},
+        EngineConfig, EngineState,
+    },
+    execution::WasmBackend,
+};
+use lmdb::DatabaseFlags;
+
+use engine_shared::os::get_page_size;
+use engine_storage::{
+    global_state::lmdb::LmdbGlobalState, protocol_data_store::lmdb::LmdbProtocolDataStore,
+    transaction_source::lmdb::LmdbEnvironment, trie_store::lmdb::LmdbTrieStore,
+};
+
+use casperlabs_engine_grpc_server::engine_server::journal::{self, Journal};
+
+const APP_NAME: &str = "CasperLabs Execution Engine Replay";
+const APP_ABOUT: &str = "Re-applies a journal of requests recorded by the Execution Engine Server \
+                         and reports the first response which differs from the recorded one";
+
+// journal
+const ARG_JOURNAL_DIR: &str = "journal-dir";
+const ARG_JOURNAL_DIR_HELP: &str =
+    "Path to the journal directory written by the server's --journal-dir option";
+const ARG_JOURNAL_DIR_EXPECT: &str = "journal-dir required";
+
+// data-dir / lmdb
+const ARG_DATA_DIR: &str = "data-dir";
+const ARG_DATA_DIR_SHORT: &str = "d";
+const ARG_DATA_DIR_VALUE: &str = "DIR";
+const ARG_DATA_DIR_HELP: &str =
+    "Sets the global_state directory holding the LMDB prestate the journal was recorded against.  \
+     It is copied before replaying, so it is left untouched.  If omitted, replay starts from an \
+     empty global state, so the journal should start with genesis";
+const REPLAY_DIR_PREFIX: &str = "casperlabs-engine-replay";
+const LMDB_ENVIRONMENT_EXPECT: &str = "Could not create LmdbEnvironment";
+const LMDB_TRIE_STORE_EXPECT: &str = "Could not create LmdbTrieStore";
+const LMDB_PROTOCOL_DATA_STORE_EXPECT: &str = "Could not create LmdbProtocolDataStore";
+const LMDB_GLOBAL_STATE_EXPECT: &str = "Could not create LmdbGlobalState";
+
+// pages / lmdb
+const ARG_PAGES: &str = "pages";
+const ARG_PAGES_SHORT: &str = "p";
+const ARG_PAGES_VALUE: &str = "NUM";
+const ARG_PAGES_HELP: &str = "Sets the max number of pages to use for lmdb's mmap";
+const GET_PAGES_EXPECT: &str = "Could not parse pages argument";
+// 750 GiB = 805306368000 bytes
+// page size on x86_64 linux = 4096 bytes
+// 805306368000 / 4096 = 196608000
+const DEFAULT_PAGES: usize = 196_608_000;
+
+// deploy thread count
+const ARG_DEPLOY_THREAD_COUNT: &str = "deploy-threads";
+const ARG_DEPLOY_THREAD_COUNT_DEFAULT: &str = "1";
+const ARG_DEPLOY_THREAD_COUNT_VALUE: &str = "NUM";
+const ARG_DEPLOY_THREAD_COUNT_HELP: &str = "Deploy worker thread count per execute request";
+
+// module cache
+const ARG_MODULE_CACHE_CAPACITY: &str = "module-cache-capacity";
+const ARG_MODULE_CACHE_CAPACITY_VALUE: &str = "NUM";
+const ARG_MODULE_CACHE_CAPACITY_HELP: &str =
+    "Sets the max number of contract modules to keep deserialized, or 0 to disable the cache";
+
+// wasm backend
+const ARG_WASM_BACKEND: &str = "wasm-backend";
+const ARG_WASM_BACKEND_DEFAULT: &str = "wasmi";
+const ARG_WASM_BACKEND_VALUE: &str = "BACKEND";
+const ARG_WASM_BACKEND_HELP: &str =
+    "Sets the Wasm execution backend: wasmi, or wasmtime if built with the wasmtime-backend feature";
+
+// use system contracts
+const ARG_USE_SYSTEM_CONTRACTS: &str = "use-system-contracts";
+const ARG_USE_SYSTEM_CONTRACTS_SHORT: &str = "z";
+const ARG_USE_SYSTEM_CONTRACTS_HELP: &str =
+    "Use system contracts instead of host-side logic for Mint, Proof of Stake and Standard Payment";
+
+// Highway
+const ARG_ENABLE_BONDING: &str = "enable-bonding";
+const ARG_ENABLE_BONDING_SHORT: &str = "b";
+const ARG_ENABLE_BONDING_HELP: &str = "Enable bonding";
+
+// replay protection
+const ARG_ENABLE_REPLAY_PROTECTION: &str = "enable-replay-protection";
+const ARG_ENABLE_REPLAY_PROTECTION_HELP: &str =
+    "Record the hashes of deploys with a TTL in their account until they expire, rejecting replays";
+
+// max deploy TTL
+const ARG_MAX_DEPLOY_TTL: &str = "max-deploy-ttl";
+const ARG_MAX_DEPLOY_TTL_VALUE: &str = "MILLIS";
+const ARG_MAX_DEPLOY_TTL_HELP: &str =
+    "Sets the longest TTL in milliseconds a deploy may have; deploys with a longer TTL are rejected";
+
+const EXIT_DIVERGENCE: i32 = 1;
+const EXIT_FAILURE: i32 = 2;
+
+fn main() {
+    let arg_matches = get_args();
+
+    match replay(&arg_matches) {
+        Ok(None) => (),
+        Ok(Some(divergence)) => {
+            println!("{}", divergence);
+            process::exit(EXIT_DIVERGENCE);
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+/// Replays the journal, returning a description of the first divergence if any.
+///
+/// The scratch directory is removed before returning, also on error.
+fn replay(arg_matches: &ArgMatches) -> Result<Option<String>, String> {
+    let journal_dir = arg_matches
+        .value_of(ARG_JOURNAL_DIR)
+        .expect(ARG_JOURNAL_DIR_EXPECT);
+    let entries = Journal::read_entries(journal_dir)
+        .map_err(|error| format!("failed to read journal {:?}: {}", journal_dir, error))?;
+
+    let engine_config = get_engine_config(arg_matches)?;
+    let replay_dir = get_replay_dir(arg_matches)?;
+    let engine_state = get_engine_state(
+        replay_dir.path().to_path_buf(),
+        get_map_size(arg_matches),
+        engine_config,
+    );
+
+    let mut compared = 0;
+    for entry in &entries {
+        let replayed = journal::replay_entry(&engine_state, entry).map_err(|error| {
+            format!(
+                "failed to replay request {} ({}): {}",
+                entry.sequence, entry.method, error
+            )
+        })?;
+
+        let recorded = match &entry.response {
+            Some(recorded) => recorded,
+            None => continue,
+        };
+
+        let difference =
+            journal::compare_responses(entry.method, recorded, &replayed).map_err(|error| {
+                format!(
+                    "failed to parse responses to request {} ({}): {}",
+                    entry.sequence, entry.method, error
+                )
+            })?;
+        compared += 1;
+
+        if let Some(difference) = difference {
+            return Ok(Some(format!(
+                "divergence at request {} ({}): {}",
+                entry.sequence, entry.method, difference
+            )));
+        }
+    }
+
+    println!(
+        "replayed {} requests, {} responses matched",
+        entries.len(),
+        compared
+    );
+    Ok(None)
+}
+
+/// A scratch directory which is removed along with its contents when dropped.
+struct ReplayDir(PathBuf);
+
+impl ReplayDir {
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ReplayDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Gets command line arguments
+fn get_args() -> ArgMatches<'static> {
+    App::new(APP_NAME)
+        .version(env!("CARGO_PKG_VERSION"))
+        .about(APP_ABOUT)
+        .arg(
+            Arg::with_name(ARG_DATA_DIR)
+                .short(ARG_DATA_DIR_SHORT)
+                .long(ARG_DATA_DIR)
+                .value_name(ARG_DATA_DIR_VALUE)
+                .help(ARG_DATA_DIR_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_PAGES)
+                .short(ARG_PAGES_SHORT)
+                .long(ARG_PAGES)
+                .value_name(ARG_PAGES_VALUE)
+                .help(ARG_PAGES_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_DEPLOY_THREAD_COUNT)
+                .long(ARG_DEPLOY_THREAD_COUNT)
+                .takes_value(true)
+                .default_value(ARG_DEPLOY_THREAD_COUNT_DEFAULT)
+                .value_name(ARG_DEPLOY_THREAD_COUNT_VALUE)
+                .help(ARG_DEPLOY_THREAD_COUNT_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MODULE_CACHE_CAPACITY)
+                .long(ARG_MODULE_CACHE_CAPACITY)
+                .takes_value(true)
+                .value_name(ARG_MODULE_CACHE_CAPACITY_VALUE)
+                .help(ARG_MODULE_CACHE_CAPACITY_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_WASM_BACKEND)
+                .long(ARG_WASM_BACKEND)
+                .takes_value(true)
+                .default_value(ARG_WASM_BACKEND_DEFAULT)
+                .value_name(ARG_WASM_BACKEND_VALUE)
+                .help(ARG_WASM_BACKEND_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_USE_SYSTEM_CONTRACTS)
+                .short(ARG_USE_SYSTEM_CONTRACTS_SHORT)
+                .long(ARG_USE_SYSTEM_CONTRACTS)
+                .help(ARG_USE_SYSTEM_CONTRACTS_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_ENABLE_BONDING)
+                .short(ARG_ENABLE_BONDING_SHORT)
+                .long(ARG_ENABLE_BONDING)
+                .help(ARG_ENABLE_BONDING_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_ENABLE_REPLAY_PROTECTION)
+                .long(ARG_ENABLE_REPLAY_PROTECTION)
+                .help(ARG_ENABLE_REPLAY_PROTECTION_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_DEPLOY_TTL)
+                .long(ARG_MAX_DEPLOY_TTL)
+                .takes_value(true)
+                .value_name(ARG_MAX_DEPLOY_TTL_VALUE)
+                .help(ARG_MAX_DEPLOY_TTL_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_JOURNAL_DIR)
+                .required(true)
+                .help(ARG_JOURNAL_DIR_HELP)
+                .index(1),
+        )
+        .get_matches()
+}
+
+/// Creates a scratch directory to replay in, holding a copy of the LMDB files of the data-dir
+/// argument if given.
+fn get_replay_dir(arg_matches: &ArgMatches) -> Result<ReplayDir, String> {
+    let path = std::env::temp_dir().join(format!("{}-{}", REPLAY_DIR_PREFIX, process::id()));
+    fs::create_dir_all(&path).map_err(|error| format!("failed to create {:?}: {}", path, error))?;
+    let replay_dir = ReplayDir(path);
+
+    if let Some(data_dir) = arg_matches.value_of(ARG_DATA_DIR) {
+        let dir_entries = fs::read_dir(data_dir)
+            .map_err(|error| format!("failed to read {:?}: {}", data_dir, error))?;
+        for dir_entry in dir_entries {
+            let path = dir_entry
+                .map_err(|error| format!("failed to read {:?}: {}", data_dir, error))?
+                .path();
+            if !path.is_file() {
+                continue;
+            }
+            let target = replay_dir
+                .path()
+                .join(path.file_name().expect("should have file name"));
+            fs::copy(&path, &target)
+                .map_err(|error| format!("failed to copy {:?}: {}", path, error))?;
+        }
+    }
+
+    Ok(replay_dir)
+}
+
+///  Parses pages argument and returns map size
+fn get_map_size(arg_matches: &ArgMatches) -> usize {
+    let page_size = get_page_size().unwrap();
+    let pages = arg_matches
+        .value_of(ARG_PAGES)
+        .map_or(Ok(DEFAULT_PAGES), usize::from_str)
+        .expect(GET_PAGES_EXPECT);
+    page_size * pages
+}
+
+/// Returns an [`EngineConfig`] matching the one the server ran with.
+fn get_engine_config(arg_matches: &ArgMatches) -> Result<EngineConfig, String> {
+    let use_system_contracts = arg_matches.is_present(ARG_USE_SYSTEM_CONTRACTS);
+    let enable_bonding = arg_matches.is_present(ARG_ENABLE_BONDING);
+    let enable_replay_protection = arg_matches.is_present(ARG_ENABLE_REPLAY_PROTECTION);
+    let max_deploy_ttl = arg_matches
+        .value_of(ARG_MAX_DEPLOY_TTL)
+        .map_or(Ok(DEFAULT_MAX_DEPLOY_TTL), u64::from_str)
+        .map_err(|error| format!("invalid max deploy TTL: {}", error))?;
+    let deploy_thread_count = arg_matches
+        .value_of(ARG_DEPLOY_THREAD_COUNT)
+        .expect("should have default value if not explicitly set")
+        .parse()
+        .map_err(|error| format!("invalid deploy thread count: {}", error))?;
+    let module_cache_capacity = arg_matches
+        .value_of(ARG_MODULE_CACHE_CAPACITY)
+        .map_or(Ok(DEFAULT_MODULE_CACHE_CAPACITY), usize::from_str)
+        .map_err(|error| format!("invalid module cache capacity: {}", error))?;
+    let wasm_backend = arg_matches
+        .value_of(ARG_WASM_BACKEND)
+        .expect("should have default value if not explicitly set");
+    let wasm_backend = WasmBackend::from_str(wasm_backend)?;
+    Ok(EngineConfig::new()
+        .with_use_system_contracts(use_system_contracts)
+        .with_enable_bonding(enable_bonding)
+        .with_enable_replay_protection(enable_replay_protection)
+        .with_max_deploy_ttl(max_deploy_ttl)
+        .with_deploy_worker_count(deploy_thread_count)
+        .with_module_cache_capacity(module_cache_capacity)
+        .with_wasm_backend(wasm_backend))
+}
+
+/// Builds and returns engine global state
+fn get_engine_state(
+    data_dir: PathBuf,
+    map_size: usize,
+    engine_config: EngineConfig,
+) -> EngineState<LmdbGlobalState> {
+    let environment = {
+        let ret = LmdbEnvironment::new(&data_dir, map_size).expect(LMDB_ENVIRONMENT_EXPECT);
+        Arc::new(ret)
+    };
+
+    let trie_store = {
+        let ret = LmdbTrieStore::new(&environment, None, DatabaseFlags::empty())
+            .expect(LMDB_TRIE_STORE_EXPECT);
+        Arc::new(ret)
+    };
+
+    let protocol_data_store = {
+        let ret = LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty())
+            .expect(LMDB_PROTOCOL_DATA_STORE_EXPECT);
+        Arc::new(ret)
+    };
+
+    let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
+        .expect(LMDB_GLOBAL_STATE_EXPECT);
+
+    EngineState::new(global_state, engine_config)
+}
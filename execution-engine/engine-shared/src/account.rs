@@ -1,20 +1,32 @@
 mod action_thresholds;
 mod associated_keys;
+mod owner;
+mod recovery;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use types::{
     account::{
-        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        AccountHash, ActionType, AddKeyFailure, ContractAllowList, RecoveryConfig, RecoveryFailure,
+        RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure, Weight,
     },
     bytesrepr::{self, Error, FromBytes, ToBytes},
     contracts::NamedKeys,
-    AccessRights, URef,
+    AccessRights, BlockTime, ContractPackageHash, URef, U512,
 };
 
 pub use action_thresholds::ActionThresholds;
 pub use associated_keys::AssociatedKeys;
+pub use owner::AccountOwner;
+pub use recovery::PendingRecovery;
+
+/// Leads an [`Account`]'s serialized form and tags the layout of the fields
+/// which follow it. Any change to that layout needs a new version.
+///
+/// Accounts written before the layout was versioned lack it and end right
+/// after their action thresholds. They are stored under a `StoredValue` tag of
+/// their own and read with [`Account::from_legacy_bytes`].
+const ACCOUNT_FIELDS_VERSION_1: u8 = 1;
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct Account {
@@ -23,6 +35,12 @@ pub struct Account {
     main_purse: URef,
     associated_keys: AssociatedKeys,
     action_thresholds: ActionThresholds,
+    spending_limit: Option<U512>,
+    recovery_config: Option<RecoveryConfig>,
+    pending_recoveries: Vec<PendingRecovery>,
+    contract_allow_list: Option<ContractAllowList>,
+    executed_deploys: BTreeMap<[u8; 32], BlockTime>,
+    owner: Option<AccountOwner>,
 }
 
 impl Account {
@@ -39,6 +57,12 @@ impl Account {
             main_purse,
             associated_keys,
             action_thresholds,
+            spending_limit: None,
+            recovery_config: None,
+            pending_recoveries: Vec::new(),
+            contract_allow_list: None,
+            executed_deploys: BTreeMap::new(),
+            owner: None,
         }
     }
 
@@ -54,6 +78,43 @@ impl Account {
         )
     }
 
+    /// Creates an account owned by a contract package.
+    ///
+    /// The account has no associated keys of its own: a deploy for it is authorized by the
+    /// accounts holding a URef of the owner's group, see [`Account::with_members`].
+    pub fn create_contract_owned(
+        account: AccountHash,
+        main_purse: URef,
+        owner: AccountOwner,
+        action_thresholds: ActionThresholds,
+    ) -> Self {
+        let mut account = Account::new(
+            account,
+            NamedKeys::new(),
+            main_purse,
+            AssociatedKeys::default(),
+            action_thresholds,
+        );
+        account.owner = Some(owner);
+        account
+    }
+
+    /// Returns the account as seen by a deploy signed by `members`, the signing accounts which
+    /// currently hold a URef of the owner's group.
+    ///
+    /// Each member is an associated key with a weight of 1, however many of the group's URefs it
+    /// holds.
+    pub fn with_members(&self, members: &BTreeSet<AccountHash>) -> Result<Account, AddKeyFailure> {
+        let mut associated_keys = AssociatedKeys::default();
+        for member in members {
+            associated_keys.add_key(*member, Weight::new(1))?;
+        }
+        Ok(Account {
+            associated_keys,
+            ..self.clone()
+        })
+    }
+
     pub fn named_keys_append(&mut self, keys: &mut NamedKeys) {
         self.named_keys.append(keys);
     }
@@ -87,11 +148,269 @@ impl Account {
         &self.action_thresholds
     }
 
+    /// Returns the most motes a single deploy may transfer out of the main purse, if limited.
+    pub fn spending_limit(&self) -> Option<U512> {
+        self.spending_limit
+    }
+
+    pub fn set_spending_limit(&mut self, spending_limit: Option<U512>) {
+        self.spending_limit = spending_limit;
+    }
+
+    /// Returns the allow-list of contracts the account's deploys may call, if restricted.
+    pub fn contract_allow_list(&self) -> Option<&ContractAllowList> {
+        self.contract_allow_list.as_ref()
+    }
+
+    pub fn set_contract_allow_list(&mut self, contract_allow_list: Option<ContractAllowList>) {
+        self.contract_allow_list = contract_allow_list;
+    }
+
+    /// Returns the hashes of the deploys executed in the account's context which haven't expired
+    /// yet, along with the block time at which they expire.
+    pub fn executed_deploys(&self) -> &BTreeMap<[u8; 32], BlockTime> {
+        &self.executed_deploys
+    }
+
+    /// Records `deploy_hash` as executed until `expires_at`, forgetting the recorded deploys which
+    /// expired before `blocktime`.
+    ///
+    /// Returns `false` without recording anything if the deploy was already recorded, i.e. if it's
+    /// a replay.
+    pub fn record_executed_deploy(
+        &mut self,
+        deploy_hash: [u8; 32],
+        expires_at: BlockTime,
+        blocktime: BlockTime,
+    ) -> bool {
+        self.executed_deploys
+            .retain(|_deploy_hash, expires_at| *expires_at >= blocktime);
+        if self.executed_deploys.contains_key(&deploy_hash) {
+            return false;
+        }
+        self.executed_deploys.insert(deploy_hash, expires_at);
+        true
+    }
+
+    /// Restores the record of executed deploys as it is, without forgetting expired ones.
+    pub fn restore_executed_deploys(&mut self, executed_deploys: BTreeMap<[u8; 32], BlockTime>) {
+        self.executed_deploys = executed_deploys;
+    }
+
+    /// Returns the contract package owning the account, if it's a contract-owned account.
+    pub fn owner(&self) -> Option<&AccountOwner> {
+        self.owner.as_ref()
+    }
+
+    /// Restores the owner as it is, without checking the associated keys.
+    pub fn restore_owner(&mut self, owner: Option<AccountOwner>) {
+        self.owner = owner;
+    }
+
+    pub fn recovery_config(&self) -> Option<&RecoveryConfig> {
+        self.recovery_config.as_ref()
+    }
+
+    /// Returns the competing recoveries waiting for approvals, in the order they were proposed.
+    pub fn pending_recoveries(&self) -> &[PendingRecovery] {
+        &self.pending_recoveries
+    }
+
+    /// Replaces the recovery configuration, or disables recovery if `None`.
+    ///
+    /// Any pending recoveries are cancelled, as they were approved under the previous
+    /// configuration.
+    pub fn set_recovery_config(
+        &mut self,
+        recovery_config: Option<RecoveryConfig>,
+    ) -> Result<(), RecoveryFailure> {
+        // Recovering a contract-owned account would hand it over to keys outside the owner's group
+        if self.owner.is_some() {
+            return Err(RecoveryFailure::PermissionDenied);
+        }
+        if let Some(config) = recovery_config.as_ref() {
+            if !config.is_valid() {
+                return Err(RecoveryFailure::InvalidConfig);
+            }
+        }
+        self.recovery_config = recovery_config;
+        self.pending_recoveries.clear();
+        Ok(())
+    }
+
+    /// Restores the recovery configuration and the pending recoveries as they are, without
+    /// validating them.
+    pub fn restore_recovery(
+        &mut self,
+        recovery_config: Option<RecoveryConfig>,
+        pending_recoveries: Vec<PendingRecovery>,
+    ) {
+        self.recovery_config = recovery_config;
+        self.pending_recoveries = pending_recoveries;
+    }
+
+    /// Cancels all of the pending recoveries.
+    pub fn cancel_recovery(&mut self) -> Result<(), RecoveryFailure> {
+        if self.pending_recoveries.is_empty() {
+            return Err(RecoveryFailure::NoPendingRecovery);
+        }
+        self.pending_recoveries.clear();
+        Ok(())
+    }
+
+    /// Proposes replacing the associated keys with `new_keys` on behalf of `guardian`, or
+    /// approves the pending recovery which already proposes the same keys.
+    ///
+    /// Proposals for different keys compete with each other rather than replacing one another,
+    /// so a single guardian can't discard the approvals of the others or restart their delay.
+    /// Each guardian backs at most one proposal: backing another one withdraws its previous
+    /// approval, and proposals nobody backs anymore are dropped.
+    pub fn propose_recovery(
+        &mut self,
+        guardian: AccountHash,
+        new_keys: BTreeMap<AccountHash, Weight>,
+        now: BlockTime,
+    ) -> Result<(), RecoveryFailure> {
+        self.check_guardian(guardian)?;
+
+        let mut associated_keys = AssociatedKeys::default();
+        for (account_hash, weight) in new_keys {
+            associated_keys
+                .add_key(account_hash, weight)
+                .map_err(|_| RecoveryFailure::InvalidKeys)?;
+        }
+        if !self.can_recover_with(&associated_keys) {
+            return Err(RecoveryFailure::InvalidKeys);
+        }
+
+        for pending in self.pending_recoveries.iter_mut() {
+            if *pending.new_keys() != associated_keys {
+                pending.withdraw(&guardian);
+            }
+        }
+        self.pending_recoveries
+            .retain(|pending| !pending.approvals().is_empty());
+
+        match self
+            .pending_recoveries
+            .iter_mut()
+            .find(|pending| *pending.new_keys() == associated_keys)
+        {
+            Some(pending) => pending.approve(guardian),
+            None => {
+                self.pending_recoveries
+                    .push(PendingRecovery::new(guardian, associated_keys, now))
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the associated keys with the ones of a pending recovery whose delay has elapsed
+    /// and which enough guardians approved. The earliest such proposal wins, and all of the
+    /// pending recoveries are cleared.
+    ///
+    /// If no proposal can be finalized, the failure of the one which got furthest is returned.
+    pub fn finalize_recovery(
+        &mut self,
+        guardian: AccountHash,
+        now: BlockTime,
+    ) -> Result<(), RecoveryFailure> {
+        let config = self.check_guardian(guardian)?;
+
+        let mut failure = RecoveryFailure::NoPendingRecovery;
+        let mut new_keys = None;
+        for pending in &self.pending_recoveries {
+            match self.check_pending_recovery(config, pending, now) {
+                Ok(()) => {
+                    new_keys = Some(pending.new_keys().clone());
+                    break;
+                }
+                Err(error) => failure = Self::furthest_recovery_failure(failure, error),
+            }
+        }
+
+        let new_keys = new_keys.ok_or(failure)?;
+        self.associated_keys = new_keys;
+        self.pending_recoveries.clear();
+        Ok(())
+    }
+
+    fn check_pending_recovery(
+        &self,
+        config: &RecoveryConfig,
+        pending: &PendingRecovery,
+        now: BlockTime,
+    ) -> Result<(), RecoveryFailure> {
+        let proposed_at: u64 = pending.proposed_at().into();
+        let now: u64 = now.into();
+        if now < proposed_at.saturating_add(config.delay()) {
+            return Err(RecoveryFailure::DelayNotElapsed);
+        }
+
+        // Guardians removed from the configuration since approving don't count.
+        let approvals = pending
+            .approvals()
+            .iter()
+            .filter(|approval| config.guardians().contains(approval))
+            .count();
+        if approvals < usize::from(config.threshold()) {
+            return Err(RecoveryFailure::InsufficientApprovals);
+        }
+
+        // Thresholds may have changed since the recovery was proposed.
+        if !self.can_recover_with(pending.new_keys()) {
+            return Err(RecoveryFailure::InvalidKeys);
+        }
+        Ok(())
+    }
+
+    /// Picks the failure of the check which got further in `check_pending_recovery`.
+    fn furthest_recovery_failure(
+        current: RecoveryFailure,
+        candidate: RecoveryFailure,
+    ) -> RecoveryFailure {
+        let stage = |failure: &RecoveryFailure| match failure {
+            RecoveryFailure::DelayNotElapsed => 1,
+            RecoveryFailure::InsufficientApprovals => 2,
+            RecoveryFailure::InvalidKeys => 3,
+            _ => 0,
+        };
+        if stage(&candidate) > stage(&current) {
+            candidate
+        } else {
+            current
+        }
+    }
+
+    fn check_guardian(&self, guardian: AccountHash) -> Result<&RecoveryConfig, RecoveryFailure> {
+        let config = self
+            .recovery_config
+            .as_ref()
+            .ok_or(RecoveryFailure::NotConfigured)?;
+        if !config.guardians().contains(&guardian) {
+            return Err(RecoveryFailure::NotGuardian);
+        }
+        Ok(config)
+    }
+
+    /// Checks if `associated_keys` would meet all of the thresholds on account.
+    fn can_recover_with(&self, associated_keys: &AssociatedKeys) -> bool {
+        let total_weight = associated_keys.total_keys_weight();
+        !associated_keys.is_empty()
+            && total_weight >= *self.action_thresholds().deployment()
+            && total_weight >= *self.action_thresholds().key_management()
+            && total_weight >= *self.action_thresholds().transfer()
+    }
+
     pub fn add_associated_key(
         &mut self,
         account_hash: AccountHash,
         weight: Weight,
     ) -> Result<(), AddKeyFailure> {
+        // The keys of a contract-owned account are managed through its owner's group
+        if self.owner.is_some() {
+            return Err(AddKeyFailure::PermissionDenied);
+        }
         self.associated_keys.add_key(account_hash, weight)
     }
 
@@ -105,6 +424,7 @@ impl Account {
         // equal to all of the thresholds.
         total_weight_without >= *self.action_thresholds().deployment()
             && total_weight_without >= *self.action_thresholds().key_management()
+            && total_weight_without >= *self.action_thresholds().transfer()
     }
 
     /// Checks if adding a weight to a sum of all weights excluding the given key would make the
@@ -122,12 +442,16 @@ impl Account {
         // the thresholds.
         new_weight >= self.action_thresholds().deployment().value()
             && new_weight >= self.action_thresholds().key_management().value()
+            && new_weight >= self.action_thresholds().transfer().value()
     }
 
     pub fn remove_associated_key(
         &mut self,
         account_hash: AccountHash,
     ) -> Result<(), RemoveKeyFailure> {
+        if self.owner.is_some() {
+            return Err(RemoveKeyFailure::PermissionDenied);
+        }
         if self.associated_keys.contains_key(&account_hash) {
             // Check if removing this weight would fall below thresholds
             if !self.can_remove_key(account_hash) {
@@ -142,6 +466,9 @@ impl Account {
         account_hash: AccountHash,
         weight: Weight,
     ) -> Result<(), UpdateKeyFailure> {
+        if self.owner.is_some() {
+            return Err(UpdateKeyFailure::PermissionDenied);
+        }
         if let Some(current_weight) = self.associated_keys.get(&account_hash) {
             if weight < *current_weight {
                 // New weight is smaller than current weight
@@ -171,6 +498,11 @@ impl Account {
 
     /// Verifies if user can set action threshold
     pub fn can_set_threshold(&self, new_threshold: Weight) -> Result<(), SetThresholdFailure> {
+        // The total weight of a contract-owned account is the number of holders of its owner's
+        // group, which changes independently of the account
+        if self.owner.is_some() {
+            return Ok(());
+        }
         let total_weight = self.associated_keys.total_keys_weight();
         if new_threshold > total_weight {
             return Err(SetThresholdFailure::InsufficientTotalWeight);
@@ -205,35 +537,132 @@ impl Account {
 
         total_weight >= *self.action_thresholds().key_management()
     }
+
+    /// Checks whether the sum of the weights of all authorization keys is
+    /// greater or equal to both the deploy and the transfer thresholds.
+    pub fn can_transfer_with(&self, authorization_keys: &BTreeSet<AccountHash>) -> bool {
+        let total_weight = self
+            .associated_keys
+            .calculate_keys_weight(authorization_keys);
+
+        total_weight >= *self.action_thresholds().deployment()
+            && total_weight >= *self.action_thresholds().transfer()
+    }
+
+    /// Checks whether a deploy authorized by `authorization_keys` may run arbitrary Wasm, i.e.
+    /// whether the account has no contract allow-list or the keys meet the key management
+    /// threshold.
+    pub fn can_execute_module_bytes_with(
+        &self,
+        authorization_keys: &BTreeSet<AccountHash>,
+    ) -> bool {
+        self.contract_allow_list.is_none() || self.can_manage_keys_with(authorization_keys)
+    }
+
+    /// Checks whether a deploy authorized by `authorization_keys` may call `entry_point` of the
+    /// given contract package.
+    pub fn can_call_contract_with(
+        &self,
+        authorization_keys: &BTreeSet<AccountHash>,
+        contract_package_hash: &ContractPackageHash,
+        entry_point: &str,
+    ) -> bool {
+        match self.contract_allow_list.as_ref() {
+            Some(allow_list) => {
+                allow_list.is_allowed(contract_package_hash, entry_point)
+                    || self.can_manage_keys_with(authorization_keys)
+            }
+            None => true,
+        }
+    }
+
+    /// Checks whether a deploy which already transferred `spent` motes out of the main purse
+    /// may transfer another `amount`.
+    pub fn can_spend(&self, spent: U512, amount: U512) -> bool {
+        match self.spending_limit {
+            Some(limit) => spent
+                .checked_add(amount)
+                .map_or(false, |total| total <= limit),
+            None => true,
+        }
+    }
 }
 
 impl ToBytes for Account {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         let mut result = bytesrepr::allocate_buffer(self)?;
+        result.push(ACCOUNT_FIELDS_VERSION_1);
         result.append(&mut self.account_hash.to_bytes()?);
         result.append(&mut self.named_keys.to_bytes()?);
         result.append(&mut self.main_purse.to_bytes()?);
         result.append(&mut self.associated_keys.to_bytes()?);
         result.append(&mut self.action_thresholds.to_bytes()?);
+        result.append(&mut self.action_thresholds.transfer().to_bytes()?);
+        result.append(&mut self.spending_limit.to_bytes()?);
+        result.append(&mut self.recovery_config.to_bytes()?);
+        result.append(&mut self.pending_recoveries.to_bytes()?);
+        result.append(&mut self.contract_allow_list.to_bytes()?);
+        result.append(&mut self.executed_deploys.to_bytes()?);
+        result.append(&mut self.owner.to_bytes()?);
         Ok(result)
     }
 
     fn serialized_length(&self) -> usize {
-        self.account_hash.serialized_length()
+        ACCOUNT_FIELDS_VERSION_1.serialized_length()
+            + self.account_hash.serialized_length()
             + self.named_keys.serialized_length()
             + self.main_purse.serialized_length()
             + self.associated_keys.serialized_length()
             + self.action_thresholds.serialized_length()
+            + self.action_thresholds.transfer().serialized_length()
+            + self.spending_limit.serialized_length()
+            + self.recovery_config.serialized_length()
+            + self.pending_recoveries.serialized_length()
+            + self.contract_allow_list.serialized_length()
+            + self.executed_deploys.serialized_length()
+            + self.owner.serialized_length()
     }
 }
 
-impl FromBytes for Account {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+impl Account {
+    /// Deserializes an account written before its layout was versioned, giving
+    /// the fields added since their defaults.
+    pub fn from_legacy_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
         let (account_hash, rem) = AccountHash::from_bytes(bytes)?;
         let (named_keys, rem) = NamedKeys::from_bytes(rem)?;
         let (main_purse, rem) = URef::from_bytes(rem)?;
         let (associated_keys, rem) = AssociatedKeys::from_bytes(rem)?;
         let (action_thresholds, rem) = ActionThresholds::from_bytes(rem)?;
+        let account = Account::new(
+            account_hash,
+            named_keys,
+            main_purse,
+            associated_keys,
+            action_thresholds,
+        );
+        Ok((account, rem))
+    }
+}
+
+impl FromBytes for Account {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (version, rem) = u8::from_bytes(bytes)?;
+        if version != ACCOUNT_FIELDS_VERSION_1 {
+            return Err(Error::Formatting);
+        }
+        let (account_hash, rem) = AccountHash::from_bytes(rem)?;
+        let (named_keys, rem) = NamedKeys::from_bytes(rem)?;
+        let (main_purse, rem) = URef::from_bytes(rem)?;
+        let (associated_keys, rem) = AssociatedKeys::from_bytes(rem)?;
+        let (mut action_thresholds, rem) = ActionThresholds::from_bytes(rem)?;
+        let (transfer_threshold, rem) = Weight::from_bytes(rem)?;
+        action_thresholds.set_transfer_threshold(transfer_threshold);
+        let (spending_limit, rem) = Option::<U512>::from_bytes(rem)?;
+        let (recovery_config, rem) = Option::<RecoveryConfig>::from_bytes(rem)?;
+        let (pending_recoveries, rem) = Vec::<PendingRecovery>::from_bytes(rem)?;
+        let (contract_allow_list, rem) = Option::<ContractAllowList>::from_bytes(rem)?;
+        let (executed_deploys, rem) = BTreeMap::<[u8; 32], BlockTime>::from_bytes(rem)?;
+        let (owner, rem) = Option::<AccountOwner>::from_bytes(rem)?;
         Ok((
             Account {
                 account_hash,
@@ -241,6 +670,12 @@ impl FromBytes for Account {
                 main_purse,
                 associated_keys,
                 action_thresholds,
+                spending_limit,
+                recovery_config,
+                pending_recoveries,
+                contract_allow_list,
+                executed_deploys,
+                owner,
             },
             rem,
         ))
@@ -252,14 +687,27 @@ pub mod gens {
 
     use types::{
         account::MAX_ASSOCIATED_KEYS,
-        gens::{account_hash_arb, named_keys_arb, uref_arb},
+        gens::{account_hash_arb, named_keys_arb, u512_arb, u8_slice_32, uref_arb},
     };
 
     use super::*;
     use crate::account::{
-        action_thresholds::gens::action_thresholds_arb, associated_keys::gens::associated_keys_arb,
+        action_thresholds::gens::action_thresholds_arb,
+        associated_keys::gens::associated_keys_arb,
+        owner::gens::account_owner_arb,
+        recovery::gens::{pending_recovery_arb, recovery_config_arb},
     };
 
+    pub fn contract_allow_list_arb() -> impl Strategy<Value = ContractAllowList> {
+        proptest::collection::vec((u8_slice_32(), "[a-z_]{1,16}"), 0..5).prop_map(|entries| {
+            let mut allow_list = ContractAllowList::new();
+            for (contract_package_hash, entry_point) in entries {
+                allow_list.allow(contract_package_hash, entry_point);
+            }
+            allow_list
+        })
+    }
+
     prop_compose! {
         pub fn account_arb()(
             account_hash in account_hash_arb(),
@@ -267,15 +715,31 @@ pub mod gens {
             purse in uref_arb(),
             thresholds in action_thresholds_arb(),
             mut associated_keys in associated_keys_arb(MAX_ASSOCIATED_KEYS - 1),
+            spending_limit in proptest::option::of(u512_arb()),
+            recovery_config in proptest::option::of(recovery_config_arb()),
+            pending_recoveries in proptest::collection::vec(pending_recovery_arb(), 0..3),
+            contract_allow_list in proptest::option::of(contract_allow_list_arb()),
+            executed_deploys in proptest::collection::btree_map(
+                u8_slice_32(),
+                any::<u64>().prop_map(BlockTime::new),
+                0..3,
+            ),
+            owner in proptest::option::of(account_owner_arb()),
         ) -> Account {
                 associated_keys.add_key(account_hash, Weight::new(1)).unwrap();
-                Account::new(
+                let mut account = Account::new(
                     account_hash,
                     urefs,
                     purse,
                     associated_keys,
                     thresholds,
-                )
+                );
+                account.set_spending_limit(spending_limit);
+                account.restore_recovery(recovery_config, pending_recoveries);
+                account.set_contract_allow_list(contract_allow_list);
+                account.restore_executed_deploys(executed_deploys);
+                account.restore_owner(owner);
+                account
         }
     }
 }
@@ -302,10 +766,11 @@ mod tests {
 
     use types::{
         account::{
-            AccountHash, ActionType, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure,
-            Weight,
+            AccountHash, ActionType, AddKeyFailure, ContractAllowList, RecoveryConfig,
+            RecoveryFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure, Weight,
         },
-        AccessRights, URef,
+        contracts::Group,
+        AccessRights, BlockTime, URef,
     };
 
     use super::*;
@@ -446,6 +911,99 @@ mod tests {
         ])));
     }
 
+    #[test]
+    fn account_can_transfer_with() {
+        let associated_keys = {
+            let mut res = AssociatedKeys::new(AccountHash::new([1u8; 32]), Weight::new(1));
+            res.add_key(AccountHash::new([2u8; 32]), Weight::new(11))
+                .expect("should add key 1");
+            res.add_key(AccountHash::new([3u8; 32]), Weight::new(11))
+                .expect("should add key 2");
+            res
+        };
+        let mut account = Account::new(
+            AccountHash::new([0u8; 32]),
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            associated_keys,
+            ActionThresholds::new(Weight::new(11), Weight::new(23))
+                .expect("should create thresholds"),
+        );
+        let one_key = BTreeSet::from_iter(vec![AccountHash::new([2u8; 32])]);
+        let two_keys = BTreeSet::from_iter(vec![
+            AccountHash::new([2u8; 32]),
+            AccountHash::new([3u8; 32]),
+        ]);
+
+        // transfer threshold defaults to the deploy threshold
+        assert!(account.can_transfer_with(&one_key));
+
+        account
+            .set_action_threshold(ActionType::Transfer, Weight::new(22))
+            .expect("should set transfer threshold");
+        // sum: 11, required 22 - can deploy but can't transfer
+        assert!(account.can_deploy_with(&one_key));
+        assert!(!account.can_transfer_with(&one_key));
+        // sum: 22, required 22 - can transfer
+        assert!(account.can_transfer_with(&two_keys));
+
+        // a transfer threshold lower than the deploy threshold doesn't lower the requirement
+        account
+            .set_action_threshold(ActionType::Transfer, Weight::new(1))
+            .expect("should set transfer threshold");
+        assert!(!account.can_transfer_with(&BTreeSet::from_iter(vec![AccountHash::new([1u8; 32])])));
+    }
+
+    #[test]
+    fn account_can_spend_within_limit() {
+        let mut account = Account::create(
+            AccountHash::new([0u8; 32]),
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+        );
+        assert!(account.can_spend(U512::zero(), U512::MAX));
+
+        account.set_spending_limit(Some(U512::from(100)));
+        assert!(account.can_spend(U512::zero(), U512::from(100)));
+        assert!(account.can_spend(U512::from(60), U512::from(40)));
+        assert!(!account.can_spend(U512::from(60), U512::from(41)));
+        assert!(!account.can_spend(U512::MAX, U512::one()));
+    }
+
+    #[test]
+    fn remove_key_would_violate_transfer_threshold() {
+        let identity_key = AccountHash::new([1u8; 32]);
+        let key_1 = AccountHash::new([2u8; 32]);
+        let associated_keys = {
+            let mut res = AssociatedKeys::new(identity_key, Weight::new(1));
+            res.add_key(key_1, Weight::new(2))
+                .expect("should add key 1");
+            res
+        };
+        let mut account = Account::new(
+            identity_key,
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            associated_keys,
+            ActionThresholds::new(Weight::new(1), Weight::new(1))
+                .expect("should create thresholds"),
+        );
+        account
+            .set_action_threshold(ActionType::Transfer, Weight::new(3))
+            .expect("should set transfer threshold");
+
+        assert_eq!(
+            account.clone().remove_associated_key(key_1).unwrap_err(),
+            RemoveKeyFailure::ThresholdViolation,
+        );
+        assert_eq!(
+            account
+                .update_associated_key(key_1, Weight::new(1))
+                .unwrap_err(),
+            UpdateKeyFailure::ThresholdViolation,
+        );
+    }
+
     #[test]
     fn set_action_threshold_higher_than_total_weight() {
         let identity_key = AccountHash::new([1u8; 32]);
@@ -651,4 +1209,387 @@ mod tests {
             .update_associated_key(key_1, Weight::new(1))
             .expect("should work");
     }
+
+    const GUARDIAN_1: AccountHash = AccountHash::new([11; 32]);
+    const GUARDIAN_2: AccountHash = AccountHash::new([12; 32]);
+    const GUARDIAN_3: AccountHash = AccountHash::new([13; 32]);
+    const RECOVERY_DELAY: u64 = 1_000;
+
+    fn recoverable_account() -> Account {
+        let mut account = Account::create(
+            AccountHash::new([1; 32]),
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+        );
+        let guardians = BTreeSet::from_iter(vec![GUARDIAN_1, GUARDIAN_2, GUARDIAN_3]);
+        account
+            .set_recovery_config(Some(RecoveryConfig::new(guardians, 2, RECOVERY_DELAY)))
+            .expect("should set recovery config");
+        account
+    }
+
+    fn new_keys() -> BTreeMap<AccountHash, Weight> {
+        let mut new_keys = BTreeMap::new();
+        new_keys.insert(AccountHash::new([2; 32]), Weight::new(1));
+        new_keys
+    }
+
+    fn other_keys() -> BTreeMap<AccountHash, Weight> {
+        let mut other_keys = BTreeMap::new();
+        other_keys.insert(AccountHash::new([3; 32]), Weight::new(1));
+        other_keys
+    }
+
+    #[test]
+    fn should_not_set_invalid_recovery_config() {
+        let mut account = recoverable_account();
+        let guardians = BTreeSet::from_iter(vec![GUARDIAN_1]);
+        assert_eq!(
+            account.set_recovery_config(Some(RecoveryConfig::new(guardians, 2, 0))),
+            Err(RecoveryFailure::InvalidConfig)
+        );
+        assert!(account.recovery_config().is_some());
+    }
+
+    #[test]
+    fn should_recover_after_delay_with_enough_guardians() {
+        let mut account = recoverable_account();
+        account
+            .propose_recovery(GUARDIAN_1, new_keys(), BlockTime::new(100))
+            .expect("should propose");
+        account
+            .propose_recovery(GUARDIAN_2, new_keys(), BlockTime::new(200))
+            .expect("should approve");
+        assert_eq!(account.pending_recoveries().len(), 1);
+        assert_eq!(
+            account.pending_recoveries()[0].proposed_at(),
+            BlockTime::new(100),
+            "approving should not restart the delay"
+        );
+
+        account
+            .finalize_recovery(GUARDIAN_3, BlockTime::new(100 + RECOVERY_DELAY))
+            .expect("should finalize");
+        assert!(account.pending_recoveries().is_empty());
+        let keys: Vec<_> = account
+            .get_associated_keys()
+            .map(|(account_hash, weight)| (*account_hash, *weight))
+            .collect();
+        assert_eq!(keys, new_keys().into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn should_not_recover_before_delay() {
+        let mut account = recoverable_account();
+        account
+            .propose_recovery(GUARDIAN_1, new_keys(), BlockTime::new(100))
+            .expect("should propose");
+        account
+            .propose_recovery(GUARDIAN_2, new_keys(), BlockTime::new(100))
+            .expect("should approve");
+        assert_eq!(
+            account.finalize_recovery(GUARDIAN_1, BlockTime::new(100 + RECOVERY_DELAY - 1)),
+            Err(RecoveryFailure::DelayNotElapsed)
+        );
+        assert!(account
+            .get_associated_key_weight(AccountHash::new([1; 32]))
+            .is_some());
+    }
+
+    #[test]
+    fn should_not_recover_with_insufficient_guardians() {
+        let mut account = recoverable_account();
+        account
+            .propose_recovery(GUARDIAN_1, new_keys(), BlockTime::new(100))
+            .expect("should propose");
+        // Approving twice doesn't count twice
+        account
+            .propose_recovery(GUARDIAN_1, new_keys(), BlockTime::new(100))
+            .expect("should approve");
+        assert_eq!(
+            account.finalize_recovery(GUARDIAN_1, BlockTime::new(100 + RECOVERY_DELAY)),
+            Err(RecoveryFailure::InsufficientApprovals)
+        );
+
+        // Switching to a competing proposal withdraws the approval of the previous one
+        account
+            .propose_recovery(GUARDIAN_1, other_keys(), BlockTime::new(200))
+            .expect("should propose other keys");
+        assert_eq!(account.pending_recoveries().len(), 1);
+        account
+            .propose_recovery(GUARDIAN_2, new_keys(), BlockTime::new(300))
+            .expect("should propose");
+        assert_eq!(
+            account.finalize_recovery(GUARDIAN_2, BlockTime::new(300 + RECOVERY_DELAY)),
+            Err(RecoveryFailure::InsufficientApprovals)
+        );
+    }
+
+    #[test]
+    fn should_not_let_competing_proposal_block_recovery() {
+        let mut account = recoverable_account();
+        account
+            .propose_recovery(GUARDIAN_1, new_keys(), BlockTime::new(100))
+            .expect("should propose");
+
+        // A rogue guardian keeps proposing other keys
+        account
+            .propose_recovery(GUARDIAN_3, other_keys(), BlockTime::new(200))
+            .expect("should propose competing keys");
+        account
+            .propose_recovery(GUARDIAN_2, new_keys(), BlockTime::new(300))
+            .expect("should approve");
+        account
+            .propose_recovery(GUARDIAN_3, other_keys(), BlockTime::new(400))
+            .expect("should approve competing keys again");
+        assert_eq!(account.pending_recoveries().len(), 2);
+        assert_eq!(
+            account.pending_recoveries()[0].proposed_at(),
+            BlockTime::new(100),
+            "competing proposals should not restart the delay"
+        );
+
+        account
+            .finalize_recovery(GUARDIAN_3, BlockTime::new(100 + RECOVERY_DELAY))
+            .expect("should finalize");
+        assert!(account.pending_recoveries().is_empty());
+        let keys: Vec<_> = account
+            .get_associated_keys()
+            .map(|(account_hash, weight)| (*account_hash, *weight))
+            .collect();
+        assert_eq!(keys, new_keys().into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn should_not_recover_by_non_guardian() {
+        let mut account = recoverable_account();
+        let attacker = AccountHash::new([66; 32]);
+        assert_eq!(
+            account.propose_recovery(attacker, new_keys(), BlockTime::new(100)),
+            Err(RecoveryFailure::NotGuardian)
+        );
+        account
+            .propose_recovery(GUARDIAN_1, new_keys(), BlockTime::new(100))
+            .expect("should propose");
+        account
+            .propose_recovery(GUARDIAN_2, new_keys(), BlockTime::new(100))
+            .expect("should approve");
+        assert_eq!(
+            account.finalize_recovery(attacker, BlockTime::new(100 + RECOVERY_DELAY)),
+            Err(RecoveryFailure::NotGuardian)
+        );
+    }
+
+    #[test]
+    fn should_not_recover_without_config() {
+        let mut account = recoverable_account();
+        account
+            .propose_recovery(GUARDIAN_1, new_keys(), BlockTime::new(100))
+            .expect("should propose");
+        account
+            .set_recovery_config(None)
+            .expect("should disable recovery");
+        assert!(account.pending_recoveries().is_empty());
+        assert_eq!(
+            account.propose_recovery(GUARDIAN_1, new_keys(), BlockTime::new(100)),
+            Err(RecoveryFailure::NotConfigured)
+        );
+    }
+
+    #[test]
+    fn should_not_recover_with_keys_below_thresholds() {
+        let mut account = recoverable_account();
+        let mut weak_keys = BTreeMap::new();
+        weak_keys.insert(AccountHash::new([2; 32]), Weight::new(0));
+        assert_eq!(
+            account.propose_recovery(GUARDIAN_1, weak_keys, BlockTime::new(100)),
+            Err(RecoveryFailure::InvalidKeys)
+        );
+        assert_eq!(
+            account.propose_recovery(GUARDIAN_1, BTreeMap::new(), BlockTime::new(100)),
+            Err(RecoveryFailure::InvalidKeys)
+        );
+    }
+
+    #[test]
+    fn should_cancel_pending_recovery() {
+        let mut account = recoverable_account();
+        assert_eq!(
+            account.cancel_recovery(),
+            Err(RecoveryFailure::NoPendingRecovery)
+        );
+        account
+            .propose_recovery(GUARDIAN_1, new_keys(), BlockTime::new(100))
+            .expect("should propose");
+        account.cancel_recovery().expect("should cancel");
+        assert_eq!(
+            account.finalize_recovery(GUARDIAN_1, BlockTime::new(100 + RECOVERY_DELAY)),
+            Err(RecoveryFailure::NoPendingRecovery)
+        );
+    }
+
+    #[test]
+    fn contract_allow_list_should_restrict_deploys_below_key_management_threshold() {
+        let deploy_key = AccountHash::new([1; 32]);
+        let management_key = AccountHash::new([2; 32]);
+        let mut associated_keys = AssociatedKeys::new(deploy_key, Weight::new(1));
+        associated_keys
+            .add_key(management_key, Weight::new(1))
+            .expect("should add key");
+        let mut account = Account::new(
+            deploy_key,
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            associated_keys,
+            ActionThresholds::new(Weight::new(1), Weight::new(2))
+                .expect("should create thresholds"),
+        );
+        let deploy_keys = BTreeSet::from_iter(vec![deploy_key]);
+        let all_keys = BTreeSet::from_iter(vec![deploy_key, management_key]);
+
+        assert!(account.can_execute_module_bytes_with(&deploy_keys));
+        assert!(account.can_call_contract_with(&deploy_keys, &[9; 32], "anything"));
+
+        let mut allow_list = ContractAllowList::new();
+        allow_list.allow([9; 32], "transfer".to_string());
+        account.set_contract_allow_list(Some(allow_list));
+
+        assert!(!account.can_execute_module_bytes_with(&deploy_keys));
+        assert!(account.can_call_contract_with(&deploy_keys, &[9; 32], "transfer"));
+        assert!(!account.can_call_contract_with(&deploy_keys, &[9; 32], "anything"));
+        assert!(!account.can_call_contract_with(&deploy_keys, &[8; 32], "transfer"));
+
+        assert!(account.can_execute_module_bytes_with(&all_keys));
+        assert!(account.can_call_contract_with(&all_keys, &[8; 32], "anything"));
+    }
+
+    #[test]
+    fn should_reject_replayed_deploy_until_it_expires() {
+        let mut account = Account::create(
+            AccountHash::new([1; 32]),
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+        );
+        let deploy_hash = [2; 32];
+        let expires_at = BlockTime::new(1_000);
+
+        assert!(account.record_executed_deploy(deploy_hash, expires_at, BlockTime::new(10)));
+        assert!(!account.record_executed_deploy(deploy_hash, expires_at, BlockTime::new(20)));
+        assert!(!account.record_executed_deploy(deploy_hash, expires_at, expires_at));
+
+        // Once expired, the deploy is forgotten, as the engine rejects it as expired anyway
+        assert!(account.record_executed_deploy(
+            [3; 32],
+            BlockTime::new(2_000),
+            BlockTime::new(1_001)
+        ));
+        assert_eq!(account.executed_deploys().len(), 1);
+        assert!(account.record_executed_deploy(deploy_hash, expires_at, BlockTime::new(1_001)));
+    }
+
+    #[test]
+    fn contract_owned_account_should_count_each_member_once() {
+        let member_1 = AccountHash::new([1; 32]);
+        let member_2 = AccountHash::new([4; 32]);
+        let account = Account::create_contract_owned(
+            AccountHash::new([2; 32]),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            AccountOwner::new([3; 32], Group::new("members")),
+            ActionThresholds::new(Weight::new(2), Weight::new(2)).unwrap(),
+        );
+        assert!(!account.can_authorize(&BTreeSet::from_iter(vec![member_1])));
+
+        let single_member = BTreeSet::from_iter(vec![member_1]);
+        let authorized = account.with_members(&single_member).unwrap();
+        assert!(authorized.can_authorize(&single_member));
+        assert!(!authorized.can_deploy_with(&single_member));
+
+        let both_members = BTreeSet::from_iter(vec![member_1, member_2]);
+        let authorized = account.with_members(&both_members).unwrap();
+        assert!(authorized.can_deploy_with(&both_members));
+        assert_eq!(authorized.owner(), account.owner());
+        assert_eq!(authorized.main_purse(), account.main_purse());
+    }
+
+    #[test]
+    fn contract_owned_account_should_not_manage_keys() {
+        let member_key = AccountHash::new([1; 32]);
+        let mut account = Account::create_contract_owned(
+            AccountHash::new([2; 32]),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            AccountOwner::new([3; 32], Group::new("members")),
+            ActionThresholds::default(),
+        )
+        .with_members(&BTreeSet::from_iter(vec![member_key]))
+        .unwrap();
+
+        assert!(account.can_deploy_with(&BTreeSet::from_iter(vec![member_key])));
+        assert_eq!(
+            account.add_associated_key(AccountHash::new([4; 32]), Weight::new(1)),
+            Err(AddKeyFailure::PermissionDenied)
+        );
+        assert_eq!(
+            account.update_associated_key(member_key, Weight::new(2)),
+            Err(UpdateKeyFailure::PermissionDenied)
+        );
+        assert_eq!(
+            account.remove_associated_key(member_key),
+            Err(RemoveKeyFailure::PermissionDenied)
+        );
+        let recovery_config = RecoveryConfig::new(BTreeSet::from_iter(vec![member_key]), 1, 0);
+        assert_eq!(
+            account.set_recovery_config(Some(recovery_config)),
+            Err(RecoveryFailure::PermissionDenied)
+        );
+    }
+
+    #[test]
+    fn should_deserialize_legacy_account() {
+        let account_hash = AccountHash::new([1; 32]);
+        let mut named_keys = NamedKeys::new();
+        named_keys.insert(
+            "key".to_string(),
+            URef::new([2; 32], AccessRights::READ).into(),
+        );
+        let main_purse = URef::new([3; 32], AccessRights::READ_ADD_WRITE);
+        let associated_keys = AssociatedKeys::new(account_hash, Weight::new(2));
+
+        // Accounts written before the layout was versioned end with their
+        // deployment and key management thresholds.
+        let mut bytes = account_hash.to_bytes().unwrap();
+        bytes.append(&mut named_keys.to_bytes().unwrap());
+        bytes.append(&mut main_purse.to_bytes().unwrap());
+        bytes.append(&mut associated_keys.to_bytes().unwrap());
+        bytes.extend_from_slice(&[2, 3]);
+        bytes.extend_from_slice(&[4, 5]);
+
+        let (account, rem) = Account::from_legacy_bytes(&bytes).expect("should deserialize");
+        assert_eq!(rem, &[4, 5]);
+        let expected = Account::new(
+            account_hash,
+            named_keys,
+            main_purse,
+            associated_keys,
+            ActionThresholds::new(Weight::new(2), Weight::new(3)).unwrap(),
+        );
+        assert_eq!(account, expected);
+        assert_eq!(*account.action_thresholds().transfer(), Weight::new(2));
+        bytesrepr::test_serialization_roundtrip(&account);
+    }
+
+    #[test]
+    fn should_not_deserialize_account_with_unknown_version() {
+        let account = Account::create(
+            AccountHash::new([1; 32]),
+            NamedKeys::new(),
+            URef::new([3; 32], AccessRights::READ_ADD_WRITE),
+        );
+        let mut bytes = account.to_bytes().unwrap();
+        assert_eq!(bytes[0], ACCOUNT_FIELDS_VERSION_1);
+        bytes[0] = ACCOUNT_FIELDS_VERSION_1 + 1;
+        assert_eq!(
+            bytesrepr::deserialize::<Account>(bytes),
+            Err(bytesrepr::Error::Formatting)
+        );
+    }
 }
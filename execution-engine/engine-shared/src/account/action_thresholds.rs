@@ -4,17 +4,24 @@ use types::{
 };
 
 /// Thresholds that have to be met when executing an action of a certain type.
+///
+/// Only the deployment and key management thresholds are part of the
+/// serialized form, which is unchanged from before the transfer threshold
+/// existed. [`Account`](crate::account::Account) stores the transfer threshold
+/// separately in its versioned layout.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ActionThresholds {
     deployment: Weight,
     key_management: Weight,
+    transfer: Weight,
 }
 
 impl ActionThresholds {
     /// Creates new ActionThresholds object with provided weights
     ///
     /// Requires deployment threshold to be lower than or equal to
-    /// key management threshold.
+    /// key management threshold. The transfer threshold starts out equal to
+    /// the deployment threshold.
     pub fn new(
         deployment: Weight,
         key_management: Weight,
@@ -25,14 +32,15 @@ impl ActionThresholds {
         Ok(ActionThresholds {
             deployment,
             key_management,
+            transfer: deployment,
         })
     }
     /// Sets new threshold for [ActionType::Deployment].
     /// Should return an error if setting new threshold for `action_type` breaks
     /// one of the invariants. Currently, invariant is that
-    /// `ActionType::Deployment` threshold shouldn't be higher than any
-    /// other, which should be checked both when increasing `Deployment`
-    /// threshold and decreasing the other.
+    /// `ActionType::Deployment` threshold shouldn't be higher than the
+    /// `ActionType::KeyManagement` one, which should be checked both when
+    /// increasing `Deployment` threshold and decreasing the other.
     pub fn set_deployment_threshold(
         &mut self,
         new_threshold: Weight,
@@ -58,6 +66,15 @@ impl ActionThresholds {
         }
     }
 
+    /// Sets new threshold for [ActionType::Transfer].
+    ///
+    /// The transfer threshold isn't bound by the other thresholds. A deploy has
+    /// to meet the deployment threshold before it can transfer anything, so a
+    /// transfer threshold lower than that has no effect.
+    pub fn set_transfer_threshold(&mut self, new_threshold: Weight) {
+        self.transfer = new_threshold;
+    }
+
     pub fn deployment(&self) -> &Weight {
         &self.deployment
     }
@@ -66,6 +83,10 @@ impl ActionThresholds {
         &self.key_management
     }
 
+    pub fn transfer(&self) -> &Weight {
+        &self.transfer
+    }
+
     /// Unified function that takes an action type, and changes appropriate
     /// threshold defined by the [ActionType] variants.
     pub fn set_threshold(
@@ -76,6 +97,10 @@ impl ActionThresholds {
         match action_type {
             ActionType::Deployment => self.set_deployment_threshold(new_threshold),
             ActionType::KeyManagement => self.set_key_management_threshold(new_threshold),
+            ActionType::Transfer => {
+                self.set_transfer_threshold(new_threshold);
+                Ok(())
+            }
         }
     }
 }
@@ -85,6 +110,7 @@ impl Default for ActionThresholds {
         ActionThresholds {
             deployment: Weight::new(1),
             key_management: Weight::new(1),
+            transfer: Weight::new(1),
         }
     }
 }
@@ -109,6 +135,7 @@ impl FromBytes for ActionThresholds {
         let ret = ActionThresholds {
             deployment,
             key_management,
+            transfer: deployment,
         };
         Ok((ret, rem))
     }
@@ -133,6 +160,24 @@ mod tests {
         let action_thresholds = ActionThresholds::new(Weight::new(1), Weight::new(42)).unwrap();
         assert_eq!(*action_thresholds.deployment(), Weight::new(1));
         assert_eq!(*action_thresholds.key_management(), Weight::new(42));
+        assert_eq!(*action_thresholds.transfer(), Weight::new(1));
+    }
+
+    #[test]
+    fn should_set_transfer_threshold_independently() {
+        let mut action_thresholds = ActionThresholds::new(Weight::new(2), Weight::new(3)).unwrap();
+        action_thresholds
+            .set_threshold(ActionType::Transfer, Weight::new(5))
+            .unwrap();
+        assert_eq!(*action_thresholds.transfer(), Weight::new(5));
+        action_thresholds
+            .set_threshold(ActionType::Transfer, Weight::new(1))
+            .unwrap();
+        assert_eq!(*action_thresholds.transfer(), Weight::new(1));
+        // Raising the deployment threshold doesn't depend on the transfer threshold
+        action_thresholds
+            .set_threshold(ActionType::Deployment, Weight::new(3))
+            .unwrap();
     }
 
     #[test]
@@ -146,4 +191,17 @@ mod tests {
         let action_thresholds = ActionThresholds::new(Weight::new(1), Weight::new(42)).unwrap();
         bytesrepr::test_serialization_roundtrip(&action_thresholds);
     }
+
+    #[test]
+    fn should_keep_legacy_serialized_form() {
+        let mut action_thresholds = ActionThresholds::new(Weight::new(1), Weight::new(42)).unwrap();
+        action_thresholds.set_transfer_threshold(Weight::new(7));
+        assert_eq!(action_thresholds.to_bytes().unwrap(), vec![1, 42]);
+
+        let (decoded, rem) = ActionThresholds::from_bytes(&[3, 5]).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(*decoded.deployment(), Weight::new(3));
+        assert_eq!(*decoded.key_management(), Weight::new(5));
+        assert_eq!(*decoded.transfer(), Weight::new(3));
+    }
 }
@@ -0,0 +1,71 @@
+use types::{
+    bytesrepr::{self, Error, FromBytes, ToBytes},
+    contracts::Group,
+    ContractPackageHash,
+};
+
+/// The contract package owning a contract-owned account, along with the group whose URefs
+/// authorize deploys for the account.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct AccountOwner {
+    contract_package_hash: ContractPackageHash,
+    group: Group,
+}
+
+impl AccountOwner {
+    pub fn new(contract_package_hash: ContractPackageHash, group: Group) -> Self {
+        AccountOwner {
+            contract_package_hash,
+            group,
+        }
+    }
+
+    pub fn contract_package_hash(&self) -> ContractPackageHash {
+        self.contract_package_hash
+    }
+
+    pub fn group(&self) -> &Group {
+        &self.group
+    }
+}
+
+impl ToBytes for AccountOwner {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.contract_package_hash.to_bytes()?);
+        result.append(&mut self.group.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.contract_package_hash.serialized_length() + self.group.serialized_length()
+    }
+}
+
+impl FromBytes for AccountOwner {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (contract_package_hash, rem) = ContractPackageHash::from_bytes(bytes)?;
+        let (group, rem) = Group::from_bytes(rem)?;
+        Ok((
+            AccountOwner {
+                contract_package_hash,
+                group,
+            },
+            rem,
+        ))
+    }
+}
+
+pub mod gens {
+    use proptest::prelude::*;
+
+    use types::gens::{group_arb, u8_slice_32};
+
+    use super::AccountOwner;
+
+    pub fn account_owner_arb() -> impl Strategy<Value = AccountOwner> {
+        (u8_slice_32(), group_arb()).prop_map(|(contract_package_hash, group)| {
+            AccountOwner::new(contract_package_hash, group)
+        })
+    }
+}
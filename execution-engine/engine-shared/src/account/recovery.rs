@@ -0,0 +1,139 @@
+use std::collections::BTreeSet;
+
+use types::{
+    account::AccountHash,
+    bytesrepr::{self, Error, FromBytes, ToBytes},
+    BlockTime,
+};
+
+use crate::account::AssociatedKeys;
+
+/// A recovery of an account's associated keys which was proposed by one of its guardians and is
+/// waiting for approvals and for the configured delay to elapse.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct PendingRecovery {
+    new_keys: AssociatedKeys,
+    approvals: BTreeSet<AccountHash>,
+    proposed_at: BlockTime,
+}
+
+impl PendingRecovery {
+    pub fn new(proposer: AccountHash, new_keys: AssociatedKeys, proposed_at: BlockTime) -> Self {
+        let mut approvals = BTreeSet::new();
+        approvals.insert(proposer);
+        PendingRecovery {
+            new_keys,
+            approvals,
+            proposed_at,
+        }
+    }
+
+    /// Returns the associated keys which replace the account's keys once the recovery is
+    /// finalized.
+    pub fn new_keys(&self) -> &AssociatedKeys {
+        &self.new_keys
+    }
+
+    /// Returns the guardians who approved this recovery, including its proposer.
+    pub fn approvals(&self) -> &BTreeSet<AccountHash> {
+        &self.approvals
+    }
+
+    pub fn proposed_at(&self) -> BlockTime {
+        self.proposed_at
+    }
+
+    pub fn approve(&mut self, guardian: AccountHash) {
+        self.approvals.insert(guardian);
+    }
+
+    /// Removes the approval of `guardian`, e.g. because it backs a competing recovery instead.
+    pub fn withdraw(&mut self, guardian: &AccountHash) {
+        self.approvals.remove(guardian);
+    }
+}
+
+impl ToBytes for PendingRecovery {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.new_keys.to_bytes()?);
+        result.append(&mut self.approvals.to_bytes()?);
+        result.append(&mut self.proposed_at.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.new_keys.serialized_length()
+            + self.approvals.serialized_length()
+            + self.proposed_at.serialized_length()
+    }
+}
+
+impl FromBytes for PendingRecovery {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (new_keys, rem) = AssociatedKeys::from_bytes(bytes)?;
+        let (approvals, rem) = BTreeSet::<AccountHash>::from_bytes(rem)?;
+        let (proposed_at, rem) = BlockTime::from_bytes(rem)?;
+        Ok((
+            PendingRecovery {
+                new_keys,
+                approvals,
+                proposed_at,
+            },
+            rem,
+        ))
+    }
+}
+
+pub mod gens {
+    use proptest::prelude::*;
+
+    use types::{account::RecoveryConfig, gens::account_hash_arb, BlockTime};
+
+    use super::PendingRecovery;
+    use crate::account::associated_keys::gens::associated_keys_arb;
+
+    pub fn recovery_config_arb() -> impl Strategy<Value = RecoveryConfig> {
+        (
+            proptest::collection::btree_set(account_hash_arb(), 1..5),
+            any::<u64>(),
+        )
+            .prop_map(|(guardians, delay)| {
+                let threshold = guardians.len() as u8;
+                RecoveryConfig::new(guardians, threshold, delay)
+            })
+    }
+
+    prop_compose! {
+        pub fn pending_recovery_arb()(
+            new_keys in associated_keys_arb(3),
+            approvals in proptest::collection::btree_set(account_hash_arb(), 1..5),
+            proposed_at in any::<u64>(),
+        ) -> PendingRecovery {
+            let mut approvals = approvals.into_iter();
+            let mut pending = PendingRecovery::new(
+                approvals.next().unwrap(),
+                new_keys,
+                BlockTime::new(proposed_at),
+            );
+            approvals.for_each(|guardian| pending.approve(guardian));
+            pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{account::Weight, bytesrepr};
+
+    use super::*;
+
+    #[test]
+    fn serialization_roundtrip() {
+        let guardian = AccountHash::new([1; 32]);
+        let new_keys = AssociatedKeys::new(AccountHash::new([2; 32]), Weight::new(1));
+        let mut pending = PendingRecovery::new(guardian, new_keys, BlockTime::new(1_000));
+        pending.approve(AccountHash::new([3; 32]));
+        bytesrepr::test_serialization_roundtrip(&pending);
+    }
+}
@@ -11,10 +11,12 @@ use crate::{account::Account, TypeMismatch};
 #[repr(u8)]
 enum Tag {
     CLValue = 0,
-    Account = 1,
+    /// An account written before its layout was versioned, only ever read.
+    LegacyAccount = 1,
     ContractWasm = 2,
     Contract = 3,
     ContractPackage = 4,
+    Account = 5,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -182,6 +184,8 @@ impl FromBytes for StoredValue {
         match tag {
             tag if tag == Tag::CLValue as u8 => CLValue::from_bytes(remainder)
                 .map(|(cl_value, remainder)| (StoredValue::CLValue(cl_value), remainder)),
+            tag if tag == Tag::LegacyAccount as u8 => Account::from_legacy_bytes(remainder)
+                .map(|(account, remainder)| (StoredValue::Account(account), remainder)),
             tag if tag == Tag::Account as u8 => Account::from_bytes(remainder)
                 .map(|(account, remainder)| (StoredValue::Account(account), remainder)),
             tag if tag == Tag::ContractWasm as u8 => {
@@ -225,7 +229,14 @@ pub mod gens {
 mod tests {
     use proptest::proptest;
 
+    use types::{
+        account::{AccountHash, Weight},
+        contracts::NamedKeys,
+        AccessRights, URef,
+    };
+
     use super::*;
+    use crate::account::{ActionThresholds, AssociatedKeys};
 
     proptest! {
         #[test]
@@ -233,4 +244,37 @@ mod tests {
             bytesrepr::test_serialization_roundtrip(&v);
         }
     }
+
+    #[test]
+    fn should_deserialize_legacy_account_followed_by_other_bytes() {
+        let account_hash = AccountHash::new([1; 32]);
+        let named_keys = NamedKeys::new();
+        let main_purse = URef::new([2; 32], AccessRights::READ_ADD_WRITE);
+        let associated_keys = AssociatedKeys::new(account_hash, Weight::new(1));
+        let action_thresholds = ActionThresholds::default();
+
+        let mut bytes = vec![Tag::LegacyAccount as u8];
+        bytes.append(&mut account_hash.to_bytes().unwrap());
+        bytes.append(&mut named_keys.to_bytes().unwrap());
+        bytes.append(&mut main_purse.to_bytes().unwrap());
+        bytes.append(&mut associated_keys.to_bytes().unwrap());
+        bytes.append(&mut action_thresholds.to_bytes().unwrap());
+        let account = Account::new(
+            account_hash,
+            named_keys,
+            main_purse,
+            associated_keys,
+            action_thresholds,
+        );
+        let legacy_length = bytes.len();
+        bytes.append(&mut StoredValue::Account(account.clone()).to_bytes().unwrap());
+
+        let (legacy_value, rem) = StoredValue::from_bytes(&bytes).expect("should deserialize");
+        assert_eq!(legacy_value, StoredValue::Account(account.clone()));
+        assert_eq!(rem, &bytes[legacy_length..]);
+
+        let (value, rem) = StoredValue::from_bytes(rem).expect("should deserialize");
+        assert_eq!(value, StoredValue::Account(account));
+        assert!(rem.is_empty());
+    }
 }
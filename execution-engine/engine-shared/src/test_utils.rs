@@ -1,6 +1,6 @@
 //! Some functions to use in tests.
 
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{wasm_costs::WasmCosts, MEM_PAGES};
 use types::{account::AccountHash, contracts::NamedKeys, AccessRights, Key, URef};
 
 use crate::{account::Account, stored_value::StoredValue};
@@ -24,6 +24,7 @@ pub fn wasm_costs_mock() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        max_memory: MEM_PAGES,
     }
 }
 
@@ -39,5 +40,6 @@ pub fn wasm_costs_free() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 1,
         opcodes_div: 1,
+        max_memory: MEM_PAGES,
     }
 }
@@ -1,4 +1,6 @@
-use engine_wasm_prep::wasm_costs::{WasmCosts, WASM_COSTS_SERIALIZED_LENGTH};
+use engine_wasm_prep::wasm_costs::{
+    WasmCosts, LEGACY_WASM_COSTS_SERIALIZED_LENGTH, WASM_COSTS_SERIALIZED_LENGTH,
+};
 use std::collections::BTreeMap;
 use types::{
     bytesrepr::{self, FromBytes, ToBytes},
@@ -6,9 +8,16 @@ use types::{
 };
 
 const PROTOCOL_DATA_SERIALIZED_LENGTH: usize = WASM_COSTS_SERIALIZED_LENGTH + 3 * KEY_HASH_LENGTH;
+const LEGACY_PROTOCOL_DATA_SERIALIZED_LENGTH: usize =
+    LEGACY_WASM_COSTS_SERIALIZED_LENGTH + 3 * KEY_HASH_LENGTH;
 const DEFAULT_ADDRESS: [u8; 32] = [0; 32];
 
 /// Represents a protocol's data. Intended to be associated with a given protocol version.
+///
+/// The system contract hashes are those of the versions installed at genesis or by the upgrade to
+/// this protocol version.  As each system contract lives in a versioned contract package, newer
+/// versions may since have been added to it; see
+/// [`resolve_current_versions`](ProtocolData::resolve_current_versions).
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ProtocolData {
     wasm_costs: WasmCosts,
@@ -48,11 +57,23 @@ impl ProtocolData {
         }
     }
 
-    /// Creates a new, partially-valid [`ProtocolData`] value where only the mint URef is known.
+    /// Creates a new, partially-valid [`ProtocolData`] value where only the costs are known.
+    ///
+    /// Used during `commit_genesis` before any system contract is installed.
+    pub fn partial_with_wasm_costs(wasm_costs: WasmCosts) -> Self {
+        ProtocolData {
+            wasm_costs,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new, partially-valid [`ProtocolData`] value where only the costs and the mint URef
+    /// are known.
     ///
     /// Used during `commit_genesis` before all system contracts' URefs are known.
-    pub fn partial_with_mint(mint: ContractHash) -> Self {
+    pub fn partial_with_mint(wasm_costs: WasmCosts, mint: ContractHash) -> Self {
         ProtocolData {
+            wasm_costs,
             mint,
             ..Default::default()
         }
@@ -121,6 +142,28 @@ impl ProtocolData {
         }
         true
     }
+
+    /// Returns a copy of `self` with each installed system contract replaced by the current
+    /// enabled version of its contract package, as looked up by `current_version`.
+    pub fn resolve_current_versions<F, E>(&self, mut current_version: F) -> Result<Self, E>
+    where
+        F: FnMut(ContractHash) -> Result<ContractHash, E>,
+    {
+        let mut resolve = |contract_hash: ContractHash| {
+            if contract_hash == DEFAULT_ADDRESS {
+                Ok(contract_hash)
+            } else {
+                current_version(contract_hash)
+            }
+        };
+
+        Ok(ProtocolData {
+            wasm_costs: self.wasm_costs,
+            mint: resolve(self.mint)?,
+            proof_of_stake: resolve(self.proof_of_stake)?,
+            standard_payment: resolve(self.standard_payment)?,
+        })
+    }
 }
 
 impl ToBytes for ProtocolData {
@@ -138,9 +181,15 @@ impl ToBytes for ProtocolData {
     }
 }
 
-impl FromBytes for ProtocolData {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (wasm_costs, rem) = WasmCosts::from_bytes(bytes)?;
+impl ProtocolData {
+    fn from_bytes_with<F>(
+        bytes: &[u8],
+        wasm_costs_from_bytes: F,
+    ) -> Result<(Self, &[u8]), bytesrepr::Error>
+    where
+        F: Fn(&[u8]) -> Result<(WasmCosts, &[u8]), bytesrepr::Error>,
+    {
+        let (wasm_costs, rem) = wasm_costs_from_bytes(bytes)?;
         let (mint, rem) = HashAddr::from_bytes(rem)?;
         let (proof_of_stake, rem) = HashAddr::from_bytes(rem)?;
         let (standard_payment, rem) = HashAddr::from_bytes(rem)?;
@@ -157,6 +206,18 @@ impl FromBytes for ProtocolData {
     }
 }
 
+impl FromBytes for ProtocolData {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        // Protocol data is only ever stored as a record of its own, so the legacy serialization,
+        // whose Wasm costs predate `max_memory`, is told apart from the current one by its length.
+        if bytes.len() == LEGACY_PROTOCOL_DATA_SERIALIZED_LENGTH {
+            ProtocolData::from_bytes_with(bytes, WasmCosts::from_legacy_bytes)
+        } else {
+            ProtocolData::from_bytes_with(bytes, WasmCosts::from_bytes)
+        }
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod gens {
     use proptest::prop_compose;
@@ -187,8 +248,11 @@ pub(crate) mod gens {
 mod tests {
     use proptest::proptest;
 
-    use engine_wasm_prep::wasm_costs::WasmCosts;
-    use types::{bytesrepr, ContractHash};
+    use engine_wasm_prep::{wasm_costs::WasmCosts, MEM_PAGES};
+    use types::{
+        bytesrepr::{self, ToBytes},
+        ContractHash,
+    };
 
     use super::{gens, ProtocolData};
 
@@ -204,6 +268,7 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 3,
             opcodes_div: 8,
+            max_memory: MEM_PAGES,
         }
     }
 
@@ -219,6 +284,7 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            max_memory: MEM_PAGES,
         }
     }
 
@@ -308,6 +374,59 @@ mod tests {
         assert_eq!(actual[1], standard_payment_reference);
     }
 
+    #[test]
+    fn should_resolve_current_versions_of_installed_system_contracts() {
+        let costs = wasm_costs_mock();
+        let protocol_data = ProtocolData::partial_with_mint(costs, [1u8; 32]);
+
+        let resolved = protocol_data
+            .resolve_current_versions::<_, ()>(|contract_hash| {
+                assert_eq!(contract_hash, [1u8; 32]);
+                Ok([4u8; 32])
+            })
+            .expect("should resolve");
+
+        assert_eq!(resolved.wasm_costs(), &costs);
+        assert_eq!(resolved.mint(), [4u8; 32]);
+        assert_eq!(resolved.system_contracts(), vec![[4u8; 32]]);
+
+        let result = protocol_data.resolve_current_versions(|_| Err("no enabled version"));
+        assert_eq!(result, Err("no enabled version"));
+    }
+
+    #[test]
+    fn should_deserialize_legacy_protocol_data_by_its_length() {
+        let wasm_costs = WasmCosts {
+            regular: u32::MAX,
+            ..wasm_costs_mock()
+        };
+        let legacy_fields = [
+            wasm_costs.regular,
+            wasm_costs.div,
+            wasm_costs.mul,
+            wasm_costs.mem,
+            wasm_costs.initial_mem,
+            wasm_costs.grow_mem,
+            wasm_costs.memcpy,
+            wasm_costs.max_stack_height,
+            wasm_costs.opcodes_mul,
+            wasm_costs.opcodes_div,
+        ];
+        let mut legacy_bytes = Vec::new();
+        for field in legacy_fields.iter() {
+            legacy_bytes.append(&mut field.to_bytes().unwrap());
+        }
+        for hash in &[[1u8; 32], [2u8; 32], [3u8; 32]] {
+            legacy_bytes.append(&mut hash.to_bytes().unwrap());
+        }
+
+        let protocol_data: ProtocolData = bytesrepr::deserialize(legacy_bytes).unwrap();
+        assert_eq!(
+            protocol_data,
+            ProtocolData::new(wasm_costs, [1u8; 32], [2u8; 32], [3u8; 32])
+        );
+    }
+
     proptest! {
         #[test]
         fn should_serialize_and_deserialize_with_arbitrary_values(
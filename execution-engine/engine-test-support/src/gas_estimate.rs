@@ -0,0 +1,48 @@
+use engine_core::engine_state::estimate::EstimateResult;
+use types::U512;
+
+/// The gas consumed by a [`Session`](crate::Session) run as a dry run, i.e. built with
+/// [`with_dry_run()`](crate::SessionBuilder::with_dry_run).
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct GasEstimate {
+    payment_cost: U512,
+    session_cost: U512,
+    error_message: Option<String>,
+}
+
+impl GasEstimate {
+    /// Returns the gas consumed by the payment code.
+    pub fn payment_cost(&self) -> U512 {
+        self.payment_cost
+    }
+
+    /// Returns the gas consumed by the session code.
+    pub fn session_cost(&self) -> U512 {
+        self.session_cost
+    }
+
+    /// Returns the gas consumed by the payment and session code together.
+    pub fn total_cost(&self) -> U512 {
+        self.payment_cost + self.session_cost
+    }
+
+    /// Returns `true` if the deploy would succeed.
+    pub fn is_success(&self) -> bool {
+        self.error_message.is_none()
+    }
+
+    /// Returns the error the deploy would fail with, if any.
+    pub fn error_message(&self) -> Option<&str> {
+        self.error_message.as_deref()
+    }
+}
+
+impl From<&EstimateResult> for GasEstimate {
+    fn from(estimate_result: &EstimateResult) -> Self {
+        GasEstimate {
+            payment_cost: estimate_result.payment_cost().value(),
+            session_cost: estimate_result.session_cost().value(),
+            error_message: estimate_result.error().map(ToString::to_string),
+        }
+    }
+}
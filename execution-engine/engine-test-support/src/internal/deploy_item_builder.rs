@@ -1,12 +1,15 @@
 use std::{collections::BTreeSet, path::Path};
 
 use engine_core::{
-    engine_state::{deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem},
+    engine_state::{
+        deploy_item::{DeployItem, DeployTtl},
+        executable_deploy_item::ExecutableDeployItem,
+    },
     DeployHash,
 };
 use types::{
-    account::AccountHash, bytesrepr::ToBytes, contracts::ContractVersion, ContractHash, HashAddr,
-    RuntimeArgs,
+    account::AccountHash, bytesrepr::ToBytes, contracts::ContractVersion, BlockTime, ContractHash,
+    HashAddr, RuntimeArgs,
 };
 
 use crate::internal::utils;
@@ -19,6 +22,7 @@ struct DeployItemData {
     pub gas_price: u64,
     pub authorization_keys: BTreeSet<AccountHash>,
     pub deploy_hash: DeployHash,
+    pub ttl: Option<DeployTtl>,
 }
 
 pub struct DeployItemBuilder {
@@ -215,6 +219,12 @@ impl DeployItemBuilder {
         self
     }
 
+    /// Makes the deploy expire `ttl` milliseconds after `timestamp`.
+    pub fn with_ttl(mut self, timestamp: u64, ttl: u64) -> Self {
+        self.deploy_item.ttl = Some(DeployTtl::new(BlockTime::new(timestamp), ttl));
+        self
+    }
+
     pub fn build(self) -> DeployItem {
         DeployItem {
             address: self
@@ -232,6 +242,7 @@ impl DeployItemBuilder {
             gas_price: self.deploy_item.gas_price,
             authorization_keys: self.deploy_item.authorization_keys,
             deploy_hash: self.deploy_item.deploy_hash,
+            ttl: self.deploy_item.ttl,
         }
     }
 
@@ -5,14 +5,14 @@ use engine_core::{
         executable_deploy_item::ExecutableDeployItem, execution_effect::ExecutionEffect,
         EngineConfig, EngineState,
     },
-    execution::{self, AddressGenerator},
+    execution::{self, AddressGenerator, WasmInstance},
+    resolvers::create_preprocessor,
     runtime::{self, Runtime},
     runtime_context::RuntimeContext,
 };
 use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
 use engine_shared::{gas::Gas, newtypes::CorrelationId};
 use engine_storage::{global_state::StateProvider, protocol_data::ProtocolData};
-use engine_wasm_prep::Preprocessor;
 use types::{
     account::AccountHash, bytesrepr::FromBytes, BlockTime, CLTyped, EntryPointType, Key, Phase,
     ProtocolVersion, RuntimeArgs, URef, U512,
@@ -103,6 +103,7 @@ where
         gas_counter,
         fn_store_id,
         address_generator,
+        Default::default(),
         protocol_version,
         correlation_id,
         phase,
@@ -117,22 +118,28 @@ where
 
     let wasm_costs = *DEFAULT_WASM_COSTS;
 
-    let preprocessor = Preprocessor::new(wasm_costs);
+    let preprocessor = create_preprocessor(wasm_costs, protocol_version);
     let parity_module = builder
         .get_engine_state()
         .get_module(
             tracking_copy,
             &deploy_item,
             &account,
+            &BTreeSet::new(),
             correlation_id,
             &preprocessor,
             &protocol_version,
         )
         .expect("should get wasm module");
 
-    let (instance, memory) =
-        runtime::instance_and_memory(parity_module.clone().take_module(), protocol_version)
-            .expect("should be able to make wasm instance from module");
+    let (instance, memory) = runtime::instance_and_memory(
+        parity_module.clone().take_module(),
+        protocol_version,
+        wasm_costs.max_memory,
+        config.wasm_backend(),
+        false,
+    )
+    .expect("should be able to make wasm instance from module");
 
     let mut runtime = Runtime::new(
         config,
@@ -142,7 +149,7 @@ where
         context,
     );
 
-    match instance.invoke_export(entry_point_name, &[], &mut runtime) {
+    match instance.invoke_export(entry_point_name, &mut runtime) {
         Ok(_) => None,
         Err(e) => {
             if let Some(host_error) = e.as_host_error() {
@@ -2,7 +2,10 @@ use std::convert::TryInto;
 
 use rand::Rng;
 
-use engine_core::engine_state::{deploy_item::DeployItem, execute_request::ExecuteRequest};
+use engine_core::engine_state::{
+    deploy_item::DeployItem,
+    execute_request::{ExecuteRequest, ExecutionMode},
+};
 use types::{
     account::AccountHash, contracts::ContractVersion, runtime_args, ContractHash, ProtocolVersion,
     RuntimeArgs,
@@ -46,6 +49,26 @@ impl ExecuteRequestBuilder {
         self
     }
 
+    pub fn with_proposer(mut self, proposer: AccountHash) -> Self {
+        self.execute_request.proposer = Some(proposer);
+        self
+    }
+
+    pub fn with_execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        self.execute_request.execution_mode = execution_mode;
+        self
+    }
+
+    pub fn with_tracing(mut self) -> Self {
+        self.execute_request.enable_tracing = true;
+        self
+    }
+
+    pub fn with_profiling(mut self) -> Self {
+        self.execute_request.enable_profiling = true;
+        self
+    }
+
     pub fn build(self) -> ExecuteRequest {
         self.execute_request
     }
@@ -10,6 +10,7 @@ use lazy_static::lazy_static;
 use num_traits::identities::Zero;
 
 use engine_core::engine_state::{
+    engine_config::EngineConfig,
     genesis::{ExecConfig, GenesisAccount, GenesisConfig},
     run_genesis_request::RunGenesisRequest,
 };
@@ -53,6 +54,9 @@ lazy_static! {
     };
     pub static ref DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
     pub static ref DEFAULT_PAYMENT: U512 = 100_000_000.into();
+    pub static ref DEFAULT_ENGINE_CONFIG: EngineConfig = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"));
     pub static ref DEFAULT_WASM_COSTS: WasmCosts = test_utils::wasm_costs_mock();
     pub static ref DEFAULT_EXEC_CONFIG: ExecConfig = {
         let mint_installer_bytes;
@@ -1,7 +1,9 @@
+use engine_core::engine_state::genesis::StakingLimits;
 use engine_grpc_server::engine_server::{
     ipc::{
-        ChainSpec_ActivationPoint, ChainSpec_CostTable_WasmCosts, ChainSpec_UpgradePoint,
-        DeployCode, UpgradeRequest,
+        ChainSpec_ActivationPoint, ChainSpec_CostTable_WasmCosts, ChainSpec_FeeBurnRate,
+        ChainSpec_InflationRate, ChainSpec_StakingLimits, ChainSpec_UpgradePoint, DeployCode,
+        UpgradeRequest,
     },
     state,
 };
@@ -15,6 +17,9 @@ pub struct UpgradeRequestBuilder {
     upgrade_installer: DeployCode,
     new_costs: Option<ChainSpec_CostTable_WasmCosts>,
     activation_point: ChainSpec_ActivationPoint,
+    new_staking_limits: Option<ChainSpec_StakingLimits>,
+    new_inflation_rate: Option<ChainSpec_InflationRate>,
+    new_fee_burn_rate: Option<ChainSpec_FeeBurnRate>,
 }
 
 impl UpgradeRequestBuilder {
@@ -54,6 +59,7 @@ impl UpgradeRequestBuilder {
         new_costs.set_max_stack_height(wasm_costs.max_stack_height);
         new_costs.set_mem(wasm_costs.mem);
         new_costs.set_memcpy(wasm_costs.memcpy);
+        new_costs.set_max_memory(wasm_costs.max_memory);
         self.new_costs = Some(new_costs);
         self
     }
@@ -67,6 +73,25 @@ impl UpgradeRequestBuilder {
         self
     }
 
+    pub fn with_new_staking_limits(mut self, staking_limits: StakingLimits) -> Self {
+        self.new_staking_limits = Some(staking_limits.into());
+        self
+    }
+
+    pub fn with_new_annual_inflation_rate(mut self, annual_inflation_rate: u64) -> Self {
+        let mut new_inflation_rate = ChainSpec_InflationRate::new();
+        new_inflation_rate.set_annual_rate(annual_inflation_rate);
+        self.new_inflation_rate = Some(new_inflation_rate);
+        self
+    }
+
+    pub fn with_new_fee_burn_rate(mut self, fee_burn_rate: u8) -> Self {
+        let mut new_fee_burn_rate = ChainSpec_FeeBurnRate::new();
+        new_fee_burn_rate.set_percentage(fee_burn_rate.into());
+        self.new_fee_burn_rate = Some(new_fee_burn_rate);
+        self
+    }
+
     pub fn build(self) -> UpgradeRequest {
         let mut upgrade_point = ChainSpec_UpgradePoint::new();
         upgrade_point.set_activation_point(self.activation_point);
@@ -79,6 +104,15 @@ impl UpgradeRequestBuilder {
                 upgrade_point.set_new_costs(cost_table);
             }
         }
+        if let Some(new_staking_limits) = self.new_staking_limits {
+            upgrade_point.set_new_staking_limits(new_staking_limits);
+        }
+        if let Some(new_inflation_rate) = self.new_inflation_rate {
+            upgrade_point.set_new_inflation_rate(new_inflation_rate);
+        }
+        if let Some(new_fee_burn_rate) = self.new_fee_burn_rate {
+            upgrade_point.set_new_fee_burn_rate(new_fee_burn_rate);
+        }
         upgrade_point.set_protocol_version(self.new_protocol_version);
         upgrade_point.set_upgrade_installer(self.upgrade_installer);
 
@@ -98,6 +132,9 @@ impl Default for UpgradeRequestBuilder {
             upgrade_installer: Default::default(),
             new_costs: None,
             activation_point: Default::default(),
+            new_staking_limits: None,
+            new_inflation_rate: None,
+            new_fee_burn_rate: None,
         }
     }
 }
@@ -8,21 +8,25 @@ use std::{
     sync::Arc,
 };
 
-use grpc::RequestOptions;
+use grpc::{RequestOptions, SingleResponse};
 use lmdb::DatabaseFlags;
 use log::LevelFilter;
 
 use engine_core::{
     engine_state::{
-        execute_request::ExecuteRequest, execution_result::ExecutionResult,
-        run_genesis_request::RunGenesisRequest, EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
+        estimate::{EstimateRequest, EstimateResult},
+        execute_request::{ExecuteRequest, ExecutionMode},
+        execution_result::{ExecutionResult, SequentialExecutionResult},
+        run_genesis_request::RunGenesisRequest,
+        EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
     },
-    execution,
+    execution::{self, ExecutionTrace, GasProfile},
 };
 use engine_grpc_server::engine_server::{
     ipc::{
-        CommitRequest, CommitResponse, GenesisResponse, QueryRequest, UpgradeRequest,
-        UpgradeResponse,
+        CommitRequest, CommitResponse, GenesisResponse, GetContractAbiRequest,
+        ProofOfStakeStepRequest, ProofOfStakeStepResponse, QueryRequest, ReadTotalSupplyRequest,
+        UpgradeRequest, UpgradeResponse,
     },
     ipc_grpc::ExecutionEngineService,
     mappings::{MappingError, TransformMap},
@@ -50,7 +54,7 @@ use types::{
     CLValue, Contract, ContractHash, ContractWasm, Key, URef, U512,
 };
 
-use crate::internal::utils;
+use crate::internal::{utils, DEFAULT_ENGINE_CONFIG, DEFAULT_PROTOCOL_VERSION};
 
 /// LMDB initial map size is calculated based on DEFAULT_LMDB_PAGES and systems page size.
 ///
@@ -70,6 +74,10 @@ pub struct WasmTestBuilder<S> {
     engine_state: Rc<EngineState<S>>,
     /// [`ExecutionResult`] is wrapped in [`Rc`] to work around a missing [`Clone`] implementation
     exec_responses: Vec<Vec<Rc<ExecutionResult>>>,
+    /// Execution traces of the deploys in `exec_responses`, if tracing was enabled
+    exec_traces: Vec<Vec<Option<ExecutionTrace>>>,
+    /// [`EstimateResult`] is wrapped in [`Rc`] to work around a missing [`Clone`] implementation
+    estimate_results: Vec<Rc<EstimateResult>>,
     upgrade_responses: Vec<UpgradeResponse>,
     genesis_hash: Option<Vec<u8>>,
     post_state_hash: Option<Vec<u8>>,
@@ -99,16 +107,14 @@ impl<S> WasmTestBuilder<S> {
 impl Default for InMemoryWasmTestBuilder {
     fn default() -> Self {
         Self::initialize_logging();
-        let engine_config = EngineConfig::new()
-            .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
-            .with_enable_bonding(cfg!(feature = "enable-bonding"));
-
         let global_state = InMemoryGlobalState::empty().expect("should create global state");
-        let engine_state = EngineState::new(global_state, engine_config);
+        let engine_state = EngineState::new(global_state, *DEFAULT_ENGINE_CONFIG);
 
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            exec_traces: Vec::new(),
+            estimate_results: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
@@ -130,6 +136,8 @@ impl<S> Clone for WasmTestBuilder<S> {
         WasmTestBuilder {
             engine_state: Rc::clone(&self.engine_state),
             exec_responses: self.exec_responses.clone(),
+            exec_traces: self.exec_traces.clone(),
+            estimate_results: self.estimate_results.clone(),
             upgrade_responses: self.upgrade_responses.clone(),
             genesis_hash: self.genesis_hash.clone(),
             post_state_hash: self.post_state_hash.clone(),
@@ -156,6 +164,15 @@ impl<S> WasmTestResult<S> {
 }
 
 impl InMemoryWasmTestBuilder {
+    pub fn new_with_config(engine_config: EngineConfig) -> Self {
+        let global_state = InMemoryGlobalState::empty().expect("should create global state");
+        let engine_state = EngineState::new(global_state, engine_config);
+        WasmTestBuilder {
+            engine_state: Rc::new(engine_state),
+            ..Default::default()
+        }
+    }
+
     pub fn new(
         global_state: InMemoryGlobalState,
         engine_config: EngineConfig,
@@ -198,6 +215,8 @@ impl LmdbWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            exec_traces: Vec::new(),
+            estimate_results: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
@@ -259,6 +278,8 @@ impl LmdbWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            exec_traces: Vec::new(),
+            estimate_results: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: Some(post_state_hash),
@@ -286,7 +307,7 @@ impl LmdbWasmTestBuilder {
 
 impl<S> WasmTestBuilder<S>
 where
-    S: StateProvider,
+    S: StateProvider + Sync,
     S::Error: Into<execution::Error>,
     EngineState<S>: ExecutionEngineService,
 {
@@ -295,6 +316,8 @@ where
         WasmTestBuilder {
             engine_state: result.0.engine_state,
             exec_responses: Vec::new(),
+            exec_traces: Vec::new(),
+            estimate_results: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: result.0.genesis_hash,
             post_state_hash: result.0.post_state_hash,
@@ -395,7 +418,8 @@ where
             .run_execute(CorrelationId::new(), exec_request);
         assert!(exec_response.is_ok());
         // Parse deploy results
-        let execution_results = exec_response.as_ref().unwrap();
+        let (execution_results, execution_traces): (Vec<_>, Vec<_>) =
+            exec_response.unwrap().into_iter().unzip();
         // Cache transformations
         self.transforms.extend(
             execution_results
@@ -403,7 +427,83 @@ where
                 .map(|res| res.effect().transforms.clone()),
         );
         self.exec_responses
-            .push(exec_response.unwrap().into_iter().map(Rc::new).collect());
+            .push(execution_results.into_iter().map(Rc::new).collect());
+        self.exec_traces.push(execution_traces);
+        self
+    }
+
+    /// Executes each deploy of `exec_request` as a dry run on the latest post-state hash, to
+    /// estimate the gas it consumes.
+    ///
+    /// Nothing is committed, and the results are not available to
+    /// [`commit`](WasmTestBuilder::commit) or [`expect_success`](WasmTestBuilder::expect_success);
+    /// use [`get_estimate_result`](WasmTestBuilder::get_estimate_result) instead.
+    pub fn dry_run(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
+        let parent_state_hash: Blake2bHash = self
+            .post_state_hash
+            .as_ref()
+            .expect("expected post_state_hash")
+            .as_slice()
+            .try_into()
+            .expect("expected a valid hash");
+        for deploy_item in exec_request.take_deploys() {
+            let deploy_item = deploy_item.expect("should have a valid deploy item");
+            let estimate_request = EstimateRequest::new(
+                parent_state_hash,
+                exec_request.block_time,
+                deploy_item,
+                exec_request.protocol_version,
+            );
+            let estimate_result = self
+                .engine_state
+                .run_estimate(CorrelationId::new(), estimate_request)
+                .expect("should estimate deploy");
+            self.estimate_results.push(Rc::new(estimate_result));
+        }
+        self
+    }
+
+    /// Executes the deploys of `exec_request` in sequential mode on the latest post-state hash,
+    /// expects their combined effect to be committed, and overwrites existing cached post state
+    /// hash with a new one.
+    ///
+    /// There is no need to call [`commit`](WasmTestBuilder::commit) afterwards.
+    pub fn exec_sequential(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
+        let exec_request = {
+            let hash = self
+                .post_state_hash
+                .clone()
+                .expect("expected post_state_hash");
+            exec_request.parent_state_hash =
+                hash.as_slice().try_into().expect("expected a valid hash");
+            exec_request.execution_mode = ExecutionMode::Sequential;
+            exec_request
+        };
+        let sequential_execution_result = self
+            .engine_state
+            .run_execute_sequential(CorrelationId::new(), exec_request)
+            .expect("should execute deploys sequentially");
+        match sequential_execution_result {
+            SequentialExecutionResult::Success {
+                execution_results,
+                effect,
+                post_state_hash,
+                bonded_validators,
+            } => {
+                let (execution_results, execution_traces): (Vec<_>, Vec<_>) =
+                    execution_results.into_iter().unzip();
+                self.transforms.push(effect.transforms);
+                self.exec_responses
+                    .push(execution_results.into_iter().map(Rc::new).collect());
+                self.exec_traces.push(execution_traces);
+                self.post_state_hash = Some(post_state_hash.to_vec());
+                self.bonded_validators.push(bonded_validators);
+            }
+            other => panic!(
+                "Expected sequential execution success but received a failure instead: {:?}",
+                other
+            ),
+        }
         self
     }
 
@@ -458,6 +558,93 @@ where
             .collect::<Result<HashMap<AccountHash, U512>, MappingError>>()
             .unwrap();
         self.bonded_validators.push(bonded_validators);
+        // System contracts can gain new versions without a protocol upgrade, so follow them to the
+        // current version of their packages.
+        self.mint_contract_hash = self
+            .mint_contract_hash
+            .map(|hash| self.current_contract_version(hash));
+        self.pos_contract_hash = self
+            .pos_contract_hash
+            .map(|hash| self.current_contract_version(hash));
+        self.standard_payment_hash = self
+            .standard_payment_hash
+            .map(|hash| self.current_contract_version(hash));
+        self
+    }
+
+    /// Returns the current enabled version of the contract package containing `contract_hash`, or
+    /// `contract_hash` itself if it can't be resolved.
+    fn current_contract_version(&self, contract_hash: ContractHash) -> ContractHash {
+        let contract_package_hash = match self.query(None, Key::Hash(contract_hash), &[]) {
+            Ok(StoredValue::Contract(contract)) => contract.contract_package_hash(),
+            _ => return contract_hash,
+        };
+        match self.query(None, Key::Hash(contract_package_hash), &[]) {
+            Ok(StoredValue::ContractPackage(contract_package)) => contract_package
+                .current_contract_hash()
+                .unwrap_or(contract_hash),
+            _ => contract_hash,
+        }
+    }
+
+    /// Runs the Proof of Stake auction at `block_time` on the latest post-state hash, expects a
+    /// successful response, and overwrites existing cached post state hash with a new one.
+    pub fn run_auction(&mut self, block_time: u64) -> &mut Self {
+        self.run_proof_of_stake_step(block_time, "auction", |engine_state, request| {
+            engine_state.run_auction(RequestOptions::new(), request)
+        })
+    }
+
+    /// Mints the seigniorage accrued up to `block_time` on the latest post-state hash, expects a
+    /// successful response, and overwrites existing cached post state hash with a new one.
+    pub fn mint_seigniorage(&mut self, block_time: u64) -> &mut Self {
+        self.run_proof_of_stake_step(block_time, "seigniorage", |engine_state, request| {
+            engine_state.mint_seigniorage(RequestOptions::new(), request)
+        })
+    }
+
+    /// Runs a Proof of Stake step at `block_time` on the latest post-state hash with `run_step`,
+    /// expects a successful response, and overwrites existing cached post state hash with a new
+    /// one.
+    fn run_proof_of_stake_step<F>(
+        &mut self,
+        block_time: u64,
+        step_name: &str,
+        run_step: F,
+    ) -> &mut Self
+    where
+        F: FnOnce(
+            &EngineState<S>,
+            ProofOfStakeStepRequest,
+        ) -> SingleResponse<ProofOfStakeStepResponse>,
+    {
+        let mut request = ProofOfStakeStepRequest::new();
+        request.set_parent_state_hash(
+            self.post_state_hash
+                .clone()
+                .expect("expected post_state_hash"),
+        );
+        request.set_block_time(block_time);
+        request.set_protocol_version((*DEFAULT_PROTOCOL_VERSION).into());
+
+        let mut response = run_step(&self.engine_state, request)
+            .wait_drop_metadata()
+            .unwrap_or_else(|_| panic!("should run {}", step_name));
+        if !response.has_success() {
+            panic!(
+                "Expected {} success but received a failure instead: {:?}",
+                step_name, response
+            );
+        }
+        let mut commit_success = response.take_success();
+        self.post_state_hash = Some(commit_success.take_poststate_hash().to_vec());
+        let bonded_validators = commit_success
+            .take_bonded_validators()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<HashMap<AccountHash, U512>, MappingError>>()
+            .unwrap();
+        self.bonded_validators.push(bonded_validators);
         self
     }
 
@@ -583,6 +770,38 @@ where
         self.exec_responses.len()
     }
 
+    /// Returns the execution trace of the first deploy of the `index`th call to
+    /// [`exec`](WasmTestBuilder::exec), if it was executed with tracing enabled.
+    pub fn get_exec_trace(&self, index: usize) -> Option<&ExecutionTrace> {
+        self.exec_traces.get(index)?.get(0)?.as_ref()
+    }
+
+    /// Returns the execution trace of the first deploy of the last call to
+    /// [`exec`](WasmTestBuilder::exec), if it was executed with tracing enabled.
+    pub fn last_exec_trace(&self) -> Option<&ExecutionTrace> {
+        self.exec_traces.last()?.get(0)?.as_ref()
+    }
+
+    /// Returns the gas profile of the first deploy of the `index`th call to
+    /// [`exec`](WasmTestBuilder::exec), if it was executed with tracing or profiling enabled.
+    pub fn get_gas_profile(&self, index: usize) -> Option<GasProfile> {
+        self.get_exec_trace(index).map(GasProfile::from)
+    }
+
+    /// Returns the gas profile of the first deploy of the last call to
+    /// [`exec`](WasmTestBuilder::exec), if it was executed with tracing or profiling enabled.
+    pub fn last_gas_profile(&self) -> Option<GasProfile> {
+        self.last_exec_trace().map(GasProfile::from)
+    }
+
+    pub fn get_estimate_result(&self, index: usize) -> Option<&EstimateResult> {
+        self.estimate_results.get(index).map(AsRef::as_ref)
+    }
+
+    pub fn last_estimate_result(&self) -> Option<&EstimateResult> {
+        self.estimate_results.last().map(AsRef::as_ref)
+    }
+
     pub fn get_upgrade_response(&self, index: usize) -> Option<&UpgradeResponse> {
         self.upgrade_responses.get(index)
     }
@@ -617,6 +836,57 @@ where
             .expect("should parse balance into a U512")
     }
 
+    pub fn get_total_supply(&self) -> U512 {
+        let mut read_total_supply_request = ReadTotalSupplyRequest::new();
+        read_total_supply_request.set_state_hash(
+            self.post_state_hash
+                .clone()
+                .expect("builder must have a post-state hash"),
+        );
+        read_total_supply_request.set_protocol_version((*DEFAULT_PROTOCOL_VERSION).into());
+
+        let mut read_total_supply_response = self
+            .engine_state
+            .read_total_supply(RequestOptions::new(), read_total_supply_request)
+            .wait_drop_metadata()
+            .expect("should read total supply");
+        if !read_total_supply_response.has_success() {
+            panic!(
+                "Expected total supply but received a failure instead: {:?}",
+                read_total_supply_response
+            );
+        }
+        read_total_supply_response
+            .take_success()
+            .try_into()
+            .expect("should parse total supply into a U512")
+    }
+
+    /// Returns the ABI JSON document of the contract stored under `contract_hash` at the latest
+    /// post-state hash.
+    pub fn get_contract_abi(&self, contract_hash: ContractHash) -> String {
+        let mut get_contract_abi_request = GetContractAbiRequest::new();
+        get_contract_abi_request.set_state_hash(
+            self.post_state_hash
+                .clone()
+                .expect("builder must have a post-state hash"),
+        );
+        get_contract_abi_request.set_contract_hash(contract_hash.to_vec());
+
+        let mut get_contract_abi_response = self
+            .engine_state
+            .get_contract_abi(RequestOptions::new(), get_contract_abi_request)
+            .wait_drop_metadata()
+            .expect("should get contract abi");
+        if !get_contract_abi_response.has_success() {
+            panic!(
+                "Expected contract abi but received a failure instead: {:?}",
+                get_contract_abi_response
+            );
+        }
+        get_contract_abi_response.take_success()
+    }
+
     pub fn get_account(&self, account_hash: AccountHash) -> Option<Account> {
         match self.query(None, Key::Account(account_hash), &[]) {
             Ok(account_value) => match account_value {
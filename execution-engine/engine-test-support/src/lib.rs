@@ -66,6 +66,7 @@
 mod account;
 mod code;
 mod error;
+mod gas_estimate;
 // This module is not intended to be used by third party crates.
 #[doc(hidden)]
 pub mod internal;
@@ -76,6 +77,7 @@ mod value;
 pub use account::Account;
 pub use code::Code;
 pub use error::{Error, Result};
+pub use gas_estimate::GasEstimate;
 pub use session::{Session, SessionBuilder, SessionTransferInfo};
 pub use test_context::{TestContext, TestContextBuilder};
 pub use types::account::AccountHash;
@@ -43,6 +43,7 @@ pub struct Session {
     pub(crate) expect_success: bool,
     pub(crate) check_transfer_success: Option<SessionTransferInfo>,
     pub(crate) commit: bool,
+    pub(crate) dry_run: bool,
 }
 
 /// Builder for a [`Session`].
@@ -52,6 +53,7 @@ pub struct SessionBuilder {
     expect_failure: bool,
     check_transfer_success: Option<SessionTransferInfo>,
     without_commit: bool,
+    dry_run: bool,
 }
 
 impl SessionBuilder {
@@ -73,12 +75,14 @@ impl SessionBuilder {
         let expect_failure = false;
         let check_transfer_success = None;
         let without_commit = false;
+        let dry_run = false;
         Self {
             er_builder: Default::default(),
             di_builder,
             expect_failure,
             check_transfer_success,
             without_commit,
+            dry_run,
         }
     }
 
@@ -149,6 +153,18 @@ impl SessionBuilder {
         self
     }
 
+    /// Only estimate the gas consumed by the deploy within
+    /// [`TestContext::run`](crate::TestContext::run).
+    ///
+    /// The deploy is executed with an effectively unlimited gas limit and regardless of its
+    /// authorization keys, nothing is committed and any transfer check is skipped.  The estimate
+    /// is then available from
+    /// [`TestContext::last_gas_estimate`](crate::TestContext::last_gas_estimate).
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
     /// Builds the [`Session`].
     pub fn build(self) -> Session {
         let mut rng = rand::thread_rng();
@@ -161,6 +177,7 @@ impl SessionBuilder {
             expect_success: !self.expect_failure,
             check_transfer_success: self.check_transfer_success,
             commit: !self.without_commit,
+            dry_run: self.dry_run,
         }
     }
 }
@@ -11,7 +11,7 @@ use types::{AccessRights, Key, URef, U512};
 
 use crate::{
     internal::{InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG, DEFAULT_GENESIS_CONFIG_HASH},
-    Account, AccountHash, Error, Result, Session, URefAddr, Value,
+    Account, AccountHash, Error, GasEstimate, Result, Session, URefAddr, Value,
 };
 
 /// Context in which to run a test of a Wasm smart contract.
@@ -44,7 +44,23 @@ impl TestContext {
     /// If `session` was built without
     /// [`without_commit()`](crate::SessionBuilder::without_commit) (the default), then `run()` will
     /// commit the resulting transforms.
+    ///
+    /// If `session` was built with [`with_dry_run()`](crate::SessionBuilder::with_dry_run) (not
+    /// the default), then `run()` only estimates the gas consumed by the deploy, without
+    /// committing anything.  The estimate is available from
+    /// [`last_gas_estimate()`](TestContext::last_gas_estimate).
     pub fn run(&mut self, session: Session) -> &mut Self {
+        if session.dry_run {
+            self.inner.dry_run(session.inner);
+            let gas_estimate = self.last_gas_estimate().expect("should have gas estimate");
+            if session.expect_success {
+                if let Some(error_message) = gas_estimate.error_message() {
+                    panic!("Expected dry run success but received: {}", error_message);
+                }
+            }
+            return self;
+        }
+
         match session.check_transfer_success {
             Some(session_transfer_info) => {
                 let source_initial_balance = self
@@ -105,6 +121,11 @@ impl TestContext {
         self
     }
 
+    /// Returns the gas estimate of the most recent [`Session`] run as a dry run, or `None`.
+    pub fn last_gas_estimate(&self) -> Option<GasEstimate> {
+        self.inner.last_estimate_result().map(GasEstimate::from)
+    }
+
     /// Queries for a [`Value`] stored under the given `key` and `path`.
     ///
     /// Returns an [`Error`] if not found.
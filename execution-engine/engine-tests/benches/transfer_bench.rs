@@ -5,7 +5,7 @@ use criterion::{
 };
 use tempfile::TempDir;
 
-use engine_core::engine_state::EngineConfig;
+use engine_core::engine_state::{engine_config::DEFAULT_MODULE_CACHE_CAPACITY, EngineConfig};
 use engine_test_support::{
     internal::{
         DeployItemBuilder, ExecuteRequestBuilder, LmdbWasmTestBuilder, DEFAULT_PAYMENT,
@@ -40,7 +40,12 @@ fn make_deploy_hash(i: u64) -> [u8; 32] {
     result
 }
 
-fn bootstrap(data_dir: &Path, accounts: Vec<AccountHash>, amount: U512) -> LmdbWasmTestBuilder {
+fn bootstrap(
+    data_dir: &Path,
+    accounts: Vec<AccountHash>,
+    amount: U512,
+    module_cache_capacity: usize,
+) -> LmdbWasmTestBuilder {
     let exec_request = ExecuteRequestBuilder::standard(
         DEFAULT_ACCOUNT_ADDR,
         CONTRACT_CREATE_ACCOUNTS,
@@ -50,7 +55,8 @@ fn bootstrap(data_dir: &Path, accounts: Vec<AccountHash>, amount: U512) -> LmdbW
 
     let engine_config = EngineConfig::new()
         .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
-        .with_enable_bonding(cfg!(feature = "enable-bonding"));
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_module_cache_capacity(module_cache_capacity);
 
     let mut builder = LmdbWasmTestBuilder::new_with_config(data_dir, engine_config);
 
@@ -218,7 +224,12 @@ pub fn transfer_to_existing_accounts(group: &mut BenchmarkGroup<WallTime>, shoul
     let bootstrap_accounts = vec![target_account];
 
     let data_dir = TempDir::new().expect("should create temp dir");
-    let mut builder = bootstrap(data_dir.path(), bootstrap_accounts.clone(), U512::one());
+    let mut builder = bootstrap(
+        data_dir.path(),
+        bootstrap_accounts.clone(),
+        U512::one(),
+        DEFAULT_MODULE_CACHE_CAPACITY,
+    );
 
     group.bench_function(
         format!(
@@ -234,7 +245,12 @@ pub fn transfer_to_existing_accounts(group: &mut BenchmarkGroup<WallTime>, shoul
     );
 
     let data_dir = TempDir::new().expect("should create temp dir");
-    let mut builder = bootstrap(data_dir.path(), bootstrap_accounts, U512::one());
+    let mut builder = bootstrap(
+        data_dir.path(),
+        bootstrap_accounts,
+        U512::one(),
+        DEFAULT_MODULE_CACHE_CAPACITY,
+    );
 
     group.bench_function(
         format!(
@@ -259,6 +275,7 @@ pub fn transfer_to_existing_purses(group: &mut BenchmarkGroup<WallTime>, should_
         data_dir.path(),
         bootstrap_accounts.clone(),
         *DEFAULT_PAYMENT * 100,
+        DEFAULT_MODULE_CACHE_CAPACITY,
     );
     let purses = create_purses(&mut builder, target_account, 1, U512::one());
 
@@ -277,7 +294,12 @@ pub fn transfer_to_existing_purses(group: &mut BenchmarkGroup<WallTime>, should_
     );
 
     let data_dir = TempDir::new().expect("should create temp dir");
-    let mut builder = bootstrap(data_dir.path(), bootstrap_accounts, *DEFAULT_PAYMENT * 10);
+    let mut builder = bootstrap(
+        data_dir.path(),
+        bootstrap_accounts,
+        *DEFAULT_PAYMENT * 10,
+        DEFAULT_MODULE_CACHE_CAPACITY,
+    );
     let purses = create_purses(&mut builder, TARGET_ADDR, 1, U512::one());
 
     group.bench_function(
@@ -295,6 +317,38 @@ pub fn transfer_to_existing_purses(group: &mut BenchmarkGroup<WallTime>, should_
     );
 }
 
+/// Transfers to an existing account with the module cache holding up to `module_cache_capacity`
+/// modules, where 0 disables the cache.  The transfers only execute stored contracts, and so only
+/// use the cache, when the system contracts are in use.
+pub fn transfer_to_existing_accounts_with_module_cache(
+    group: &mut BenchmarkGroup<WallTime>,
+    module_cache_capacity: usize,
+) {
+    let target_account = TARGET_ADDR;
+
+    let data_dir = TempDir::new().expect("should create temp dir");
+    let mut builder = bootstrap(
+        data_dir.path(),
+        vec![target_account],
+        U512::one(),
+        module_cache_capacity,
+    );
+
+    group.bench_function(
+        format!(
+            "transfer_to_existing_account_module_cache/{}/{}",
+            TRANSFER_BATCH_SIZE, module_cache_capacity
+        ),
+        |b| b.iter(|| transfer_to_account_multiple_execs(&mut builder, target_account, true)),
+    );
+
+    println!(
+        "module cache hit rate with capacity {}: {:.3}",
+        module_cache_capacity,
+        builder.get_engine_state().module_cache().hit_rate()
+    );
+}
+
 pub fn transfer_bench(c: &mut Criterion) {
     let mut group = c.benchmark_group("tps");
 
@@ -318,6 +372,10 @@ pub fn transfer_bench(c: &mut Criterion) {
     // Transfers to existing purses, with commits
     transfer_to_existing_purses(&mut group, true);
 
+    // Transfers to existing accounts, with commits, with and without the module cache
+    transfer_to_existing_accounts_with_module_cache(&mut group, 0);
+    transfer_to_existing_accounts_with_module_cache(&mut group, DEFAULT_MODULE_CACHE_CAPACITY);
+
     group.finish();
 }
 
@@ -59,6 +59,12 @@ const REQUEST_COUNT_ARG_DEFAULT: &str = "100";
 const REQUEST_COUNT_ARG_VALUE_NAME: &str = "NUM";
 const REQUEST_COUNT_ARG_HELP: &str = "Total number of 'ExecuteRequest's to send";
 
+const DEPLOY_COUNT_ARG_NAME: &str = "deploys-per-request";
+const DEPLOY_COUNT_ARG_SHORT: &str = "d";
+const DEPLOY_COUNT_ARG_DEFAULT: &str = "1";
+const DEPLOY_COUNT_ARG_VALUE_NAME: &str = "NUM";
+const DEPLOY_COUNT_ARG_HELP: &str = "Number of deploys in each 'ExecuteRequest'";
+
 const TRANSFER_MODE_ARG_NAME: &str = "transfer-mode";
 const TRANSFER_MODE_ARG_SHORT: &str = "m";
 const TRANSFER_MODE_ARG_DEFAULT: &str = "WASMLESS";
@@ -106,6 +112,15 @@ fn request_count_arg() -> Arg<'static, 'static> {
         .help(REQUEST_COUNT_ARG_HELP)
 }
 
+fn deploy_count_arg() -> Arg<'static, 'static> {
+    Arg::with_name(DEPLOY_COUNT_ARG_NAME)
+        .long(DEPLOY_COUNT_ARG_NAME)
+        .short(DEPLOY_COUNT_ARG_SHORT)
+        .default_value(DEPLOY_COUNT_ARG_DEFAULT)
+        .value_name(DEPLOY_COUNT_ARG_VALUE_NAME)
+        .help(DEPLOY_COUNT_ARG_HELP)
+}
+
 fn transfer_mode_arg() -> Arg<'static, 'static> {
     Arg::with_name(TRANSFER_MODE_ARG_NAME)
         .long(TRANSFER_MODE_ARG_NAME)
@@ -120,6 +135,7 @@ struct Args {
     pre_state_hash: Vec<u8>,
     thread_count: usize,
     request_count: usize,
+    deploy_count: usize,
     transfer_mode: TransferMode,
 }
 
@@ -132,6 +148,7 @@ impl Args {
             .arg(pre_state_hash_arg())
             .arg(thread_count_arg())
             .arg(request_count_arg())
+            .arg(deploy_count_arg())
             .arg(transfer_mode_arg())
             .get_matches();
 
@@ -151,6 +168,10 @@ impl Args {
             .value_of(REQUEST_COUNT_ARG_NAME)
             .map(profiling::parse_count)
             .expect("Expected request count");
+        let deploy_count = arg_matches
+            .value_of(DEPLOY_COUNT_ARG_NAME)
+            .map(profiling::parse_count)
+            .expect("Expected deploy count");
         let transfer_mode = arg_matches
             .value_of(TRANSFER_MODE_ARG_NAME)
             .map(profiling::parse_transfer_mode)
@@ -161,6 +182,7 @@ impl Args {
             pre_state_hash,
             thread_count,
             request_count,
+            deploy_count,
             transfer_mode,
         }
     }
@@ -233,19 +255,20 @@ impl Worker {
             .expect("Expected ExecuteResponse");
         let duration = Instant::now() - start;
 
-        let deploy_result = response
-            .get_success()
-            .get_deploy_results()
-            .get(0)
-            .expect("Expected single deploy result");
-        if !deploy_result.has_execution_result() {
-            panic!("Expected ExecutionResult, got {:?} instead", deploy_result);
+        let deploy_results = response.get_success().get_deploy_results();
+        if deploy_results.is_empty() {
+            panic!("Expected at least one deploy result");
         }
-        if deploy_result.get_execution_result().has_error() {
-            panic!(
-                "Expected successful execution result, but instead got: {:?}",
-                deploy_result.get_execution_result().get_error(),
-            );
+        for deploy_result in deploy_results {
+            if !deploy_result.has_execution_result() {
+                panic!("Expected ExecutionResult, got {:?} instead", deploy_result);
+            }
+            if deploy_result.get_execution_result().has_error() {
+                panic!(
+                    "Expected successful execution result, but instead got: {:?}",
+                    deploy_result.get_execution_result().get_error(),
+                );
+            }
         }
 
         info!(
@@ -345,26 +368,33 @@ impl Drop for ClientPool {
 
 fn new_execute_request(args: &Args) -> ExecuteRequest {
     let account_1_addr = profiling::account_1_account_hash();
-    let transfer_args = runtime_args! { ARG_TARGET => profiling::account_2_account_hash(), ARG_AMOUNT => U512::one() };
-    let deploy_item = match args.transfer_mode {
-        TransferMode::WASM => DeployItemBuilder::new()
-            .with_address(account_1_addr)
-            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
-            .with_session_code(CONTRACT_NAME, transfer_args)
-            .with_authorization_keys(&[account_1_addr])
-            .build(),
-        TransferMode::WASMLESS => DeployItemBuilder::new()
-            .with_address(account_1_addr)
-            .with_empty_payment_bytes(runtime_args! {})
-            .with_transfer_args(transfer_args)
-            .with_authorization_keys(&[account_1_addr])
-            .build(),
-    };
-
-    ExecuteRequestBuilder::from_deploy_item(deploy_item)
-        .with_pre_state_hash(&args.pre_state_hash)
-        .build()
-        .into()
+    let mut execute_request_builder =
+        ExecuteRequestBuilder::new().with_pre_state_hash(&args.pre_state_hash);
+
+    for deploy_num in 0..args.deploy_count {
+        let transfer_args = runtime_args! { ARG_TARGET => profiling::account_2_account_hash(), ARG_AMOUNT => U512::one() };
+        let mut deploy_hash = [0u8; 32];
+        deploy_hash[..8].copy_from_slice(&(deploy_num as u64).to_le_bytes());
+        let deploy_item = match args.transfer_mode {
+            TransferMode::WASM => DeployItemBuilder::new()
+                .with_address(account_1_addr)
+                .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+                .with_session_code(CONTRACT_NAME, transfer_args)
+                .with_authorization_keys(&[account_1_addr])
+                .with_deploy_hash(deploy_hash)
+                .build(),
+            TransferMode::WASMLESS => DeployItemBuilder::new()
+                .with_address(account_1_addr)
+                .with_empty_payment_bytes(runtime_args! {})
+                .with_transfer_args(transfer_args)
+                .with_authorization_keys(&[account_1_addr])
+                .with_deploy_hash(deploy_hash)
+                .build(),
+        };
+        execute_request_builder = execute_request_builder.push_deploy(deploy_item);
+    }
+
+    execute_request_builder.build().into()
 }
 
 fn main() {
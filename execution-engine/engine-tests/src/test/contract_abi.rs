@@ -0,0 +1,50 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{contracts::EntryPoints, runtime_args, RuntimeArgs};
+
+const CONTRACT_CONTRACT_METADATA: &str = "contract_metadata.wasm";
+const PACKAGE_HASH_KEY: &str = "metadata_package";
+const ENTRY_FUNCTION_NAME: &str = "delegate";
+const ARG_STAGE: &str = "stage";
+
+#[ignore]
+#[test]
+fn should_export_contract_entry_points_as_abi_json() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_METADATA,
+        runtime_args! { ARG_STAGE => "install" },
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let package_hash = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(PACKAGE_HASH_KEY)
+        .expect("should have package hash");
+    let contract_hash = builder
+        .query(None, package_hash, &[])
+        .expect("should have package")
+        .as_contract_package()
+        .and_then(|contract_package| contract_package.current_contract_hash())
+        .expect("should have version");
+    let contract = builder
+        .get_contract(contract_hash)
+        .expect("should have contract");
+
+    let abi = builder.get_contract_abi(contract_hash);
+
+    let entry_points: EntryPoints = serde_json::from_str(&abi).expect("should parse abi");
+    assert_eq!(&entry_points, contract.entry_points());
+
+    let abi: serde_json::Value = serde_json::from_str(&abi).expect("should parse abi");
+    assert_eq!(abi[ENTRY_FUNCTION_NAME]["ret"], "Unit");
+    assert_eq!(abi[ENTRY_FUNCTION_NAME]["access"], "Public");
+}
@@ -0,0 +1,241 @@
+use engine_core::engine_state::{self, ExecuteRequest};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, ContractPackageHash, RuntimeArgs, U512};
+
+const CONTRACT_CONTRACT_ALLOW_LIST: &str = "contract_allow_list.wasm";
+const CONTRACT_DO_NOTHING_STORED: &str = "do_nothing_stored.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const DO_NOTHING_PACKAGE_HASH_KEY_NAME: &str = "do_nothing_package_hash";
+const ENTRY_FUNCTION_NAME: &str = "delegate";
+const ARG_STAGE: &str = "stage";
+const ARG_ACCOUNT: &str = "account";
+const ARG_CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+const ARG_ENTRY_POINT: &str = "entry_point";
+const ARG_TARGET: &str = "target";
+
+const HOT_KEY: AccountHash = AccountHash::new([1; 32]);
+
+fn deploy_request(
+    authorization_keys: &[AccountHash],
+    deploy_hash: [u8; 32],
+    build_session: impl FnOnce(DeployItemBuilder) -> DeployItemBuilder,
+) -> ExecuteRequest {
+    let deploy = build_session(
+        DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, }),
+    )
+    .with_deploy_hash(deploy_hash)
+    .with_authorization_keys(authorization_keys)
+    .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy).build()
+}
+
+fn allow_request(
+    authorization_keys: &[AccountHash],
+    deploy_hash: [u8; 32],
+    contract_package_hash: ContractPackageHash,
+    entry_point: &str,
+) -> ExecuteRequest {
+    deploy_request(authorization_keys, deploy_hash, |builder| {
+        builder.with_session_code(
+            CONTRACT_CONTRACT_ALLOW_LIST,
+            runtime_args! {
+                ARG_STAGE => "allow",
+                ARG_CONTRACT_PACKAGE_HASH => contract_package_hash,
+                ARG_ENTRY_POINT => entry_point,
+            },
+        )
+    })
+}
+
+fn call_do_nothing_request(
+    authorization_keys: &[AccountHash],
+    deploy_hash: [u8; 32],
+) -> ExecuteRequest {
+    deploy_request(authorization_keys, deploy_hash, |builder| {
+        builder.with_stored_versioned_contract_by_name(
+            DO_NOTHING_PACKAGE_HASH_KEY_NAME,
+            None,
+            ENTRY_FUNCTION_NAME,
+            RuntimeArgs::default(),
+        )
+    })
+}
+
+fn transfer_request(authorization_keys: &[AccountHash], deploy_hash: [u8; 32]) -> ExecuteRequest {
+    deploy_request(authorization_keys, deploy_hash, |builder| {
+        builder.with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! { ARG_TARGET => HOT_KEY, ARG_AMOUNT => U512::one() },
+        )
+    })
+}
+
+/// Stores the "do nothing" contract and adds a hot key to the default account which can deploy on
+/// its own, but needs the default key to meet the key management threshold.
+fn setup() -> (InMemoryWasmTestBuilder, ContractPackageHash) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let store_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DO_NOTHING_STORED,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(store_request).expect_success().commit();
+
+    let setup_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_ALLOW_LIST,
+        runtime_args! { ARG_STAGE => "setup", ARG_ACCOUNT => HOT_KEY },
+    )
+    .build();
+    builder.exec(setup_request).expect_success().commit();
+
+    let contract_package_hash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(DO_NOTHING_PACKAGE_HASH_KEY_NAME)
+        .expect("should have contract package hash")
+        .into_seed();
+
+    (builder, contract_package_hash)
+}
+
+fn assert_not_allowed(builder: &mut InMemoryWasmTestBuilder, request: ExecuteRequest) {
+    builder.exec(request).commit();
+    let response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have exec response");
+    let deploy_result = response.get(0).expect("should have deploy result");
+    assert!(
+        deploy_result.has_precondition_failure(),
+        "{:?}",
+        deploy_result
+    );
+    assert_eq!(
+        format!("{}", deploy_result.as_error().unwrap()),
+        format!("{}", engine_state::Error::ContractNotAllowed)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_restrict_hot_key_to_allowed_entry_points() {
+    let (mut builder, contract_package_hash) = setup();
+
+    builder
+        .exec(allow_request(
+            &[DEFAULT_ACCOUNT_ADDR, HOT_KEY],
+            [1; 32],
+            contract_package_hash,
+            ENTRY_FUNCTION_NAME,
+        ))
+        .expect_success()
+        .commit();
+
+    builder
+        .exec(call_do_nothing_request(&[HOT_KEY], [2; 32]))
+        .expect_success()
+        .commit();
+
+    assert_not_allowed(&mut builder, transfer_request(&[HOT_KEY], [3; 32]));
+}
+
+#[ignore]
+#[test]
+fn should_not_call_entry_point_missing_from_allow_list() {
+    let (mut builder, contract_package_hash) = setup();
+
+    builder
+        .exec(allow_request(
+            &[DEFAULT_ACCOUNT_ADDR, HOT_KEY],
+            [1; 32],
+            contract_package_hash,
+            "other_entry_point",
+        ))
+        .expect_success()
+        .commit();
+
+    assert_not_allowed(&mut builder, call_do_nothing_request(&[HOT_KEY], [2; 32]));
+}
+
+#[ignore]
+#[test]
+fn should_allow_any_deploy_meeting_key_management_threshold() {
+    let (mut builder, _contract_package_hash) = setup();
+
+    builder
+        .exec(allow_request(
+            &[DEFAULT_ACCOUNT_ADDR, HOT_KEY],
+            [1; 32],
+            [42; 32],
+            ENTRY_FUNCTION_NAME,
+        ))
+        .expect_success()
+        .commit();
+
+    builder
+        .exec(call_do_nothing_request(
+            &[DEFAULT_ACCOUNT_ADDR, HOT_KEY],
+            [2; 32],
+        ))
+        .expect_success()
+        .commit();
+    builder
+        .exec(transfer_request(&[DEFAULT_ACCOUNT_ADDR, HOT_KEY], [3; 32]))
+        .expect_success()
+        .commit();
+
+    // Lifting the restriction lets the hot key deploy arbitrary session code again
+    builder
+        .exec(deploy_request(
+            &[DEFAULT_ACCOUNT_ADDR, HOT_KEY],
+            [4; 32],
+            |builder| {
+                builder.with_session_code(
+                    CONTRACT_CONTRACT_ALLOW_LIST,
+                    runtime_args! { ARG_STAGE => "clear" },
+                )
+            },
+        ))
+        .expect_success()
+        .commit();
+    builder
+        .exec(transfer_request(&[HOT_KEY], [5; 32]))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_change_allow_list_without_key_management_threshold() {
+    let (mut builder, contract_package_hash) = setup();
+
+    builder
+        .exec(allow_request(
+            &[HOT_KEY],
+            [1; 32],
+            contract_package_hash,
+            ENTRY_FUNCTION_NAME,
+        ))
+        .commit();
+    let message = builder
+        .exec_error_message(builder.get_exec_responses_count() - 1)
+        .expect("should have error message");
+    assert!(message.contains("PermissionDenied"), "{}", message);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.contract_allow_list().is_none());
+}
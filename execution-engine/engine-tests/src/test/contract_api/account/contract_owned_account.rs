@@ -0,0 +1,311 @@
+use engine_core::{
+    engine_state::{self, ExecuteRequest},
+    execution,
+};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, ApiError, Key, RuntimeArgs, URef, U512};
+
+const CONTRACT_CONTRACT_OWNED_ACCOUNT: &str = "contract_owned_account.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const PACKAGE_HASH_KEY: &str = "dao_package";
+const DAO_ACCOUNT_KEY: &str = "dao_account";
+const MEMBERSHIP_KEY: &str = "dao_membership";
+const ARG_STAGE: &str = "stage";
+const ARG_MEMBERS: &str = "members";
+const ARG_MEMBER: &str = "member";
+const ARG_MEMBERSHIP: &str = "membership";
+const ARG_PACKAGE_HASH: &str = "package_hash";
+const ARG_TARGET: &str = "target";
+
+const MEMBER_1: AccountHash = AccountHash::new([1; 32]);
+const MEMBER_2: AccountHash = AccountHash::new([2; 32]);
+const MEMBER_3: AccountHash = AccountHash::new([3; 32]);
+const OUTSIDER: AccountHash = AccountHash::new([66; 32]);
+const RECIPIENT: AccountHash = AccountHash::new([77; 32]);
+
+fn transfer_request(target: AccountHash, amount: U512) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => target, ARG_AMOUNT => amount },
+    )
+    .build()
+}
+
+fn claim_request(member: AccountHash, package_hash: Key) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        member,
+        CONTRACT_CONTRACT_OWNED_ACCOUNT,
+        runtime_args! { ARG_STAGE => "claim", ARG_PACKAGE_HASH => package_hash },
+    )
+    .build()
+}
+
+/// Adds a URef to the DAO's members group and sets it aside for `member` to claim.
+fn admit_request(member: AccountHash) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_OWNED_ACCOUNT,
+        runtime_args! { ARG_STAGE => "admit", ARG_MEMBER => member },
+    )
+    .build()
+}
+
+/// Removes `membership` from the DAO's members group.
+fn expel_request(membership: URef) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_OWNED_ACCOUNT,
+        runtime_args! { ARG_STAGE => "expel", ARG_MEMBERSHIP => membership },
+    )
+    .build()
+}
+
+fn package_hash(builder: &InMemoryWasmTestBuilder) -> Key {
+    *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(PACKAGE_HASH_KEY)
+        .expect("should have package hash")
+}
+
+/// Returns the URefs of the DAO's members group claimed by `member`.
+fn memberships(builder: &InMemoryWasmTestBuilder, member: AccountHash) -> Vec<URef> {
+    builder
+        .get_account(member)
+        .expect("should have member account")
+        .named_keys()
+        .iter()
+        .filter(|(name, _key)| name.starts_with(MEMBERSHIP_KEY))
+        .filter_map(|(_name, key)| key.into_uref())
+        .collect()
+}
+
+/// A transfer out of the DAO's treasury, signed by `authorization_keys`.
+fn dao_transfer_request(
+    dao_account: AccountHash,
+    authorization_keys: &[AccountHash],
+) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(dao_account)
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+        .with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! { ARG_TARGET => RECIPIENT, ARG_AMOUNT => U512::one() },
+        )
+        .with_deploy_hash([42; 32])
+        .with_authorization_keys(authorization_keys)
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy).build()
+}
+
+/// Installs a DAO whose account needs two out of three members to deploy, lets each member claim
+/// its membership, and funds the DAO's treasury.
+fn setup() -> (InMemoryWasmTestBuilder, AccountHash) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    for account in &[MEMBER_1, MEMBER_2, MEMBER_3, OUTSIDER] {
+        builder
+            .exec(transfer_request(
+                *account,
+                U512::from(10) * *DEFAULT_PAYMENT,
+            ))
+            .expect_success()
+            .commit();
+    }
+
+    let install_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_OWNED_ACCOUNT,
+        runtime_args! {
+            ARG_STAGE => "install",
+            ARG_MEMBERS => vec![MEMBER_1, MEMBER_2, MEMBER_3],
+        },
+    )
+    .build();
+    builder.exec(install_request).expect_success().commit();
+
+    let package_hash = package_hash(&builder);
+    let dao_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(DAO_ACCOUNT_KEY)
+        .and_then(|key| key.into_account())
+        .expect("should have DAO account");
+
+    for member in &[MEMBER_1, MEMBER_2, MEMBER_3] {
+        builder
+            .exec(claim_request(*member, package_hash))
+            .expect_success()
+            .commit();
+    }
+
+    builder
+        .exec(transfer_request(
+            dao_account,
+            U512::from(10) * *DEFAULT_PAYMENT,
+        ))
+        .expect_success()
+        .commit();
+
+    (builder, dao_account)
+}
+
+fn assert_precondition_failure(
+    builder: &mut InMemoryWasmTestBuilder,
+    request: ExecuteRequest,
+    expected: &str,
+) {
+    builder.exec(request).commit();
+    let response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have exec response");
+    let deploy_result = response.get(0).expect("should have deploy result");
+    assert!(
+        deploy_result.has_precondition_failure(),
+        "{:?}",
+        deploy_result
+    );
+    let message = format!("{}", deploy_result.as_error().unwrap());
+    assert!(message.contains(expected), "{}", message);
+}
+
+#[ignore]
+#[test]
+fn should_deploy_for_contract_owned_account_with_two_of_three_members() {
+    let (mut builder, dao_account) = setup();
+
+    let account = builder
+        .get_account(dao_account)
+        .expect("should have DAO account");
+    assert!(account.owner().is_some());
+    // The members are looked up in the owner's group when a deploy is authorized
+    assert_eq!(account.get_associated_keys().count(), 0);
+
+    builder
+        .exec(dao_transfer_request(dao_account, &[MEMBER_1, MEMBER_3]))
+        .expect_success()
+        .commit();
+
+    let recipient = builder
+        .get_account(RECIPIENT)
+        .expect("should have recipient account");
+    assert_eq!(
+        builder.get_purse_balance(recipient.main_purse()),
+        U512::one()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_deploy_for_contract_owned_account_with_single_member() {
+    let (mut builder, dao_account) = setup();
+
+    assert_precondition_failure(
+        &mut builder,
+        dao_transfer_request(dao_account, &[MEMBER_2]),
+        &format!("{}", execution::Error::DeploymentAuthorizationFailure),
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_deploy_for_contract_owned_account_with_outsider() {
+    let (mut builder, dao_account) = setup();
+
+    // The outsider has no membership set aside to claim
+    let package_hash = package_hash(&builder);
+    builder.exec(claim_request(OUTSIDER, package_hash)).commit();
+    let message = builder
+        .exec_error_message(builder.get_exec_responses_count() - 1)
+        .expect("should have error message");
+    assert!(
+        message.contains(&format!("{:?}", ApiError::PermissionDenied)),
+        "{}",
+        message
+    );
+
+    assert_precondition_failure(
+        &mut builder,
+        dao_transfer_request(dao_account, &[MEMBER_1, OUTSIDER]),
+        &format!("{}", engine_state::Error::Authorization),
+    );
+    assert_precondition_failure(
+        &mut builder,
+        dao_transfer_request(dao_account, &[DEFAULT_ACCOUNT_ADDR]),
+        &format!("{}", engine_state::Error::Authorization),
+    );
+}
+
+#[ignore]
+#[test]
+fn should_deploy_for_contract_owned_account_with_member_admitted_later() {
+    let (mut builder, dao_account) = setup();
+    let package_hash = package_hash(&builder);
+
+    builder
+        .exec(admit_request(OUTSIDER))
+        .expect_success()
+        .commit();
+    builder
+        .exec(claim_request(OUTSIDER, package_hash))
+        .expect_success()
+        .commit();
+
+    builder
+        .exec(dao_transfer_request(dao_account, &[MEMBER_1, OUTSIDER]))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_deploy_for_contract_owned_account_with_expelled_member() {
+    let (mut builder, dao_account) = setup();
+
+    let membership = memberships(&builder, MEMBER_2)
+        .pop()
+        .expect("should have membership");
+    builder
+        .exec(expel_request(membership))
+        .expect_success()
+        .commit();
+
+    assert_precondition_failure(
+        &mut builder,
+        dao_transfer_request(dao_account, &[MEMBER_1, MEMBER_2]),
+        &format!("{}", engine_state::Error::Authorization),
+    );
+}
+
+#[ignore]
+#[test]
+fn should_count_member_holding_several_urefs_once() {
+    let (mut builder, dao_account) = setup();
+    let package_hash = package_hash(&builder);
+
+    builder
+        .exec(admit_request(MEMBER_1))
+        .expect_success()
+        .commit();
+    builder
+        .exec(claim_request(MEMBER_1, package_hash))
+        .expect_success()
+        .commit();
+    assert_eq!(memberships(&builder, MEMBER_1).len(), 2);
+
+    assert_precondition_failure(
+        &mut builder,
+        dao_transfer_request(dao_account, &[MEMBER_1]),
+        &format!("{}", execution::Error::DeploymentAuthorizationFailure),
+    );
+}
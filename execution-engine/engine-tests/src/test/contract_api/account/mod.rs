@@ -1,4 +1,8 @@
 mod associated_keys;
 mod authorized_keys;
+mod contract_allow_list;
+mod contract_owned_account;
 mod key_management_thresholds;
 mod named_keys;
+mod recovery;
+mod transfer_policy;
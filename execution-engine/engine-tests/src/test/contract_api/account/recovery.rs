@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+
+use engine_core::engine_state::ExecuteRequest;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{AccountHash, RecoveryFailure, Weight},
+    runtime_args, ApiError, RuntimeArgs, U512,
+};
+
+const CONTRACT_ACCOUNT_RECOVERY: &str = "account_recovery.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ARG_STAGE: &str = "stage";
+const ARG_GUARDIANS: &str = "guardians";
+const ARG_THRESHOLD: &str = "threshold";
+const ARG_DELAY: &str = "delay";
+const ARG_ACCOUNT: &str = "account";
+const ARG_NEW_KEYS: &str = "new_keys";
+const ARG_TARGET: &str = "target";
+
+const GUARDIAN_1: AccountHash = AccountHash::new([1; 32]);
+const GUARDIAN_2: AccountHash = AccountHash::new([2; 32]);
+const GUARDIAN_3: AccountHash = AccountHash::new([3; 32]);
+const ATTACKER: AccountHash = AccountHash::new([66; 32]);
+const NEW_KEY: AccountHash = AccountHash::new([77; 32]);
+
+const RECOVERY_DELAY: u64 = 1_000;
+const PROPOSED_AT: u64 = 100;
+
+fn recovery_request(sender: AccountHash, args: RuntimeArgs, block_time: u64) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(sender, CONTRACT_ACCOUNT_RECOVERY, args)
+        .with_block_time(block_time)
+        .build()
+}
+
+fn propose_request(guardian: AccountHash, block_time: u64) -> ExecuteRequest {
+    let mut new_keys = BTreeMap::new();
+    new_keys.insert(NEW_KEY, Weight::new(1));
+    recovery_request(
+        guardian,
+        runtime_args! {
+            ARG_STAGE => "propose",
+            ARG_ACCOUNT => DEFAULT_ACCOUNT_ADDR,
+            ARG_NEW_KEYS => new_keys,
+        },
+        block_time,
+    )
+}
+
+fn finalize_request(guardian: AccountHash, block_time: u64) -> ExecuteRequest {
+    recovery_request(
+        guardian,
+        runtime_args! {
+            ARG_STAGE => "finalize",
+            ARG_ACCOUNT => DEFAULT_ACCOUNT_ADDR,
+        },
+        block_time,
+    )
+}
+
+/// Funds the guardians and an attacker, and configures the default account to be recoverable by
+/// two out of three guardians.
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    for account in &[GUARDIAN_1, GUARDIAN_2, GUARDIAN_3, ATTACKER] {
+        let transfer_request = ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! {
+                ARG_TARGET => *account,
+                ARG_AMOUNT => U512::from(10) * *DEFAULT_PAYMENT,
+            },
+        )
+        .build();
+        builder.exec(transfer_request).expect_success().commit();
+    }
+
+    let configure_request = recovery_request(
+        DEFAULT_ACCOUNT_ADDR,
+        runtime_args! {
+            ARG_STAGE => "configure",
+            ARG_GUARDIANS => vec![GUARDIAN_1, GUARDIAN_2, GUARDIAN_3],
+            ARG_THRESHOLD => 2u8,
+            ARG_DELAY => RECOVERY_DELAY,
+        },
+        0,
+    );
+    builder.exec(configure_request).expect_success().commit();
+    builder
+}
+
+fn assert_recovery_failure(
+    builder: &mut InMemoryWasmTestBuilder,
+    request: ExecuteRequest,
+    expected: RecoveryFailure,
+) {
+    builder.exec(request).commit();
+    let index = builder.get_exec_responses_count() - 1;
+    let message = builder
+        .exec_error_message(index)
+        .expect("should have error message");
+    let expected = ApiError::User(expected as u16);
+    assert!(
+        message.contains(&format!("{:?}", expected)),
+        "expected {:?}, got {}",
+        expected,
+        message
+    );
+}
+
+fn assert_default_account_keys(builder: &InMemoryWasmTestBuilder, expected: &[AccountHash]) {
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let keys: Vec<AccountHash> = account
+        .get_associated_keys()
+        .map(|(account_hash, _weight)| *account_hash)
+        .collect();
+    assert_eq!(keys, expected);
+}
+
+#[ignore]
+#[test]
+fn should_recover_account_keys_after_delay() {
+    let mut builder = setup();
+
+    builder
+        .exec(propose_request(GUARDIAN_1, PROPOSED_AT))
+        .expect_success()
+        .commit();
+    builder
+        .exec(propose_request(GUARDIAN_2, PROPOSED_AT + 1))
+        .expect_success()
+        .commit();
+    builder
+        .exec(finalize_request(GUARDIAN_3, PROPOSED_AT + RECOVERY_DELAY))
+        .expect_success()
+        .commit();
+
+    assert_default_account_keys(&builder, &[NEW_KEY]);
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.pending_recoveries().is_empty());
+
+    // The recovered account is usable with its new key only
+    let deploy_with_new_key = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+            .with_session_code(
+                CONTRACT_TRANSFER_TO_ACCOUNT,
+                runtime_args! { ARG_TARGET => GUARDIAN_1, ARG_AMOUNT => U512::one() },
+            )
+            .with_deploy_hash([42; 32])
+            .with_authorization_keys(&[NEW_KEY])
+            .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy).build()
+    };
+    builder.exec(deploy_with_new_key).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_recover_before_delay_elapsed() {
+    let mut builder = setup();
+
+    builder
+        .exec(propose_request(GUARDIAN_1, PROPOSED_AT))
+        .expect_success()
+        .commit();
+    builder
+        .exec(propose_request(GUARDIAN_2, PROPOSED_AT))
+        .expect_success()
+        .commit();
+
+    assert_recovery_failure(
+        &mut builder,
+        finalize_request(GUARDIAN_1, PROPOSED_AT + RECOVERY_DELAY - 1),
+        RecoveryFailure::DelayNotElapsed,
+    );
+    assert_default_account_keys(&builder, &[DEFAULT_ACCOUNT_ADDR]);
+}
+
+#[ignore]
+#[test]
+fn should_not_recover_with_insufficient_guardians() {
+    let mut builder = setup();
+
+    builder
+        .exec(propose_request(GUARDIAN_1, PROPOSED_AT))
+        .expect_success()
+        .commit();
+
+    // Approving again doesn't count twice
+    builder
+        .exec(propose_request(GUARDIAN_1, PROPOSED_AT + 1))
+        .expect_success()
+        .commit();
+
+    assert_recovery_failure(
+        &mut builder,
+        finalize_request(GUARDIAN_1, PROPOSED_AT + RECOVERY_DELAY),
+        RecoveryFailure::InsufficientApprovals,
+    );
+    assert_default_account_keys(&builder, &[DEFAULT_ACCOUNT_ADDR]);
+}
+
+#[ignore]
+#[test]
+fn should_not_recover_by_non_guardian() {
+    let mut builder = setup();
+
+    assert_recovery_failure(
+        &mut builder,
+        propose_request(ATTACKER, PROPOSED_AT),
+        RecoveryFailure::NotGuardian,
+    );
+
+    builder
+        .exec(propose_request(GUARDIAN_1, PROPOSED_AT))
+        .expect_success()
+        .commit();
+    builder
+        .exec(propose_request(GUARDIAN_2, PROPOSED_AT))
+        .expect_success()
+        .commit();
+
+    assert_recovery_failure(
+        &mut builder,
+        finalize_request(ATTACKER, PROPOSED_AT + RECOVERY_DELAY),
+        RecoveryFailure::NotGuardian,
+    );
+    assert_default_account_keys(&builder, &[DEFAULT_ACCOUNT_ADDR]);
+}
+
+#[ignore]
+#[test]
+fn should_not_recover_account_without_config() {
+    let mut builder = setup();
+
+    let mut new_keys = BTreeMap::new();
+    new_keys.insert(NEW_KEY, Weight::new(1));
+    let propose_for_guardian = recovery_request(
+        GUARDIAN_1,
+        runtime_args! {
+            ARG_STAGE => "propose",
+            ARG_ACCOUNT => GUARDIAN_2,
+            ARG_NEW_KEYS => new_keys,
+        },
+        PROPOSED_AT,
+    );
+    assert_recovery_failure(
+        &mut builder,
+        propose_for_guardian,
+        RecoveryFailure::NotConfigured,
+    );
+}
+
+#[ignore]
+#[test]
+fn should_cancel_pending_recovery() {
+    let mut builder = setup();
+
+    builder
+        .exec(propose_request(GUARDIAN_1, PROPOSED_AT))
+        .expect_success()
+        .commit();
+    builder
+        .exec(propose_request(GUARDIAN_2, PROPOSED_AT))
+        .expect_success()
+        .commit();
+
+    // The owner notices the recovery during the delay and cancels it
+    builder
+        .exec(recovery_request(
+            DEFAULT_ACCOUNT_ADDR,
+            runtime_args! { ARG_STAGE => "cancel" },
+            PROPOSED_AT + 1,
+        ))
+        .expect_success()
+        .commit();
+
+    assert_recovery_failure(
+        &mut builder,
+        finalize_request(GUARDIAN_1, PROPOSED_AT + RECOVERY_DELAY),
+        RecoveryFailure::NoPendingRecovery,
+    );
+    assert_default_account_keys(&builder, &[DEFAULT_ACCOUNT_ADDR]);
+}
@@ -0,0 +1,392 @@
+use engine_core::{
+    engine_state::{self, ExecuteRequest},
+    execution,
+};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{AccountHash, Weight},
+    runtime_args, ApiError, RuntimeArgs, U512,
+};
+
+const CONTRACT_TRANSFER_POLICY: &str = "transfer_policy.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const CONTRACT_TRANSFER_TO_TWO_PURSES: &str = "transfer_main_purse_to_two_purses.wasm";
+const CONTRACT_TRANSFER_VIA_MINT: &str = "transfer_main_purse_via_mint.wasm";
+const CONTRACT_PAYMENT_WITH_TRANSFER: &str = "payment_with_transfer.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const ARG_ACCOUNT: &str = "account";
+const ARG_TRANSFER_THRESHOLD: &str = "transfer_threshold";
+const ARG_SPENDING_LIMIT: &str = "spending_limit";
+const ARG_TARGET: &str = "target";
+const ARG_DESTINATION_PURSE_ONE: &str = "destination_purse_one";
+const ARG_DESTINATION_PURSE_TWO: &str = "destination_purse_two";
+const ARG_TRANSFER_AMOUNT_ONE: &str = "transfer_amount_one";
+const ARG_TRANSFER_AMOUNT_TWO: &str = "transfer_amount_two";
+const ARG_TRANSFER_AMOUNT: &str = "transfer_amount";
+
+const SECOND_KEY: AccountHash = AccountHash::new([42; 32]);
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1; 32]);
+
+/// Sets up the default account with a second associated key, so both keys have to sign transfers
+/// if `transfer_threshold` is 2.
+fn setup(transfer_threshold: Weight, spending_limit: Option<U512>) -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_POLICY,
+        runtime_args! {
+            ARG_ACCOUNT => SECOND_KEY,
+            ARG_TRANSFER_THRESHOLD => transfer_threshold,
+            ARG_SPENDING_LIMIT => spending_limit,
+        },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn session_request(
+    session_file: &str,
+    session_args: RuntimeArgs,
+    authorization_keys: &[AccountHash],
+    deploy_hash: [u8; 32],
+) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+        .with_session_code(session_file, session_args)
+        .with_deploy_hash(deploy_hash)
+        .with_authorization_keys(authorization_keys)
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy).build()
+}
+
+fn wasmless_transfer_request(
+    amount: U512,
+    authorization_keys: &[AccountHash],
+    deploy_hash: [u8; 32],
+) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_empty_payment_bytes(runtime_args! {})
+        .with_transfer_args(runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => amount })
+        .with_deploy_hash(deploy_hash)
+        .with_authorization_keys(authorization_keys)
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy).build()
+}
+
+fn payment_with_transfer_request(
+    transfer_amount: U512,
+    authorization_keys: &[AccountHash],
+    deploy_hash: [u8; 32],
+) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_payment_code(
+            CONTRACT_PAYMENT_WITH_TRANSFER,
+            runtime_args! {
+                ARG_AMOUNT => *DEFAULT_PAYMENT,
+                ARG_TARGET => ACCOUNT_1_ADDR,
+                ARG_TRANSFER_AMOUNT => transfer_amount,
+            },
+        )
+        .with_session_code(CONTRACT_DO_NOTHING, RuntimeArgs::new())
+        .with_deploy_hash(deploy_hash)
+        .with_authorization_keys(authorization_keys)
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy).build()
+}
+
+fn transfer_to_two_purses_args(amount_one: u64, amount_two: u64) -> RuntimeArgs {
+    runtime_args! {
+        ARG_DESTINATION_PURSE_ONE => "purse_one",
+        ARG_DESTINATION_PURSE_TWO => "purse_two",
+        ARG_TRANSFER_AMOUNT_ONE => U512::from(amount_one),
+        ARG_TRANSFER_AMOUNT_TWO => U512::from(amount_two),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_store_transfer_policy_on_account() {
+    let builder = setup(Weight::new(2), Some(U512::from(1_000)));
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(*account.action_thresholds().deployment(), Weight::new(1));
+    assert_eq!(*account.action_thresholds().transfer(), Weight::new(2));
+    assert_eq!(account.spending_limit(), Some(U512::from(1_000)));
+}
+
+#[ignore]
+#[test]
+fn should_require_transfer_threshold_for_wasmless_transfer() {
+    let mut builder = setup(Weight::new(2), None);
+
+    builder.exec(wasmless_transfer_request(
+        U512::from(1_000),
+        &[DEFAULT_ACCOUNT_ADDR],
+        [1; 32],
+    ));
+    let deploy_result = builder
+        .get_exec_response(1)
+        .expect("should have exec response")
+        .get(0)
+        .expect("should have deploy result");
+    assert!(
+        deploy_result.has_precondition_failure(),
+        "{:?}",
+        deploy_result
+    );
+    let message = format!("{}", deploy_result.as_error().unwrap());
+    assert_eq!(
+        message,
+        format!(
+            "{}",
+            engine_state::Error::Exec(execution::Error::TransferAuthorizationFailure)
+        )
+    );
+
+    builder
+        .exec(wasmless_transfer_request(
+            U512::from(1_000),
+            &[DEFAULT_ACCOUNT_ADDR, SECOND_KEY],
+            [2; 32],
+        ))
+        .expect_success()
+        .commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should create account 1");
+    assert_eq!(
+        builder.get_purse_balance(account_1.main_purse()),
+        U512::from(1_000)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_require_transfer_threshold_for_transfer_host_functions() {
+    let mut builder = setup(Weight::new(2), None);
+    let transfer_args = runtime_args! {
+        ARG_TARGET => ACCOUNT_1_ADDR,
+        ARG_AMOUNT => U512::from(1_000),
+    };
+
+    // A single key still meets the deployment threshold, but not the transfer threshold
+    builder
+        .exec(session_request(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            transfer_args.clone(),
+            &[DEFAULT_ACCOUNT_ADDR],
+            [1; 32],
+        ))
+        .commit();
+    assert!(builder.is_error());
+    let message = builder.exec_error_message(1).expect("should have error");
+    assert!(
+        message.contains(&format!("{:?}", ApiError::PermissionDenied)),
+        "{}",
+        message
+    );
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_none());
+
+    builder
+        .exec(session_request(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            transfer_args,
+            &[DEFAULT_ACCOUNT_ADDR, SECOND_KEY],
+            [2; 32],
+        ))
+        .expect_success()
+        .commit();
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_some());
+}
+
+#[ignore]
+#[test]
+fn should_enforce_spending_limit_per_deploy() {
+    let mut builder = setup(Weight::new(1), Some(U512::from(1_000)));
+
+    // 600 + 600 exceeds the limit, so the second transfer fails
+    builder
+        .exec(session_request(
+            CONTRACT_TRANSFER_TO_TWO_PURSES,
+            transfer_to_two_purses_args(600, 600),
+            &[DEFAULT_ACCOUNT_ADDR],
+            [1; 32],
+        ))
+        .commit();
+    assert!(builder.is_error());
+    let message = builder.exec_error_message(1).expect("should have error");
+    assert!(
+        message.contains(&format!("{:?}", ApiError::User(102))),
+        "{}",
+        message
+    );
+
+    // 600 + 400 is within the limit, and the limit applies to each deploy separately
+    for deploy_hash in &[[2; 32], [3; 32]] {
+        builder
+            .exec(session_request(
+                CONTRACT_TRANSFER_TO_TWO_PURSES,
+                transfer_to_two_purses_args(600, 400),
+                &[DEFAULT_ACCOUNT_ADDR],
+                *deploy_hash,
+            ))
+            .expect_success()
+            .commit();
+    }
+
+    builder.exec(wasmless_transfer_request(
+        U512::from(1_001),
+        &[DEFAULT_ACCOUNT_ADDR],
+        [4; 32],
+    ));
+    assert!(builder.is_error());
+    let message = builder.exec_error_message(4).expect("should have error");
+    assert!(
+        message.contains(&format!("{:?}", ApiError::SpendingLimitExceeded)),
+        "{}",
+        message
+    );
+
+    builder
+        .exec(wasmless_transfer_request(
+            U512::from(1_000),
+            &[DEFAULT_ACCOUNT_ADDR],
+            [5; 32],
+        ))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_limit_spending_without_limit() {
+    let mut builder = setup(Weight::new(1), None);
+
+    builder
+        .exec(session_request(
+            CONTRACT_TRANSFER_TO_TWO_PURSES,
+            transfer_to_two_purses_args(600_000, 600_000),
+            &[DEFAULT_ACCOUNT_ADDR],
+            [1; 32],
+        ))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_enforce_transfer_policy_when_calling_mint_directly() {
+    let mut builder = setup(Weight::new(2), Some(U512::from(1_000)));
+
+    // A single key doesn't meet the transfer threshold
+    builder
+        .exec(session_request(
+            CONTRACT_TRANSFER_VIA_MINT,
+            runtime_args! { ARG_AMOUNT => U512::from(1_000) },
+            &[DEFAULT_ACCOUNT_ADDR],
+            [1; 32],
+        ))
+        .commit();
+    let message = builder.exec_error_message(1).expect("should have error");
+    assert!(
+        message.contains(&format!("{:?}", ApiError::PermissionDenied)),
+        "{}",
+        message
+    );
+
+    builder
+        .exec(session_request(
+            CONTRACT_TRANSFER_VIA_MINT,
+            runtime_args! { ARG_AMOUNT => U512::from(1_001) },
+            &[DEFAULT_ACCOUNT_ADDR, SECOND_KEY],
+            [2; 32],
+        ))
+        .commit();
+    let message = builder.exec_error_message(2).expect("should have error");
+    assert!(
+        message.contains(&format!("{:?}", ApiError::SpendingLimitExceeded)),
+        "{}",
+        message
+    );
+
+    builder
+        .exec(session_request(
+            CONTRACT_TRANSFER_VIA_MINT,
+            runtime_args! { ARG_AMOUNT => U512::from(1_000) },
+            &[DEFAULT_ACCOUNT_ADDR, SECOND_KEY],
+            [3; 32],
+        ))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_enforce_transfer_policy_in_payment_code() {
+    // The limit is well below the payment, which goes to the payment purse unchecked
+    let mut builder = setup(Weight::new(2), Some(U512::from(1_000)));
+
+    builder
+        .exec(payment_with_transfer_request(
+            U512::from(1_000),
+            &[DEFAULT_ACCOUNT_ADDR],
+            [1; 32],
+        ))
+        .commit();
+    let message = builder.exec_error_message(1).expect("should have error");
+    assert!(
+        message.contains(&format!("{:?}", ApiError::PermissionDenied)),
+        "{}",
+        message
+    );
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_none());
+
+    builder
+        .exec(payment_with_transfer_request(
+            U512::from(1_001),
+            &[DEFAULT_ACCOUNT_ADDR, SECOND_KEY],
+            [2; 32],
+        ))
+        .commit();
+    let message = builder.exec_error_message(2).expect("should have error");
+    assert!(
+        message.contains(&format!("{:?}", ApiError::SpendingLimitExceeded)),
+        "{}",
+        message
+    );
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_none());
+
+    builder
+        .exec(payment_with_transfer_request(
+            U512::from(1_000),
+            &[DEFAULT_ACCOUNT_ADDR, SECOND_KEY],
+            [3; 32],
+        ))
+        .expect_success()
+        .commit();
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should create account 1");
+    assert_eq!(
+        builder.get_purse_balance(account_1.main_purse()),
+        U512::from(1_000)
+    );
+}
@@ -0,0 +1,128 @@
+use engine_core::engine_state::ExecuteRequest;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, CLType, Key, RuntimeArgs, U512};
+
+const CONTRACT_CONTRACT_METADATA: &str = "contract_metadata.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const PACKAGE_HASH_KEY: &str = "metadata_package";
+const ENTRY_FUNCTION_NAME: &str = "delegate";
+const COUNTER_KEY: &str = "counter";
+const ARG_STAGE: &str = "stage";
+const ARG_PACKAGE_HASH: &str = "package_hash";
+const ARG_TARGET: &str = "target";
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1; 32]);
+
+fn metadata_request(account_hash: AccountHash, args: RuntimeArgs) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(account_hash, CONTRACT_CONTRACT_METADATA, args).build()
+}
+
+fn setup() -> (InMemoryWasmTestBuilder, Key) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    builder
+        .exec(metadata_request(
+            DEFAULT_ACCOUNT_ADDR,
+            runtime_args! { ARG_STAGE => "install" },
+        ))
+        .expect_success()
+        .commit();
+
+    let package_hash = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(PACKAGE_HASH_KEY)
+        .expect("should have package hash");
+
+    (builder, package_hash)
+}
+
+#[ignore]
+#[test]
+fn should_set_package_and_version_metadata() {
+    let (builder, package_hash) = setup();
+
+    let contract_package = builder
+        .query(None, package_hash, &[])
+        .expect("should have package")
+        .as_contract_package()
+        .cloned()
+        .expect("should be package");
+    let package_metadata = contract_package
+        .metadata()
+        .expect("package should have metadata");
+    assert_eq!(package_metadata.name(), "Metadata");
+    assert_eq!(package_metadata.source_hash(), None);
+
+    let contract_hash = contract_package
+        .current_contract_hash()
+        .expect("should have version");
+    let contract = builder
+        .query(None, Key::Hash(contract_hash), &[])
+        .expect("should have contract")
+        .as_contract()
+        .cloned()
+        .expect("should be contract");
+    let version_metadata = contract.metadata().expect("version should have metadata");
+    assert_eq!(version_metadata.name(), "Metadata v1");
+    assert_eq!(version_metadata.source_hash(), Some([1; 32]));
+    assert_eq!(
+        version_metadata
+            .entry_point_docs()
+            .get(ENTRY_FUNCTION_NAME)
+            .map(String::as_str),
+        Some("Does nothing")
+    );
+    assert_eq!(
+        version_metadata.named_keys_schema().get(COUNTER_KEY),
+        Some(&CLType::U64)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_set_metadata_without_access_key() {
+    let (mut builder, package_hash) = setup();
+
+    let fund_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => ACCOUNT_1_ADDR,
+            ARG_AMOUNT => U512::from(10) * *DEFAULT_PAYMENT,
+        },
+    )
+    .build();
+    builder.exec(fund_request).expect_success().commit();
+
+    builder
+        .exec(metadata_request(
+            ACCOUNT_1_ADDR,
+            runtime_args! { ARG_STAGE => "set_foreign", ARG_PACKAGE_HASH => package_hash },
+        ))
+        .commit();
+    let error_message = builder
+        .exec_error_message(builder.get_exec_responses_count() - 1)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("ForgedReference"),
+        "{}",
+        error_message
+    );
+
+    let contract_package = builder
+        .query(None, package_hash, &[])
+        .expect("should have package")
+        .as_contract_package()
+        .cloned()
+        .expect("should be package");
+    assert!(contract_package.metadata().is_some());
+}
@@ -0,0 +1,140 @@
+use engine_core::engine_state::deploy_item::DeployItem;
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    Code, SessionBuilder, TestContextBuilder, DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const REVERT_WASM: &str = "revert.wasm";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000;
+
+fn transfer_deploy(payment_amount: U512, authorization_key: AccountHash) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::from(TRANSFER_AMOUNT) },
+        )
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => payment_amount })
+        .with_authorization_keys(&[authorization_key])
+        .build()
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_estimate_exact_cost_without_committing() {
+    let mut builder = setup();
+    let pre_state_hash = builder.get_post_state_hash();
+
+    let deploy = transfer_deploy(*DEFAULT_PAYMENT, DEFAULT_ACCOUNT_ADDR);
+    builder.dry_run(ExecuteRequestBuilder::from_deploy_item(deploy.clone()).build());
+
+    let estimate_result = builder
+        .last_estimate_result()
+        .expect("should have estimate result");
+    assert!(estimate_result.is_success());
+    assert!(estimate_result.session_cost() > Gas::default());
+    assert!(!estimate_result.effect().transforms.is_empty());
+    let estimated_cost = estimate_result.total_cost();
+
+    assert_eq!(builder.get_post_state_hash(), pre_state_hash);
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_none());
+
+    builder
+        .exec(ExecuteRequestBuilder::from_deploy_item(deploy).build())
+        .expect_success()
+        .commit();
+    assert_eq!(builder.exec_costs(0), vec![estimated_cost]);
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_some());
+}
+
+#[ignore]
+#[test]
+fn should_estimate_regardless_of_payment_amount() {
+    let mut builder = setup();
+
+    let deploy = transfer_deploy(U512::one(), DEFAULT_ACCOUNT_ADDR);
+    builder.dry_run(ExecuteRequestBuilder::from_deploy_item(deploy.clone()).build());
+    let estimate_result = builder
+        .last_estimate_result()
+        .expect("should have estimate result");
+    assert!(estimate_result.is_success());
+
+    // With such a low payment the session code runs out of gas when actually executed.
+    builder.exec(ExecuteRequestBuilder::from_deploy_item(deploy).build());
+    assert!(builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_estimate_regardless_of_authorization_keys() {
+    let mut builder = setup();
+
+    let deploy = transfer_deploy(*DEFAULT_PAYMENT, AccountHash::new([42; 32]));
+    builder.dry_run(ExecuteRequestBuilder::from_deploy_item(deploy).build());
+
+    let estimate_result = builder
+        .last_estimate_result()
+        .expect("should have estimate result");
+    assert!(estimate_result.is_success());
+}
+
+#[ignore]
+#[test]
+fn should_report_session_error_in_estimate() {
+    let mut builder = setup();
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, REVERT_WASM, RuntimeArgs::default())
+            .build();
+    builder.dry_run(exec_request);
+
+    let estimate_result = builder
+        .last_estimate_result()
+        .expect("should have estimate result");
+    assert!(!estimate_result.is_success());
+    assert!(estimate_result.payment_cost() > Gas::default());
+    assert!(estimate_result.session_cost() > Gas::default());
+}
+
+#[ignore]
+#[test]
+fn should_estimate_session_with_dry_run() {
+    let mut context = TestContextBuilder::new().build();
+
+    let session = SessionBuilder::new(
+        Code::from(CONTRACT_TRANSFER_TO_ACCOUNT),
+        runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::from(TRANSFER_AMOUNT) },
+    )
+    .with_address(DEFAULT_ACCOUNT_ADDR)
+    .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+    .with_dry_run()
+    .build();
+    context.run(session);
+
+    let gas_estimate = context
+        .last_gas_estimate()
+        .expect("should have gas estimate");
+    assert!(gas_estimate.is_success());
+    assert_eq!(
+        gas_estimate.total_cost(),
+        gas_estimate.payment_cost() + gas_estimate.session_cost()
+    );
+    assert!(context.get_account(ACCOUNT_1_ADDR).is_none());
+}
@@ -0,0 +1,163 @@
+use engine_core::{
+    execution::{ExecutionTrace, TraceEntry},
+    resolvers::v1_function_index::FunctionIndex,
+};
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, Key, Phase, RuntimeArgs};
+
+const CONTRACT_COUNTER_DEFINE: &str = "counter_define.wasm";
+const REVERT_WASM: &str = "revert.wasm";
+const HASH_KEY_NAME: &str = "counter_package_hash";
+const ENTRYPOINT_SESSION: &str = "session";
+const COUNTER_CONTRACT_HASH_KEY_NAME: &str = "counter_contract_hash";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+fn session_entry_points(trace: &ExecutionTrace) -> Vec<&str> {
+    trace
+        .entries()
+        .iter()
+        .filter_map(|entry| match entry {
+            TraceEntry::Enter {
+                phase: Phase::Session,
+                entry_point,
+                ..
+            } => Some(entry_point.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[ignore]
+#[test]
+fn should_not_trace_by_default() {
+    let mut builder = setup();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_COUNTER_DEFINE,
+        RuntimeArgs::new(),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    assert!(builder.last_exec_trace().is_none());
+}
+
+#[ignore]
+#[test]
+fn should_trace_nested_contract_calls() {
+    let mut builder = setup();
+
+    let install_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_COUNTER_DEFINE,
+        RuntimeArgs::new(),
+    )
+    .build();
+    builder.exec(install_request).expect_success().commit();
+
+    let counter_contract_hash_key: Key = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(COUNTER_CONTRACT_HASH_KEY_NAME)
+        .expect("should have counter contract hash key");
+
+    let increment_request = ExecuteRequestBuilder::versioned_contract_call_by_hash_key_name(
+        DEFAULT_ACCOUNT_ADDR,
+        HASH_KEY_NAME,
+        None,
+        ENTRYPOINT_SESSION,
+        runtime_args! { COUNTER_CONTRACT_HASH_KEY_NAME => counter_contract_hash_key },
+    )
+    .with_tracing()
+    .build();
+    builder.exec(increment_request).expect_success().commit();
+
+    let trace = builder.last_exec_trace().expect("should have trace");
+
+    // The stored session code is entered first, then the counter contract it calls.
+    let entry_points = session_entry_points(trace);
+    assert!(entry_points.len() >= 2, "{:?}", entry_points);
+    assert_eq!(entry_points[0], ENTRYPOINT_SESSION);
+
+    // Every frame which is entered is exited.
+    let enter_count = trace
+        .entries()
+        .iter()
+        .filter(|entry| matches!(entry, TraceEntry::Enter { .. }))
+        .count();
+    let exit_count = trace
+        .entries()
+        .iter()
+        .filter(|entry| matches!(entry, TraceEntry::Exit { error: None, .. }))
+        .count();
+    assert_eq!(enter_count, exit_count);
+
+    // The counter contract hash was passed in as an argument, so it's decoded by the host.
+    assert!(trace.entries().iter().any(|entry| match entry {
+        TraceEntry::HostCall { args, .. } => !args.is_empty(),
+        _ => false,
+    }));
+
+    for entry in trace.entries() {
+        if let TraceEntry::HostCall {
+            gas_before,
+            gas_after,
+            ..
+        } = entry
+        {
+            assert!(gas_before <= gas_after);
+        }
+    }
+}
+
+#[ignore]
+#[test]
+fn should_trace_reverting_session() {
+    let mut builder = setup();
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, REVERT_WASM, RuntimeArgs::default())
+            .with_tracing()
+            .build();
+    builder.exec(exec_request).commit();
+    assert!(builder.is_error());
+
+    let trace = builder.last_exec_trace().expect("should have trace");
+
+    let revert_call = trace
+        .entries()
+        .iter()
+        .find_map(|entry| match entry {
+            TraceEntry::HostCall {
+                function: FunctionIndex::RevertFuncIndex,
+                error,
+                ..
+            } => Some(error),
+            _ => None,
+        })
+        .expect("should have called revert");
+    assert!(revert_call.is_some());
+
+    match trace.entries().last() {
+        Some(TraceEntry::Exit { error, .. }) => assert!(error.is_some()),
+        other => panic!("trace should end with an exit frame, got {:?}", other),
+    }
+
+    let json: serde_json::Value =
+        serde_json::from_str(&trace.to_json(true)).expect("should parse trace json");
+    let entries = json["entries"].as_array().expect("should have entries");
+    assert_eq!(entries.len(), trace.entries().len());
+    assert!(entries
+        .iter()
+        .any(|entry| entry["function"] == "RevertFuncIndex"));
+}
@@ -0,0 +1,64 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::RuntimeArgs;
+
+const CONTRACT_COUNTER_DEFINE: &str = "counter_define.wasm";
+const ENTRYPOINT_CALL: &str = "call";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_profile_without_changing_cost() {
+    let mut builder = setup();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_COUNTER_DEFINE,
+        RuntimeArgs::new(),
+    )
+    .build();
+    builder.exec(exec_request).expect_success();
+    let unprofiled_cost = builder.last_exec_gas_cost();
+    assert!(builder.last_gas_profile().is_none());
+
+    let mut builder = setup();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_COUNTER_DEFINE,
+        RuntimeArgs::new(),
+    )
+    .with_profiling()
+    .build();
+    builder.exec(exec_request).expect_success();
+    let profiled_cost = builder.last_exec_gas_cost();
+
+    assert_eq!(profiled_cost, unprofiled_cost);
+
+    let profile = builder.last_gas_profile().expect("should have profile");
+    assert!(profile.total() > Default::default());
+
+    // The exported entry point is entered as a Wasm function under its contract frame.
+    assert!(
+        profile
+            .stacks()
+            .keys()
+            .any(|stack| stack.split(';').nth(1) == Some(ENTRYPOINT_CALL)),
+        "{}",
+        profile.to_folded()
+    );
+    assert!(profile.total_cost(ENTRYPOINT_CALL) > Default::default());
+
+    for line in profile.to_folded().lines() {
+        let (stack, gas) = line.split_at(line.rfind(' ').expect("should have gas"));
+        assert!(!stack.is_empty());
+        gas.trim().parse::<u64>().expect("gas should be a number");
+    }
+}
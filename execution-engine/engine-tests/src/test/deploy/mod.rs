@@ -1,3 +1,10 @@
+mod dry_run;
+mod execution_trace;
+mod gas_profile;
 mod non_standard_payment;
+mod parallel_execution;
 mod preconditions;
+mod replay_protection;
+mod sequential_execution;
 mod stored_contracts;
+mod wasm_validation;
@@ -0,0 +1,293 @@
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use engine_core::engine_state::{
+    conflicts::DeployConflicts, deploy_item::DeployItem, engine_config::EngineConfig,
+    genesis::GenesisAccount,
+};
+use engine_shared::{
+    motes::Motes,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+const ARG_ENTRY_POINT: &str = "entry_point";
+const TEST_UNBOND: &str = "unbond";
+
+const DEPLOY_WORKER_COUNT: usize = 4;
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ACCOUNT_2_ADDR: AccountHash = AccountHash::new([2u8; 32]);
+const FUNDING_AMOUNT: u64 = 1_000_000_000;
+const TRANSFER_AMOUNT: u64 = 1_000;
+const ACCOUNT_1_STAKE: u64 = 100_000;
+const ACCOUNT_1_UNBOND: u64 = 10_000;
+
+fn transfer_deploy(sender: AccountHash, target: AccountHash, amount: u64, seed: u8) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(sender)
+        .with_deploy_hash([seed; 32])
+        .with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! { ARG_TARGET => target, ARG_AMOUNT => U512::from(amount) },
+        )
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[sender])
+        .build()
+}
+
+fn unbond_deploy(sender: AccountHash, amount: u64, seed: u8) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(sender)
+        .with_deploy_hash([seed; 32])
+        .with_session_code(
+            CONTRACT_POS_BONDING,
+            runtime_args! {
+                ARG_ENTRY_POINT => TEST_UNBOND,
+                ARG_AMOUNT => Some(U512::from(amount)),
+            },
+        )
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[sender])
+        .build()
+}
+
+/// Runs genesis with account 1 as a validator and funds two accounts, so that the later deploys
+/// have several senders.
+fn setup(engine_config: EngineConfig) -> InMemoryWasmTestBuilder {
+    let mut accounts = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        ACCOUNT_1_ADDR,
+        Motes::new(ACCOUNT_1_STAKE.into()),
+        Motes::new(ACCOUNT_1_STAKE.into()),
+    ));
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&run_genesis_request);
+    for (seed, account) in [ACCOUNT_1_ADDR, ACCOUNT_2_ADDR].iter().enumerate() {
+        let deploy = transfer_deploy(DEFAULT_ACCOUNT_ADDR, *account, FUNDING_AMOUNT, seed as u8);
+        let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+        builder.exec(exec_request).expect_success().commit();
+    }
+    builder
+}
+
+/// Deploys 0 and 2 share a sender, deploy 1 has a different one.
+fn deploys() -> Vec<DeployItem> {
+    vec![
+        transfer_deploy(
+            ACCOUNT_1_ADDR,
+            AccountHash::new([3; 32]),
+            TRANSFER_AMOUNT,
+            10,
+        ),
+        transfer_deploy(
+            ACCOUNT_2_ADDR,
+            AccountHash::new([4; 32]),
+            TRANSFER_AMOUNT,
+            11,
+        ),
+        transfer_deploy(
+            ACCOUNT_1_ADDR,
+            AccountHash::new([5; 32]),
+            TRANSFER_AMOUNT,
+            12,
+        ),
+        transfer_deploy(
+            DEFAULT_ACCOUNT_ADDR,
+            AccountHash::new([6; 32]),
+            TRANSFER_AMOUNT,
+            13,
+        ),
+        transfer_deploy(
+            ACCOUNT_2_ADDR,
+            AccountHash::new([7; 32]),
+            TRANSFER_AMOUNT,
+            14,
+        ),
+    ]
+}
+
+fn exec_deploys(builder: &mut InMemoryWasmTestBuilder, deploys: Vec<DeployItem>) {
+    let exec_request = deploys
+        .into_iter()
+        .fold(
+            ExecuteRequestBuilder::new(),
+            ExecuteRequestBuilder::push_deploy,
+        )
+        .build();
+    builder.exec(exec_request);
+}
+
+fn engine_config() -> EngineConfig {
+    EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+}
+
+#[ignore]
+#[test]
+fn should_match_single_threaded_results_when_executing_deploys_in_parallel() {
+    let mut single_threaded_builder = setup(engine_config());
+    let mut parallel_builder = setup(engine_config().with_deploy_worker_count(DEPLOY_WORKER_COUNT));
+
+    assert_eq!(
+        single_threaded_builder.get_post_state_hash(),
+        parallel_builder.get_post_state_hash()
+    );
+
+    exec_deploys(&mut single_threaded_builder, deploys());
+    exec_deploys(&mut parallel_builder, deploys());
+
+    let single_threaded_results = single_threaded_builder
+        .get_exec_response(2)
+        .expect("there should be a response");
+    let parallel_results = parallel_builder
+        .get_exec_response(2)
+        .expect("there should be a response");
+
+    assert_eq!(parallel_results.len(), deploys().len());
+    assert_eq!(single_threaded_results.len(), parallel_results.len());
+    for (single_threaded_result, parallel_result) in
+        single_threaded_results.iter().zip(parallel_results.iter())
+    {
+        assert!(parallel_result.is_success(), "{:?}", parallel_result);
+        assert_eq!(single_threaded_result.cost(), parallel_result.cost());
+        assert_eq!(single_threaded_result.effect(), parallel_result.effect());
+    }
+}
+
+/// Returns the finalization keys of deploys executed on the latest post-state hash, along with
+/// their values.
+fn finalization_keys(builder: &InMemoryWasmTestBuilder) -> BTreeMap<Key, StoredValue> {
+    let state_hash = Blake2bHash::try_from(builder.get_post_state_hash().as_slice())
+        .expect("should be a valid state hash");
+    builder
+        .get_engine_state()
+        .finalization_keys(CorrelationId::new(), *DEFAULT_PROTOCOL_VERSION, state_hash)
+        .expect("should read finalization keys")
+        .expect("should find state hash")
+}
+
+/// Commits the effects of the deploys at `indices` of the latest exec response one after another
+/// on the latest post-state hash, and returns the resulting post-state hash.
+fn commit_in_order(builder: &InMemoryWasmTestBuilder, indices: &[usize]) -> Vec<u8> {
+    let results = builder
+        .get_exec_response(2)
+        .expect("there should be a response");
+    let mut builder = builder.clone();
+    for index in indices {
+        let prestate_hash = builder.get_post_state_hash();
+        let transforms = results[*index].effect().transforms.clone();
+        builder.commit_effects(prestate_hash, transforms);
+    }
+    builder.get_post_state_hash()
+}
+
+#[ignore]
+#[test]
+fn should_report_deploys_from_the_same_account_as_conflicting() {
+    let mut builder = setup(engine_config().with_deploy_worker_count(DEPLOY_WORKER_COUNT));
+    let finalization_keys = finalization_keys(&builder);
+
+    exec_deploys(&mut builder, deploys());
+
+    let results = builder
+        .get_exec_response(2)
+        .expect("there should be a response");
+    let deploy_conflicts = DeployConflicts::new(
+        results.iter().map(|result| result.effect()),
+        &finalization_keys,
+    );
+
+    // Both deploys write the balance of account 1's main purse.
+    assert!(deploy_conflicts.conflicting_pairs().contains(&(0, 2)));
+    assert!(deploy_conflicts.conflicting_pairs().contains(&(1, 4)));
+
+    // Deploys from different senders to different targets commute.
+    assert!(deploy_conflicts.commuting_pairs().contains(&(0, 1)));
+    assert!(!deploy_conflicts.conflicting_pairs().contains(&(0, 1)));
+
+    let deploy_count = results.len();
+    let pair_count =
+        deploy_conflicts.commuting_pairs().len() + deploy_conflicts.conflicting_pairs().len();
+    assert_eq!(pair_count, deploy_count * (deploy_count - 1) / 2);
+}
+
+#[ignore]
+#[test]
+fn should_reach_the_same_state_when_committing_commuting_deploys_in_either_order() {
+    let mut builder = setup(engine_config().with_deploy_worker_count(DEPLOY_WORKER_COUNT));
+    let finalization_keys = finalization_keys(&builder);
+
+    exec_deploys(&mut builder, deploys());
+
+    let results = builder
+        .get_exec_response(2)
+        .expect("there should be a response");
+    let deploy_conflicts = DeployConflicts::new(
+        results.iter().map(|result| result.effect()),
+        &finalization_keys,
+    );
+    assert!(!deploy_conflicts.commuting_pairs().is_empty());
+
+    for &(first, second) in deploy_conflicts.commuting_pairs() {
+        assert_eq!(
+            commit_in_order(&builder, &[first, second]),
+            commit_in_order(&builder, &[second, first]),
+            "deploys {} and {} should commute",
+            first,
+            second
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_report_unbonding_as_conflicting_with_other_deploys() {
+    let mut builder = setup(engine_config().with_deploy_worker_count(DEPLOY_WORKER_COUNT));
+    let finalization_keys = finalization_keys(&builder);
+
+    let deploys = vec![
+        unbond_deploy(ACCOUNT_1_ADDR, ACCOUNT_1_UNBOND, 10),
+        transfer_deploy(
+            ACCOUNT_2_ADDR,
+            AccountHash::new([3; 32]),
+            TRANSFER_AMOUNT,
+            11,
+        ),
+    ];
+    exec_deploys(&mut builder, deploys);
+
+    let results = builder
+        .get_exec_response(2)
+        .expect("there should be a response");
+    if !cfg!(feature = "enable-bonding") {
+        assert!(results[0].is_failure());
+        return;
+    }
+    assert!(results.iter().all(|result| result.is_success()));
+
+    // The transfer's finalization writes the Proof of Stake contract with the stakes from before
+    // the unbonding, so committing it after the unbonding would revert it.
+    let deploy_conflicts = DeployConflicts::new(
+        results.iter().map(|result| result.effect()),
+        &finalization_keys,
+    );
+    assert_eq!(deploy_conflicts.conflicting_pairs(), &[(0, 1)]);
+    assert_ne!(
+        commit_in_order(&builder, &[0, 1]),
+        commit_in_order(&builder, &[1, 0])
+    );
+}
@@ -0,0 +1,214 @@
+use engine_core::engine_state::{EngineConfig, Error, ExecuteRequest};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_ENGINE_CONFIG, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const ARG_TARGET: &str = "target";
+
+const DEPLOY_HASH: [u8; 32] = [1; 32];
+const TIMESTAMP: u64 = 1_000;
+const TTL: u64 = 500;
+
+fn transfer_request(deploy_hash: [u8; 32], block_time: u64) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+        .with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::one() },
+        )
+        .with_deploy_hash(deploy_hash)
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_ttl(TIMESTAMP, TTL)
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy)
+        .with_block_time(block_time)
+        .build()
+}
+
+fn wasmless_transfer_request(deploy_hash: [u8; 32], block_time: u64) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_empty_payment_bytes(runtime_args! {})
+        .with_transfer_args(
+            runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::one() },
+        )
+        .with_deploy_hash(deploy_hash)
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_ttl(TIMESTAMP, TTL)
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy)
+        .with_block_time(block_time)
+        .build()
+}
+
+fn setup(enable_replay_protection: bool) -> InMemoryWasmTestBuilder {
+    let engine_config =
+        DEFAULT_ENGINE_CONFIG.with_enable_replay_protection(enable_replay_protection);
+    setup_with_config(engine_config)
+}
+
+fn setup_with_config(engine_config: EngineConfig) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+fn assert_precondition_failure(
+    builder: &mut InMemoryWasmTestBuilder,
+    request: ExecuteRequest,
+    expected: Error,
+) {
+    builder.exec(request).commit();
+    let response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have exec response");
+    let error = utils::get_precondition_failure(response);
+    assert_eq!(format!("{}", error), format!("{}", expected));
+}
+
+#[ignore]
+#[test]
+fn should_reject_expired_deploy() {
+    let mut builder = setup(false);
+
+    // The last block time at which the deploy may be executed
+    builder
+        .exec(transfer_request(DEPLOY_HASH, TIMESTAMP + TTL))
+        .expect_success()
+        .commit();
+
+    assert_precondition_failure(
+        &mut builder,
+        transfer_request([2; 32], TIMESTAMP + TTL + 1),
+        Error::DeployExpired,
+    );
+
+    assert_precondition_failure(
+        &mut builder,
+        wasmless_transfer_request([3; 32], TIMESTAMP + TTL + 1),
+        Error::DeployExpired,
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_with_ttl_above_maximum() {
+    let max_deploy_ttl = TTL - 1;
+    let engine_config = DEFAULT_ENGINE_CONFIG
+        .with_enable_replay_protection(true)
+        .with_max_deploy_ttl(max_deploy_ttl);
+    let mut builder = setup_with_config(engine_config);
+
+    assert_precondition_failure(
+        &mut builder,
+        transfer_request(DEPLOY_HASH, TIMESTAMP),
+        Error::DeployTtlTooLong(max_deploy_ttl),
+    );
+
+    assert_precondition_failure(
+        &mut builder,
+        wasmless_transfer_request([2; 32], TIMESTAMP),
+        Error::DeployTtlTooLong(max_deploy_ttl),
+    );
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.executed_deploys().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_reject_replayed_deploy() {
+    let mut builder = setup(true);
+
+    builder
+        .exec(transfer_request(DEPLOY_HASH, TIMESTAMP))
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.executed_deploys().contains_key(&DEPLOY_HASH));
+
+    assert_precondition_failure(
+        &mut builder,
+        transfer_request(DEPLOY_HASH, TIMESTAMP + 1),
+        Error::DeployReplayed,
+    );
+
+    builder
+        .exec(wasmless_transfer_request([2; 32], TIMESTAMP + 1))
+        .expect_success()
+        .commit();
+    assert_precondition_failure(
+        &mut builder,
+        wasmless_transfer_request([2; 32], TIMESTAMP + 2),
+        Error::DeployReplayed,
+    );
+}
+
+#[ignore]
+#[test]
+fn should_forget_expired_deploys() {
+    let mut builder = setup(true);
+
+    builder
+        .exec(transfer_request(DEPLOY_HASH, TIMESTAMP))
+        .expect_success()
+        .commit();
+
+    // A deploy created after the first one expired replaces it in the record
+    let later_deploy = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+            .with_session_code(
+                CONTRACT_TRANSFER_TO_ACCOUNT,
+                runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::one() },
+            )
+            .with_deploy_hash([2; 32])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .with_ttl(TIMESTAMP + TTL + 1, TTL)
+            .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy)
+            .with_block_time(TIMESTAMP + TTL + 1)
+            .build()
+    };
+    builder.exec(later_deploy).expect_success().commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let executed_deploys: Vec<[u8; 32]> = account.executed_deploys().keys().cloned().collect();
+    assert_eq!(executed_deploys, vec![[2; 32]]);
+}
+
+#[ignore]
+#[test]
+fn should_not_record_deploys_without_replay_protection() {
+    let mut builder = setup(false);
+
+    builder
+        .exec(transfer_request(DEPLOY_HASH, TIMESTAMP))
+        .expect_success()
+        .commit();
+    builder
+        .exec(transfer_request(DEPLOY_HASH, TIMESTAMP + 1))
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.executed_deploys().is_empty());
+}
@@ -0,0 +1,167 @@
+use assert_matches::assert_matches;
+
+use engine_core::engine_state::{deploy_item::DeployItem, Error};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ACCOUNT_2_ADDR: AccountHash = AccountHash::new([2u8; 32]);
+const ACCOUNT_1_TRANSFER_AMOUNT: u64 = 1_000_000_000;
+const ACCOUNT_2_TRANSFER_AMOUNT: u64 = 100_000_000;
+
+fn transfer_deploy(
+    sender: AccountHash,
+    target: AccountHash,
+    amount: u64,
+    deploy_hash: [u8; 32],
+) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(sender)
+        .with_deploy_hash(deploy_hash)
+        .with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! { ARG_TARGET => target, ARG_AMOUNT => U512::from(amount) },
+        )
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[sender])
+        .build()
+}
+
+/// The second deploy is sent from the account which the first deploy creates.
+fn dependent_deploys() -> (DeployItem, DeployItem) {
+    (
+        transfer_deploy(
+            DEFAULT_ACCOUNT_ADDR,
+            ACCOUNT_1_ADDR,
+            ACCOUNT_1_TRANSFER_AMOUNT,
+            [1; 32],
+        ),
+        transfer_deploy(
+            ACCOUNT_1_ADDR,
+            ACCOUNT_2_ADDR,
+            ACCOUNT_2_TRANSFER_AMOUNT,
+            [2; 32],
+        ),
+    )
+}
+
+fn get_main_purse_balance(builder: &InMemoryWasmTestBuilder, account_hash: AccountHash) -> U512 {
+    let account = builder
+        .get_account(account_hash)
+        .expect("should have account");
+    builder.get_purse_balance(account.main_purse())
+}
+
+#[ignore]
+#[test]
+fn should_observe_effects_of_previous_deploys_in_sequential_mode() {
+    let (deploy_1, deploy_2) = dependent_deploys();
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(deploy_1)
+        .push_deploy(deploy_2)
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let genesis_hash = builder.get_post_state_hash();
+
+    builder.exec_sequential(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    assert_eq!(response.len(), 2);
+    assert!(response.iter().all(|result| result.is_success()));
+
+    assert_ne!(builder.get_post_state_hash(), genesis_hash);
+    assert_eq!(
+        get_main_purse_balance(&builder, ACCOUNT_2_ADDR),
+        U512::from(ACCOUNT_2_TRANSFER_AMOUNT)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_observe_effects_of_previous_deploys_in_independent_mode() {
+    let (deploy_1, deploy_2) = dependent_deploys();
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(deploy_1)
+        .push_deploy(deploy_2)
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    assert_eq!(response.len(), 2);
+    assert!(response[0].is_success());
+
+    // account 1 does not exist in the parent state, so its deploy can't be authorized
+    let precondition_failure = utils::get_precondition_failure(&response[1..]);
+    assert_matches!(precondition_failure, Error::Authorization);
+}
+
+#[ignore]
+#[test]
+fn should_commit_the_same_state_as_executing_deploys_one_by_one() {
+    let mut sequential_builder = InMemoryWasmTestBuilder::default();
+    {
+        let (deploy_1, deploy_2) = dependent_deploys();
+        let exec_request = ExecuteRequestBuilder::new()
+            .push_deploy(deploy_1)
+            .push_deploy(deploy_2)
+            .build();
+        sequential_builder
+            .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+            .exec_sequential(exec_request);
+    }
+
+    let mut one_by_one_builder = InMemoryWasmTestBuilder::default();
+    {
+        let (deploy_1, deploy_2) = dependent_deploys();
+        one_by_one_builder
+            .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+            .exec(ExecuteRequestBuilder::from_deploy_item(deploy_1).build())
+            .expect_success()
+            .commit()
+            .exec(ExecuteRequestBuilder::from_deploy_item(deploy_2).build())
+            .expect_success()
+            .commit();
+    }
+
+    let sequential_costs: Vec<_> = sequential_builder
+        .get_exec_response(0)
+        .expect("there should be a response")
+        .iter()
+        .map(|result| result.cost())
+        .collect();
+    let one_by_one_costs = vec![
+        one_by_one_builder.exec_costs(0)[0],
+        one_by_one_builder.exec_costs(1)[0],
+    ];
+    assert_eq!(sequential_costs, one_by_one_costs);
+
+    for account_hash in &[DEFAULT_ACCOUNT_ADDR, ACCOUNT_1_ADDR, ACCOUNT_2_ADDR] {
+        assert_eq!(
+            get_main_purse_balance(&sequential_builder, *account_hash),
+            get_main_purse_balance(&one_by_one_builder, *account_hash)
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use engine_core::engine_state::{upgrade::ActivationPoint, CONV_RATE};
+use engine_core::engine_state::{upgrade::ActivationPoint, ExecuteRequest, CONV_RATE};
 use engine_grpc_server::engine_server::ipc::DeployCode;
 use engine_shared::{
     account::Account, motes::Motes, stored_value::StoredValue, transform::Transform,
@@ -9,14 +9,15 @@ use engine_storage::global_state::in_memory::InMemoryGlobalState;
 use engine_test_support::{
     internal::{
         utils, AdditiveMapDiff, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
-        UpgradeRequestBuilder, WasmTestBuilder, DEFAULT_ACCOUNT_KEY, DEFAULT_RUN_GENESIS_REQUEST,
+        UpgradeRequestBuilder, WasmTestBuilder, DEFAULT_ACCOUNT_KEY, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
     },
     DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
 };
 use types::{
     account::AccountHash,
-    contracts::{ContractVersion, CONTRACT_INITIAL_VERSION, DEFAULT_ENTRY_POINT_NAME},
-    runtime_args, ContractHash, Key, ProtocolVersion, RuntimeArgs, U512,
+    contracts::{self, ContractVersion, CONTRACT_INITIAL_VERSION, DEFAULT_ENTRY_POINT_NAME},
+    runtime_args, ApiError, ContractHash, Key, ProtocolVersion, RuntimeArgs, U512,
 };
 
 const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
@@ -41,8 +42,15 @@ const TRANSFER_PURSE_TO_ACCOUNT_STORED_HASH_KEY_NAME: &str = "transfer_purse_to_
 const EXPECTED_ERROR_MESSAGE: &str = "IncompatibleProtocolMajorVersion { expected: 2, actual: 1 }";
 const EXPECTED_VERSION_ERROR_MESSAGE: &str = "InvalidContractVersion(ContractVersionKey(2, 1))";
 
+const CONTRACT_MIGRATION_CONTRACT_NAME: &str = "contract_migration.wasm";
+const MIGRATION_PACKAGE_HASH_NAME: &str = "migration_package";
+const MIGRATED_FROM_NAME: &str = "migrated_from";
+const MIGRATE: &str = "migrate";
+const MIGRATION_FAILED: u16 = 1;
+
 const ARG_TARGET: &str = "target";
 const ARG_AMOUNT: &str = "amount";
+const ARG_STAGE: &str = "stage";
 
 /// Prepares a upgrade request with pre-loaded deploy code, and new protocol version.
 fn make_upgrade_request(
@@ -1206,3 +1214,199 @@ fn should_execute_stored_payment_and_session_code_with_new_major_version() {
         .expect_success()
         .commit();
 }
+
+fn contract_migration_request(stage: &str) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MIGRATION_CONTRACT_NAME,
+        runtime_args! { ARG_STAGE => stage },
+    )
+    .build()
+}
+
+fn migration_package_versions(builder: &InMemoryWasmTestBuilder) -> Vec<ContractHash> {
+    let package_hash = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(MIGRATION_PACKAGE_HASH_NAME)
+        .expect("should have package hash");
+    builder
+        .query(None, package_hash, &[])
+        .expect("should have package")
+        .as_contract_package()
+        .expect("should be package")
+        .versions()
+        .values()
+        .cloned()
+        .collect()
+}
+
+#[ignore]
+#[test]
+fn should_migrate_when_adding_contract_version() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    builder
+        .exec(contract_migration_request("install"))
+        .expect_success()
+        .commit();
+    builder
+        .exec(contract_migration_request("upgrade"))
+        .expect_success()
+        .commit();
+
+    let versions = migration_package_versions(&builder);
+    assert_eq!(versions.len(), 2);
+
+    let new_contract = builder
+        .query(None, Key::Hash(versions[1]), &[])
+        .expect("should have new contract")
+        .as_contract()
+        .cloned()
+        .expect("should be contract");
+    assert_eq!(
+        new_contract.named_keys().get(MIGRATED_FROM_NAME),
+        Some(&Key::Hash(versions[0])),
+        "migration should have been called with the previous version"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_roll_back_contract_version_when_migration_fails() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    builder
+        .exec(contract_migration_request("install"))
+        .expect_success()
+        .commit();
+    builder
+        .exec(contract_migration_request("upgrade_failing"))
+        .commit();
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have exec error");
+    assert!(
+        error_message.contains(&format!("{:?}", ApiError::User(MIGRATION_FAILED))),
+        "{:?}",
+        error_message
+    );
+
+    let versions = migration_package_versions(&builder);
+    assert_eq!(
+        versions.len(),
+        1,
+        "new version should have been rolled back"
+    );
+}
+
+fn assert_migration_rejected(stage: &str, expected_error: contracts::Error) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    builder
+        .exec(contract_migration_request("install"))
+        .expect_success()
+        .commit();
+    builder.exec(contract_migration_request(stage)).commit();
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have exec error");
+    assert!(
+        error_message.contains(&format!("{:?}", ApiError::from(expected_error))),
+        "{:?}",
+        error_message
+    );
+
+    let versions = migration_package_versions(&builder);
+    assert_eq!(versions.len(), 1, "new version should not have been added");
+}
+
+#[ignore]
+#[test]
+fn should_not_migrate_with_unknown_entry_point() {
+    assert_migration_rejected(
+        "upgrade_unknown",
+        contracts::Error::MigrationEntryPointNotFound,
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_migrate_with_session_entry_point() {
+    assert_migration_rejected(
+        "upgrade_session",
+        contracts::Error::InvalidMigrationEntryPoint,
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_migrate_with_public_entry_point() {
+    assert_migration_rejected(
+        "upgrade_public",
+        contracts::Error::InvalidMigrationEntryPoint,
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_call_migration_entry_point_after_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    builder
+        .exec(contract_migration_request("install"))
+        .expect_success()
+        .commit();
+    builder
+        .exec(contract_migration_request("upgrade"))
+        .expect_success()
+        .commit();
+
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_stored_versioned_contract_by_name(
+                MIGRATION_PACKAGE_HASH_NAME,
+                None,
+                MIGRATE,
+                runtime_args! {
+                    contracts::ARG_PREVIOUS_CONTRACT_HASH => ContractHash::default(),
+                },
+            )
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_deploy_hash([3; 32])
+            .build();
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+    builder.exec(exec_request).commit();
+
+    let error_message = builder
+        .exec_error_message(2)
+        .expect("should have exec error");
+    assert!(
+        error_message.contains("InvalidContext"),
+        "{:?}",
+        error_message
+    );
+
+    let versions = migration_package_versions(&builder);
+    let new_contract = builder
+        .query(None, Key::Hash(versions[1]), &[])
+        .expect("should have new contract")
+        .as_contract()
+        .cloned()
+        .expect("should be contract");
+    assert_eq!(
+        new_contract.named_keys().get(MIGRATED_FROM_NAME),
+        Some(&Key::Hash(versions[0])),
+        "migration should not have been called again"
+    );
+}
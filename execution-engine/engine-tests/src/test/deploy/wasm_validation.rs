@@ -0,0 +1,113 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, RuntimeArgs};
+
+const FOREIGN_IMPORT_WAT: &str = r#"
+(module
+    (import "other" "write" (func (param i32 i32 i32 i32)))
+    (memory (;0;) 1)
+    (func (;1;) nop)
+    (export "call" (func 1)))
+"#;
+
+const WRONG_SIGNATURE_WAT: &str = r#"
+(module
+    (import "env" "write" (func (param i32 i32)))
+    (memory (;0;) 1)
+    (func (;1;) nop)
+    (export "call" (func 1)))
+"#;
+
+const UNKNOWN_HOST_FUNCTION_WAT: &str = r#"
+(module
+    (import "env" "no_such_function" (func))
+    (memory (;0;) 1)
+    (func (;1;) nop)
+    (export "call" (func 1)))
+"#;
+
+const MISSING_CALL_WAT: &str = r#"
+(module
+    (memory (;0;) 1)
+    (func (;0;) nop)
+    (export "other" (func 0)))
+"#;
+
+const TOO_MUCH_MEMORY_WAT: &str = r#"
+(module
+    (memory (;0;) 65)
+    (func (;0;) nop)
+    (export "call" (func 0)))
+"#;
+
+fn session_error_message(wat: &str) -> String {
+    let wasm_binary = wabt::wat2wasm(wat).expect("should parse");
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_bytes(wasm_binary, RuntimeArgs::new())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([42; 32])
+        .build();
+
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit()
+        .finish();
+    result.builder().exec_error_message(0).expect("should fail")
+}
+
+fn assert_rejected(wat: &str, expected_error: &str) {
+    let message = session_error_message(wat);
+    assert!(
+        message.contains("WasmPreprocessing") && message.contains(expected_error),
+        "Error message {:?} does not contain expected pattern {:?}",
+        message,
+        expected_error
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_import_from_foreign_module() {
+    assert_rejected(FOREIGN_IMPORT_WAT, "ForeignImport(\"other.write\")");
+}
+
+#[ignore]
+#[test]
+fn should_reject_host_function_with_wrong_signature() {
+    assert_rejected(
+        WRONG_SIGNATURE_WAT,
+        "HostFunctionSignatureMismatch(\"env.write\")",
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_unknown_host_function() {
+    assert_rejected(
+        UNKNOWN_HOST_FUNCTION_WAT,
+        "UnknownHostFunction(\"env.no_such_function\")",
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_session_without_call() {
+    assert_rejected(MISSING_CALL_WAT, "MissingEntryPoint");
+}
+
+#[ignore]
+#[test]
+fn should_reject_memory_beyond_limit() {
+    assert_rejected(TOO_MUCH_MEMORY_WAT, "MemoryTooLarge { pages: 65");
+}
@@ -0,0 +1,169 @@
+use engine_core::engine_state::ExecuteRequest;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    contracts::{self, CONTRACT_INITIAL_VERSION},
+    runtime_args, AccessRights, ApiError, ContractVersionKey, Key, RuntimeArgs,
+};
+
+const CONTRACT_LOCKED_CONTRACT: &str = "locked_contract.wasm";
+const PACKAGE_HASH_KEY: &str = "locked_package";
+const CONTRACT_HASH_KEY: &str = "locked_contract";
+const ENTRY_FUNCTION_NAME: &str = "delegate";
+const ARG_STAGE: &str = "stage";
+
+fn stage_request(stage: &str) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_LOCKED_CONTRACT,
+        runtime_args! { ARG_STAGE => stage },
+    )
+    .build()
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+        .exec(stage_request("install"))
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn named_key(builder: &InMemoryWasmTestBuilder, name: &str) -> Key {
+    *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(name)
+        .expect("should have named key")
+}
+
+fn assert_locked_contract_error(builder: &mut InMemoryWasmTestBuilder, stage: &str) {
+    builder.exec(stage_request(stage)).commit();
+    let message = builder
+        .exec_error_message(builder.get_exec_responses_count() - 1)
+        .expect("should have error message");
+    let expected = format!("{:?}", ApiError::from(contracts::Error::LockedContract));
+    assert!(message.contains(&expected), "{}", message);
+}
+
+#[ignore]
+#[test]
+fn should_install_callable_locked_contract() {
+    let mut builder = setup();
+
+    let package_hash = named_key(&builder, PACKAGE_HASH_KEY);
+    let contract_hash = named_key(&builder, CONTRACT_HASH_KEY);
+    let contract_package = builder
+        .query(None, package_hash, &[])
+        .expect("should have package")
+        .as_contract_package()
+        .cloned()
+        .expect("should be package");
+    assert!(contract_package.is_locked());
+    assert_eq!(contract_package.versions().len(), 1);
+    let version_key = ContractVersionKey::new(
+        DEFAULT_PROTOCOL_VERSION.value().major,
+        CONTRACT_INITIAL_VERSION,
+    );
+    assert_eq!(
+        contract_package
+            .lookup_contract_hash(version_key)
+            .map(|hash| Key::Hash(*hash)),
+        Some(contract_hash)
+    );
+    assert!(
+        builder
+            .get_account(DEFAULT_ACCOUNT_ADDR)
+            .expect("should have account")
+            .named_keys()
+            .values()
+            .all(|key| key.as_uref() != Some(&contract_package.access_key())),
+        "access key should not be handed out"
+    );
+    assert_eq!(
+        contract_package.access_key().access_rights(),
+        AccessRights::NONE
+    );
+    assert!(
+        builder
+            .query(None, Key::URef(contract_package.access_key()), &[])
+            .is_err(),
+        "access key should not be stored"
+    );
+
+    let call_request = ExecuteRequestBuilder::versioned_contract_call_by_hash_key_name(
+        DEFAULT_ACCOUNT_ADDR,
+        PACKAGE_HASH_KEY,
+        None,
+        ENTRY_FUNCTION_NAME,
+        RuntimeArgs::new(),
+    )
+    .build();
+    builder.exec(call_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_add_version_to_locked_contract() {
+    let mut builder = setup();
+
+    assert_locked_contract_error(&mut builder, "add_version");
+}
+
+#[ignore]
+#[test]
+fn should_not_disable_version_of_locked_contract() {
+    let mut builder = setup();
+
+    assert_locked_contract_error(&mut builder, "disable_version");
+
+    let package_hash = named_key(&builder, PACKAGE_HASH_KEY);
+    let contract_package = builder
+        .query(None, package_hash, &[])
+        .expect("should have package")
+        .as_contract_package()
+        .cloned()
+        .expect("should be package");
+    assert!(contract_package.disabled_versions().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_not_change_groups_of_locked_contract() {
+    let mut builder = setup();
+
+    for stage in &[
+        "create_group",
+        "remove_group",
+        "provision_uref",
+        "remove_urefs",
+    ] {
+        assert_locked_contract_error(&mut builder, stage);
+    }
+
+    let package_hash = named_key(&builder, PACKAGE_HASH_KEY);
+    let contract_package = builder
+        .query(None, package_hash, &[])
+        .expect("should have package")
+        .as_contract_package()
+        .cloned()
+        .expect("should be package");
+    assert!(contract_package.groups().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_not_set_metadata_of_locked_contract() {
+    let mut builder = setup();
+
+    assert_locked_contract_error(&mut builder, "set_package_metadata");
+    assert_locked_contract_error(&mut builder, "set_version_metadata");
+}
@@ -1,12 +1,19 @@
 mod check_transfer_success;
+mod contract_abi;
 mod contract_api;
 mod contract_context;
+mod contract_metadata;
 mod counter;
 mod deploy;
 mod explorer;
 mod groups;
+mod locked_contract;
 mod manage_groups;
+mod module_cache;
 mod regression;
 mod system_contracts;
 mod upgrade;
+#[cfg(feature = "wasmtime-backend")]
+mod wasm_backends;
+mod wasm_memory;
 mod wasmless_transfer;
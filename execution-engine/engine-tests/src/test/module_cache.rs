@@ -0,0 +1,101 @@
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ENGINE_CONFIG,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, Key, RuntimeArgs};
+
+const CONTRACT_COUNTER_DEFINE: &str = "counter_define.wasm";
+const HASH_KEY_NAME: &str = "counter_package_hash";
+const COUNTER_VALUE_UREF: &str = "counter";
+const ENTRYPOINT_SESSION: &str = "session";
+const COUNTER_CONTRACT_HASH_KEY_NAME: &str = "counter_contract_hash";
+
+/// Installs the counter contract and returns its hash key.
+fn install_counter(builder: &mut InMemoryWasmTestBuilder) -> Key {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_COUNTER_DEFINE,
+        RuntimeArgs::new(),
+    )
+    .build();
+
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    *account
+        .named_keys()
+        .get(COUNTER_CONTRACT_HASH_KEY_NAME)
+        .expect("should have counter contract hash key")
+}
+
+/// Calls the stored session entry point, which in turn calls the stored counter contract.
+fn increment_counter(builder: &mut InMemoryWasmTestBuilder, counter_contract_hash_key: Key) {
+    let exec_request = ExecuteRequestBuilder::versioned_contract_call_by_hash_key_name(
+        DEFAULT_ACCOUNT_ADDR,
+        HASH_KEY_NAME,
+        None,
+        ENTRYPOINT_SESSION,
+        runtime_args! { COUNTER_CONTRACT_HASH_KEY_NAME => counter_contract_hash_key },
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+fn get_counter_value(builder: &InMemoryWasmTestBuilder, counter_contract_hash_key: Key) -> i32 {
+    builder
+        .query(None, counter_contract_hash_key, &[COUNTER_VALUE_UREF])
+        .expect("should have counter value")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should cast CLValue to integer")
+}
+
+#[ignore]
+#[test]
+fn should_reuse_cached_modules_of_stored_contracts() {
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(*DEFAULT_ENGINE_CONFIG);
+
+    let counter_contract_hash_key = install_counter(&mut builder);
+    increment_counter(&mut builder, counter_contract_hash_key);
+    assert_eq!(get_counter_value(&builder, counter_contract_hash_key), 1);
+
+    let module_cache = builder.get_engine_state().module_cache();
+    let hits_after_first_call = module_cache.hits();
+    assert!(!module_cache.is_empty());
+
+    increment_counter(&mut builder, counter_contract_hash_key);
+    assert_eq!(get_counter_value(&builder, counter_contract_hash_key), 2);
+
+    // Both the stored session code and the stored contract it calls are taken from the cache.
+    let module_cache = builder.get_engine_state().module_cache();
+    assert!(module_cache.hits() >= hits_after_first_call + 2);
+    assert!(module_cache.hit_rate() > 0.0);
+}
+
+#[ignore]
+#[test]
+fn should_execute_stored_contracts_with_module_cache_disabled() {
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(
+        DEFAULT_ENGINE_CONFIG.with_module_cache_capacity(0),
+    );
+
+    let counter_contract_hash_key = install_counter(&mut builder);
+    increment_counter(&mut builder, counter_contract_hash_key);
+    increment_counter(&mut builder, counter_contract_hash_key);
+    assert_eq!(get_counter_value(&builder, counter_contract_hash_key), 2);
+
+    let module_cache = builder.get_engine_state().module_cache();
+    assert!(module_cache.is_empty());
+    assert_eq!(module_cache.hits(), 0);
+}
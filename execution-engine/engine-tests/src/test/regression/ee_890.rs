@@ -43,7 +43,7 @@ fn should_run_ee_890_gracefully_reject_start_node_in_session() {
         .finish();
     let message = result.builder().exec_error_message(0).expect("should fail");
     assert!(
-        message.contains("UnsupportedWasmStart"),
+        message.contains("StartFunction"),
         "Error message {:?} does not contain expected pattern",
         message
     );
@@ -71,7 +71,7 @@ fn should_run_ee_890_gracefully_reject_start_node_in_payment() {
         .finish();
     let message = result.builder().exec_error_message(0).expect("should fail");
     assert!(
-        message.contains("UnsupportedWasmStart"),
+        message.contains("StartFunction"),
         "Error message {:?} does not contain expected pattern",
         message
     );
@@ -0,0 +1,426 @@
+use engine_core::engine_state::{run_genesis_request::RunGenesisRequest, ExecuteRequest};
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT, DEFAULT_ACCOUNTS,
+        DEFAULT_ENGINE_CONFIG, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT,
+        DEFAULT_PROTOCOL_VERSION,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::AccountHash, runtime_args, ContractHash, ContractPackage, ContractPackageHash, Key,
+    RuntimeArgs, U512,
+};
+
+const CONTRACT_ADD_STANDARD_PAYMENT_VERSION: &str = "add_standard_payment_version.wasm";
+const CONTRACT_ADD_SYSTEM_CONTRACT_VERSION: &str = "add_system_contract_version.wasm";
+const CONTRACT_DISABLE_CONTRACT_VERSION: &str = "disable_contract_version.wasm";
+const CONTRACT_SYSTEM_CONTRACT_HASHES: &str = "system_contract_hashes.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const MINT_HASH_KEY: &str = "mint_hash";
+const POS_HASH_KEY: &str = "pos_hash";
+const STANDARD_PAYMENT_HASH_KEY: &str = "standard_payment_hash";
+const LAST_PAYMENT_AMOUNT_KEY: &str = "last_payment_amount";
+const NEW_VERSION_KEY: &str = "new_version";
+const MINT_CONTRACT_HASH_KEY: &str = "mint_contract_hash";
+const POS_CONTRACT_HASH_KEY: &str = "pos_contract_hash";
+const STANDARD_PAYMENT_CONTRACT_HASH_KEY: &str = "standard_payment_contract_hash";
+const TOTAL_SUPPLY_KEY: &str = "total_supply";
+const POS_PAYMENT_PURSE_KEY: &str = "pos_payment_purse";
+const ARG_PACKAGE_HASH: &str = "package_hash";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+const ARG_SYSTEM_CONTRACT: &str = "system_contract";
+const ARG_TARGET: &str = "target";
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1; 32]);
+
+/// Runs genesis with the default account as the governance account.  The system contracts are
+/// executed as Wasm, so that the versions added by the tests are the code actually run.
+fn setup() -> (InMemoryWasmTestBuilder, ContractPackageHash) {
+    let exec_config = utils::create_exec_config(DEFAULT_ACCOUNTS.clone())
+        .with_governance_account(DEFAULT_ACCOUNT_ADDR);
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let engine_config = DEFAULT_ENGINE_CONFIG.with_use_system_contracts(true);
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&run_genesis_request);
+
+    let package_hash = governance_package_hash(&builder, STANDARD_PAYMENT_HASH_KEY);
+
+    (builder, package_hash)
+}
+
+fn governance_package_hash(builder: &InMemoryWasmTestBuilder, name: &str) -> ContractPackageHash {
+    builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(name)
+        .and_then(|key| key.into_hash())
+        .expect("should have system contract package hash")
+}
+
+fn add_version_request(
+    account_hash: AccountHash,
+    package_hash: ContractPackageHash,
+) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        account_hash,
+        CONTRACT_ADD_STANDARD_PAYMENT_VERSION,
+        runtime_args! { ARG_PACKAGE_HASH => package_hash },
+    )
+    .build()
+}
+
+fn add_system_contract_version_request(
+    package_hash: ContractPackageHash,
+    system_contract: &str,
+) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_ADD_SYSTEM_CONTRACT_VERSION,
+        runtime_args! {
+            ARG_PACKAGE_HASH => package_hash,
+            ARG_SYSTEM_CONTRACT => system_contract,
+        },
+    )
+    .build()
+}
+
+fn disable_version_request(
+    package_hash: ContractPackageHash,
+    contract_hash: ContractHash,
+) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DISABLE_CONTRACT_VERSION,
+        runtime_args! {
+            ARG_PACKAGE_HASH => package_hash,
+            ARG_CONTRACT_HASH => contract_hash,
+        },
+    )
+    .build()
+}
+
+fn fund_request(amount: U512) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => ACCOUNT_1_ADDR,
+            ARG_AMOUNT => amount,
+        },
+    )
+    .build()
+}
+
+fn contract_package(
+    builder: &InMemoryWasmTestBuilder,
+    package_hash: ContractPackageHash,
+) -> ContractPackage {
+    match builder
+        .query(None, Key::Hash(package_hash), &[])
+        .expect("should have contract package")
+    {
+        StoredValue::ContractPackage(contract_package) => contract_package,
+        _ => panic!("should be a contract package"),
+    }
+}
+
+fn current_version(
+    builder: &InMemoryWasmTestBuilder,
+    package_hash: ContractPackageHash,
+) -> ContractHash {
+    contract_package(builder, package_hash)
+        .current_contract_hash()
+        .expect("should have enabled version")
+}
+
+/// Returns the named key under which the added standard payment version recorded the last payment
+/// it made, if any, along with the amount recorded there.  Each payment records under a new URef.
+fn last_payment(builder: &InMemoryWasmTestBuilder) -> Option<(Key, U512)> {
+    let key = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(LAST_PAYMENT_AMOUNT_KEY)?;
+    let amount = builder
+        .query(None, key, &[])
+        .expect("should have last payment amount")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should be U512");
+    Some((key, amount))
+}
+
+/// Returns the system contract stored by the "system_contract_hashes" session code under `name`.
+fn session_system_contract(builder: &InMemoryWasmTestBuilder, name: &str) -> ContractHash {
+    builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(name)
+        .and_then(|key| key.into_hash())
+        .expect("should have system contract hash")
+}
+
+#[ignore]
+#[test]
+fn should_give_governance_account_access_to_system_contracts() {
+    let (builder, _) = setup();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    for name in &[
+        "mint_hash",
+        "mint_access",
+        "pos_hash",
+        "pos_access",
+        "standard_payment_hash",
+        "standard_payment_access",
+    ] {
+        assert!(
+            account.named_keys().contains_key(*name),
+            "governance account should have {}",
+            name
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_add_standard_payment_version_without_protocol_upgrade() {
+    let (mut builder, package_hash) = setup();
+    let genesis_standard_payment_hash = builder.get_standard_payment_contract_hash();
+
+    builder
+        .exec(add_version_request(DEFAULT_ACCOUNT_ADDR, package_hash))
+        .expect_success()
+        .commit();
+    // That deploy was still paid for by the genesis version, which records nothing.
+    assert_eq!(last_payment(&builder), None);
+
+    let current_hash = current_version(&builder, package_hash);
+    assert_ne!(current_hash, genesis_standard_payment_hash);
+    assert_eq!(builder.get_standard_payment_contract_hash(), current_hash);
+
+    // Deploys using standard payment are now paid for by the new version.
+    builder
+        .exec(fund_request(*DEFAULT_PAYMENT))
+        .expect_success()
+        .commit();
+    let (_key, amount) = last_payment(&builder).expect("new version should record payment");
+    assert_eq!(amount, *DEFAULT_PAYMENT);
+}
+
+#[ignore]
+#[test]
+fn should_fall_back_to_genesis_standard_payment_version_when_new_version_disabled() {
+    let (mut builder, package_hash) = setup();
+    let genesis_standard_payment_hash = builder.get_standard_payment_contract_hash();
+
+    builder
+        .exec(add_version_request(DEFAULT_ACCOUNT_ADDR, package_hash))
+        .expect_success()
+        .commit();
+    let new_version_hash = current_version(&builder, package_hash);
+    builder
+        .exec(disable_version_request(package_hash, new_version_hash))
+        .expect_success()
+        .commit();
+    // The new version made the payment for disabling itself.
+    let recorded_payment = last_payment(&builder).expect("new version should record payment");
+    assert_eq!(recorded_payment.1, *DEFAULT_PAYMENT);
+
+    assert_eq!(
+        current_version(&builder, package_hash),
+        genesis_standard_payment_hash
+    );
+    assert_eq!(
+        builder.get_standard_payment_contract_hash(),
+        genesis_standard_payment_hash
+    );
+
+    builder
+        .exec(fund_request(U512::from(1)))
+        .expect_success()
+        .commit();
+    assert_eq!(
+        last_payment(&builder),
+        Some(recorded_payment),
+        "the disabled version should not have made the payment"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_use_new_standard_payment_version_when_genesis_version_disabled() {
+    let (mut builder, package_hash) = setup();
+    let genesis_standard_payment_hash = builder.get_standard_payment_contract_hash();
+
+    builder
+        .exec(add_version_request(DEFAULT_ACCOUNT_ADDR, package_hash))
+        .expect_success()
+        .commit();
+    let new_version_hash = current_version(&builder, package_hash);
+    builder
+        .exec(disable_version_request(
+            package_hash,
+            genesis_standard_payment_hash,
+        ))
+        .expect_success()
+        .commit();
+
+    let previous_payment_key = last_payment(&builder).map(|(key, _amount)| key);
+
+    let enabled_versions = contract_package(&builder, package_hash).enabled_versions();
+    assert!(!enabled_versions
+        .values()
+        .any(|hash| *hash == genesis_standard_payment_hash));
+    assert_eq!(
+        builder.get_standard_payment_contract_hash(),
+        new_version_hash
+    );
+
+    builder
+        .exec(fund_request(U512::from(1)))
+        .expect_success()
+        .commit();
+    let (key, amount) = last_payment(&builder).expect("new version should record payment");
+    assert_ne!(
+        Some(key),
+        previous_payment_key,
+        "new version should have made the payment"
+    );
+    assert_eq!(amount, *DEFAULT_PAYMENT);
+}
+
+#[ignore]
+#[test]
+fn should_not_execute_deploys_once_only_standard_payment_version_disabled() {
+    let (mut builder, package_hash) = setup();
+    let genesis_standard_payment_hash = builder.get_standard_payment_contract_hash();
+
+    builder
+        .exec(disable_version_request(
+            package_hash,
+            genesis_standard_payment_hash,
+        ))
+        .expect_success()
+        .commit();
+
+    builder.exec(fund_request(U512::from(1))).commit();
+    let response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have exec response");
+    let error = utils::get_precondition_failure(response);
+    assert!(
+        error.to_string().contains("no enabled version"),
+        "{}",
+        error
+    );
+}
+
+#[ignore]
+#[test]
+fn should_resolve_new_mint_and_proof_of_stake_versions() {
+    let (mut builder, _) = setup();
+    let genesis_mint_hash = builder.get_mint_contract_hash();
+    let genesis_pos_hash = builder.get_pos_contract_hash();
+    let mint_package_hash = governance_package_hash(&builder, MINT_HASH_KEY);
+    let pos_package_hash = governance_package_hash(&builder, POS_HASH_KEY);
+
+    builder
+        .exec(add_system_contract_version_request(
+            mint_package_hash,
+            "mint",
+        ))
+        .expect_success()
+        .commit();
+    builder
+        .exec(add_system_contract_version_request(pos_package_hash, "pos"))
+        .expect_success()
+        .commit();
+
+    let mint_hash = current_version(&builder, mint_package_hash);
+    let pos_hash = current_version(&builder, pos_package_hash);
+    assert_ne!(mint_hash, genesis_mint_hash);
+    assert_ne!(pos_hash, genesis_pos_hash);
+    assert_eq!(builder.get_mint_contract_hash(), mint_hash);
+    assert_eq!(builder.get_pos_contract_hash(), pos_hash);
+
+    // The new versions carry over the state kept in the named keys of the genesis versions.
+    let mint = builder.get_contract(mint_hash).expect("should have mint");
+    assert!(mint.named_keys().contains_key(NEW_VERSION_KEY));
+    assert!(mint.named_keys().contains_key(TOTAL_SUPPLY_KEY));
+    let pos = builder.get_contract(pos_hash).expect("should have pos");
+    assert!(pos.named_keys().contains_key(NEW_VERSION_KEY));
+    assert!(pos.named_keys().contains_key(POS_PAYMENT_PURSE_KEY));
+
+    // Payment, transfers and finalization all go through the new versions.
+    builder
+        .exec(fund_request(*DEFAULT_PAYMENT))
+        .expect_success()
+        .commit();
+    assert_eq!(
+        builder.get_purse_balance(
+            builder
+                .get_account(ACCOUNT_1_ADDR)
+                .expect("should have account")
+                .main_purse()
+        ),
+        *DEFAULT_PAYMENT
+    );
+
+    // Session code sees the new versions as the system contracts.
+    let request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_SYSTEM_CONTRACT_HASHES,
+        RuntimeArgs::new(),
+    )
+    .build();
+    builder.exec(request).expect_success().commit();
+    assert_eq!(
+        session_system_contract(&builder, MINT_CONTRACT_HASH_KEY),
+        mint_hash
+    );
+    assert_eq!(
+        session_system_contract(&builder, POS_CONTRACT_HASH_KEY),
+        pos_hash
+    );
+    assert_eq!(
+        session_system_contract(&builder, STANDARD_PAYMENT_CONTRACT_HASH_KEY),
+        builder.get_standard_payment_contract_hash()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_add_standard_payment_version_without_governance_access() {
+    let (mut builder, package_hash) = setup();
+
+    builder
+        .exec(fund_request(U512::from(10) * *DEFAULT_PAYMENT))
+        .expect_success()
+        .commit();
+
+    builder
+        .exec(add_version_request(ACCOUNT_1_ADDR, package_hash))
+        .commit();
+    let error_message = builder
+        .exec_error_message(builder.get_exec_responses_count() - 1)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("ForgedReference"),
+        "{}",
+        error_message
+    );
+}
@@ -1,7 +1,9 @@
 mod genesis;
+mod governance;
 mod mint_install;
 mod pos_install;
 mod proof_of_stake;
 mod standard_payment;
 mod standard_payment_install;
+mod total_supply;
 mod upgrade;
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use engine_core::engine_state::{
+    genesis::{AuctionConfig, GenesisAccount},
+    run_genesis_request::RunGenesisRequest,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::internal::{
+    utils, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS, DEFAULT_GENESIS_CONFIG_HASH,
+    DEFAULT_PROTOCOL_VERSION,
+};
+use types::{account::AccountHash, U512};
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ACCOUNT_1_BOND: u64 = 1000;
+
+const ACCOUNT_2_ADDR: AccountHash = AccountHash::new([2u8; 32]);
+const ACCOUNT_2_BOND: u64 = 200;
+
+const ACCOUNT_3_ADDR: AccountHash = AccountHash::new([3u8; 32]);
+const ACCOUNT_3_BOND: u64 = 500;
+
+const ACCOUNT_BALANCE: u64 = 2000;
+
+const VALIDATOR_SLOTS: u32 = 2;
+const ERA_LENGTH: u64 = 1000;
+const MINIMUM_BID: u64 = 100;
+
+fn run_genesis_request() -> RunGenesisRequest {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for &(account_hash, bond) in &[
+            (ACCOUNT_1_ADDR, ACCOUNT_1_BOND),
+            (ACCOUNT_2_ADDR, ACCOUNT_2_BOND),
+            (ACCOUNT_3_ADDR, ACCOUNT_3_BOND),
+        ] {
+            tmp.push(GenesisAccount::new(
+                account_hash,
+                Motes::new(ACCOUNT_BALANCE.into()),
+                Motes::new(bond.into()),
+            ));
+        }
+        tmp
+    };
+    let auction_config = AuctionConfig::new(VALIDATOR_SLOTS, ERA_LENGTH, U512::from(MINIMUM_BID));
+    let exec_config = utils::create_exec_config(accounts).with_auction_config(auction_config);
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+#[ignore]
+#[test]
+fn should_elect_highest_stakes_at_end_of_era() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request());
+
+    let account_2_purse = builder
+        .get_account(ACCOUNT_2_ADDR)
+        .expect("should have account 2")
+        .main_purse();
+    assert_eq!(
+        builder.get_purse_balance(account_2_purse),
+        U512::from(ACCOUNT_BALANCE)
+    );
+
+    let bonded_validators = builder.run_auction(ERA_LENGTH).get_bonded_validators()[0].clone();
+
+    let expected: HashMap<AccountHash, U512> = vec![
+        (ACCOUNT_1_ADDR, U512::from(ACCOUNT_1_BOND)),
+        (ACCOUNT_3_ADDR, U512::from(ACCOUNT_3_BOND)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(bonded_validators, expected);
+
+    // The losing validator's stake is paid back to its main purse.
+    assert_eq!(
+        builder.get_purse_balance(account_2_purse),
+        U512::from(ACCOUNT_BALANCE + ACCOUNT_2_BOND)
+    );
+}
+
+#[ignore]
+#[test]
+#[should_panic]
+fn should_not_run_auction_before_end_of_era() {
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&run_genesis_request())
+        .run_auction(ERA_LENGTH - 1);
+}
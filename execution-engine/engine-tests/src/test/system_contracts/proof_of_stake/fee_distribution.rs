@@ -0,0 +1,124 @@
+use engine_core::engine_state::{
+    genesis::POS_REWARDS_PURSE, run_genesis_request::RunGenesisRequest, CONV_RATE,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const PROPOSER_ADDR: AccountHash = AccountHash::new([7u8; 32]);
+const TRANSFER_AMOUNT: u64 = 250_000_000;
+const FEE_BURN_RATE: u8 = 25;
+
+fn get_pos_rewards_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let rewards_purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_REWARDS_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS rewards purse");
+    builder.get_purse_balance(rewards_purse)
+}
+
+fn get_proposer_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let proposer_purse = builder
+        .get_account(PROPOSER_ADDR)
+        .expect("should have proposer account")
+        .main_purse();
+    builder.get_purse_balance(proposer_purse)
+}
+
+/// Executes a transfer proposed by `PROPOSER_ADDR` and returns the fee which was paid for it.
+fn exec_transfer_with_proposer(builder: &mut InMemoryWasmTestBuilder) -> U512 {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::from(TRANSFER_AMOUNT) },
+    )
+    .with_proposer(PROPOSER_ADDR)
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    Motes::from_gas(builder.last_exec_gas_cost(), CONV_RATE)
+        .expect("should convert gas to motes")
+        .value()
+}
+
+#[ignore]
+#[test]
+fn should_pay_fee_to_proposer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let genesis_total_supply = builder.get_total_supply();
+    let genesis_rewards = get_pos_rewards_purse_balance(&builder);
+
+    let fee = exec_transfer_with_proposer(&mut builder);
+
+    assert_eq!(get_proposer_balance(&builder), fee);
+    assert_eq!(get_pos_rewards_purse_balance(&builder), genesis_rewards);
+    assert_eq!(builder.get_total_supply(), genesis_total_supply);
+}
+
+#[ignore]
+#[test]
+fn should_burn_part_of_fee() {
+    let exec_config =
+        utils::create_exec_config(DEFAULT_ACCOUNTS.clone()).with_fee_burn_rate(FEE_BURN_RATE);
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let genesis_total_supply = builder.get_total_supply();
+
+    let fee = exec_transfer_with_proposer(&mut builder);
+
+    let burned = fee * U512::from(FEE_BURN_RATE) / U512::from(100);
+    assert_eq!(get_proposer_balance(&builder), fee - burned);
+    assert_eq!(builder.get_total_supply(), genesis_total_supply - burned);
+}
+
+#[ignore]
+#[test]
+fn should_pay_fee_to_rewards_purse_without_proposer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let genesis_rewards = get_pos_rewards_purse_balance(&builder);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::from(TRANSFER_AMOUNT) },
+    )
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let fee = Motes::from_gas(builder.last_exec_gas_cost(), CONV_RATE)
+        .expect("should convert gas to motes")
+        .value();
+
+    assert_eq!(
+        get_pos_rewards_purse_balance(&builder),
+        genesis_rewards + fee
+    );
+    assert!(builder.get_account(PROPOSER_ADDR).is_none());
+}
@@ -1,5 +1,8 @@
+mod auction;
 mod bonding;
 mod commit_validators;
+mod fee_distribution;
 mod finalize_payment;
 mod get_payment_purse;
 mod refund_purse;
+mod seigniorage;
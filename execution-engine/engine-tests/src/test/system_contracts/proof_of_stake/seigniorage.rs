@@ -0,0 +1,77 @@
+use engine_core::engine_state::{
+    genesis::POS_REWARDS_PURSE, run_genesis_request::RunGenesisRequest,
+};
+use engine_test_support::internal::{
+    utils, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS, DEFAULT_GENESIS_CONFIG_HASH,
+    DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+};
+use types::{Key, U512};
+
+const MILLIS_PER_YEAR: u64 = 365 * 24 * 60 * 60 * 1000;
+// 5% per year
+const ANNUAL_INFLATION_RATE: u64 = 50_000;
+const ERA_START: u64 = 1000;
+
+fn run_genesis_request() -> RunGenesisRequest {
+    let exec_config = utils::create_exec_config(DEFAULT_ACCOUNTS.clone())
+        .with_annual_inflation_rate(ANNUAL_INFLATION_RATE);
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+fn get_pos_rewards_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let rewards_purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_REWARDS_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS rewards purse");
+    builder.get_purse_balance(rewards_purse)
+}
+
+#[ignore]
+#[test]
+fn should_mint_seigniorage_into_rewards_purse() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request());
+
+    let genesis_total_supply = builder.get_total_supply();
+    let genesis_rewards = get_pos_rewards_purse_balance(&builder);
+
+    // The first call only starts the clock.
+    builder.mint_seigniorage(ERA_START);
+    assert_eq!(builder.get_total_supply(), genesis_total_supply);
+    assert_eq!(get_pos_rewards_purse_balance(&builder), genesis_rewards);
+
+    builder.mint_seigniorage(ERA_START + MILLIS_PER_YEAR);
+
+    let expected_seigniorage =
+        genesis_total_supply * U512::from(ANNUAL_INFLATION_RATE) / U512::from(1_000_000);
+    assert_eq!(
+        builder.get_total_supply(),
+        genesis_total_supply + expected_seigniorage
+    );
+    assert_eq!(
+        get_pos_rewards_purse_balance(&builder),
+        genesis_rewards + expected_seigniorage
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_mint_seigniorage_without_inflation() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let genesis_total_supply = builder.get_total_supply();
+
+    builder
+        .mint_seigniorage(ERA_START)
+        .mint_seigniorage(ERA_START + MILLIS_PER_YEAR);
+
+    assert_eq!(builder.get_total_supply(), genesis_total_supply);
+}
@@ -0,0 +1,115 @@
+use engine_core::engine_state::genesis::{GenesisAccount, TOTAL_SUPPLY_KEY};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ACCOUNT_1_BALANCE: u64 = 1_000_000_000;
+const ACCOUNT_1_BOND: u64 = 500_000_000;
+const TRANSFER_AMOUNT: u64 = 250_000_000;
+
+/// Sums the balances of all the purses known to the Mint.
+fn sum_of_purse_balances(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let mint_contract = builder
+        .get_contract(builder.get_mint_contract_hash())
+        .expect("should have mint contract");
+
+    mint_contract
+        .named_keys()
+        .iter()
+        .filter(|(name, _)| name.as_str() != TOTAL_SUPPLY_KEY)
+        .map(|(_, balance_key)| {
+            builder
+                .query(None, *balance_key, &[])
+                .expect("should have purse balance")
+                .as_cl_value()
+                .expect("should be CLValue")
+                .clone()
+                .into_t::<U512>()
+                .expect("should cast CLValue to U512")
+        })
+        .fold(U512::zero(), |sum, balance| sum + balance)
+}
+
+#[ignore]
+#[test]
+fn should_track_total_supply_at_genesis() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let total_supply = builder.get_total_supply();
+
+    assert_eq!(total_supply, U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE));
+    assert_eq!(total_supply, sum_of_purse_balances(&builder));
+}
+
+#[ignore]
+#[test]
+fn should_track_total_supply_of_bonded_genesis_accounts() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        tmp.push(GenesisAccount::new(
+            ACCOUNT_1_ADDR,
+            Motes::new(ACCOUNT_1_BALANCE.into()),
+            Motes::new(ACCOUNT_1_BOND.into()),
+        ));
+        tmp
+    };
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&run_genesis_request);
+
+    let total_supply = builder.get_total_supply();
+
+    assert_eq!(
+        total_supply,
+        U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE + ACCOUNT_1_BALANCE + ACCOUNT_1_BOND)
+    );
+    assert_eq!(total_supply, sum_of_purse_balances(&builder));
+}
+
+#[ignore]
+#[test]
+fn should_preserve_total_supply_after_transfer_and_fee_payment() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let genesis_total_supply = builder.get_total_supply();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::from(TRANSFER_AMOUNT) },
+    )
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let account_1_purse = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1")
+        .main_purse();
+    assert_eq!(
+        builder.get_purse_balance(account_1_purse),
+        U512::from(TRANSFER_AMOUNT)
+    );
+
+    let total_supply = builder.get_total_supply();
+
+    assert_eq!(total_supply, genesis_total_supply);
+    assert_eq!(total_supply, sum_of_purse_balances(&builder));
+}
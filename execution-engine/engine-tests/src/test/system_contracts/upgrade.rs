@@ -1,4 +1,8 @@
-use engine_core::engine_state::{upgrade::ActivationPoint, Error};
+use engine_core::engine_state::{
+    genesis::{StakingLimits, POS_FEE_BURN_RATE, POS_INFLATION_RATE, POS_STAKING_LIMITS},
+    upgrade::ActivationPoint,
+    Error,
+};
 use engine_grpc_server::engine_server::ipc::DeployCode;
 #[cfg(feature = "use-system-contracts")]
 use engine_shared::{stored_value::StoredValue, transform::Transform};
@@ -8,10 +12,10 @@ use engine_test_support::internal::{
 };
 #[cfg(feature = "use-system-contracts")]
 use engine_test_support::{internal::ExecuteRequestBuilder, DEFAULT_ACCOUNT_ADDR};
-use engine_wasm_prep::wasm_costs::WasmCosts;
-use types::ProtocolVersion;
+use engine_wasm_prep::{wasm_costs::WasmCosts, MEM_PAGES};
 #[cfg(feature = "use-system-contracts")]
-use types::{runtime_args, CLValue, Key, RuntimeArgs, U512};
+use types::{runtime_args, CLValue, RuntimeArgs};
+use types::{Key, ProtocolVersion, U512};
 
 const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
@@ -35,6 +39,7 @@ fn get_upgraded_wasm_costs() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        max_memory: MEM_PAGES,
     }
 }
 
@@ -353,6 +358,70 @@ fn should_allow_only_wasm_costs_patch_version() {
     );
 }
 
+#[ignore]
+#[test]
+fn should_upgrade_staking_limits() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let pos_key = Key::Hash(builder.get_pos_contract_hash());
+
+    let genesis_staking_limits: StakingLimits = builder
+        .query(None, pos_key, &[POS_STAKING_LIMITS])
+        .expect("should have genesis staking limits")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should cast CLValue to StakingLimits");
+    assert_eq!(genesis_staking_limits, StakingLimits::default());
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let new_staking_limits = StakingLimits::new(
+        U512::from(1_000_000),
+        U512::from(10_000),
+        U512::from(10_000),
+        100_000,
+        100_000,
+        10,
+        10,
+    );
+
+    let mut upgrade_request = {
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_new_staking_limits(new_staking_limits)
+            .build()
+    };
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let upgraded_staking_limits: StakingLimits = builder
+        .query(None, pos_key, &[POS_STAKING_LIMITS])
+        .expect("should have upgraded staking limits")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should cast CLValue to StakingLimits");
+    assert_eq!(
+        new_staking_limits, upgraded_staking_limits,
+        "upgraded staking limits should equal new staking limits"
+    );
+}
+
 #[ignore]
 #[test]
 fn should_allow_only_wasm_costs_minor_version() {
@@ -642,3 +711,115 @@ fn should_fail_major_upgrade_without_installer() {
         Error::InvalidUpgradeConfig.to_string()
     );
 }
+
+#[ignore]
+#[test]
+fn should_upgrade_annual_inflation_rate() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let pos_key = Key::Hash(builder.get_pos_contract_hash());
+
+    let genesis_inflation_rate: u64 = builder
+        .query(None, pos_key, &[POS_INFLATION_RATE])
+        .expect("should have genesis inflation rate")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should cast CLValue to u64");
+    assert_eq!(genesis_inflation_rate, 0);
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let new_inflation_rate = 20_000;
+
+    let mut upgrade_request = {
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_new_annual_inflation_rate(new_inflation_rate)
+            .build()
+    };
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let upgraded_inflation_rate: u64 = builder
+        .query(None, pos_key, &[POS_INFLATION_RATE])
+        .expect("should have upgraded inflation rate")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should cast CLValue to u64");
+    assert_eq!(
+        new_inflation_rate, upgraded_inflation_rate,
+        "upgraded inflation rate should equal new inflation rate"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_upgrade_fee_burn_rate() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let pos_key = Key::Hash(builder.get_pos_contract_hash());
+
+    let genesis_fee_burn_rate: u8 = builder
+        .query(None, pos_key, &[POS_FEE_BURN_RATE])
+        .expect("should have genesis fee burn rate")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should cast CLValue to u8");
+    assert_eq!(genesis_fee_burn_rate, 0);
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let new_fee_burn_rate = 50;
+
+    let mut upgrade_request = {
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_new_fee_burn_rate(new_fee_burn_rate)
+            .build()
+    };
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let upgraded_fee_burn_rate: u8 = builder
+        .query(None, pos_key, &[POS_FEE_BURN_RATE])
+        .expect("should have upgraded fee burn rate")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should cast CLValue to u8");
+    assert_eq!(
+        new_fee_burn_rate, upgraded_fee_burn_rate,
+        "upgraded fee burn rate should equal new fee burn rate"
+    );
+}
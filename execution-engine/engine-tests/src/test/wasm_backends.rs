@@ -0,0 +1,127 @@
+use engine_core::execution::WasmBackend;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ENGINE_CONFIG,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U512};
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const TRANSFER_PURSE_TO_ACCOUNT_WASM: &str = "transfer_purse_to_account.wasm";
+const CONTRACT_COUNTER_DEFINE: &str = "counter_define.wasm";
+const REVERT_WASM: &str = "revert.wasm";
+const CONTRACT_EXPENSIVE_CALCULATION: &str = "expensive_calculation.wasm";
+const EXPENSIVE_CALCULATION_KEY: &str = "expensive-calculation";
+const ENTRYPOINT_CALCULATE: &str = "calculate";
+const HASH_KEY_NAME: &str = "counter_package_hash";
+const ENTRYPOINT_SESSION: &str = "session";
+const COUNTER_CONTRACT_HASH_KEY_NAME: &str = "counter_contract_hash";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+
+/// Runs the same sequence of deploys, covering session code, stored contracts, a compute-heavy
+/// contract and a failing deploy, using the given backend.
+fn run_deploys(wasm_backend: WasmBackend) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(
+        DEFAULT_ENGINE_CONFIG.with_wasm_backend(wasm_backend),
+    );
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let transfer_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        TRANSFER_PURSE_TO_ACCOUNT_WASM,
+        runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::from(1_000_000) },
+    )
+    .build();
+    builder.exec(transfer_request).expect_success().commit();
+
+    let install_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_COUNTER_DEFINE,
+        RuntimeArgs::new(),
+    )
+    .build();
+    builder.exec(install_request).expect_success().commit();
+
+    let counter_contract_hash_key: Key = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(COUNTER_CONTRACT_HASH_KEY_NAME)
+        .expect("should have counter contract hash key");
+
+    let increment_request = ExecuteRequestBuilder::versioned_contract_call_by_hash_key_name(
+        DEFAULT_ACCOUNT_ADDR,
+        HASH_KEY_NAME,
+        None,
+        ENTRYPOINT_SESSION,
+        runtime_args! { COUNTER_CONTRACT_HASH_KEY_NAME => counter_contract_hash_key },
+    )
+    .build();
+    builder.exec(increment_request).expect_success().commit();
+
+    let store_calculation_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_EXPENSIVE_CALCULATION,
+        RuntimeArgs::new(),
+    )
+    .build();
+    builder
+        .exec(store_calculation_request)
+        .expect_success()
+        .commit();
+
+    let expensive_calculation_contract_hash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(EXPENSIVE_CALCULATION_KEY)
+        .and_then(|key| key.into_hash())
+        .expect("should have expensive calculation contract hash");
+
+    let calculate_request = ExecuteRequestBuilder::contract_call_by_hash(
+        DEFAULT_ACCOUNT_ADDR,
+        expensive_calculation_contract_hash,
+        ENTRYPOINT_CALCULATE,
+        RuntimeArgs::new(),
+    )
+    .build();
+    builder.exec(calculate_request).expect_success().commit();
+
+    let revert_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, REVERT_WASM, RuntimeArgs::default())
+            .build();
+    builder.exec(revert_request).commit();
+    assert!(builder.is_error());
+
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_produce_identical_effects_and_costs_with_each_backend() {
+    let interpreted = run_deploys(WasmBackend::Interpreted);
+    let compiled = run_deploys(WasmBackend::Compiled);
+
+    let exec_count = interpreted.get_exec_responses_count();
+    assert_eq!(exec_count, compiled.get_exec_responses_count());
+    for index in 0..exec_count {
+        assert_eq!(
+            interpreted.exec_costs(index),
+            compiled.exec_costs(index),
+            "costs of deploy {} should match",
+            index
+        );
+    }
+    assert_eq!(
+        interpreted.exec_error_message(exec_count - 1),
+        compiled.exec_error_message(exec_count - 1)
+    );
+    assert_eq!(interpreted.get_transforms(), compiled.get_transforms());
+    assert_eq!(
+        interpreted.get_post_state_hash(),
+        compiled.get_post_state_hash()
+    );
+}
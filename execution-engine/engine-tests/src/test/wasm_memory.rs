@@ -0,0 +1,161 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        ARG_AMOUNT, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_WASM_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::{wasm_costs::WasmCosts, MEM_PAGES};
+use types::{runtime_args, ProtocolVersion, RuntimeArgs, U512};
+
+const DEFAULT_ACTIVATION_POINT: u64 = 1;
+
+/// Session code starting with `initial_pages` of memory and growing it by `grow_pages`, trapping if
+/// the memory can't grow.
+fn grow_memory_wasm(initial_pages: u32, grow_pages: u32) -> Vec<u8> {
+    let wat = format!(
+        r#"
+(module
+    (memory (;0;) {})
+    (func (;0;)
+      (if (i32.eq (memory.grow (i32.const {})) (i32.const -1))
+        (then unreachable)))
+    (export "call" (func 0)))
+"#,
+        initial_pages, grow_pages
+    );
+    wabt::wat2wasm(wat).expect("should parse")
+}
+
+fn exec_session(
+    builder: &mut InMemoryWasmTestBuilder,
+    protocol_version: ProtocolVersion,
+    session_bytes: Vec<u8>,
+) {
+    let deploy_hash = [builder.get_exec_responses_count() as u8; 32];
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_bytes(session_bytes, RuntimeArgs::new())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash)
+        .build();
+
+    let exec_request = ExecuteRequestBuilder::new()
+        .with_protocol_version(protocol_version)
+        .push_deploy(deploy)
+        .build();
+
+    builder.exec(exec_request);
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_charge_grow_mem_per_page() {
+    const GROW_PAGES: u32 = 3;
+
+    let mut builder = setup();
+    exec_session(
+        &mut builder,
+        ProtocolVersion::V1_0_0,
+        grow_memory_wasm(1, 0),
+    );
+    builder.expect_success();
+    let base_cost = builder.last_exec_gas_cost();
+
+    let mut builder = setup();
+    exec_session(
+        &mut builder,
+        ProtocolVersion::V1_0_0,
+        grow_memory_wasm(1, GROW_PAGES),
+    );
+    builder.expect_success();
+    let grow_cost = builder.last_exec_gas_cost();
+
+    assert_eq!(
+        (grow_cost - base_cost).value(),
+        U512::from(DEFAULT_WASM_COSTS.grow_mem * GROW_PAGES)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_grow_memory_up_to_max_memory() {
+    let mut builder = setup();
+    exec_session(
+        &mut builder,
+        ProtocolVersion::V1_0_0,
+        grow_memory_wasm(1, MEM_PAGES - 1),
+    );
+    builder.expect_success();
+
+    let mut builder = setup();
+    exec_session(
+        &mut builder,
+        ProtocolVersion::V1_0_0,
+        grow_memory_wasm(1, MEM_PAGES),
+    );
+    let message = builder.exec_error_message(0).expect("should fail");
+    assert!(message.contains("Unreachable"), "{}", message);
+}
+
+#[ignore]
+#[test]
+fn should_change_max_memory_on_upgrade() {
+    const NEW_MAX_MEMORY: u32 = MEM_PAGES * 2;
+
+    let mut builder = setup();
+
+    exec_session(
+        &mut builder,
+        ProtocolVersion::V1_0_0,
+        grow_memory_wasm(NEW_MAX_MEMORY, 0),
+    );
+    let message = builder.exec_error_message(0).expect("should fail");
+    assert!(message.contains("MemoryTooLarge"), "{}", message);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 0, 1);
+    let new_wasm_costs = WasmCosts {
+        max_memory: NEW_MAX_MEMORY,
+        ..*DEFAULT_WASM_COSTS
+    };
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(ProtocolVersion::V1_0_0)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_costs(new_wasm_costs)
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let upgraded_wasm_costs = builder
+        .get_engine_state()
+        .wasm_costs(new_protocol_version)
+        .expect("should have result")
+        .expect("should have costs");
+    assert_eq!(upgraded_wasm_costs, new_wasm_costs);
+
+    exec_session(
+        &mut builder,
+        new_protocol_version,
+        grow_memory_wasm(MEM_PAGES + 1, NEW_MAX_MEMORY - MEM_PAGES - 1),
+    );
+    builder.expect_success();
+
+    exec_session(
+        &mut builder,
+        new_protocol_version,
+        grow_memory_wasm(NEW_MAX_MEMORY, 1),
+    );
+    let message = builder.exec_error_message(2).expect("should fail");
+    assert!(message.contains("Unreachable"), "{}", message);
+}
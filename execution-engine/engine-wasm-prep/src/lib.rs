@@ -1,20 +1,67 @@
+pub mod profiling;
+mod validation;
 pub mod wasm_costs;
 
 use std::fmt::{self, Display, Formatter};
 
-use parity_wasm::elements::{self, Module};
+use parity_wasm::elements::{self, External, ImportEntry, MemoryType, Module};
 use pwasm_utils::{self, stack_height};
 
 use crate::wasm_costs::WasmCosts;
+pub use validation::HostFunctions;
 
 //NOTE: size of Wasm memory page is 64 KiB
+/// Default maximum number of memory pages, used for costs predating the configurable maximum.
 pub const MEM_PAGES: u32 = 64;
+/// Number of pages of the largest memory addressable by Wasm, i.e. 4 GiB.
+pub const MAX_MEMORY_PAGES: u32 = 65_536;
+/// Maximum number of functions a module can define.
+pub const MAX_FUNCTIONS: u32 = 10_000;
+/// Maximum number of elements of a table of a module.
+pub const MAX_TABLE_SIZE: u32 = 4_096;
+/// Maximum number of globals a module can define.
+pub const MAX_GLOBALS: u32 = 256;
+/// Maximum number of data segments of a module.
+pub const MAX_DATA_SEGMENTS: u32 = 1_024;
+
+/// Name of the module exporting the host functions.
+pub(crate) const HOST_MODULE: &str = "env";
+/// Name of the function a module is run from.
+pub(crate) const DEFAULT_ENTRY_POINT: &str = "call";
 
 #[derive(Debug, Clone)]
 pub enum PreprocessingError {
+    /// The bytes are not a valid Wasm module.
     Deserialize(String),
+    /// An instruction which has no cost in the gas rules, e.g. a floating point operation.
     OperationForbiddenByGasRules,
+    /// The stack height limiter couldn't instrument the module.
     StackLimiter,
+    /// An import from a module other than the host module.
+    ForeignImport(String),
+    /// An import of something other than a function.
+    UnsupportedImport(String),
+    /// An import of a function the host module doesn't export.
+    UnknownHostFunction(String),
+    /// An import of a host function with a signature other than the host function's.
+    HostFunctionSignatureMismatch(String),
+    /// A start function, which would run when the module is instantiated.
+    StartFunction,
+    /// More functions than [`MAX_FUNCTIONS`].
+    TooManyFunctions(usize),
+    /// A table with more elements than [`MAX_TABLE_SIZE`].
+    TableTooLarge(u32),
+    /// More globals than [`MAX_GLOBALS`].
+    TooManyGlobals(usize),
+    /// More data segments than [`MAX_DATA_SEGMENTS`].
+    TooManyDataSegments(usize),
+    /// No memory declared by the module.  An imported memory doesn't count, as the memory is
+    /// replaced by an import of the host's memory.
+    MissingMemory,
+    /// A memory larger than the maximum number of pages set by the Wasm costs.
+    MemoryTooLarge { pages: u32, max_pages: u32 },
+    /// No exported `call` function.
+    MissingEntryPoint,
 }
 
 impl From<elements::Error> for PreprocessingError {
@@ -29,6 +76,18 @@ impl Display for PreprocessingError {
             PreprocessingError::Deserialize(error) => write!(f, "Deserialization error: {}", error),
             PreprocessingError::OperationForbiddenByGasRules => write!(f, "Encountered operation forbidden by gas rules. Consult instruction -> metering config map"),
             PreprocessingError::StackLimiter => write!(f, "Stack limiter error"),
+            PreprocessingError::ForeignImport(name) => write!(f, "Import {} is not from the host module \"{}\"", name, HOST_MODULE),
+            PreprocessingError::UnsupportedImport(name) => write!(f, "Import {} is not a function", name),
+            PreprocessingError::UnknownHostFunction(name) => write!(f, "Imported host function {} does not exist", name),
+            PreprocessingError::HostFunctionSignatureMismatch(name) => write!(f, "Imported host function {} has the wrong signature", name),
+            PreprocessingError::StartFunction => write!(f, "Start function is not allowed"),
+            PreprocessingError::TooManyFunctions(count) => write!(f, "Module defines {} functions, more than the maximum of {}", count, MAX_FUNCTIONS),
+            PreprocessingError::TableTooLarge(size) => write!(f, "Table has {} elements, more than the maximum of {}", size, MAX_TABLE_SIZE),
+            PreprocessingError::TooManyGlobals(count) => write!(f, "Module defines {} globals, more than the maximum of {}", count, MAX_GLOBALS),
+            PreprocessingError::TooManyDataSegments(count) => write!(f, "Module has {} data segments, more than the maximum of {}", count, MAX_DATA_SEGMENTS),
+            PreprocessingError::MissingMemory => write!(f, "Module does not define a memory"),
+            PreprocessingError::MemoryTooLarge { pages, max_pages } => write!(f, "Memory of {} pages is larger than the maximum of {} pages", pages, max_pages),
+            PreprocessingError::MissingEntryPoint => write!(f, "Module does not export a \"{}\" function", DEFAULT_ENTRY_POINT),
         }
     }
 }
@@ -37,18 +96,36 @@ pub struct Preprocessor {
     wasm_costs: WasmCosts,
     // Number of memory pages.
     mem_pages: u32,
+    host_functions: Option<Box<dyn HostFunctions>>,
 }
 
 impl Preprocessor {
     pub fn new(wasm_costs: WasmCosts) -> Self {
         Self {
             wasm_costs,
-            mem_pages: MEM_PAGES,
+            mem_pages: wasm_costs.max_memory,
+            host_functions: None,
         }
     }
 
+    /// Makes [`preprocess`](Preprocessor::preprocess) check the functions imported by modules
+    /// against `host_functions`.
+    pub fn with_host_functions<H: HostFunctions + 'static>(mut self, host_functions: H) -> Self {
+        self.host_functions = Some(Box::new(host_functions));
+        self
+    }
+
+    pub fn wasm_costs(&self) -> &WasmCosts {
+        &self.wasm_costs
+    }
+
+    /// Validates the module and instruments it for execution.
+    ///
+    /// The module must only import functions of the host, must define its memory, and must
+    /// export a `call` function to be run from.
     pub fn preprocess(&self, module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
         let module = deserialize(module_bytes)?;
+        validation::validate(&module, self.mem_pages, self.host_functions.as_deref())?;
         let module = pwasm_utils::externalize_mem(module, None, self.mem_pages);
         let module = pwasm_utils::inject_gas_counter(module, &self.wasm_costs.to_set())
             .map_err(|_| PreprocessingError::OperationForbiddenByGasRules)?;
@@ -62,3 +139,124 @@ impl Preprocessor {
 pub fn deserialize(module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
     parity_wasm::deserialize_buffer::<Module>(module_bytes).map_err(Into::into)
 }
+
+/// Sets the maximum of the memory imported by a preprocessed module to `max_pages`.
+///
+/// Preprocessing fixes the maximum at the limit in force at the time, so a stored contract needs
+/// this to run under the limit of a later protocol version.  Fails if the module starts with more
+/// than `max_pages` pages.
+pub fn apply_memory_limit(
+    mut module: Module,
+    max_pages: u32,
+) -> Result<Module, PreprocessingError> {
+    if let Some(import_section) = module.import_section_mut() {
+        for entry in import_section.entries_mut().iter_mut() {
+            let initial = match entry.external() {
+                External::Memory(memory_type) => memory_type.limits().initial(),
+                _ => continue,
+            };
+            if initial > max_pages {
+                return Err(PreprocessingError::MemoryTooLarge {
+                    pages: initial,
+                    max_pages,
+                });
+            }
+            *entry = ImportEntry::new(
+                entry.module().to_string(),
+                entry.field().to_string(),
+                External::Memory(MemoryType::new(initial, Some(max_pages))),
+            );
+        }
+    }
+    Ok(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder,
+        elements::{External, Module},
+    };
+
+    use crate::{apply_memory_limit, wasm_costs::WasmCosts, PreprocessingError, Preprocessor};
+
+    fn module_bytes(pages: u32) -> Vec<u8> {
+        let module = builder::module()
+            .memory()
+            .with_min(pages)
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .build()
+            .build()
+            .export()
+            .field("call")
+            .internal()
+            .func(0)
+            .build()
+            .build();
+        parity_wasm::serialize(module).unwrap()
+    }
+
+    fn imported_memory_limits(module: &Module) -> (u32, Option<u32>) {
+        module
+            .import_section()
+            .unwrap()
+            .entries()
+            .iter()
+            .find_map(|entry| match entry.external() {
+                External::Memory(memory) => {
+                    Some((memory.limits().initial(), memory.limits().maximum()))
+                }
+                _ => None,
+            })
+            .expect("should import memory")
+    }
+
+    #[test]
+    fn should_limit_memory_to_max_memory() {
+        let wasm_costs = WasmCosts {
+            max_memory: 128,
+            ..Default::default()
+        };
+        let preprocessor = Preprocessor::new(wasm_costs);
+
+        let module = preprocessor.preprocess(&module_bytes(100)).unwrap();
+        assert_eq!(imported_memory_limits(&module), (100, Some(128)));
+
+        assert!(matches!(
+            preprocessor.preprocess(&module_bytes(129)),
+            Err(PreprocessingError::MemoryTooLarge {
+                pages: 129,
+                max_pages: 128
+            })
+        ));
+    }
+
+    #[test]
+    fn should_apply_memory_limit_to_preprocessed_module() {
+        let wasm_costs = WasmCosts {
+            max_memory: 128,
+            ..Default::default()
+        };
+        let module = Preprocessor::new(wasm_costs)
+            .preprocess(&module_bytes(100))
+            .unwrap();
+
+        let raised = apply_memory_limit(module.clone(), 256).unwrap();
+        assert_eq!(imported_memory_limits(&raised), (100, Some(256)));
+
+        let lowered = apply_memory_limit(module.clone(), 100).unwrap();
+        assert_eq!(imported_memory_limits(&lowered), (100, Some(100)));
+
+        assert!(matches!(
+            apply_memory_limit(module, 99),
+            Err(PreprocessingError::MemoryTooLarge {
+                pages: 100,
+                max_pages: 99
+            })
+        ));
+    }
+}
@@ -0,0 +1,397 @@
+//! Instrumentation of Wasm modules for gas profiling.
+//!
+//! [`inject_profiling`] makes every function defined by a module report when it's entered and
+//! when it returns, by calling the host functions [`PROFILE_ENTER_FUNC`] and [`PROFILE_EXIT_FUNC`]
+//! with the index of the function.  Along with the gas charged while a function is running, this
+//! lets the host attribute gas to the stack of Wasm functions it was charged under.
+//!
+//! Instrumentation doesn't charge any gas, so an instrumented module costs exactly as much to run
+//! as the original one.
+
+use std::collections::BTreeMap;
+
+use parity_wasm::elements::{
+    External, FuncBody, FunctionType, ImportCountType, ImportEntry, ImportSection, Instruction,
+    Instructions, Internal, Module, Section, Type, TypeSection, ValueType,
+};
+
+use crate::HOST_MODULE;
+
+/// Name of the host function called on entry to a function, with the index of the function.
+pub const PROFILE_ENTER_FUNC: &str = "profile_enter";
+/// Name of the host function called on return from a function, with the index of the function.
+pub const PROFILE_EXIT_FUNC: &str = "profile_exit";
+
+const GAS_FUNC: &str = "gas";
+
+/// Returns a copy of `module` in which every defined function calls [`PROFILE_ENTER_FUNC`] on
+/// entry and [`PROFILE_EXIT_FUNC`] on return.
+///
+/// The functions are passed their index in `module`, which is also the key of their name in
+/// [`function_names`].  A function which traps doesn't report returning.
+///
+/// Each function is replaced by a wrapper which makes the calls around a call to a copy of the
+/// original function appended to the module, so the indices of the functions of `module` are
+/// unchanged other than for the two new imports.
+pub fn inject_profiling(mut module: Module) -> Module {
+    let import_count = module.import_count(ImportCountType::Function) as u32;
+    let defined_count = module
+        .function_section()
+        .map(|section| section.entries().len() as u32)
+        .unwrap_or_default();
+
+    let type_index = profile_func_type_index(&mut module);
+    let enter_func = import_count;
+    let exit_func = import_count + 1;
+    add_profile_imports(&mut module, type_index);
+
+    // Every function defined by the original module is now 2 indices further on, and the copy of
+    // its body is `defined_count` indices further still.
+    let shift = |index: u32| {
+        if index >= import_count {
+            index + 2
+        } else {
+            index
+        }
+    };
+    shift_function_indices(&mut module, shift);
+
+    let signatures: Vec<Option<(u32, usize)>> = {
+        let types = module
+            .type_section()
+            .map(|section| section.types())
+            .unwrap_or_default();
+        module
+            .function_section()
+            .map(|section| section.entries())
+            .unwrap_or_default()
+            .iter()
+            .map(|func| {
+                types.get(func.type_ref() as usize).map(|ty| match ty {
+                    Type::Function(function_type) => {
+                        (func.type_ref(), function_type.params().len())
+                    }
+                })
+            })
+            .collect()
+    };
+
+    let mut copies = Vec::new();
+    if let Some(code_section) = module.code_section_mut() {
+        for (defined_index, body) in code_section.bodies_mut().iter_mut().enumerate() {
+            let signature = match signatures.get(defined_index) {
+                Some(Some(signature)) => *signature,
+                _ => continue,
+            };
+            let original_index = import_count + defined_index as u32;
+            let copy_index = import_count + 2 + defined_count + copies.len() as u32;
+
+            let mut instructions = vec![
+                Instruction::I32Const(original_index as i32),
+                Instruction::Call(enter_func),
+            ];
+            instructions.extend((0..signature.1 as u32).map(Instruction::GetLocal));
+            instructions.extend(vec![
+                Instruction::Call(copy_index),
+                Instruction::I32Const(original_index as i32),
+                Instruction::Call(exit_func),
+                Instruction::End,
+            ]);
+            let wrapper = FuncBody::new(Vec::new(), Instructions::new(instructions));
+
+            copies.push((signature.0, std::mem::replace(body, wrapper)));
+        }
+    }
+
+    if let Some(function_section) = module.function_section_mut() {
+        for (type_ref, _) in &copies {
+            function_section
+                .entries_mut()
+                .push(parity_wasm::elements::Func::new(*type_ref));
+        }
+    }
+    if let Some(code_section) = module.code_section_mut() {
+        code_section
+            .bodies_mut()
+            .extend(copies.into_iter().map(|(_, body)| body));
+    }
+
+    module
+}
+
+/// Returns the names of the functions of `module`, keyed by function index.
+///
+/// Imported functions are named after their import.  Defined functions are named from the name
+/// section if present, else after their export, else as `func[<index>]`.
+///
+/// The name section is emitted by the compiler before the gas counter is imported by the
+/// preprocessor, so its indices of defined functions are corrected by one if `module` imports the
+/// gas counter.
+pub fn function_names(module: &Module) -> BTreeMap<u32, String> {
+    let mut names = BTreeMap::new();
+
+    let mut gas_import = None;
+    if let Some(import_section) = module.import_section() {
+        let function_imports = import_section
+            .entries()
+            .iter()
+            .filter(|entry| matches!(entry.external(), External::Function(_)));
+        for (index, entry) in function_imports.enumerate() {
+            if entry.module() == HOST_MODULE && entry.field() == GAS_FUNC {
+                gas_import = Some(index as u32);
+            }
+            names.insert(index as u32, entry.field().to_string());
+        }
+    }
+    let import_count = names.len() as u32;
+    let function_count = module.functions_space() as u32;
+
+    let name_section_names: BTreeMap<u32, String> = match module.clone().parse_names() {
+        Ok(module) | Err((_, module)) => module
+            .names_section()
+            .and_then(|section| section.functions())
+            .map(|functions| {
+                functions
+                    .names()
+                    .iter()
+                    .map(|(index, name)| (index, name.clone()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+
+    let export_names: BTreeMap<u32, String> = module
+        .export_section()
+        .map(|section| {
+            section
+                .entries()
+                .iter()
+                .filter_map(|entry| match entry.internal() {
+                    Internal::Function(index) => Some((*index, entry.field().to_string())),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for index in import_count..function_count {
+        let name_section_index = match gas_import {
+            Some(gas_import) if index > gas_import => index - 1,
+            _ => index,
+        };
+        let name = name_section_names
+            .get(&name_section_index)
+            .or_else(|| export_names.get(&index))
+            .cloned()
+            .unwrap_or_else(|| format!("func[{}]", index));
+        names.insert(index, name);
+    }
+
+    names
+}
+
+/// Returns the index of the `(i32) -> ()` type of the profiling host functions, adding it to the
+/// module if needed.
+fn profile_func_type_index(module: &mut Module) -> u32 {
+    let profile_func_type = Type::Function(FunctionType::new(vec![ValueType::I32], None));
+    if module.type_section().is_none() {
+        module
+            .insert_section(Section::Type(TypeSection::default()))
+            .expect("should insert type section");
+    }
+    let types = module
+        .type_section_mut()
+        .expect("should have type section")
+        .types_mut();
+    match types.iter().position(|ty| *ty == profile_func_type) {
+        Some(index) => index as u32,
+        None => {
+            types.push(profile_func_type);
+            types.len() as u32 - 1
+        }
+    }
+}
+
+/// Adds the imports of the profiling host functions after the existing function imports.
+fn add_profile_imports(module: &mut Module, type_index: u32) {
+    if module.import_section().is_none() {
+        module
+            .insert_section(Section::Import(ImportSection::default()))
+            .expect("should insert import section");
+    }
+    let entries = module
+        .import_section_mut()
+        .expect("should have import section")
+        .entries_mut();
+    // Function indices are assigned to imports in order, so the new imports have to follow the
+    // existing function imports to get the indices expected by `inject_profiling`.
+    let position = entries
+        .iter()
+        .rposition(|entry| matches!(entry.external(), External::Function(_)))
+        .map(|index| index + 1)
+        .unwrap_or_default();
+    for (offset, name) in [PROFILE_ENTER_FUNC, PROFILE_EXIT_FUNC].iter().enumerate() {
+        entries.insert(
+            position + offset,
+            ImportEntry::new(
+                HOST_MODULE.to_string(),
+                name.to_string(),
+                External::Function(type_index),
+            ),
+        );
+    }
+}
+
+/// Applies `shift` to every reference to a function by index.
+fn shift_function_indices<F: Fn(u32) -> u32>(module: &mut Module, shift: F) {
+    for section in module.sections_mut() {
+        match section {
+            Section::Code(code_section) => {
+                for body in code_section.bodies_mut() {
+                    for instruction in body.code_mut().elements_mut() {
+                        if let Instruction::Call(index) = instruction {
+                            *index = shift(*index);
+                        }
+                    }
+                }
+            }
+            Section::Export(export_section) => {
+                for entry in export_section.entries_mut() {
+                    if let Internal::Function(index) = entry.internal_mut() {
+                        *index = shift(*index);
+                    }
+                }
+            }
+            Section::Element(element_section) => {
+                for segment in element_section.entries_mut() {
+                    for index in segment.members_mut() {
+                        *index = shift(*index);
+                    }
+                }
+            }
+            Section::Start(index) => *index = shift(*index),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder,
+        elements::{Instruction, Instructions, Module},
+    };
+
+    use super::{function_names, inject_profiling, PROFILE_ENTER_FUNC, PROFILE_EXIT_FUNC};
+
+    /// A module importing the gas counter, with an exported function `call` calling another
+    /// function.
+    fn module() -> Module {
+        builder::module()
+            .function()
+            .signature()
+            .param()
+            .i32()
+            .build()
+            .body()
+            .build()
+            .build()
+            .import()
+            .module("env")
+            .field("gas")
+            .external()
+            .func(0)
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::I32Const(1),
+                Instruction::Call(3),
+                Instruction::Drop,
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .function()
+            .signature()
+            .param()
+            .i32()
+            .return_type()
+            .i32()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::GetLocal(0),
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .export()
+            .field("call")
+            .internal()
+            .func(2)
+            .build()
+            .build()
+    }
+
+    #[test]
+    fn should_wrap_defined_functions() {
+        let module = inject_profiling(module());
+
+        let imports: Vec<&str> = module
+            .import_section()
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|entry| entry.field())
+            .collect();
+        assert_eq!(imports, vec!["gas", PROFILE_ENTER_FUNC, PROFILE_EXIT_FUNC]);
+
+        // gas, enter, exit, 3 wrappers, 3 copies
+        assert_eq!(module.functions_space(), 9);
+
+        let bodies = module.code_section().unwrap().bodies();
+        // The wrapper of the function which was at index 3 takes its argument and calls its copy.
+        assert_eq!(
+            bodies[2].code().elements(),
+            &[
+                Instruction::I32Const(3),
+                Instruction::Call(1),
+                Instruction::GetLocal(0),
+                Instruction::Call(8),
+                Instruction::I32Const(3),
+                Instruction::Call(2),
+                Instruction::End,
+            ]
+        );
+        // The copy of `call` calls the wrapper of the function it called.
+        assert_eq!(
+            bodies[4].code().elements(),
+            &[
+                Instruction::I32Const(1),
+                Instruction::Call(5),
+                Instruction::Drop,
+                Instruction::End
+            ]
+        );
+
+        let export = &module.export_section().unwrap().entries()[0];
+        assert_eq!(
+            export.internal(),
+            &parity_wasm::elements::Internal::Function(4)
+        );
+
+        wasmi::Module::from_parity_wasm_module(module).expect("should be valid");
+    }
+
+    #[test]
+    fn should_name_functions() {
+        let names = function_names(&module());
+        assert_eq!(names.get(&0).map(String::as_str), Some("gas"));
+        assert_eq!(names.get(&1).map(String::as_str), Some("func[1]"));
+        assert_eq!(names.get(&2).map(String::as_str), Some("call"));
+        assert_eq!(names.get(&3).map(String::as_str), Some("func[3]"));
+    }
+}
@@ -0,0 +1,292 @@
+//! Validation of Wasm modules before they are instrumented.
+//!
+//! Modules which would be rejected at instantiation, or which could make the host allocate
+//! excessively, are rejected up front with a [`PreprocessingError`] naming the rule they break.
+
+use parity_wasm::elements::{External, FunctionType, Internal, Module, ResizableLimits, Type};
+
+use crate::{
+    PreprocessingError, DEFAULT_ENTRY_POINT, HOST_MODULE, MAX_DATA_SEGMENTS, MAX_FUNCTIONS,
+    MAX_GLOBALS, MAX_TABLE_SIZE,
+};
+
+/// The functions a module can import from the host module.
+pub trait HostFunctions {
+    /// Returns the type of the host function named `name`, or `None` if there is no such function.
+    fn function_type(&self, name: &str) -> Option<FunctionType>;
+}
+
+/// Checks `module` against the rules every module run by the host has to follow.
+///
+/// If `host_functions` is `None`, imported functions are only checked to come from the host
+/// module, not to match its functions.
+pub(crate) fn validate(
+    module: &Module,
+    mem_pages: u32,
+    host_functions: Option<&dyn HostFunctions>,
+) -> Result<(), PreprocessingError> {
+    validate_imports(module, host_functions)?;
+
+    if module.start_section().is_some() {
+        return Err(PreprocessingError::StartFunction);
+    }
+
+    let function_count = module
+        .function_section()
+        .map(|section| section.entries().len())
+        .unwrap_or_default();
+    if function_count > MAX_FUNCTIONS as usize {
+        return Err(PreprocessingError::TooManyFunctions(function_count));
+    }
+
+    if let Some(table_section) = module.table_section() {
+        for table in table_section.entries() {
+            let size = limit(table.limits());
+            if size > MAX_TABLE_SIZE {
+                return Err(PreprocessingError::TableTooLarge(size));
+            }
+        }
+    }
+
+    let global_count = module
+        .global_section()
+        .map(|section| section.entries().len())
+        .unwrap_or_default();
+    if global_count > MAX_GLOBALS as usize {
+        return Err(PreprocessingError::TooManyGlobals(global_count));
+    }
+
+    let data_segment_count = module
+        .data_section()
+        .map(|section| section.entries().len())
+        .unwrap_or_default();
+    if data_segment_count > MAX_DATA_SEGMENTS as usize {
+        return Err(PreprocessingError::TooManyDataSegments(data_segment_count));
+    }
+
+    // The memory of the module is replaced by an import of the host's memory, so it has to be
+    // declared by the module rather than imported.
+    let memory = module
+        .memory_section()
+        .and_then(|section| section.entries().first())
+        .ok_or(PreprocessingError::MissingMemory)?;
+    let pages = limit(memory.limits());
+    if pages > mem_pages {
+        return Err(PreprocessingError::MemoryTooLarge {
+            pages,
+            max_pages: mem_pages,
+        });
+    }
+
+    let exports_entry_point = module
+        .export_section()
+        .map(|section| {
+            section.entries().iter().any(|entry| {
+                entry.field() == DEFAULT_ENTRY_POINT
+                    && matches!(entry.internal(), Internal::Function(_))
+            })
+        })
+        .unwrap_or_default();
+    if !exports_entry_point {
+        return Err(PreprocessingError::MissingEntryPoint);
+    }
+
+    Ok(())
+}
+
+fn validate_imports(
+    module: &Module,
+    host_functions: Option<&dyn HostFunctions>,
+) -> Result<(), PreprocessingError> {
+    let import_section = match module.import_section() {
+        Some(import_section) => import_section,
+        None => return Ok(()),
+    };
+    let types = module
+        .type_section()
+        .map(|section| section.types())
+        .unwrap_or_default();
+
+    for entry in import_section.entries() {
+        let name = format!("{}.{}", entry.module(), entry.field());
+        if entry.module() != HOST_MODULE {
+            return Err(PreprocessingError::ForeignImport(name));
+        }
+        let type_ref = match entry.external() {
+            External::Function(type_ref) => *type_ref,
+            External::Table(_) | External::Memory(_) | External::Global(_) => {
+                return Err(PreprocessingError::UnsupportedImport(name));
+            }
+        };
+        let host_functions = match host_functions {
+            Some(host_functions) => host_functions,
+            None => continue,
+        };
+        let expected = host_functions
+            .function_type(entry.field())
+            .ok_or_else(|| PreprocessingError::UnknownHostFunction(name.clone()))?;
+        match types.get(type_ref as usize) {
+            Some(Type::Function(function_type)) if *function_type == expected => {}
+            _ => return Err(PreprocessingError::HostFunctionSignatureMismatch(name)),
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the greatest size allowed by `limits`.
+fn limit(limits: &ResizableLimits) -> u32 {
+    limits.maximum().unwrap_or_else(|| limits.initial())
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder,
+        elements::{FunctionType, Instruction, Module, Section, ValueType},
+    };
+
+    use super::{validate, HostFunctions};
+    use crate::{PreprocessingError, MAX_GLOBALS, MEM_PAGES};
+
+    struct WriteOnly;
+
+    impl HostFunctions for WriteOnly {
+        fn function_type(&self, name: &str) -> Option<FunctionType> {
+            if name == "write" {
+                Some(FunctionType::new(vec![ValueType::I32; 4], None))
+            } else {
+                None
+            }
+        }
+    }
+
+    fn module_builder() -> builder::ModuleBuilder {
+        builder::module()
+            .memory()
+            .with_min(1)
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .build()
+            .build()
+            .export()
+            .field("call")
+            .internal()
+            .func(0)
+            .build()
+    }
+
+    /// A module importing a function of `params` `i32` parameters, which it also exports as
+    /// `call`.
+    fn with_import(module: &str, field: &str, params: usize) -> Module {
+        builder::module()
+            .function()
+            .signature()
+            .with_params(vec![ValueType::I32; params])
+            .build()
+            .body()
+            .build()
+            .build()
+            .import()
+            .module(module)
+            .field(field)
+            .external()
+            .func(0)
+            .build()
+            .memory()
+            .with_min(1)
+            .build()
+            .export()
+            .field("call")
+            .internal()
+            .func(1)
+            .build()
+            .build()
+    }
+
+    fn validate_module(module: &Module) -> Result<(), PreprocessingError> {
+        validate(module, MEM_PAGES, Some(&WriteOnly))
+    }
+
+    #[test]
+    fn should_accept_valid_module() {
+        assert!(validate_module(&module_builder().build()).is_ok());
+        assert!(validate_module(&with_import("env", "write", 4)).is_ok());
+    }
+
+    #[test]
+    fn should_reject_imports_not_matching_host_functions() {
+        assert!(matches!(
+            validate_module(&with_import("other", "write", 4)),
+            Err(PreprocessingError::ForeignImport(name)) if name == "other.write"
+        ));
+        assert!(matches!(
+            validate_module(&with_import("env", "read", 4)),
+            Err(PreprocessingError::UnknownHostFunction(_))
+        ));
+        assert!(matches!(
+            validate_module(&with_import("env", "write", 3)),
+            Err(PreprocessingError::HostFunctionSignatureMismatch(_))
+        ));
+        // Without host functions to check against, only the module imported from is checked.
+        assert!(validate(&with_import("env", "read", 4), MEM_PAGES, None).is_ok());
+    }
+
+    #[test]
+    fn should_reject_start_function() {
+        let module = module_builder().with_section(Section::Start(0)).build();
+        assert!(matches!(
+            validate_module(&module),
+            Err(PreprocessingError::StartFunction)
+        ));
+    }
+
+    #[test]
+    fn should_reject_too_many_globals() {
+        let mut module_builder = module_builder();
+        for _ in 0..=MAX_GLOBALS {
+            module_builder = module_builder
+                .global()
+                .value_type()
+                .i32()
+                .init_expr(Instruction::I32Const(0))
+                .build();
+        }
+        assert!(matches!(
+            validate_module(&module_builder.build()),
+            Err(PreprocessingError::TooManyGlobals(_))
+        ));
+    }
+
+    #[test]
+    fn should_reject_memory_beyond_limit() {
+        let module = builder::module()
+            .memory()
+            .with_min(MEM_PAGES + 1)
+            .build()
+            .build();
+        assert!(matches!(
+            validate_module(&module),
+            Err(PreprocessingError::MemoryTooLarge { pages, max_pages })
+                if pages == MEM_PAGES + 1 && max_pages == MEM_PAGES
+        ));
+
+        let module = builder::module().build();
+        assert!(matches!(
+            validate_module(&module),
+            Err(PreprocessingError::MissingMemory)
+        ));
+    }
+
+    #[test]
+    fn should_require_call_export() {
+        let module = builder::module().memory().with_min(1).build().build();
+        assert!(matches!(
+            validate_module(&module),
+            Err(PreprocessingError::MissingEntryPoint)
+        ));
+    }
+}
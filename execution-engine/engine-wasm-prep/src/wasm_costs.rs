@@ -4,11 +4,15 @@ use pwasm_utils::rules::{InstructionType, Metering, Set};
 
 use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
 
-const NUM_FIELDS: usize = 10;
+use crate::{MAX_MEMORY_PAGES, MEM_PAGES};
+
+const NUM_FIELDS: usize = 11;
 pub const WASM_COSTS_SERIALIZED_LENGTH: usize = NUM_FIELDS * U32_SERIALIZED_LENGTH;
+/// The length of the first serialization, which predates `max_memory`.
+pub const LEGACY_WASM_COSTS_SERIALIZED_LENGTH: usize = (NUM_FIELDS - 1) * U32_SERIALIZED_LENGTH;
 
 // Taken (partially) from parity-ethereum
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct WasmCosts {
     /// Default opcode cost
     pub regular: u32,
@@ -33,6 +37,27 @@ pub struct WasmCosts {
     /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` /
     /// `opcodes_div`
     pub opcodes_div: u32,
+    /// Maximum memory (in 64kb pages) a contract can have, including memory it grows.
+    pub max_memory: u32,
+}
+
+/// Provides free costs, with memory limited to [`MEM_PAGES`].
+impl Default for WasmCosts {
+    fn default() -> Self {
+        WasmCosts {
+            regular: 0,
+            div: 0,
+            mul: 0,
+            mem: 0,
+            initial_mem: 0,
+            grow_mem: 0,
+            memcpy: 0,
+            max_stack_height: 0,
+            opcodes_mul: 0,
+            opcodes_div: 0,
+            max_memory: MEM_PAGES,
+        }
+    }
 }
 
 impl WasmCosts {
@@ -49,31 +74,26 @@ impl WasmCosts {
             .with_grow_cost(self.grow_mem)
             .with_forbidden_floats()
     }
-}
 
-impl ToBytes for WasmCosts {
-    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
-        ret.append(&mut self.regular.to_bytes()?);
-        ret.append(&mut self.div.to_bytes()?);
-        ret.append(&mut self.mul.to_bytes()?);
-        ret.append(&mut self.mem.to_bytes()?);
-        ret.append(&mut self.initial_mem.to_bytes()?);
-        ret.append(&mut self.grow_mem.to_bytes()?);
-        ret.append(&mut self.memcpy.to_bytes()?);
-        ret.append(&mut self.max_stack_height.to_bytes()?);
-        ret.append(&mut self.opcodes_mul.to_bytes()?);
-        ret.append(&mut self.opcodes_div.to_bytes()?);
-        Ok(ret)
+    /// Returns `true` if `max_memory` is at least one page and at most the largest memory
+    /// addressable by Wasm.
+    pub fn has_valid_max_memory(&self) -> bool {
+        self.max_memory >= 1 && self.max_memory <= MAX_MEMORY_PAGES
     }
 
-    fn serialized_length(&self) -> usize {
-        WASM_COSTS_SERIALIZED_LENGTH
+    /// Deserializes the first serialization, whose costs predate `max_memory`.
+    ///
+    /// It can't be told apart from the current one by its contents, so callers have to know which
+    /// one they are reading, e.g. from the length of the record holding it.
+    pub fn from_legacy_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        Self::from_field_bytes(bytes, false)
     }
-}
 
-impl FromBytes for WasmCosts {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+    /// Deserializes the fields, with `max_memory` last if `with_max_memory`.
+    fn from_field_bytes(
+        bytes: &[u8],
+        with_max_memory: bool,
+    ) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (regular, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
         let (div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
@@ -84,6 +104,12 @@ impl FromBytes for WasmCosts {
         let (max_stack_height, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_memory, rem): (u32, &[u8]) = if with_max_memory {
+            FromBytes::from_bytes(rem)?
+        } else {
+            // Memory was limited to a fixed number of pages before being made configurable.
+            (MEM_PAGES, rem)
+        };
         let wasm_costs = WasmCosts {
             regular,
             div,
@@ -95,15 +121,44 @@ impl FromBytes for WasmCosts {
             max_stack_height,
             opcodes_mul,
             opcodes_div,
+            max_memory,
         };
         Ok((wasm_costs, rem))
     }
 }
 
+impl ToBytes for WasmCosts {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut self.regular.to_bytes()?);
+        ret.append(&mut self.div.to_bytes()?);
+        ret.append(&mut self.mul.to_bytes()?);
+        ret.append(&mut self.mem.to_bytes()?);
+        ret.append(&mut self.initial_mem.to_bytes()?);
+        ret.append(&mut self.grow_mem.to_bytes()?);
+        ret.append(&mut self.memcpy.to_bytes()?);
+        ret.append(&mut self.max_stack_height.to_bytes()?);
+        ret.append(&mut self.opcodes_mul.to_bytes()?);
+        ret.append(&mut self.opcodes_div.to_bytes()?);
+        ret.append(&mut self.max_memory.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        WASM_COSTS_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for WasmCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        Self::from_field_bytes(bytes, true)
+    }
+}
+
 pub mod gens {
     use proptest::{num, prop_compose};
 
-    use crate::wasm_costs::WasmCosts;
+    use crate::{wasm_costs::WasmCosts, MAX_MEMORY_PAGES};
 
     prop_compose! {
         pub fn wasm_costs_arb()(
@@ -117,6 +172,7 @@ pub mod gens {
             max_stack_height in num::u32::ANY,
             opcodes_mul in num::u32::ANY,
             opcodes_div in num::u32::ANY,
+            max_memory in 1..=MAX_MEMORY_PAGES,
         ) -> WasmCosts {
             WasmCosts {
                 regular,
@@ -129,6 +185,7 @@ pub mod gens {
                 max_stack_height,
                 opcodes_mul,
                 opcodes_div,
+                max_memory,
             }
         }
     }
@@ -138,10 +195,13 @@ pub mod gens {
 mod tests {
     use proptest::proptest;
 
-    use types::bytesrepr;
+    use types::bytesrepr::{self, ToBytes};
 
     use super::gens;
-    use crate::wasm_costs::WasmCosts;
+    use crate::{
+        wasm_costs::{WasmCosts, LEGACY_WASM_COSTS_SERIALIZED_LENGTH},
+        MAX_MEMORY_PAGES, MEM_PAGES,
+    };
 
     fn wasm_costs_mock() -> WasmCosts {
         WasmCosts {
@@ -155,6 +215,7 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 3,
             opcodes_div: 8,
+            max_memory: 64,
         }
     }
 
@@ -170,6 +231,7 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            max_memory: 128,
         }
     }
 
@@ -181,6 +243,55 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&free);
     }
 
+    #[test]
+    fn should_deserialize_legacy_format_with_default_max_memory() {
+        for regular in [wasm_costs_mock().regular, u32::MAX].iter() {
+            let mock = WasmCosts {
+                regular: *regular,
+                ..wasm_costs_mock()
+            };
+            let legacy_fields = [
+                mock.regular,
+                mock.div,
+                mock.mul,
+                mock.mem,
+                mock.initial_mem,
+                mock.grow_mem,
+                mock.memcpy,
+                mock.max_stack_height,
+                mock.opcodes_mul,
+                mock.opcodes_div,
+            ];
+            let mut legacy_bytes = Vec::new();
+            for field in legacy_fields.iter() {
+                legacy_bytes.append(&mut field.to_bytes().unwrap());
+            }
+            assert_eq!(legacy_bytes.len(), LEGACY_WASM_COSTS_SERIALIZED_LENGTH);
+
+            let (wasm_costs, rem) = WasmCosts::from_legacy_bytes(&legacy_bytes).unwrap();
+            assert!(rem.is_empty());
+            assert_eq!(
+                wasm_costs,
+                WasmCosts {
+                    max_memory: MEM_PAGES,
+                    ..mock
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn should_validate_max_memory() {
+        let with_max_memory = |max_memory| WasmCosts {
+            max_memory,
+            ..wasm_costs_mock()
+        };
+        assert!(!with_max_memory(0).has_valid_max_memory());
+        assert!(with_max_memory(1).has_valid_max_memory());
+        assert!(with_max_memory(MAX_MEMORY_PAGES).has_valid_max_memory());
+        assert!(!with_max_memory(MAX_MEMORY_PAGES + 1).has_valid_max_memory());
+    }
+
     proptest! {
         #[test]
         fn should_serialize_and_deserialize_with_arbitrary_values(
@@ -11,6 +11,9 @@ pub use crate::{runtime_provider::RuntimeProvider, storage_provider::StorageProv
 
 const SYSTEM_ACCOUNT: AccountHash = AccountHash::new([0; 32]);
 
+/// Name of the Mint's named key under which the total supply of tokens is stored.
+pub const TOTAL_SUPPLY_KEY: &str = "total_supply";
+
 pub trait Mint: RuntimeProvider + StorageProvider {
     fn mint(&mut self, initial_balance: U512) -> Result<URef, Error> {
         let caller = self.get_caller();
@@ -18,6 +21,11 @@ pub trait Mint: RuntimeProvider + StorageProvider {
             return Err(Error::InvalidNonEmptyPurseCreation);
         }
 
+        if !initial_balance.is_zero() {
+            let total_supply_uref = self.total_supply_uref()?;
+            self.add(total_supply_uref, initial_balance)?;
+        }
+
         let balance_key: Key = self.new_uref(initial_balance).into();
         let purse_uref: URef = self.new_uref(());
         let purse_uref_name = purse_uref.remove_access_rights().as_string();
@@ -66,4 +74,72 @@ pub trait Mint: RuntimeProvider + StorageProvider {
         self.add(target_balance, amount)?;
         Ok(())
     }
+
+    /// Burns `amount` tokens from `purse`, reducing the total supply accordingly.  Only the system
+    /// account is allowed to burn tokens.
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        if self.get_caller() != SYSTEM_ACCOUNT {
+            return Err(Error::InvalidCaller);
+        }
+        if !purse.is_writeable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        let balance_uref: URef = match self.read_local(&purse.addr())? {
+            Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
+            None => return Err(Error::PurseNotFound),
+        };
+        let balance: U512 = match self.read(balance_uref)? {
+            Some(balance) => balance,
+            None => return Err(Error::PurseNotFound),
+        };
+        if amount > balance {
+            return Err(Error::InsufficientFunds);
+        }
+        let total_supply_uref = self.total_supply_uref()?;
+        let total_supply: U512 = self.read(total_supply_uref)?.unwrap_or_default();
+        let new_total_supply = total_supply
+            .checked_sub(amount)
+            .ok_or(Error::InvalidTotalSupply)?;
+        self.write(balance_uref, balance - amount)?;
+        self.write(total_supply_uref, new_total_supply)?;
+        Ok(())
+    }
+
+    /// Issues `amount` new tokens into the existing `purse`, increasing the total supply
+    /// accordingly.  Only the system account is allowed to issue tokens.
+    fn mint_into(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        if self.get_caller() != SYSTEM_ACCOUNT {
+            return Err(Error::InvalidCaller);
+        }
+        if !purse.is_addable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        let balance_uref: URef = match self.read_local(&purse.addr())? {
+            Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
+            None => return Err(Error::DestNotFound),
+        };
+        let total_supply_uref = self.total_supply_uref()?;
+        self.add(balance_uref, amount)?;
+        self.add(total_supply_uref, amount)?;
+        Ok(())
+    }
+
+    /// Returns the total supply of tokens.
+    fn total_supply(&mut self) -> Result<U512, Error> {
+        let total_supply_uref = self.total_supply_uref()?;
+        Ok(self.read(total_supply_uref)?.unwrap_or_default())
+    }
+
+    /// Returns the URef under which the total supply is stored, creating it if the Mint was
+    /// installed before the total supply was tracked.
+    fn total_supply_uref(&mut self) -> Result<URef, Error> {
+        match self.get_key(TOTAL_SUPPLY_KEY) {
+            Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidURef),
+            None => {
+                let total_supply_uref = self.new_uref(U512::zero());
+                self.put_key(TOTAL_SUPPLY_KEY, total_supply_uref.into());
+                Ok(total_supply_uref)
+            }
+        }
+    }
 }
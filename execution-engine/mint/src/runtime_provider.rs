@@ -3,5 +3,7 @@ use types::{account::AccountHash, Key};
 pub trait RuntimeProvider {
     fn get_caller(&self) -> AccountHash;
 
+    fn get_key(&self, name: &str) -> Option<Key>;
+
     fn put_key(&mut self, name: &str, key: Key);
 }
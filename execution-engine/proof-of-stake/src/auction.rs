@@ -0,0 +1,259 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::result;
+
+use types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U64_SERIALIZED_LENGTH},
+    system_contract_errors::pos::{Error, Result},
+    CLType, CLTyped, U512,
+};
+
+use crate::stakes::Stakes;
+
+/// The parameters of the auction which selects the validators of each era.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuctionConfig {
+    /// The number of validators elected at the end of each era.
+    validator_slots: u32,
+    /// The length of an era in milliseconds.
+    era_length: u64,
+    /// The smallest amount of motes which may be bid in a single era.
+    minimum_bid: U512,
+}
+
+impl AuctionConfig {
+    /// Creates a new `AuctionConfig`.
+    pub fn new(validator_slots: u32, era_length: u64, minimum_bid: U512) -> Self {
+        AuctionConfig {
+            validator_slots,
+            era_length,
+            minimum_bid,
+        }
+    }
+
+    /// Returns the number of validators elected at the end of each era.
+    pub fn validator_slots(&self) -> u32 {
+        self.validator_slots
+    }
+
+    /// Returns the length of an era in milliseconds.
+    pub fn era_length(&self) -> u64 {
+        self.era_length
+    }
+
+    /// Returns the smallest amount of motes which may be bid in a single era.
+    pub fn minimum_bid(&self) -> U512 {
+        self.minimum_bid
+    }
+
+    /// Returns `true` if the auction can elect at least one validator per era.
+    pub fn is_valid(&self) -> bool {
+        self.validator_slots > 0 && self.era_length > 0
+    }
+}
+
+impl ToBytes for AuctionConfig {
+    fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
+        let mut bytes = bytesrepr::allocate_buffer(self)?;
+        bytes.append(&mut self.validator_slots.to_bytes()?);
+        bytes.append(&mut self.era_length.to_bytes()?);
+        bytes.append(&mut self.minimum_bid.to_bytes()?);
+        Ok(bytes)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + U64_SERIALIZED_LENGTH + self.minimum_bid.serialized_length()
+    }
+}
+
+impl FromBytes for AuctionConfig {
+    fn from_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
+        let (validator_slots, bytes) = u32::from_bytes(bytes)?;
+        let (era_length, bytes) = u64::from_bytes(bytes)?;
+        let (minimum_bid, bytes) = U512::from_bytes(bytes)?;
+        let config = AuctionConfig {
+            validator_slots,
+            era_length,
+            minimum_bid,
+        };
+        Ok((config, bytes))
+    }
+}
+
+impl CLTyped for AuctionConfig {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+/// The bids placed during the current era, assigning the total amount of motes bid to each
+/// bidder.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Bids(pub BTreeMap<AccountHash, U512>);
+
+impl Bids {
+    /// Adds `amount` to the bidder's bid for the current era.
+    ///
+    /// Returns an error if the resulting bid would be lower than `minimum_bid`.
+    pub fn add(&mut self, bidder: AccountHash, amount: U512, minimum_bid: U512) -> Result<()> {
+        let bid = self
+            .0
+            .get(&bidder)
+            .map(|bid| bid.saturating_add(amount))
+            .unwrap_or(amount);
+        if amount.is_zero() || bid < minimum_bid {
+            return Err(Error::BidTooSmall);
+        }
+        self.0.insert(bidder, bid);
+        Ok(())
+    }
+
+    /// Settles the auction against the stakes of the current era.
+    ///
+    /// Every current stake is a standing bid, to which the bidder's new bid is added. The
+    /// `validator_slots` highest bids win and become the stakes of the next era; ties are broken
+    /// in favor of the lower account hash. Returns the new stakes together with the amounts which
+    /// have to be paid back to the bidders who lost.
+    pub fn settle(
+        self,
+        stakes: &Stakes,
+        validator_slots: u32,
+    ) -> (Stakes, Vec<(AccountHash, U512)>) {
+        let mut candidates: BTreeMap<AccountHash, U512> = stakes.0.clone();
+        for (bidder, amount) in self.0 {
+            candidates
+                .entry(bidder)
+                .and_modify(|stake| *stake = stake.saturating_add(amount))
+                .or_insert(amount);
+        }
+
+        let mut ranking: Vec<(AccountHash, U512)> = candidates.into_iter().collect();
+        // `sort_by` is stable, so equal amounts keep the ascending account hash order.
+        ranking.sort_by(|(_, lhs), (_, rhs)| rhs.cmp(lhs));
+
+        let payouts = ranking.split_off(ranking.len().min(validator_slots as usize));
+        (Stakes(ranking.into_iter().collect()), payouts)
+    }
+}
+
+impl ToBytes for Bids {
+    fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+}
+
+impl FromBytes for Bids {
+    fn from_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
+        let (bids, bytes) = BTreeMap::from_bytes(bytes)?;
+        Ok((Bids(bids), bytes))
+    }
+}
+
+impl CLTyped for Bids {
+    fn cl_type() -> CLType {
+        BTreeMap::<AccountHash, U512>::cl_type()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use types::{account::AccountHash, bytesrepr, system_contract_errors::pos::Error, U512};
+
+    use super::{AuctionConfig, Bids};
+    use crate::stakes::Stakes;
+
+    const KEY1: [u8; 32] = [1; 32];
+    const KEY2: [u8; 32] = [2; 32];
+    const KEY3: [u8; 32] = [3; 32];
+
+    fn new_stakes(stakes: &[([u8; 32], u64)]) -> Stakes {
+        Stakes(
+            stakes
+                .iter()
+                .map(|&(key, amount)| (AccountHash::new(key), U512::from(amount)))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_add_bid() {
+        let mut bids = Bids::default();
+        let minimum_bid = U512::from(10);
+        assert_eq!(
+            Err(Error::BidTooSmall),
+            bids.add(AccountHash::new(KEY1), U512::from(9), minimum_bid)
+        );
+        assert_eq!(
+            Ok(()),
+            bids.add(AccountHash::new(KEY1), U512::from(10), minimum_bid)
+        );
+        assert_eq!(
+            Ok(()),
+            bids.add(AccountHash::new(KEY1), U512::from(5), minimum_bid)
+        );
+        assert_eq!(
+            Err(Error::BidTooSmall),
+            bids.add(AccountHash::new(KEY1), U512::zero(), minimum_bid)
+        );
+        assert_eq!(Some(&U512::from(15)), bids.0.get(&AccountHash::new(KEY1)));
+    }
+
+    #[test]
+    fn test_settle_elects_highest_bids() {
+        let stakes = new_stakes(&[(KEY1, 100)]);
+        let mut bids = Bids::default();
+        bids.add(AccountHash::new(KEY2), U512::from(200), U512::zero())
+            .unwrap();
+        bids.add(AccountHash::new(KEY3), U512::from(50), U512::zero())
+            .unwrap();
+
+        let (elected, payouts) = bids.settle(&stakes, 2);
+        assert_eq!(new_stakes(&[(KEY1, 100), (KEY2, 200)]), elected);
+        assert_eq!(vec![(AccountHash::new(KEY3), U512::from(50))], payouts);
+    }
+
+    #[test]
+    fn test_settle_adds_bids_to_stakes() {
+        let stakes = new_stakes(&[(KEY1, 100), (KEY2, 120)]);
+        let mut bids = Bids::default();
+        bids.add(AccountHash::new(KEY1), U512::from(30), U512::zero())
+            .unwrap();
+
+        let (elected, payouts) = bids.settle(&stakes, 1);
+        assert_eq!(new_stakes(&[(KEY1, 130)]), elected);
+        assert_eq!(vec![(AccountHash::new(KEY2), U512::from(120))], payouts);
+    }
+
+    #[test]
+    fn test_settle_breaks_ties_by_account_hash() {
+        let stakes = new_stakes(&[(KEY3, 100)]);
+        let mut bids = Bids::default();
+        bids.add(AccountHash::new(KEY2), U512::from(100), U512::zero())
+            .unwrap();
+
+        let (elected, payouts) = bids.settle(&stakes, 1);
+        assert_eq!(new_stakes(&[(KEY2, 100)]), elected);
+        assert_eq!(vec![(AccountHash::new(KEY3), U512::from(100))], payouts);
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let mut bids = Bids::default();
+        bids.add(AccountHash::new(KEY1), U512::from(5), U512::zero())
+            .unwrap();
+        bids.add(AccountHash::new(KEY2), U512::from(6), U512::zero())
+            .unwrap();
+        bytesrepr::test_serialization_roundtrip(&bids);
+        bytesrepr::test_serialization_roundtrip(&AuctionConfig::new(
+            10,
+            3_600_000,
+            U512::from(1_000),
+        ));
+    }
+}
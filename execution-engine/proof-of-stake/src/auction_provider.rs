@@ -0,0 +1,20 @@
+use types::BlockTime;
+
+use crate::auction::{AuctionConfig, Bids};
+
+pub trait AuctionProvider {
+    /// Reads the auction configuration, or `None` if the auction mode is disabled.
+    fn read_auction_config(&mut self) -> Option<AuctionConfig>;
+
+    /// Reads the bids placed during the current era.
+    fn read_bids(&mut self) -> Bids;
+
+    /// Writes the bids placed during the current era.
+    fn write_bids(&mut self, bids: Bids);
+
+    /// Reads the time at which the current era ends, or `None` if the first era is still running.
+    fn read_era_end(&mut self) -> Option<BlockTime>;
+
+    /// Writes the time at which the current era ends.
+    fn write_era_end(&mut self, era_end: BlockTime);
+}
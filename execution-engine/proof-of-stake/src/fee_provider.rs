@@ -0,0 +1,4 @@
+pub trait FeeProvider {
+    /// Reads the percentage of the fees which is burned rather than paid to the block proposer.
+    fn read_fee_burn_rate(&mut self) -> u8;
+}
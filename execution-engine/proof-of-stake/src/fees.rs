@@ -0,0 +1,52 @@
+use types::U512;
+
+/// The largest valid fee burn rate, i.e. burning the whole fee.
+pub const MAX_FEE_BURN_RATE: u8 = 100;
+
+/// Splits the `fee` into the part which is burned at a `fee_burn_rate` given in percent, and the
+/// part which is paid to the block proposer.
+///
+/// The burned part is rounded down.  Returns `None` if the rate is larger than 100%.
+pub fn split_fee(fee: U512, fee_burn_rate: u8) -> Option<(U512, U512)> {
+    if fee_burn_rate > MAX_FEE_BURN_RATE {
+        return None;
+    }
+    let burned = fee * U512::from(fee_burn_rate) / U512::from(MAX_FEE_BURN_RATE);
+    Some((burned, fee - burned))
+}
+
+#[cfg(test)]
+mod tests {
+    use types::U512;
+
+    use super::split_fee;
+
+    #[test]
+    fn test_split_fee() {
+        assert_eq!(
+            Some((U512::zero(), U512::from(1_000))),
+            split_fee(U512::from(1_000), 0)
+        );
+        assert_eq!(
+            Some((U512::from(250), U512::from(750))),
+            split_fee(U512::from(1_000), 25)
+        );
+        assert_eq!(
+            Some((U512::from(1_000), U512::zero())),
+            split_fee(U512::from(1_000), 100)
+        );
+    }
+
+    #[test]
+    fn test_split_fee_rounds_burn_down() {
+        assert_eq!(
+            Some((U512::zero(), U512::one())),
+            split_fee(U512::one(), 99)
+        );
+    }
+
+    #[test]
+    fn test_split_fee_rejects_rates_above_100_percent() {
+        assert_eq!(None, split_fee(U512::from(1_000), 101));
+    }
+}
@@ -2,12 +2,19 @@
 
 extern crate alloc;
 
+mod auction;
+mod auction_provider;
+mod fee_provider;
+mod fees;
 mod mint_provider;
 mod queue;
 mod queue_provider;
 mod runtime_provider;
+mod seigniorage;
+mod seigniorage_provider;
 mod stakes;
 mod stakes_provider;
+mod staking_limits;
 
 use core::marker::Sized;
 
@@ -18,12 +25,29 @@ use types::{
 };
 
 pub use crate::{
-    mint_provider::MintProvider, queue::Queue, queue_provider::QueueProvider,
-    runtime_provider::RuntimeProvider, stakes::Stakes, stakes_provider::StakesProvider,
+    auction::{AuctionConfig, Bids},
+    auction_provider::AuctionProvider,
+    fee_provider::FeeProvider,
+    fees::MAX_FEE_BURN_RATE,
+    mint_provider::MintProvider,
+    queue::Queue,
+    queue_provider::QueueProvider,
+    runtime_provider::RuntimeProvider,
+    seigniorage_provider::SeigniorageProvider,
+    stakes::Stakes,
+    stakes_provider::StakesProvider,
+    staking_limits::StakingLimits,
 };
 
 pub trait ProofOfStake:
-    MintProvider + QueueProvider + RuntimeProvider + StakesProvider + Sized
+    AuctionProvider
+    + FeeProvider
+    + MintProvider
+    + QueueProvider
+    + RuntimeProvider
+    + SeigniorageProvider
+    + StakesProvider
+    + Sized
 {
     fn bond(&mut self, validator: AccountHash, amount: U512, source: URef) -> Result<()> {
         if amount.is_zero() {
@@ -36,6 +60,13 @@ pub trait ProofOfStake:
 
         self.transfer_purse_to_purse(source, target, amount)
             .map_err(|_| Error::BondTransferFailed)?;
+
+        // In auction mode the bond is only a bid, which is held in the bonding purse until the
+        // auction at the end of the era decides whether it becomes a stake.
+        if let Some(auction_config) = self.read_auction_config() {
+            return internal::bid(self, &auction_config, validator, amount);
+        }
+
         internal::bond(self, amount, validator, timestamp)?;
 
         // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
@@ -51,6 +82,13 @@ pub trait ProofOfStake:
     fn unbond(&mut self, validator: AccountHash, maybe_amount: Option<U512>) -> Result<()> {
         let pos_purse = internal::get_bonding_purse(self)?;
         let timestamp = self.get_block_time();
+
+        // In auction mode the validator set is fixed for the whole era, so the unbond is only
+        // applied and paid out by the auction at the end of the era.
+        if self.read_auction_config().is_some() {
+            return internal::unbond_at_era_end(self, maybe_amount, validator, timestamp);
+        }
+
         internal::unbond(self, maybe_amount, validator, timestamp)?;
 
         // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
@@ -80,8 +118,27 @@ pub trait ProofOfStake:
         Ok(maybe_purse.map(|p| p.remove_access_rights()))
     }
 
-    fn finalize_payment(&mut self, amount_spent: U512, account: AccountHash) -> Result<()> {
-        internal::finalize_payment(self, amount_spent, account)
+    fn finalize_payment(
+        &mut self,
+        amount_spent: U512,
+        account: AccountHash,
+        proposer: Option<AccountHash>,
+    ) -> Result<()> {
+        internal::finalize_payment(self, amount_spent, account, proposer)
+    }
+
+    fn run_auction(&mut self) -> Result<()> {
+        let bonding_purse = internal::get_bonding_purse(self)?;
+        let payouts = internal::run_auction(self)?;
+        for (bidder, amount) in payouts {
+            self.transfer_purse_to_account(bonding_purse, bidder, amount)
+                .map_err(|_| Error::AuctionPayoutFailed)?;
+        }
+        Ok(())
+    }
+
+    fn mint_seigniorage(&mut self) -> Result<()> {
+        internal::mint_seigniorage(self).map(|_| ())
     }
 }
 
@@ -95,8 +152,17 @@ mod internal {
     };
 
     use crate::{
-        mint_provider::MintProvider, queue::QueueEntry, queue_provider::QueueProvider,
-        runtime_provider::RuntimeProvider, stakes_provider::StakesProvider,
+        auction::{AuctionConfig, Bids},
+        auction_provider::AuctionProvider,
+        fee_provider::FeeProvider,
+        fees,
+        mint_provider::MintProvider,
+        queue::QueueEntry,
+        queue_provider::QueueProvider,
+        runtime_provider::RuntimeProvider,
+        seigniorage,
+        seigniorage_provider::SeigniorageProvider,
+        stakes_provider::StakesProvider,
     };
 
     /// Account used to run system functions (in particular `finalize_payment`).
@@ -122,12 +188,6 @@ mod internal {
     /// The time from an unbonding request until the stakes are paid out.
     const UNBOND_DELAY: u64 = 0;
 
-    /// The maximum number of pending bonding requests.
-    const MAX_BOND_LEN: usize = 100;
-
-    /// The maximum number of pending unbonding requests.
-    const MAX_UNBOND_LEN: usize = 1000;
-
     /// Enqueues the deploy's creator for becoming a validator. The bond `amount` is paid from the
     /// purse `source`.
     pub fn bond<P: QueueProvider + StakesProvider>(
@@ -136,8 +196,9 @@ mod internal {
         validator: AccountHash,
         timestamp: BlockTime,
     ) -> Result<()> {
+        let limits = provider.read_limits();
         let mut queue = provider.read_bonding();
-        if queue.0.len() >= MAX_BOND_LEN {
+        if queue.0.len() >= limits.max_bond_len() as usize {
             return Err(Error::TooManyEventsInQueue);
         }
 
@@ -146,7 +207,7 @@ mod internal {
         for entry in &queue.0 {
             stakes.bond(&entry.validator, entry.amount);
         }
-        stakes.validate_bonding(&validator, amount)?;
+        stakes.validate_bonding(&validator, amount, &limits)?;
 
         queue.push(validator, amount, timestamp)?;
         provider.write_bonding(queue);
@@ -162,13 +223,14 @@ mod internal {
         validator: AccountHash,
         timestamp: BlockTime,
     ) -> Result<()> {
+        let limits = provider.read_limits();
         let mut queue = provider.read_unbonding();
-        if queue.0.len() >= MAX_UNBOND_LEN {
+        if queue.0.len() >= limits.max_unbond_len() as usize {
             return Err(Error::TooManyEventsInQueue);
         }
 
         let mut stakes = provider.read()?;
-        let payout = stakes.unbond(&validator, maybe_amount)?;
+        let payout = stakes.unbond(&validator, maybe_amount, &limits)?;
         provider.write(&stakes);
         // TODO: Make sure the destination is valid and the amount can be paid. The actual payment
         // will be made later, after the unbonding delay. contract_api::transfer_dry_run(POS_PURSE,
@@ -178,6 +240,32 @@ mod internal {
         Ok(())
     }
 
+    /// Enqueues the deploy's creator for unbonding at the end of the current era. Unlike `unbond`,
+    /// the stakes are left unchanged until the auction applies the request and pays out the funds.
+    pub fn unbond_at_era_end<P: QueueProvider + StakesProvider>(
+        provider: &mut P,
+        maybe_amount: Option<U512>,
+        validator: AccountHash,
+        timestamp: BlockTime,
+    ) -> Result<()> {
+        let limits = provider.read_limits();
+        let mut queue = provider.read_unbonding();
+        if queue.0.len() >= limits.max_unbond_len() as usize {
+            return Err(Error::TooManyEventsInQueue);
+        }
+
+        let mut stakes = provider.read()?;
+        // Simulate applying all earlier unbonds. The modified stakes are not written.
+        for entry in &queue.0 {
+            stakes.withdraw(&entry.validator, entry.amount);
+        }
+        let payout = stakes.unbond(&validator, maybe_amount, &limits)?;
+
+        queue.push(validator, payout, timestamp)?;
+        provider.write_unbonding(queue);
+        Ok(())
+    }
+
     /// Removes all due requests from the queues and applies them.
     pub fn step<P: QueueProvider + StakesProvider>(
         provider: &mut P,
@@ -206,6 +294,123 @@ mod internal {
         Ok(unbonds)
     }
 
+    /// Adds a bid of `amount` by `bidder` to the bids of the current era.
+    pub fn bid<P: AuctionProvider>(
+        provider: &mut P,
+        auction_config: &AuctionConfig,
+        bidder: AccountHash,
+        amount: U512,
+    ) -> Result<()> {
+        let mut bids = provider.read_bids();
+        bids.add(bidder, amount, auction_config.minimum_bid())?;
+        provider.write_bids(bids);
+        Ok(())
+    }
+
+    /// Elects the validators of the next era from the current stakes and the bids placed during
+    /// the current era, and starts the next era.
+    ///
+    /// The unbonds requested during the era are applied to the stakes before the election.
+    /// Returns the bids and stakes which have to be paid back to the bidders who lost, together
+    /// with the unbonded amounts.
+    pub fn run_auction<P: AuctionProvider + QueueProvider + RuntimeProvider + StakesProvider>(
+        provider: &mut P,
+    ) -> Result<Vec<(AccountHash, U512)>> {
+        if provider.get_caller() != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+
+        let auction_config = provider
+            .read_auction_config()
+            .ok_or(Error::AuctionDisabled)?;
+
+        // The first era starts at genesis.
+        let era_end: u64 = provider
+            .read_era_end()
+            .map(Into::into)
+            .unwrap_or_else(|| auction_config.era_length());
+        let timestamp: u64 = provider.get_block_time().into();
+        if timestamp < era_end {
+            return Err(Error::EraNotFinished);
+        }
+
+        let mut stakes = provider.read()?;
+        let mut unbonding_queue = provider.read_unbonding();
+        let unbonds = unbonding_queue.pop_due(BlockTime::new(timestamp));
+        if !unbonds.is_empty() {
+            provider.write_unbonding(unbonding_queue);
+        }
+        for entry in &unbonds {
+            stakes.withdraw(&entry.validator, entry.amount);
+        }
+
+        let bids = provider.read_bids();
+        let (elected, mut payouts) = bids.settle(&stakes, auction_config.validator_slots());
+        payouts.extend(
+            unbonds
+                .into_iter()
+                .map(|entry| (entry.validator, entry.amount)),
+        );
+
+        // If steps were missed, the eras which passed without an auction are skipped, so that the
+        // next era ends after the current block.
+        let era_length = auction_config.era_length();
+        let missed_eras = (timestamp - era_end) / era_length;
+        let next_era_end = era_end
+            .saturating_add(missed_eras.saturating_mul(era_length))
+            .saturating_add(era_length);
+
+        provider.write(&elected);
+        provider.write_bids(Bids::default());
+        provider.write_era_end(BlockTime::new(next_era_end));
+
+        Ok(payouts)
+    }
+
+    /// Mints the seigniorage accrued since it was last minted into the rewards purse, and returns
+    /// the minted amount.
+    ///
+    /// The seigniorage is computed pro rata from the annual inflation rate and the time elapsed
+    /// since the previous call, so it is expected to be invoked by the system at the end of each
+    /// era.  The first call only starts the clock.
+    pub fn mint_seigniorage<P: MintProvider + RuntimeProvider + SeigniorageProvider>(
+        provider: &mut P,
+    ) -> Result<U512> {
+        if provider.get_caller() != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+
+        let timestamp = provider.get_block_time();
+        let last_seigniorage = match provider.read_last_seigniorage() {
+            Some(last_seigniorage) => last_seigniorage,
+            None => {
+                provider.write_last_seigniorage(timestamp);
+                return Ok(U512::zero());
+            }
+        };
+        if timestamp < last_seigniorage {
+            return Err(Error::TimeWentBackwards);
+        }
+        provider.write_last_seigniorage(timestamp);
+
+        let annual_inflation_rate = provider.read_inflation_rate();
+        if annual_inflation_rate == 0 {
+            return Ok(U512::zero());
+        }
+        let total_supply = provider.total_supply().ok_or(Error::TotalSupplyNotFound)?;
+        let elapsed: u64 = timestamp.saturating_sub(last_seigniorage).into();
+        let amount = seigniorage::compute_seigniorage(total_supply, annual_inflation_rate, elapsed);
+        if amount.is_zero() {
+            return Ok(amount);
+        }
+
+        let rewards_purse = get_rewards_purse(provider)?;
+        provider
+            .mint_into(rewards_purse, amount)
+            .map_err(|_| Error::SeigniorageMintFailed)?;
+        Ok(amount)
+    }
+
     /// Attempts to look up a purse from the named_keys
     fn get_purse<R: RuntimeProvider>(
         runtime_provider: &R,
@@ -255,14 +460,17 @@ mod internal {
         }
     }
 
-    /// Transfers funds from the payment purse to the validator rewards purse, as well as to the
-    /// refund purse, depending on how much was spent on the computation. This function maintains
-    /// the invariant that the balance of the payment purse is zero at the beginning and end of each
+    /// Transfers funds from the payment purse to the block proposer or the validator rewards purse,
+    /// as well as to the refund purse, depending on how much was spent on the computation. The
+    /// configured percentage of the fee is burned, and the remainder goes to the `proposer` of the
+    /// block, or to the rewards purse if there is no proposer. This function maintains the
+    /// invariant that the balance of the payment purse is zero at the beginning and end of each
     /// deploy and that the refund purse is unset at the beginning and end of each deploy.
-    pub fn finalize_payment<P: MintProvider + RuntimeProvider>(
+    pub fn finalize_payment<P: FeeProvider + MintProvider + RuntimeProvider>(
         provider: &mut P,
         amount_spent: U512,
         account: AccountHash,
+        proposer: Option<AccountHash>,
     ) -> Result<()> {
         let caller = provider.get_caller();
         if caller != SYSTEM_ACCOUNT {
@@ -280,14 +488,33 @@ mod internal {
         }
         let refund_amount = total - amount_spent;
 
+        let fee_burn_rate = provider.read_fee_burn_rate();
+        let (burned_amount, fee_amount) =
+            fees::split_fee(amount_spent, fee_burn_rate).ok_or(Error::InvalidFeeBurnRate)?;
+
         let rewards_purse = get_rewards_purse(provider)?;
         let refund_purse = get_refund_purse(provider)?;
         provider.remove_key(REFUND_PURSE_KEY); //unset refund purse after reading it
 
-        // pay validators
-        provider
-            .transfer_purse_to_purse(payment_purse, rewards_purse, amount_spent)
-            .map_err(|_| Error::FailedTransferToRewardsPurse)?;
+        if !burned_amount.is_zero() {
+            provider
+                .burn(payment_purse, burned_amount)
+                .map_err(|_| Error::FeeBurnFailed)?;
+        }
+
+        match proposer {
+            // pay proposer
+            Some(proposer) if !fee_amount.is_zero() => {
+                provider
+                    .transfer_purse_to_account(payment_purse, proposer, fee_amount)
+                    .map_err(|_| Error::FailedTransferToProposer)?;
+            }
+            Some(_) => {}
+            // pay validators
+            None => provider
+                .transfer_purse_to_purse(payment_purse, rewards_purse, fee_amount)
+                .map_err(|_| Error::FailedTransferToRewardsPurse)?,
+        }
 
         if refund_amount.is_zero() {
             return Ok(());
@@ -326,18 +553,44 @@ mod internal {
     mod tests {
         extern crate std;
 
-        use std::{cell::RefCell, iter, thread_local};
+        use std::{cell::RefCell, iter, thread_local, vec, vec::Vec};
 
-        use types::{account::AccountHash, system_contract_errors::pos::Result, BlockTime, U512};
+        use types::{
+            account::AccountHash,
+            system_contract_errors::pos::{Error, Result},
+            AccessRights, BlockTime, Key, Phase, TransferResult, TransferredTo, URef, U512,
+        };
 
-        use super::{bond, step, unbond, BOND_DELAY, UNBOND_DELAY};
+        use super::{
+            bid, bond, finalize_payment, mint_seigniorage, run_auction, step, unbond,
+            unbond_at_era_end, BOND_DELAY, PAYMENT_PURSE_KEY, REWARDS_PURSE_KEY, SYSTEM_ACCOUNT,
+            UNBOND_DELAY,
+        };
         use crate::{
-            queue::Queue, queue_provider::QueueProvider, stakes::Stakes,
+            auction::{AuctionConfig, Bids},
+            auction_provider::AuctionProvider,
+            fee_provider::FeeProvider,
+            mint_provider::MintProvider,
+            queue::Queue,
+            queue_provider::QueueProvider,
+            runtime_provider::RuntimeProvider,
+            seigniorage_provider::SeigniorageProvider,
+            stakes::Stakes,
             stakes_provider::StakesProvider,
+            staking_limits::StakingLimits,
         };
 
         const KEY1: [u8; 32] = [1; 32];
         const KEY2: [u8; 32] = [2; 32];
+        const KEY3: [u8; 32] = [3; 32];
+
+        const ERA_LENGTH: u64 = 100;
+
+        const REWARDS_PURSE_ADDR: [u8; 32] = [4; 32];
+        const PAYMENT_PURSE_ADDR: [u8; 32] = [5; 32];
+        const FEE_BURN_RATE: u8 = 10;
+        const INFLATION_RATE: u64 = 100_000;
+        const MILLIS_PER_YEAR: u64 = 365 * 24 * 60 * 60 * 1000;
 
         thread_local! {
             static BONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
@@ -345,10 +598,133 @@ mod internal {
             static STAKES: RefCell<Stakes> = RefCell::new(
                 Stakes(iter::once((AccountHash::new(KEY1), U512::from(1_000))).collect())
             );
+            static BIDS: RefCell<Bids> = RefCell::new(Bids::default());
+            static ERA_END: RefCell<Option<BlockTime>> = RefCell::new(None);
+            static BLOCK_TIME: RefCell<BlockTime> = RefCell::new(BlockTime::new(0));
+            static REWARDS: RefCell<U512> = RefCell::new(U512::zero());
+            static TOTAL_SUPPLY: RefCell<U512> = RefCell::new(U512::from(1_000_000));
+            static LAST_SEIGNIORAGE: RefCell<Option<BlockTime>> = RefCell::new(None);
+            static PAYMENT: RefCell<U512> = RefCell::new(U512::zero());
+            static PAYOUTS: RefCell<Vec<(AccountHash, U512)>> = RefCell::new(Vec::new());
         }
 
         struct Provider;
 
+        impl AuctionProvider for Provider {
+            fn read_auction_config(&mut self) -> Option<AuctionConfig> {
+                Some(AuctionConfig::new(2, ERA_LENGTH, U512::from(10)))
+            }
+
+            fn read_bids(&mut self) -> Bids {
+                BIDS.with(|b| b.borrow().clone())
+            }
+
+            fn write_bids(&mut self, bids: Bids) {
+                BIDS.with(|b| b.replace(bids));
+            }
+
+            fn read_era_end(&mut self) -> Option<BlockTime> {
+                ERA_END.with(|e| *e.borrow())
+            }
+
+            fn write_era_end(&mut self, era_end: BlockTime) {
+                ERA_END.with(|e| e.replace(Some(era_end)));
+            }
+        }
+
+        impl MintProvider for Provider {
+            fn transfer_purse_to_account(
+                &mut self,
+                source: URef,
+                target: AccountHash,
+                amount: U512,
+            ) -> TransferResult {
+                assert_eq!(PAYMENT_PURSE_ADDR, source.addr());
+                PAYMENT.with(|p| *p.borrow_mut() -= amount);
+                PAYOUTS.with(|p| p.borrow_mut().push((target, amount)));
+                Ok(TransferredTo::ExistingAccount)
+            }
+
+            fn transfer_purse_to_purse(
+                &mut self,
+                _source: URef,
+                _target: URef,
+                _amount: U512,
+            ) -> core::result::Result<(), ()> {
+                panic!("purse to purse transfers are not expected by these tests")
+            }
+
+            fn balance(&mut self, purse: URef) -> Option<U512> {
+                assert_eq!(PAYMENT_PURSE_ADDR, purse.addr());
+                PAYMENT.with(|p| Some(*p.borrow()))
+            }
+
+            fn mint_into(&mut self, purse: URef, amount: U512) -> core::result::Result<(), ()> {
+                assert_eq!(REWARDS_PURSE_ADDR, purse.addr());
+                REWARDS.with(|r| *r.borrow_mut() += amount);
+                TOTAL_SUPPLY.with(|t| *t.borrow_mut() += amount);
+                Ok(())
+            }
+
+            fn total_supply(&mut self) -> Option<U512> {
+                TOTAL_SUPPLY.with(|t| Some(*t.borrow()))
+            }
+
+            fn burn(&mut self, purse: URef, amount: U512) -> core::result::Result<(), ()> {
+                assert_eq!(PAYMENT_PURSE_ADDR, purse.addr());
+                PAYMENT.with(|p| *p.borrow_mut() -= amount);
+                TOTAL_SUPPLY.with(|t| *t.borrow_mut() -= amount);
+                Ok(())
+            }
+        }
+
+        impl FeeProvider for Provider {
+            fn read_fee_burn_rate(&mut self) -> u8 {
+                FEE_BURN_RATE
+            }
+        }
+
+        impl SeigniorageProvider for Provider {
+            fn read_inflation_rate(&mut self) -> u64 {
+                INFLATION_RATE
+            }
+
+            fn read_last_seigniorage(&mut self) -> Option<BlockTime> {
+                LAST_SEIGNIORAGE.with(|l| *l.borrow())
+            }
+
+            fn write_last_seigniorage(&mut self, timestamp: BlockTime) {
+                LAST_SEIGNIORAGE.with(|l| l.replace(Some(timestamp)));
+            }
+        }
+
+        impl RuntimeProvider for Provider {
+            fn get_key(&self, name: &str) -> Option<Key> {
+                let addr = match name {
+                    REWARDS_PURSE_KEY => REWARDS_PURSE_ADDR,
+                    PAYMENT_PURSE_KEY => PAYMENT_PURSE_ADDR,
+                    _ => return None,
+                };
+                Some(Key::URef(URef::new(addr, AccessRights::READ_ADD_WRITE)))
+            }
+
+            fn put_key(&mut self, _name: &str, _key: Key) {}
+
+            fn remove_key(&mut self, _name: &str) {}
+
+            fn get_phase(&self) -> Phase {
+                Phase::System
+            }
+
+            fn get_block_time(&self) -> BlockTime {
+                BLOCK_TIME.with(|t| *t.borrow())
+            }
+
+            fn get_caller(&self) -> AccountHash {
+                SYSTEM_ACCOUNT
+            }
+        }
+
         impl QueueProvider for Provider {
             fn read_bonding(&mut self) -> Queue {
                 BONDING.with(|b| b.borrow().clone())
@@ -375,6 +751,10 @@ mod internal {
             fn write(&mut self, stakes: &Stakes) {
                 STAKES.with(|s| s.replace(stakes.clone()));
             }
+
+            fn read_limits(&self) -> StakingLimits {
+                StakingLimits::default()
+            }
         }
 
         fn assert_stakes(stakes: &[([u8; 32], usize)]) {
@@ -418,5 +798,134 @@ mod internal {
             step::<Provider>(&mut provider, BlockTime::new(2 + UNBOND_DELAY)).expect("step 3");
             assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
         }
+
+        #[test]
+        fn test_bid_run_auction() {
+            let mut provider = Provider;
+            let auction_config = provider.read_auction_config().unwrap();
+            bid(
+                &mut provider,
+                &auction_config,
+                AccountHash::new(KEY2),
+                U512::from(500),
+            )
+            .expect("bid validator 2");
+            bid(
+                &mut provider,
+                &auction_config,
+                AccountHash::new(KEY3),
+                U512::from(2_000),
+            )
+            .expect("bid validator 3");
+
+            // Bids only become stakes once the era is over.
+            assert_stakes(&[(KEY1, 1_000)]);
+            BLOCK_TIME.with(|t| t.replace(BlockTime::new(ERA_LENGTH - 1)));
+            assert_eq!(Err(Error::EraNotFinished), run_auction(&mut provider));
+
+            BLOCK_TIME.with(|t| t.replace(BlockTime::new(ERA_LENGTH)));
+            let payouts = run_auction(&mut provider).expect("run auction");
+            assert_stakes(&[(KEY1, 1_000), (KEY3, 2_000)]);
+            assert_eq!(vec![(AccountHash::new(KEY2), U512::from(500))], payouts);
+            assert_eq!(Bids::default(), provider.read_bids());
+            assert_eq!(
+                Some(BlockTime::new(2 * ERA_LENGTH)),
+                provider.read_era_end()
+            );
+
+            // The next auction can only run at the end of the next era.
+            assert_eq!(Err(Error::EraNotFinished), run_auction(&mut provider));
+        }
+
+        #[test]
+        fn test_run_auction_skips_missed_eras() {
+            let mut provider = Provider;
+            BLOCK_TIME.with(|t| t.replace(BlockTime::new(3 * ERA_LENGTH + 1)));
+            run_auction(&mut provider).expect("run auction");
+            assert_eq!(
+                Some(BlockTime::new(4 * ERA_LENGTH)),
+                provider.read_era_end()
+            );
+
+            // Only one auction runs for the missed eras.
+            assert_eq!(Err(Error::EraNotFinished), run_auction(&mut provider));
+        }
+
+        #[test]
+        fn test_unbond_at_era_end() {
+            let mut provider = Provider;
+            let auction_config = provider.read_auction_config().unwrap();
+            bid(
+                &mut provider,
+                &auction_config,
+                AccountHash::new(KEY2),
+                U512::from(1_000),
+            )
+            .expect("bid validator 2");
+            BLOCK_TIME.with(|t| t.replace(BlockTime::new(ERA_LENGTH)));
+            run_auction(&mut provider).expect("run auction 1");
+            assert_stakes(&[(KEY1, 1_000), (KEY2, 1_000)]);
+
+            unbond_at_era_end(
+                &mut provider,
+                Some(U512::from(500)),
+                AccountHash::new(KEY1),
+                BlockTime::new(ERA_LENGTH + 1),
+            )
+            .expect("partly unbond validator 1");
+
+            // Unbonding only becomes effective at the end of the era.
+            assert_stakes(&[(KEY1, 1_000), (KEY2, 1_000)]);
+            BLOCK_TIME.with(|t| t.replace(BlockTime::new(2 * ERA_LENGTH)));
+            let payouts = run_auction(&mut provider).expect("run auction 2");
+            assert_stakes(&[(KEY1, 500), (KEY2, 1_000)]);
+            assert_eq!(vec![(AccountHash::new(KEY1), U512::from(500))], payouts);
+            assert_eq!(Queue::default(), provider.read_unbonding());
+        }
+
+        #[test]
+        fn test_mint_seigniorage() {
+            let mut provider = Provider;
+
+            // The first call only starts the clock.
+            BLOCK_TIME.with(|t| t.replace(BlockTime::new(ERA_LENGTH)));
+            assert_eq!(Ok(U512::zero()), mint_seigniorage(&mut provider));
+            assert_eq!(
+                Some(BlockTime::new(ERA_LENGTH)),
+                provider.read_last_seigniorage()
+            );
+
+            BLOCK_TIME.with(|t| t.replace(BlockTime::new(ERA_LENGTH + MILLIS_PER_YEAR)));
+            let expected = U512::from(100_000);
+            assert_eq!(Ok(expected), mint_seigniorage(&mut provider));
+            assert_eq!(expected, REWARDS.with(|r| *r.borrow()));
+            assert_eq!(Some(U512::from(1_100_000)), provider.total_supply());
+
+            BLOCK_TIME.with(|t| t.replace(BlockTime::new(ERA_LENGTH)));
+            assert_eq!(
+                Err(Error::TimeWentBackwards),
+                mint_seigniorage(&mut provider)
+            );
+        }
+
+        #[test]
+        fn test_finalize_payment_pays_proposer() {
+            let mut provider = Provider;
+            let account = AccountHash::new(KEY2);
+            let proposer = AccountHash::new(KEY3);
+            PAYMENT.with(|p| p.replace(U512::from(1_000)));
+
+            finalize_payment(&mut provider, U512::from(600), account, Some(proposer))
+                .expect("finalize payment");
+
+            // 10% of the fee is burned, the rest goes to the proposer and the unspent payment is
+            // refunded.
+            assert_eq!(
+                vec![(proposer, U512::from(540)), (account, U512::from(400))],
+                PAYOUTS.with(|p| p.borrow().clone())
+            );
+            assert_eq!(U512::from(1_000_000 - 60), provider.total_supply().unwrap());
+            assert_eq!(U512::zero(), PAYMENT.with(|p| *p.borrow()));
+        }
     }
 }
@@ -16,4 +16,10 @@ pub trait MintProvider {
     ) -> Result<(), ()>;
 
     fn balance(&mut self, purse: URef) -> Option<U512>;
+
+    fn mint_into(&mut self, purse: URef, amount: U512) -> Result<(), ()>;
+
+    fn total_supply(&mut self) -> Option<U512>;
+
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), ()>;
 }
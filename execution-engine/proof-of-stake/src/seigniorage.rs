@@ -0,0 +1,53 @@
+use types::U512;
+
+/// The number of milliseconds in a (non-leap) year.
+const MILLIS_PER_YEAR: u64 = 365 * 24 * 60 * 60 * 1000;
+
+/// The denominator of the annual inflation rate, which is given in millionths.
+const INFLATION_RATE_DENOMINATOR: u64 = 1_000_000;
+
+/// Computes the seigniorage accrued on `total_supply` over `elapsed` milliseconds at an
+/// `annual_inflation_rate` given in millionths of the total supply per year.
+///
+/// The result is rounded down.
+pub fn compute_seigniorage(total_supply: U512, annual_inflation_rate: u64, elapsed: u64) -> U512 {
+    let numerator = total_supply * U512::from(annual_inflation_rate) * U512::from(elapsed);
+    let denominator = U512::from(MILLIS_PER_YEAR) * U512::from(INFLATION_RATE_DENOMINATOR);
+    numerator / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use types::U512;
+
+    use super::{compute_seigniorage, MILLIS_PER_YEAR};
+
+    #[test]
+    fn test_compute_seigniorage_for_a_year() {
+        // 5% of 1_000_000 over a full year
+        assert_eq!(
+            U512::from(50_000),
+            compute_seigniorage(U512::from(1_000_000), 50_000, MILLIS_PER_YEAR)
+        );
+    }
+
+    #[test]
+    fn test_compute_seigniorage_is_pro_rata() {
+        assert_eq!(
+            U512::from(12_500),
+            compute_seigniorage(U512::from(1_000_000), 50_000, MILLIS_PER_YEAR / 4)
+        );
+    }
+
+    #[test]
+    fn test_compute_seigniorage_rounds_down() {
+        assert_eq!(
+            U512::zero(),
+            compute_seigniorage(U512::from(1_000_000), 50_000, 1)
+        );
+        assert_eq!(
+            U512::zero(),
+            compute_seigniorage(U512::from(1_000_000), 0, MILLIS_PER_YEAR)
+        );
+    }
+}
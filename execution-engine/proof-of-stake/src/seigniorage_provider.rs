@@ -0,0 +1,12 @@
+use types::BlockTime;
+
+pub trait SeigniorageProvider {
+    /// Reads the annual inflation rate in millionths of the total supply.
+    fn read_inflation_rate(&mut self) -> u64;
+
+    /// Reads the time at which seigniorage was last minted, or `None` if it never was.
+    fn read_last_seigniorage(&mut self) -> Option<BlockTime>;
+
+    /// Writes the time at which seigniorage was last minted.
+    fn write_last_seigniorage(&mut self, timestamp: BlockTime);
+}
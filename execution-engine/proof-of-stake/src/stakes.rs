@@ -13,22 +13,7 @@ use types::{
     U512,
 };
 
-/// The maximum difference between the largest and the smallest stakes.
-// TODO: Should this be a percentage instead?
-// TODO: Pick a reasonable value.
-const MAX_SPREAD: U512 = U512::MAX;
-
-/// The maximum increase of stakes in a single bonding request.
-const MAX_INCREASE: U512 = U512::MAX;
-
-/// The maximum decrease of stakes in a single unbonding request.
-const MAX_DECREASE: U512 = U512::MAX;
-
-/// The maximum increase of stakes in millionths of the total stakes in a single bonding request.
-const MAX_REL_INCREASE: u64 = 1_000_000_000;
-
-/// The maximum decrease of stakes in millionths of the total stakes in a single unbonding request.
-const MAX_REL_DECREASE: u64 = 900_000;
+use crate::staking_limits::StakingLimits;
 
 /// The stakes map, assigning the staked amount of motes to each bonded
 /// validator.
@@ -69,12 +54,19 @@ impl Stakes {
     /// * unbonding the specified amount is not allowed,
     /// * tries to unbond last validator,
     /// * validator was not bonded.
-    pub fn unbond(&mut self, validator: &AccountHash, maybe_amount: Option<U512>) -> Result<U512> {
+    pub fn unbond(
+        &mut self,
+        validator: &AccountHash,
+        maybe_amount: Option<U512>,
+        limits: &StakingLimits,
+    ) -> Result<U512> {
         let min = self
             .max_without(validator)
             .unwrap_or_else(U512::zero)
-            .saturating_sub(MAX_SPREAD);
-        let max_decrease = MAX_DECREASE.min(self.sum() * MAX_REL_DECREASE / 1_000_000);
+            .saturating_sub(limits.max_spread());
+        let max_decrease = limits
+            .max_decrease()
+            .min(self.sum() * limits.max_rel_decrease() / 1_000_000);
 
         if let Some(amount) = maybe_amount {
             // The minimum stake value to not violate the maximum spread.
@@ -117,21 +109,38 @@ impl Stakes {
             .or_insert(amount);
     }
 
+    /// Subtracts `amount` from the validator's stakes, removing the validator if nothing is left.
+    pub fn withdraw(&mut self, validator: &AccountHash, amount: U512) {
+        if let Some(stake) = self.0.get_mut(validator) {
+            *stake = stake.saturating_sub(amount);
+            if stake.is_zero() {
+                self.0.remove(validator);
+            }
+        }
+    }
+
     /// Returns an error if bonding the specified amount is not allowed.
-    pub fn validate_bonding(&self, validator: &AccountHash, amount: U512) -> Result<()> {
+    pub fn validate_bonding(
+        &self,
+        validator: &AccountHash,
+        amount: U512,
+        limits: &StakingLimits,
+    ) -> Result<()> {
         let max = self
             .min_without(validator)
             .unwrap_or(U512::MAX)
-            .saturating_add(MAX_SPREAD);
+            .saturating_add(limits.max_spread());
         let min = self
             .max_without(validator)
             .unwrap_or_else(U512::zero)
-            .saturating_sub(MAX_SPREAD);
+            .saturating_sub(limits.max_spread());
         let stake = self.0.get(validator).map(|s| *s + amount).unwrap_or(amount);
         if stake > max || stake < min {
             return Err(Error::SpreadTooHigh);
         }
-        let max_increase = MAX_INCREASE.min(self.sum() * MAX_REL_INCREASE / 1_000_000);
+        let max_increase = limits
+            .max_increase()
+            .min(self.sum() * limits.max_rel_increase() / 1_000_000);
         if (stake.is_zero() && amount > min.saturating_add(max_increase))
             || (!stake.is_zero() && amount > max_increase)
         {
@@ -173,6 +182,7 @@ mod tests {
     use types::{account::AccountHash, system_contract_errors::pos::Error, U512};
 
     use super::Stakes;
+    use crate::staking_limits::StakingLimits;
 
     const KEY1: [u8; 32] = [1; 32];
     const KEY2: [u8; 32] = [2; 32];
@@ -191,7 +201,11 @@ mod tests {
         let mut stakes = new_stakes(&[(KEY2, 100)]);
         assert_eq!(
             Ok(()),
-            stakes.validate_bonding(&AccountHash::new(KEY1), U512::from(5))
+            stakes.validate_bonding(
+                &AccountHash::new(KEY1),
+                U512::from(5),
+                &StakingLimits::default()
+            )
         );
         stakes.bond(&AccountHash::new(KEY1), U512::from(5));
         assert_eq!(new_stakes(&[(KEY1, 5), (KEY2, 100)]), stakes);
@@ -202,7 +216,11 @@ mod tests {
         let mut stakes = new_stakes(&[(KEY1, 50), (KEY2, 100)]);
         assert_eq!(
             Ok(()),
-            stakes.validate_bonding(&AccountHash::new(KEY1), U512::from(4))
+            stakes.validate_bonding(
+                &AccountHash::new(KEY1),
+                U512::from(4),
+                &StakingLimits::default()
+            )
         );
         stakes.bond(&AccountHash::new(KEY1), U512::from(4));
         assert_eq!(new_stakes(&[(KEY1, 54), (KEY2, 100)]), stakes);
@@ -211,12 +229,14 @@ mod tests {
     #[test]
     fn test_bond_too_much_rel() {
         let stakes = new_stakes(&[(KEY1, 1_000), (KEY2, 1_000)]);
+        let limits = StakingLimits::default();
         let total = 1_000 + 1_000;
         assert_eq!(
             Err(Error::BondTooLarge),
             stakes.validate_bonding(
                 &AccountHash::new(KEY1),
-                U512::from(super::MAX_REL_INCREASE * total / 1_000_000 + 1),
+                U512::from(limits.max_rel_increase() * total / 1_000_000 + 1),
+                &limits,
             ),
             "Successfully bonded more than the maximum amount."
         );
@@ -224,7 +244,8 @@ mod tests {
             Ok(()),
             stakes.validate_bonding(
                 &AccountHash::new(KEY1),
-                U512::from(super::MAX_REL_INCREASE * total / 1_000_000),
+                U512::from(limits.max_rel_increase() * total / 1_000_000),
+                &limits,
             ),
             "Failed to bond the maximum amount."
         );
@@ -235,7 +256,7 @@ mod tests {
         let mut stakes = new_stakes(&[(KEY1, 5), (KEY2, 100)]);
         assert_eq!(
             Ok(U512::from(5)),
-            stakes.unbond(&AccountHash::new(KEY1), None)
+            stakes.unbond(&AccountHash::new(KEY1), None, &StakingLimits::default())
         );
         assert_eq!(new_stakes(&[(KEY2, 100)]), stakes);
     }
@@ -245,7 +266,7 @@ mod tests {
         let mut stakes = new_stakes(&[(KEY1, 5)]);
         assert_eq!(
             Err(Error::CannotUnbondLastValidator),
-            stakes.unbond(&AccountHash::new(KEY1), None)
+            stakes.unbond(&AccountHash::new(KEY1), None, &StakingLimits::default())
         );
     }
 
@@ -254,7 +275,11 @@ mod tests {
         let mut stakes = new_stakes(&[(KEY1, 50)]);
         assert_eq!(
             Ok(U512::from(4)),
-            stakes.unbond(&AccountHash::new(KEY1), Some(U512::from(4)))
+            stakes.unbond(
+                &AccountHash::new(KEY1),
+                Some(U512::from(4)),
+                &StakingLimits::default()
+            )
         );
         assert_eq!(new_stakes(&[(KEY1, 46)]), stakes);
     }
@@ -262,22 +287,49 @@ mod tests {
     #[test]
     fn test_unbond_too_much_rel() {
         let mut stakes = new_stakes(&[(KEY1, 999), (KEY2, 1)]);
+        let limits = StakingLimits::default();
         let total = 999 + 1;
         assert_eq!(
             Err(Error::UnbondTooLarge),
             stakes.unbond(
                 &AccountHash::new(KEY1),
-                Some(U512::from(super::MAX_REL_DECREASE * total / 1_000_000 + 1)),
+                Some(U512::from(
+                    limits.max_rel_decrease() * total / 1_000_000 + 1
+                )),
+                &limits,
             ),
             "Successfully unbonded more than the maximum amount."
         );
         assert_eq!(
-            Ok(U512::from(super::MAX_REL_DECREASE * total / 1_000_000)),
+            Ok(U512::from(limits.max_rel_decrease() * total / 1_000_000)),
             stakes.unbond(
                 &AccountHash::new(KEY1),
-                Some(U512::from(super::MAX_REL_DECREASE * total / 1_000_000)),
+                Some(U512::from(limits.max_rel_decrease() * total / 1_000_000)),
+                &limits,
             ),
             "Failed to unbond the maximum amount."
         );
     }
+
+    #[test]
+    fn test_bond_spread_too_high() {
+        let stakes = new_stakes(&[(KEY1, 100), (KEY2, 100)]);
+        let limits = StakingLimits::new(
+            U512::from(50),
+            U512::MAX,
+            U512::MAX,
+            1_000_000_000,
+            900_000,
+            100,
+            1000,
+        );
+        assert_eq!(
+            Err(Error::SpreadTooHigh),
+            stakes.validate_bonding(&AccountHash::new(KEY1), U512::from(51), &limits)
+        );
+        assert_eq!(
+            Ok(()),
+            stakes.validate_bonding(&AccountHash::new(KEY1), U512::from(50), &limits)
+        );
+    }
 }
@@ -1,8 +1,11 @@
-use crate::{stakes::Stakes, Result};
+use crate::{stakes::Stakes, staking_limits::StakingLimits, Result};
 
 /// A `StakesProvider` that reads and writes the stakes to/from the contract's known urefs.
 pub trait StakesProvider {
     fn read(&self) -> Result<Stakes>;
 
     fn write(&mut self, stakes: &Stakes);
+
+    /// Reads the limits enforced when bonding and unbonding.
+    fn read_limits(&self) -> StakingLimits;
 }
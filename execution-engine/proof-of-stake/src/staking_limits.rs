@@ -0,0 +1,187 @@
+use alloc::vec::Vec;
+use core::result;
+
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U64_SERIALIZED_LENGTH},
+    CLType, CLTyped, U512,
+};
+
+/// The maximum difference between the largest and the smallest stakes.
+// TODO: Should this be a percentage instead?
+// TODO: Pick a reasonable value.
+const DEFAULT_MAX_SPREAD: U512 = U512::MAX;
+
+/// The maximum increase of stakes in a single bonding request.
+const DEFAULT_MAX_INCREASE: U512 = U512::MAX;
+
+/// The maximum decrease of stakes in a single unbonding request.
+const DEFAULT_MAX_DECREASE: U512 = U512::MAX;
+
+/// The maximum increase of stakes in millionths of the total stakes in a single bonding request.
+const DEFAULT_MAX_REL_INCREASE: u64 = 1_000_000_000;
+
+/// The maximum decrease of stakes in millionths of the total stakes in a single unbonding request.
+const DEFAULT_MAX_REL_DECREASE: u64 = 900_000;
+
+/// The maximum number of pending bonding requests.
+const DEFAULT_MAX_BOND_LEN: u32 = 100;
+
+/// The maximum number of pending unbonding requests.
+const DEFAULT_MAX_UNBOND_LEN: u32 = 1000;
+
+/// The limits which the Proof of Stake contract enforces when validators bond and unbond.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StakingLimits {
+    max_spread: U512,
+    max_increase: U512,
+    max_decrease: U512,
+    max_rel_increase: u64,
+    max_rel_decrease: u64,
+    max_bond_len: u32,
+    max_unbond_len: u32,
+}
+
+impl StakingLimits {
+    /// Creates new `StakingLimits`.
+    pub fn new(
+        max_spread: U512,
+        max_increase: U512,
+        max_decrease: U512,
+        max_rel_increase: u64,
+        max_rel_decrease: u64,
+        max_bond_len: u32,
+        max_unbond_len: u32,
+    ) -> Self {
+        StakingLimits {
+            max_spread,
+            max_increase,
+            max_decrease,
+            max_rel_increase,
+            max_rel_decrease,
+            max_bond_len,
+            max_unbond_len,
+        }
+    }
+
+    /// Returns the maximum difference between the largest and the smallest stakes.
+    pub fn max_spread(&self) -> U512 {
+        self.max_spread
+    }
+
+    /// Returns the maximum increase of stakes in a single bonding request.
+    pub fn max_increase(&self) -> U512 {
+        self.max_increase
+    }
+
+    /// Returns the maximum decrease of stakes in a single unbonding request.
+    pub fn max_decrease(&self) -> U512 {
+        self.max_decrease
+    }
+
+    /// Returns the maximum increase of stakes in millionths of the total stakes in a single
+    /// bonding request.
+    pub fn max_rel_increase(&self) -> u64 {
+        self.max_rel_increase
+    }
+
+    /// Returns the maximum decrease of stakes in millionths of the total stakes in a single
+    /// unbonding request.
+    pub fn max_rel_decrease(&self) -> u64 {
+        self.max_rel_decrease
+    }
+
+    /// Returns the maximum number of pending bonding requests.
+    pub fn max_bond_len(&self) -> u32 {
+        self.max_bond_len
+    }
+
+    /// Returns the maximum number of pending unbonding requests.
+    pub fn max_unbond_len(&self) -> u32 {
+        self.max_unbond_len
+    }
+}
+
+impl Default for StakingLimits {
+    fn default() -> Self {
+        StakingLimits {
+            max_spread: DEFAULT_MAX_SPREAD,
+            max_increase: DEFAULT_MAX_INCREASE,
+            max_decrease: DEFAULT_MAX_DECREASE,
+            max_rel_increase: DEFAULT_MAX_REL_INCREASE,
+            max_rel_decrease: DEFAULT_MAX_REL_DECREASE,
+            max_bond_len: DEFAULT_MAX_BOND_LEN,
+            max_unbond_len: DEFAULT_MAX_UNBOND_LEN,
+        }
+    }
+}
+
+impl ToBytes for StakingLimits {
+    fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
+        let mut bytes = bytesrepr::allocate_buffer(self)?;
+        bytes.append(&mut self.max_spread.to_bytes()?);
+        bytes.append(&mut self.max_increase.to_bytes()?);
+        bytes.append(&mut self.max_decrease.to_bytes()?);
+        bytes.append(&mut self.max_rel_increase.to_bytes()?);
+        bytes.append(&mut self.max_rel_decrease.to_bytes()?);
+        bytes.append(&mut self.max_bond_len.to_bytes()?);
+        bytes.append(&mut self.max_unbond_len.to_bytes()?);
+        Ok(bytes)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.max_spread.serialized_length()
+            + self.max_increase.serialized_length()
+            + self.max_decrease.serialized_length()
+            + 2 * U64_SERIALIZED_LENGTH
+            + 2 * U32_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for StakingLimits {
+    fn from_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
+        let (max_spread, bytes) = U512::from_bytes(bytes)?;
+        let (max_increase, bytes) = U512::from_bytes(bytes)?;
+        let (max_decrease, bytes) = U512::from_bytes(bytes)?;
+        let (max_rel_increase, bytes) = u64::from_bytes(bytes)?;
+        let (max_rel_decrease, bytes) = u64::from_bytes(bytes)?;
+        let (max_bond_len, bytes) = u32::from_bytes(bytes)?;
+        let (max_unbond_len, bytes) = u32::from_bytes(bytes)?;
+        let staking_limits = StakingLimits {
+            max_spread,
+            max_increase,
+            max_decrease,
+            max_rel_increase,
+            max_rel_decrease,
+            max_bond_len,
+            max_unbond_len,
+        };
+        Ok((staking_limits, bytes))
+    }
+}
+
+impl CLTyped for StakingLimits {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{bytesrepr, U512};
+
+    use super::StakingLimits;
+
+    #[test]
+    fn serialization_roundtrip() {
+        bytesrepr::test_serialization_roundtrip(&StakingLimits::default());
+        bytesrepr::test_serialization_roundtrip(&StakingLimits::new(
+            U512::from(1_000),
+            U512::from(2_000),
+            U512::from(3_000),
+            500_000,
+            400_000,
+            10,
+            20,
+        ));
+    }
+}
@@ -1,6 +1,11 @@
 //! Contains types and constants associated with user accounts.
 
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
 use core::{
     convert::TryFrom,
     fmt::{Debug, Display, Formatter},
@@ -9,8 +14,8 @@ use core::{
 use failure::Fail;
 
 use crate::{
-    bytesrepr::{Error, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
-    CLType, CLTyped,
+    bytesrepr::{self, Error, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped, ContractPackageHash,
 };
 
 // This error type is not intended to be used by third party crates.
@@ -31,6 +36,8 @@ pub enum ActionType {
     /// action thresholds (i.e. the total [`Weight`]s of signing [`AccountHash`]s required to
     /// perform various actions).
     KeyManagement = 1,
+    /// Represents transferring motes out of the account's main purse.
+    Transfer = 2,
 }
 
 // This conversion is not intended to be used by third party crates.
@@ -45,6 +52,7 @@ impl TryFrom<u32> for ActionType {
         match value {
             d if d == ActionType::Deployment as u32 => Ok(ActionType::Deployment),
             d if d == ActionType::KeyManagement as u32 => Ok(ActionType::KeyManagement),
+            d if d == ActionType::Transfer as u32 => Ok(ActionType::Transfer),
             _ => Err(TryFromIntError(())),
         }
     }
@@ -101,6 +109,9 @@ impl TryFrom<i32> for SetThresholdFailure {
 /// account.
 pub const MAX_ASSOCIATED_KEYS: usize = 10;
 
+/// Maximum number of guardians in a single account's [`RecoveryConfig`].
+pub const MAX_GUARDIANS: usize = 10;
+
 /// The number of bytes in a serialized [`Weight`].
 pub const WEIGHT_SERIALIZED_LENGTH: usize = U8_SERIALIZED_LENGTH;
 
@@ -335,6 +346,198 @@ impl TryFrom<i32> for UpdateKeyFailure {
     }
 }
 
+/// Errors that can occur while configuring or performing the recovery of an account's associated
+/// keys by its guardians.
+#[derive(PartialEq, Eq, Fail, Debug, Copy, Clone)]
+#[repr(i32)]
+pub enum RecoveryFailure {
+    /// Caller doesn't have sufficient permissions to change the recovery configuration or to act
+    /// as a guardian.
+    #[fail(display = "Unable to perform recovery action due to insufficient permissions")]
+    PermissionDenied = 1,
+    /// The recovery configuration has no guardians or more than [`MAX_GUARDIANS`], or its
+    /// threshold is zero or greater than the number of guardians.
+    #[fail(display = "Invalid recovery configuration")]
+    InvalidConfig = 2,
+    /// The given account doesn't exist or has no recovery configuration.
+    #[fail(display = "Account recovery is not configured")]
+    NotConfigured = 3,
+    /// The caller is not one of the account's guardians.
+    #[fail(display = "Caller is not a guardian of the account")]
+    NotGuardian = 4,
+    /// The proposed associated keys are empty, exceed [`MAX_ASSOCIATED_KEYS`], or their total
+    /// [`Weight`] falls below one of the account's action thresholds.
+    #[fail(display = "Invalid associated keys proposed for recovery")]
+    InvalidKeys = 5,
+    /// There is no pending recovery for the given account.
+    #[fail(display = "No pending recovery")]
+    NoPendingRecovery = 6,
+    /// The pending recovery's delay hasn't elapsed yet.
+    #[fail(display = "Recovery delay has not elapsed")]
+    DelayNotElapsed = 7,
+    /// Fewer guardians than the configured threshold have approved the pending recovery.
+    #[fail(display = "Insufficient guardian approvals for recovery")]
+    InsufficientApprovals = 8,
+}
+
+// This conversion is not intended to be used by third party crates.
+#[doc(hidden)]
+impl TryFrom<i32> for RecoveryFailure {
+    type Error = TryFromIntError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            d if d == RecoveryFailure::PermissionDenied as i32 => {
+                Ok(RecoveryFailure::PermissionDenied)
+            }
+            d if d == RecoveryFailure::InvalidConfig as i32 => Ok(RecoveryFailure::InvalidConfig),
+            d if d == RecoveryFailure::NotConfigured as i32 => Ok(RecoveryFailure::NotConfigured),
+            d if d == RecoveryFailure::NotGuardian as i32 => Ok(RecoveryFailure::NotGuardian),
+            d if d == RecoveryFailure::InvalidKeys as i32 => Ok(RecoveryFailure::InvalidKeys),
+            d if d == RecoveryFailure::NoPendingRecovery as i32 => {
+                Ok(RecoveryFailure::NoPendingRecovery)
+            }
+            d if d == RecoveryFailure::DelayNotElapsed as i32 => {
+                Ok(RecoveryFailure::DelayNotElapsed)
+            }
+            d if d == RecoveryFailure::InsufficientApprovals as i32 => {
+                Ok(RecoveryFailure::InsufficientApprovals)
+            }
+            _ => Err(TryFromIntError(())),
+        }
+    }
+}
+
+/// An account's opt-in configuration allowing a set of guardian accounts to replace its associated
+/// keys, e.g. after enough keys were lost to fall below the key-management threshold.
+///
+/// A recovery has to be approved by at least `threshold` guardians and can only be finalized once
+/// `delay` milliseconds of block time have passed since it was proposed.  Until then, the account
+/// can cancel it with its key-management threshold.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct RecoveryConfig {
+    guardians: BTreeSet<AccountHash>,
+    threshold: u8,
+    delay: u64,
+}
+
+impl RecoveryConfig {
+    /// Constructs a new `RecoveryConfig`.
+    pub fn new(guardians: BTreeSet<AccountHash>, threshold: u8, delay: u64) -> Self {
+        RecoveryConfig {
+            guardians,
+            threshold,
+            delay,
+        }
+    }
+
+    /// Returns the accounts allowed to propose and approve a recovery.
+    pub fn guardians(&self) -> &BTreeSet<AccountHash> {
+        &self.guardians
+    }
+
+    /// Returns the number of guardians required to approve a recovery.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Returns the time in milliseconds between proposing and finalizing a recovery.
+    pub fn delay(&self) -> u64 {
+        self.delay
+    }
+
+    /// Returns `true` if there is at least one guardian, at most [`MAX_GUARDIANS`], and the
+    /// threshold can be met.
+    pub fn is_valid(&self) -> bool {
+        !self.guardians.is_empty()
+            && self.guardians.len() <= MAX_GUARDIANS
+            && self.threshold > 0
+            && usize::from(self.threshold) <= self.guardians.len()
+    }
+}
+
+impl ToBytes for RecoveryConfig {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.guardians.to_bytes()?);
+        result.append(&mut self.threshold.to_bytes()?);
+        result.append(&mut self.delay.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.guardians.serialized_length()
+            + self.threshold.serialized_length()
+            + self.delay.serialized_length()
+    }
+}
+
+impl FromBytes for RecoveryConfig {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (guardians, rem) = BTreeSet::<AccountHash>::from_bytes(bytes)?;
+        let (threshold, rem) = u8::from_bytes(rem)?;
+        let (delay, rem) = u64::from_bytes(rem)?;
+        Ok((RecoveryConfig::new(guardians, threshold, delay), rem))
+    }
+}
+
+/// An account's allow-list of the stored contract entry points its deploys may call.
+///
+/// While an account has an allow-list, deploys which aren't authorized with its key-management
+/// threshold can't run arbitrary Wasm as session or custom payment code, and can only call the
+/// listed entry points of stored contracts.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct ContractAllowList(BTreeMap<ContractPackageHash, BTreeSet<String>>);
+
+impl ContractAllowList {
+    /// Constructs an empty `ContractAllowList`, which doesn't allow calling any contract.
+    pub fn new() -> Self {
+        ContractAllowList::default()
+    }
+
+    /// Allows calling `entry_point` of any version of the given contract package.
+    pub fn allow(&mut self, contract_package_hash: ContractPackageHash, entry_point: String) {
+        self.0
+            .entry(contract_package_hash)
+            .or_default()
+            .insert(entry_point);
+    }
+
+    /// Returns `true` if `entry_point` of the given contract package may be called.
+    pub fn is_allowed(
+        &self,
+        contract_package_hash: &ContractPackageHash,
+        entry_point: &str,
+    ) -> bool {
+        self.0
+            .get(contract_package_hash)
+            .map_or(false, |entry_points| entry_points.contains(entry_point))
+    }
+
+    /// Returns an iterator over the allowed contract packages and their allowed entry points.
+    pub fn iter(&self) -> impl Iterator<Item = (&ContractPackageHash, &BTreeSet<String>)> {
+        self.0.iter()
+    }
+}
+
+impl ToBytes for ContractAllowList {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+}
+
+impl FromBytes for ContractAllowList {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (allow_list, rem) =
+            BTreeMap::<ContractPackageHash, BTreeSet<String>>::from_bytes(bytes)?;
+        Ok((ContractAllowList(allow_list), rem))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{convert::TryFrom, vec::Vec};
@@ -403,4 +606,50 @@ mod tests {
                    `UpdateKeyFailure`, or `max_valid_value_for_variant` in this test?"
         );
     }
+
+    #[test]
+    fn try_from_i32_for_recovery_failure() {
+        let max_valid_value_for_variant = RecoveryFailure::InsufficientApprovals as i32;
+        assert_eq!(
+            Err(TryFromIntError(())),
+            RecoveryFailure::try_from(max_valid_value_for_variant + 1),
+            "Did you forget to update `RecoveryFailure::try_from` for a new variant of \
+                   `RecoveryFailure`, or `max_valid_value_for_variant` in this test?"
+        );
+    }
+
+    #[test]
+    fn recovery_config_validity() {
+        let guardians: BTreeSet<AccountHash> = (1..=3).map(|i| AccountHash::new([i; 32])).collect();
+        assert!(RecoveryConfig::new(guardians.clone(), 2, 1_000).is_valid());
+        assert!(RecoveryConfig::new(guardians.clone(), 3, 0).is_valid());
+        assert!(!RecoveryConfig::new(guardians.clone(), 0, 1_000).is_valid());
+        assert!(!RecoveryConfig::new(guardians, 4, 1_000).is_valid());
+        assert!(!RecoveryConfig::new(BTreeSet::new(), 0, 1_000).is_valid());
+
+        let too_many_guardians: BTreeSet<AccountHash> = (0..=MAX_GUARDIANS as u8)
+            .map(|i| AccountHash::new([i; 32]))
+            .collect();
+        assert!(!RecoveryConfig::new(too_many_guardians, 1, 1_000).is_valid());
+    }
+
+    #[test]
+    fn contract_allow_list_should_only_allow_listed_entry_points() {
+        let mut allow_list = ContractAllowList::new();
+        assert!(!allow_list.is_allowed(&[1; 32], "transfer"));
+
+        allow_list.allow([1; 32], "transfer".into());
+        assert!(allow_list.is_allowed(&[1; 32], "transfer"));
+        assert!(!allow_list.is_allowed(&[1; 32], "approve"));
+        assert!(!allow_list.is_allowed(&[2; 32], "transfer"));
+
+        bytesrepr::test_serialization_roundtrip(&allow_list);
+    }
+
+    #[test]
+    fn recovery_config_serialization_roundtrip() {
+        let guardians: BTreeSet<AccountHash> = (1..=3).map(|i| AccountHash::new([i; 32])).collect();
+        let config = RecoveryConfig::new(guardians, 2, 86_400_000);
+        bytesrepr::test_serialization_roundtrip(&config);
+    }
 }
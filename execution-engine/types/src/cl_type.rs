@@ -6,6 +6,9 @@ use alloc::{
 };
 use core::mem;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::{
     bytesrepr::{self, FromBytes, ToBytes},
     Key, URef, U128, U256, U512,
@@ -38,6 +41,7 @@ const CL_TYPE_TAG_ANY: u8 = 21;
 ///
 /// Provides a description of the underlying data type of a [`CLValue`](crate::CLValue).
 #[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CLType {
     /// `bool` primitive.
     Bool,
@@ -2,10 +2,10 @@
 
 use crate::{
     alloc::string::ToString,
-    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
     uref::URef,
     CLType, ContractHash, ContractPackageHash, ContractWasmHash, Key, ProtocolVersion,
-    KEY_HASH_LENGTH,
+    BLAKE2B_DIGEST_LENGTH, KEY_HASH_LENGTH,
 };
 use alloc::{
     collections::{BTreeMap, BTreeSet},
@@ -14,6 +14,9 @@ use alloc::{
 };
 use core::fmt;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Maximum number of distinct user groups.
 pub const MAX_GROUPS: u8 = 10;
 /// Maximum number of URefs which can be assigned across all user groups.
@@ -47,11 +50,21 @@ pub enum Error {
     GroupInUse = 8,
     /// URef already exists in given group.
     URefAlreadyExists = 9,
+    /// Attempted to modify a locked contract package or one of its versions.
+    LockedContract = 10,
+    /// Attempted to migrate a contract package which has no enabled version to migrate from.
+    NoPreviousVersion = 11,
+    /// Attempted to migrate using an entry point missing from the new version.
+    MigrationEntryPointNotFound = 12,
+    /// Attempted to migrate using an entry point which isn't of `EntryPointType::Contract` or
+    /// which is public.
+    InvalidMigrationEntryPoint = 13,
 }
 
 /// A (labelled) "user group". Each method of a versioned contract may be
 /// assoicated with one or more user groups which are allowed to call it.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Group(String);
 
 impl Group {
@@ -168,6 +181,161 @@ pub type DisabledVersions = BTreeSet<ContractVersionKey>;
 /// Collection of named groups.
 pub type Groups = BTreeMap<Group, BTreeSet<URef>>;
 
+/// Documentation of entry points, by entry point name.
+pub type EntryPointDocs = BTreeMap<String, String>;
+
+/// Types of the values stored under named keys, by name.
+pub type NamedKeysSchema = BTreeMap<String, CLType>;
+
+/// Human-readable description of a contract package or of one of its contract versions.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ContractMetadata {
+    name: String,
+    description: String,
+    source_hash: Option<[u8; BLAKE2B_DIGEST_LENGTH]>,
+    entry_point_docs: EntryPointDocs,
+    named_keys_schema: NamedKeysSchema,
+}
+
+impl ContractMetadata {
+    /// `ContractMetadata` constructor.
+    pub fn new(
+        name: String,
+        description: String,
+        source_hash: Option<[u8; BLAKE2B_DIGEST_LENGTH]>,
+        entry_point_docs: EntryPointDocs,
+        named_keys_schema: NamedKeysSchema,
+    ) -> Self {
+        ContractMetadata {
+            name,
+            description,
+            source_hash,
+            entry_point_docs,
+            named_keys_schema,
+        }
+    }
+
+    /// Human-readable name of the contract.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Human-readable description of the contract.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Hash of the source code the contract was built from, if published.
+    pub fn source_hash(&self) -> Option<[u8; BLAKE2B_DIGEST_LENGTH]> {
+        self.source_hash
+    }
+
+    /// Documentation of the contract's entry points.
+    pub fn entry_point_docs(&self) -> &EntryPointDocs {
+        &self.entry_point_docs
+    }
+
+    /// Types of the values the contract stores under its named keys.
+    pub fn named_keys_schema(&self) -> &NamedKeysSchema {
+        &self.named_keys_schema
+    }
+}
+
+impl ToBytes for ContractMetadata {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.name.to_bytes()?);
+        result.append(&mut self.description.to_bytes()?);
+        result.append(&mut self.source_hash.to_bytes()?);
+        result.append(&mut self.entry_point_docs.to_bytes()?);
+        // `CLType` isn't `ToBytes`, so the schema is written as a length-prefixed list of pairs
+        result.append(&mut (self.named_keys_schema.len() as u32).to_bytes()?);
+        for (name, cl_type) in &self.named_keys_schema {
+            result.append(&mut name.to_bytes()?);
+            cl_type.append_bytes(&mut result);
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.name.serialized_length()
+            + self.description.serialized_length()
+            + self.source_hash.serialized_length()
+            + self.entry_point_docs.serialized_length()
+            + U32_SERIALIZED_LENGTH
+            + self
+                .named_keys_schema
+                .iter()
+                .map(|(name, cl_type)| name.serialized_length() + cl_type.serialized_length())
+                .sum::<usize>()
+    }
+}
+
+impl FromBytes for ContractMetadata {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (name, bytes) = String::from_bytes(bytes)?;
+        let (description, bytes) = String::from_bytes(bytes)?;
+        let (source_hash, bytes) = <Option<[u8; BLAKE2B_DIGEST_LENGTH]>>::from_bytes(bytes)?;
+        let (entry_point_docs, bytes) = EntryPointDocs::from_bytes(bytes)?;
+        let (schema_length, mut bytes) = u32::from_bytes(bytes)?;
+        let mut named_keys_schema = NamedKeysSchema::new();
+        for _ in 0..schema_length {
+            let (name, rem) = String::from_bytes(bytes)?;
+            let (cl_type, rem) = CLType::from_bytes(rem)?;
+            named_keys_schema.insert(name, cl_type);
+            bytes = rem;
+        }
+        Ok((
+            ContractMetadata {
+                name,
+                description,
+                source_hash,
+                entry_point_docs,
+                named_keys_schema,
+            },
+            bytes,
+        ))
+    }
+}
+
+/// Whether versions of a contract package may still be added or disabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ContractPackageStatus {
+    /// Versions may be added or disabled by the holder of the package's access key.
+    Unlocked = 0,
+    /// The package is immutable; its versions may never change.
+    Locked = 1,
+}
+
+impl Default for ContractPackageStatus {
+    fn default() -> Self {
+        ContractPackageStatus::Unlocked
+    }
+}
+
+impl ToBytes for ContractPackageStatus {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for ContractPackageStatus {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        let status = match tag {
+            tag if tag == ContractPackageStatus::Unlocked as u8 => ContractPackageStatus::Unlocked,
+            tag if tag == ContractPackageStatus::Locked as u8 => ContractPackageStatus::Locked,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((status, rem))
+    }
+}
+
 /// Contract definition, metadata, and security container.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct ContractPackage {
@@ -182,6 +350,10 @@ pub struct ContractPackage {
     /// version of the contract. A method is callable by any context which
     /// "knows" any of the URefs assoicated with the mthod's user group.
     groups: Groups,
+    /// Whether versions may still be added or disabled
+    status: ContractPackageStatus,
+    /// Human-readable description of the package
+    metadata: Option<ContractMetadata>,
 }
 
 impl ContractPackage {
@@ -191,12 +363,15 @@ impl ContractPackage {
         versions: ContractVersions,
         disabled_versions: DisabledVersions,
         groups: Groups,
+        status: ContractPackageStatus,
     ) -> Self {
         ContractPackage {
             access_key,
             versions,
             disabled_versions,
             groups,
+            status,
+            metadata: None,
         }
     }
 
@@ -205,6 +380,26 @@ impl ContractPackage {
         self.access_key
     }
 
+    /// Returns whether versions of this contract may still be added or disabled.
+    pub fn status(&self) -> ContractPackageStatus {
+        self.status
+    }
+
+    /// Returns `true` if this contract's versions may never change.
+    pub fn is_locked(&self) -> bool {
+        self.status == ContractPackageStatus::Locked
+    }
+
+    /// Returns the human-readable description of this contract package, if any.
+    pub fn metadata(&self) -> Option<&ContractMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Sets or clears the human-readable description of this contract package.
+    pub fn set_metadata(&mut self, metadata: Option<ContractMetadata>) {
+        self.metadata = metadata;
+    }
+
     /// Get the mutable group definitions for this contract.
     pub fn groups_mut(&mut self) -> &mut Groups {
         &mut self.groups
@@ -252,6 +447,10 @@ impl ContractPackage {
 
     /// Disable the contract version corresponding to the given hash (if it exists).
     pub fn disable_contract_version(&mut self, contract_hash: ContractHash) -> Result<(), Error> {
+        if self.is_locked() {
+            return Err(Error::LockedContract);
+        }
+
         let contract_version_key = self
             .versions
             .iter()
@@ -351,6 +550,8 @@ impl ToBytes for ContractPackage {
         result.append(&mut self.versions.to_bytes()?);
         result.append(&mut self.disabled_versions.to_bytes()?);
         result.append(&mut self.groups.to_bytes()?);
+        result.append(&mut self.status.to_bytes()?);
+        result.append(&mut self.metadata.to_bytes()?);
 
         Ok(result)
     }
@@ -360,6 +561,8 @@ impl ToBytes for ContractPackage {
             + self.versions.serialized_length()
             + self.disabled_versions.serialized_length()
             + self.groups.serialized_length()
+            + self.status.serialized_length()
+            + self.metadata.serialized_length()
     }
 }
 
@@ -369,11 +572,15 @@ impl FromBytes for ContractPackage {
         let (versions, bytes) = ContractVersions::from_bytes(bytes)?;
         let (disabled_versions, bytes) = DisabledVersions::from_bytes(bytes)?;
         let (groups, bytes) = Groups::from_bytes(bytes)?;
+        let (status, bytes) = ContractPackageStatus::from_bytes(bytes)?;
+        let (metadata, bytes) = <Option<ContractMetadata>>::from_bytes(bytes)?;
         let result = ContractPackage {
             access_key,
             versions,
             disabled_versions,
             groups,
+            status,
+            metadata,
         };
 
         Ok((result, bytes))
@@ -385,6 +592,7 @@ pub type EntryPointsMap = BTreeMap<String, EntryPoint>;
 
 /// Collection of named entry points
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EntryPoints(EntryPointsMap);
 
 impl Default for EntryPoints {
@@ -465,6 +673,7 @@ pub struct Contract {
     named_keys: NamedKeys,
     entry_points: EntryPoints,
     protocol_version: ProtocolVersion,
+    metadata: Option<ContractMetadata>,
 }
 
 impl From<Contract>
@@ -502,6 +711,7 @@ impl Contract {
             named_keys,
             entry_points,
             protocol_version,
+            metadata: None,
         }
     }
 
@@ -515,6 +725,16 @@ impl Contract {
         self.contract_wasm_hash
     }
 
+    /// Returns the human-readable description of this contract version, if any.
+    pub fn metadata(&self) -> Option<&ContractMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Sets or clears the human-readable description of this contract version.
+    pub fn set_metadata(&mut self, metadata: Option<ContractMetadata>) {
+        self.metadata = metadata;
+    }
+
     /// Checks whether there is a method with the given name
     pub fn has_entry_point(&self, name: &str) -> bool {
         self.entry_points.has_entry_point(name)
@@ -579,6 +799,7 @@ impl ToBytes for Contract {
         result.append(&mut self.named_keys.to_bytes()?);
         result.append(&mut self.entry_points.to_bytes()?);
         result.append(&mut self.protocol_version.to_bytes()?);
+        result.append(&mut self.metadata.to_bytes()?);
         Ok(result)
     }
 
@@ -588,6 +809,7 @@ impl ToBytes for Contract {
             + ToBytes::serialized_length(&self.contract_wasm_hash)
             + ToBytes::serialized_length(&self.protocol_version)
             + ToBytes::serialized_length(&self.named_keys)
+            + ToBytes::serialized_length(&self.metadata)
     }
 }
 
@@ -598,6 +820,7 @@ impl FromBytes for Contract {
         let (named_keys, bytes) = NamedKeys::from_bytes(bytes)?;
         let (entry_points, bytes) = EntryPoints::from_bytes(bytes)?;
         let (protocol_version, bytes) = ProtocolVersion::from_bytes(bytes)?;
+        let (metadata, bytes) = <Option<ContractMetadata>>::from_bytes(bytes)?;
         Ok((
             Contract {
                 contract_package_hash,
@@ -605,6 +828,7 @@ impl FromBytes for Contract {
                 named_keys,
                 entry_points,
                 protocol_version,
+                metadata,
             },
             bytes,
         ))
@@ -619,6 +843,7 @@ impl Default for Contract {
             contract_wasm_hash: [0; KEY_HASH_LENGTH],
             contract_package_hash: [0; KEY_HASH_LENGTH],
             protocol_version: ProtocolVersion::V1_0_0,
+            metadata: None,
         }
     }
 }
@@ -626,6 +851,7 @@ impl Default for Contract {
 /// Context of method execution
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EntryPointType {
     /// Runs as session code
     Session = 0,
@@ -663,12 +889,17 @@ pub const ENTRY_POINT_NAME_INSTALL: &str = "install";
 /// Default name for an upgrader entry point
 pub const UPGRADE_ENTRY_POINT_NAME: &str = "upgrade";
 
+/// Name of the argument holding the hash of the previous contract version, passed to the migration
+/// entry point of a newly added version
+pub const ARG_PREVIOUS_CONTRACT_HASH: &str = "previous_contract_hash";
+
 /// Collection of entry point parameters.
 pub type Parameters = Vec<Parameter>;
 
 /// Type signature of a method. Order of arguments matter since can be
 /// referenced by index as well as name.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EntryPoint {
     name: String,
     args: Parameters,
@@ -799,6 +1030,7 @@ impl FromBytes for EntryPoint {
 /// Enum describing the possible access control options for a contract entry
 /// point (method).
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EntryPointAccess {
     /// Anyone can call this method (no access controls).
     Public,
@@ -861,6 +1093,7 @@ impl FromBytes for EntryPointAccess {
 
 /// Parameter to a method
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Parameter {
     name: String,
     cl_type: CLType,
@@ -913,7 +1146,7 @@ impl FromBytes for Parameter {
 mod tests {
     use super::*;
     use crate::{AccessRights, URef};
-    use alloc::borrow::ToOwned;
+    use alloc::{borrow::ToOwned, boxed::Box};
 
     fn make_contract_package() -> ContractPackage {
         let mut contract_package = ContractPackage::new(
@@ -921,6 +1154,7 @@ mod tests {
             ContractVersions::default(),
             DisabledVersions::default(),
             Groups::default(),
+            ContractPackageStatus::Unlocked,
         );
 
         // add groups
@@ -981,6 +1215,7 @@ mod tests {
             ContractVersions::default(),
             DisabledVersions::default(),
             Groups::default(),
+            ContractPackageStatus::Unlocked,
         );
         assert_eq!(contract_package.next_contract_version_for(major), 1);
 
@@ -1006,6 +1241,30 @@ mod tests {
         assert_eq!(rem.len(), 0);
     }
 
+    #[test]
+    fn roundtrip_serialization_with_metadata() {
+        let mut entry_point_docs = EntryPointDocs::new();
+        entry_point_docs.insert("method0".to_string(), "Returns a number".to_string());
+        let mut named_keys_schema = NamedKeysSchema::new();
+        named_keys_schema.insert("balances".to_string(), CLType::List(Box::new(CLType::U512)));
+        let metadata = ContractMetadata::new(
+            "Contract".to_string(),
+            "A contract".to_string(),
+            Some([7; BLAKE2B_DIGEST_LENGTH]),
+            entry_point_docs,
+            named_keys_schema,
+        );
+
+        let mut contract_package = make_contract_package();
+        contract_package.set_metadata(Some(metadata));
+        let bytes = contract_package.to_bytes().expect("should serialize");
+        assert_eq!(bytes.len(), contract_package.serialized_length());
+        let (decoded_package, rem) =
+            ContractPackage::from_bytes(&bytes).expect("should deserialize");
+        assert_eq!(contract_package, decoded_package);
+        assert_eq!(rem.len(), 0);
+    }
+
     #[test]
     fn should_remove_group() {
         let mut contract_package = make_contract_package();
@@ -1049,4 +1308,26 @@ mod tests {
             "version should not be enabled"
         );
     }
+
+    #[test]
+    fn should_not_disable_version_of_locked_package() {
+        const CONTRACT_HASH: ContractHash = [123; 32];
+        let mut versions = ContractVersions::new();
+        versions.insert(ContractVersionKey::new(1, 1), CONTRACT_HASH);
+        let mut contract_package = ContractPackage::new(
+            URef::new([0; 32], AccessRights::NONE),
+            versions,
+            DisabledVersions::default(),
+            Groups::default(),
+            ContractPackageStatus::Locked,
+        );
+        assert!(contract_package.is_locked());
+
+        assert_eq!(
+            contract_package.disable_contract_version(CONTRACT_HASH),
+            Err(Error::LockedContract),
+            "should not be able to disable version of locked package"
+        );
+        assert!(contract_package.is_version_enabled(ContractVersionKey::new(1, 1)));
+    }
 }
@@ -14,10 +14,12 @@ use proptest::{
 
 use crate::{
     account::{AccountHash, Weight},
-    contracts::{ContractVersions, DisabledVersions, Groups, NamedKeys, Parameters},
-    AccessRights, CLType, CLValue, Contract, ContractPackage, ContractVersionKey, ContractWasm,
-    EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Group, Key, NamedArg, Parameter,
-    Phase, ProtocolVersion, SemVer, URef, U128, U256, U512,
+    contracts::{
+        ContractMetadata, ContractVersions, DisabledVersions, Groups, NamedKeys, Parameters,
+    },
+    AccessRights, CLType, CLValue, Contract, ContractPackage, ContractPackageStatus,
+    ContractVersionKey, ContractWasm, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
+    Group, Key, NamedArg, Parameter, Phase, ProtocolVersion, SemVer, URef, U128, U256, U512,
 };
 
 pub fn u8_slice_32() -> impl Strategy<Value = [u8; 32]> {
@@ -268,6 +270,27 @@ pub fn entry_points_arb() -> impl Strategy<Value = EntryPoints> {
     vec(entry_point_arb(), 1..10).prop_map(EntryPoints::from)
 }
 
+pub fn contract_metadata_arb() -> impl Strategy<Value = ContractMetadata> {
+    (
+        "\\PC*",
+        "\\PC*",
+        option::of(u8_slice_32()),
+        btree_map("\\PC*", "\\PC*", 0..5),
+        btree_map("\\PC*", cl_type_arb(), 0..5),
+    )
+        .prop_map(
+            |(name, description, source_hash, entry_point_docs, named_keys_schema)| {
+                ContractMetadata::new(
+                    name,
+                    description,
+                    source_hash,
+                    entry_point_docs,
+                    named_keys_schema,
+                )
+            },
+        )
+}
+
 pub fn contract_arb() -> impl Strategy<Value = Contract> {
     (
         protocol_version_arb(),
@@ -275,6 +298,7 @@ pub fn contract_arb() -> impl Strategy<Value = Contract> {
         u8_slice_32(),
         u8_slice_32(),
         named_keys_arb(20),
+        option::of(contract_metadata_arb()),
     )
         .prop_map(
             |(
@@ -283,14 +307,17 @@ pub fn contract_arb() -> impl Strategy<Value = Contract> {
                 contract_package_hash_arb,
                 contract_wasm_hash,
                 named_keys,
+                metadata,
             )| {
-                Contract::new(
+                let mut contract = Contract::new(
                     contract_package_hash_arb,
                     contract_wasm_hash,
                     named_keys,
                     entry_points,
                     protocol_version,
-                )
+                );
+                contract.set_metadata(metadata);
+                contract
             },
         )
 }
@@ -316,14 +343,28 @@ pub fn groups_arb() -> impl Strategy<Value = Groups> {
     btree_map(group_arb(), btree_set(uref_arb(), 1..10), 0..5)
 }
 
+pub fn contract_package_status_arb() -> impl Strategy<Value = ContractPackageStatus> {
+    prop_oneof![
+        Just(ContractPackageStatus::Unlocked),
+        Just(ContractPackageStatus::Locked),
+    ]
+}
+
 pub fn contract_package_arb() -> impl Strategy<Value = ContractPackage> {
     (
         uref_arb(),
         contract_versions_arb(),
         disabled_versions_arb(),
         groups_arb(),
+        contract_package_status_arb(),
+        option::of(contract_metadata_arb()),
     )
-        .prop_map(|(access_key, versions, disabled_versions, groups)| {
-            ContractPackage::new(access_key, versions, disabled_versions, groups)
-        })
+        .prop_map(
+            |(access_key, versions, disabled_versions, groups, status, metadata)| {
+                let mut contract_package =
+                    ContractPackage::new(access_key, versions, disabled_versions, groups, status);
+                contract_package.set_metadata(metadata);
+                contract_package
+            },
+        )
 }
@@ -4,6 +4,11 @@
 //!
 //! By default, the library is `no_std`, however you can enable full `std` functionality by enabling
 //! the crate's `std` feature.
+//!
+//! # `serde`
+//!
+//! Enabling the crate's `serde` feature derives `Serialize` and `Deserialize` for [`CLType`] and
+//! the contract entry point types, allowing a contract's ABI to be exported as e.g. JSON.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(
@@ -53,8 +58,9 @@ pub use block_time::{BlockTime, BLOCKTIME_SERIALIZED_LENGTH};
 pub use cl_type::{named_key_type, CLType, CLTyped};
 pub use cl_value::{CLTypeMismatch, CLValue, CLValueError};
 pub use contracts::{
-    Contract, ContractPackage, ContractVersion, ContractVersionKey, EntryPoint, EntryPointAccess,
-    EntryPointType, EntryPoints, Group, Parameter,
+    Contract, ContractMetadata, ContractPackage, ContractPackageStatus, ContractVersion,
+    ContractVersionKey, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Group,
+    Parameter,
 };
 //pub use contract_ref::ContractRef;
 pub use contract_wasm::ContractWasm;
@@ -38,6 +38,12 @@ pub enum Error {
     /// Purse not found while trying to get balance.
     #[fail(display = "Purse not found")]
     PurseNotFound = 7,
+    /// Tried to burn tokens from a caller other than the system account.
+    #[fail(display = "Invalid caller")]
+    InvalidCaller = 8,
+    /// Tried to burn more tokens than the current total supply.
+    #[fail(display = "Invalid total supply")]
+    InvalidTotalSupply = 9,
 }
 
 impl From<PurseError> for Error {
@@ -79,6 +85,10 @@ impl TryFrom<u8> for Error {
             d if d == Error::InvalidNonEmptyPurseCreation as u8 => {
                 Ok(Error::InvalidNonEmptyPurseCreation)
             }
+            d if d == Error::Storage as u8 => Ok(Error::Storage),
+            d if d == Error::PurseNotFound as u8 => Ok(Error::PurseNotFound),
+            d if d == Error::InvalidCaller as u8 => Ok(Error::InvalidCaller),
+            d if d == Error::InvalidTotalSupply as u8 => Ok(Error::InvalidTotalSupply),
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -129,3 +139,32 @@ impl fmt::Display for PurseError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use super::Error;
+    use crate::bytesrepr;
+
+    #[test]
+    fn error_roundtrip() {
+        let errors = [
+            Error::InsufficientFunds,
+            Error::SourceNotFound,
+            Error::DestNotFound,
+            Error::InvalidURef,
+            Error::InvalidAccessRights,
+            Error::InvalidNonEmptyPurseCreation,
+            Error::Storage,
+            Error::PurseNotFound,
+            Error::InvalidCaller,
+            Error::InvalidTotalSupply,
+        ];
+        for error in errors.iter() {
+            assert_eq!(Ok(*error), Error::try_from(*error as u8).map_err(|_| ()));
+            bytesrepr::test_serialization_roundtrip(error);
+        }
+        assert!(Error::try_from(errors.len() as u8).is_err());
+    }
+}
@@ -105,6 +105,37 @@ pub enum Error {
     /// deploy, but was called by the session code.
     #[fail(display = "Set refund purse was called outside payment")]
     SetRefundPurseCalledOutsidePayment,
+    /// Attempted to place a bid which was lower than the auction's minimum bid.
+    #[fail(display = "Bid is too small")]
+    BidTooSmall,
+    /// The invoked PoS function requires the auction mode, which was not enabled at genesis.
+    #[fail(display = "Auction mode is disabled")]
+    AuctionDisabled,
+    /// Attempted to run the auction before the end of the current era.
+    #[fail(display = "Era has not finished")]
+    EraNotFinished,
+    /// Internal error: while running the auction, failed to pay back a losing bid (the transfer
+    /// from the PoS contract's bonding purse to the bidder's main purse failed).
+    #[fail(display = "Auction payout failed")]
+    AuctionPayoutFailed,
+    /// Internal error: failed to read the total supply of tokens from the Mint.
+    #[fail(display = "Total supply not found")]
+    TotalSupplyNotFound,
+    /// Internal error: while minting seigniorage, failed to issue new tokens into the PoS
+    /// contract's rewards purse.
+    #[fail(display = "Seigniorage mint failed")]
+    SeigniorageMintFailed,
+    /// The fee burn rate is not a percentage between 0 and 100.
+    #[fail(display = "Invalid fee burn rate")]
+    InvalidFeeBurnRate,
+    /// Internal error: while finalizing payment, failed to burn the configured portion of the
+    /// fee.
+    #[fail(display = "Fee burn failed")]
+    FeeBurnFailed,
+    /// Internal error: while finalizing payment, failed to transfer the fee to the block
+    /// proposer.
+    #[fail(display = "Failed transfer to proposer")]
+    FailedTransferToProposer,
 }
 
 impl CLTyped for Error {